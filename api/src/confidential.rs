@@ -0,0 +1,212 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Contains functions which encrypt and decrypt the values of confidential properties
+//!
+//! Every record with confidential properties has a random 256 bit data key, and the values of
+//! those properties are encrypted with it using AES-256-GCM. The data key is stored on the
+//! record wrapped for each agent allowed to read it: encrypted with the ECDH secret of a new
+//! secp256k1 key and the public key of the agent, so only the private key of the agent can
+//! unwrap it.
+//!
+//! When an agent loses its last role on a record its wrapped keys are removed, and the next
+//! write of a confidential value rotates the data key: a new version of it is wrapped for the
+//! remaining agents and later values are encrypted with it. Each encrypted value names the
+//! version it was encrypted with. The removed agent can still decrypt the values written
+//! before the rotation, and an agent that is given the data key later only receives its
+//! current version, so it can not decrypt values written before the last rotation.
+
+use std::collections::HashMap;
+
+use crypto::aead::{AeadDecryptor, AeadEncryptor};
+use crypto::aes::KeySize;
+use crypto::aes_gcm::AesGcm;
+use rand::RngCore;
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+use sawtooth_sdk::signing::create_context;
+use sawtooth_sdk::signing::secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey};
+use sawtooth_sdk::signing::{PrivateKey, PublicKey as _};
+
+use crate::error::RestApiResponseError;
+use dgc_config::protocol::schema::state::{PropertyValue, PropertyValueBuilder};
+use dgc_config::protocol::track_and_trace::state::{DataKey, DataKeyBuilder, Record};
+use dgc_config::protos::{FromBytes, IntoBytes};
+
+const DATA_KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const COMPRESSED_PUBLIC_KEY_LEN: usize = 33;
+
+/// Generates a new data key for a record
+pub fn generate_data_key() -> Vec<u8> {
+    random_bytes(DATA_KEY_LEN)
+}
+
+/// Wraps a data key for the agent with the given hex encoded public key
+pub fn wrap_data_key(data_key: &[u8], public_key: &str) -> Result<Vec<u8>, RestApiResponseError> {
+    let public_key = Secp256k1PublicKey::from_hex(public_key)?;
+    let public_key = PublicKey::from_slice(public_key.as_slice())
+        .map_err(|err| RestApiResponseError::UserError(format!("Invalid public key: {}", err)))?;
+
+    // A zero or out of range scalar is practically impossible, but is retried all the same
+    let ephemeral_key = loop {
+        if let Ok(key) = SecretKey::from_slice(&random_bytes(32)) {
+            break key;
+        }
+    };
+    let shared_secret = SharedSecret::new(&public_key, &ephemeral_key);
+
+    let mut wrapped_key = PublicKey::from_secret_key(&Secp256k1::new(), &ephemeral_key)
+        .serialize()
+        .to_vec();
+    wrapped_key.extend(seal(&shared_secret[..], data_key));
+    Ok(wrapped_key)
+}
+
+/// Unwraps a data key with the hex encoded private key of the agent it was wrapped for
+pub fn unwrap_data_key(
+    wrapped_key: &[u8],
+    private_key: &str,
+) -> Result<Vec<u8>, RestApiResponseError> {
+    if wrapped_key.len() < COMPRESSED_PUBLIC_KEY_LEN {
+        return Err(RestApiResponseError::UserError(
+            "Wrapped data key is too short".to_string(),
+        ));
+    }
+    let (ephemeral_key, sealed_key) = wrapped_key.split_at(COMPRESSED_PUBLIC_KEY_LEN);
+    let ephemeral_key = PublicKey::from_slice(ephemeral_key).map_err(|err| {
+        RestApiResponseError::UserError(format!("Invalid wrapped data key: {}", err))
+    })?;
+    let private_key = Secp256k1PrivateKey::from_hex(private_key)?;
+    let private_key = SecretKey::from_slice(private_key.as_slice())
+        .map_err(|err| RestApiResponseError::UserError(format!("Invalid private key: {}", err)))?;
+    let shared_secret = SharedSecret::new(&ephemeral_key, &private_key);
+
+    open(&shared_secret[..], sealed_key)
+}
+
+/// Unwraps the current version of the data key of a record with the hex encoded private key
+/// of an agent, if the key has been shared with the agent
+pub fn record_data_key(
+    record: &Record,
+    private_key: &str,
+) -> Result<Option<Vec<u8>>, RestApiResponseError> {
+    match record.data_key(&public_key_of(private_key)?) {
+        Some(data_key) => Ok(Some(unwrap_data_key(data_key.wrapped_key(), private_key)?)),
+        None => Ok(None),
+    }
+}
+
+/// Unwraps every version of the data key of a record that has been shared with an agent,
+/// by version
+pub fn record_data_keys(
+    record: &Record,
+    private_key: &str,
+) -> Result<HashMap<u32, Vec<u8>>, RestApiResponseError> {
+    let public_key = public_key_of(private_key)?;
+    record
+        .data_keys()
+        .iter()
+        .filter(|data_key| data_key.agent_id() == public_key)
+        .map(|data_key| {
+            let key = unwrap_data_key(data_key.wrapped_key(), private_key)?;
+            Ok((*data_key.version(), key))
+        })
+        .collect()
+}
+
+/// Generates the next version of the data key of a record, returning it with the new version
+/// wrapped for every agent that holds the data key
+pub fn rotate_data_key(record: &Record) -> Result<(Vec<u8>, Vec<DataKey>), RestApiResponseError> {
+    let data_key = generate_data_key();
+    let version = record.data_key_version() + 1;
+    let mut data_keys: Vec<DataKey> = Vec::new();
+    for agent_id in record.data_keys().iter().map(|data_key| data_key.agent_id()) {
+        if data_keys.iter().any(|data_key| data_key.agent_id() == agent_id) {
+            continue;
+        }
+        data_keys.push(
+            DataKeyBuilder::new()
+                .with_agent_id(agent_id.to_string())
+                .with_wrapped_key(wrap_data_key(&data_key, agent_id)?)
+                .with_version(version)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+    Ok((data_key, data_keys))
+}
+
+/// Returns the hex encoded public key of a hex encoded private key
+pub fn public_key_of(private_key: &str) -> Result<String, RestApiResponseError> {
+    let private_key = Secp256k1PrivateKey::from_hex(private_key)?;
+    let context = create_context("secp256k1")?;
+    Ok(context.get_public_key(&private_key)?.as_hex())
+}
+
+/// Encrypts a property value with a version of the data key of its record. The encrypted value
+/// keeps the name and the data type of the value, so it can still be checked against the
+/// schema.
+pub fn encrypt_value(
+    data_key: &[u8],
+    key_version: u32,
+    value: PropertyValue,
+) -> Result<PropertyValue, RestApiResponseError> {
+    let name = value.name().to_string();
+    let data_type = value.data_type().clone();
+    let encrypted_value = seal(data_key, &value.into_bytes()?);
+    PropertyValueBuilder::new()
+        .with_name(name)
+        .with_data_type(data_type)
+        .with_encrypted_value(encrypted_value)
+        .with_key_version(key_version)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))
+}
+
+/// Decrypts a property value that was encrypted with the data key of its record
+pub fn decrypt_value(
+    data_key: &[u8],
+    value: &PropertyValue,
+) -> Result<PropertyValue, RestApiResponseError> {
+    let plaintext = open(data_key, value.encrypted_value())?;
+    Ok(PropertyValue::from_bytes(&plaintext)?)
+}
+
+/// Encrypts with AES-256-GCM under a new nonce, returning the nonce, the tag and the
+/// ciphertext
+fn seal(key: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let nonce = random_bytes(NONCE_LEN);
+    let mut ciphertext = vec![0; plaintext.len()];
+    let mut tag = [0; TAG_LEN];
+    let mut cipher = AesGcm::new(KeySize::KeySize256, key, &nonce, &[]);
+    cipher.encrypt(plaintext, &mut ciphertext, &mut tag);
+    [nonce, tag.to_vec(), ciphertext].concat()
+}
+
+/// Decrypts the output of `seal`, failing if it was not encrypted with the key or was altered
+fn open(key: &[u8], sealed: &[u8]) -> Result<Vec<u8>, RestApiResponseError> {
+    if sealed.len() < NONCE_LEN + TAG_LEN {
+        return Err(RestApiResponseError::UserError(
+            "Encrypted data is too short".to_string(),
+        ));
+    }
+    let (nonce, sealed) = sealed.split_at(NONCE_LEN);
+    let (tag, ciphertext) = sealed.split_at(TAG_LEN);
+    let mut plaintext = vec![0; ciphertext.len()];
+    let mut cipher = AesGcm::new(KeySize::KeySize256, key, nonce, &[]);
+    if !cipher.decrypt(ciphertext, &mut plaintext, tag) {
+        return Err(RestApiResponseError::UserError(
+            "Encrypted data could not be decrypted with the data key".to_string(),
+        ));
+    }
+    Ok(plaintext)
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+}
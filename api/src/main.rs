@@ -4,6 +4,7 @@
 mod routes;
 mod error;
 mod transaction;
+mod confidential;
 
 use actix_web::*;
 use serde::Deserialize;
@@ -13,6 +14,8 @@ use crate::routes::organizations::*;
 use crate::routes::products::*;
 use crate::routes::schemas::*;
 use crate::routes::records::*;
+use crate::routes::proposals::*;
+use crate::routes::recalls::*;
 
 #[derive(Deserialize)]
 pub struct List {
@@ -79,10 +82,20 @@ async fn main() -> std::io::Result<()> {
             .service(web::resource("/organization/{org_id}")
                 .route(web::get().to(fetch_org)))
 
+            .service(web::resource("/gs1_company_prefixes")
+                .name("gs1_company_prefixes")
+                .route(web::post().to(add_gs1_company_prefixes))
+                .route(web::delete().to(remove_gs1_company_prefixes)))
+
+            .service(web::resource("/gs1_company_prefixes/{org_id}")
+                .route(web::get().to(fetch_gs1_company_prefixes)))
+
             .service(web::resource("/product")
                 .name("product")
                 .route(web::post().to(create_product))
-                .route(web::put().to(update_product)))
+                .route(web::put().to(update_product))
+                .route(web::patch().to(patch_product))
+                .route(web::delete().to(delete_product)))
 
             .service(web::resource("/products")
                 .name("products")
@@ -91,10 +104,26 @@ async fn main() -> std::io::Result<()> {
             .service(web::resource("/product/{product_id}")
                 .route(web::get().to(fetch_product)))
 
+            .service(web::resource("/product/{product_id}/revisions")
+                .route(web::get().to(list_product_revisions)))
+
+            .service(web::resource("/product_proposal")
+                .name("product_proposal")
+                .route(web::post().to(create_product_proposal))
+                .route(web::put().to(answer_product_proposal)))
+
+            .service(web::resource("/product_proposals/{product_id}")
+                .route(web::get().to(list_product_proposals)))
+
             .service(web::resource("/schema")
                 .name("schema")
                 .route(web::post().to(create_schema))
-                .route(web::put().to(update_schema)))
+                .route(web::put().to(update_schema))
+                .route(web::delete().to(deprecate_schema)))
+
+            .service(web::resource("/schema_transfer")
+                .name("schema_transfer")
+                .route(web::post().to(transfer_schema)))
 
             .service(web::resource("/schemas")
                 .name("schemas")
@@ -112,9 +141,95 @@ async fn main() -> std::io::Result<()> {
                 .name("records")
                 .route(web::get().to(list_records)))
 
+            .service(web::resource("/records/within_bounding_box")
+                .route(web::get().to(list_records_within_bounding_box)))
+
             .service(web::resource("/record/{record_id}")
                 .route(web::get().to(fetch_record)))
 
+            .service(web::resource("/record_properties")
+                .name("record_properties")
+                .route(web::put().to(update_record_properties)))
+
+            .service(web::resource("/record/{record_id}/readings")
+                .data(web::PayloadConfig::new(16 * 1024 * 1024))
+                .route(web::post().to(ingest_record_readings)))
+
+            .service(web::resource("/record/{record_id}/property/{property_name}")
+                .route(web::get().to(fetch_record_property)))
+
+            .service(web::resource("/record/{record_id}/property/{property_name}/reporters")
+                .route(web::get().to(fetch_record_property_reporters)))
+
+            .service(web::resource("/record/{record_id}/trace/{property_name}")
+                .route(web::get().to(fetch_record_trace)))
+
+            .service(web::resource("/record/{record_id}/excursions")
+                .route(web::get().to(list_record_excursions)))
+
+            .service(web::resource("/custodian/{agent_id}/excursions")
+                .route(web::get().to(list_custodian_excursions)))
+
+            .service(web::resource("/record_split")
+                .name("record_split")
+                .route(web::post().to(split_record)))
+
+            .service(web::resource("/record_merge")
+                .name("record_merge")
+                .route(web::post().to(merge_records)))
+
+            .service(web::resource("/record_transform")
+                .name("record_transform")
+                .route(web::post().to(transform_record)))
+
+            .service(web::resource("/record/{record_id}/genealogy")
+                .route(web::get().to(fetch_record_genealogy)))
+
+            .service(web::resource("/record_proposal")
+                .name("record_proposal")
+                .route(web::post().to(create_record_proposal))
+                .route(web::put().to(answer_record_proposal)))
+
+            .service(web::resource("/record_proposal_countersignature")
+                .name("record_proposal_countersignature")
+                .route(web::post().to(counter_sign_record_proposal)))
+
+            .service(web::resource("/record_proposals/{record_id}")
+                .route(web::get().to(list_record_proposals)))
+
+            .service(web::resource("/record/{record_id}/custody_chain")
+                .route(web::get().to(fetch_record_custody_chain)))
+
+            .service(web::resource("/bulk_proposal")
+                .name("bulk_proposal")
+                .route(web::post().to(create_bulk_proposal))
+                .route(web::put().to(answer_bulk_proposal)))
+
+            .service(web::resource("/bulk_proposal/{proposal_id}")
+                .route(web::get().to(fetch_bulk_proposal)))
+
+            .service(web::resource("/bulk_proposals/agent/{public_key}")
+                .route(web::get().to(list_agent_bulk_proposals)))
+
+            .service(web::resource("/recall")
+                .name("recall")
+                .route(web::post().to(create_recall))
+                .route(web::delete().to(close_recall)))
+
+            .service(web::resource("/recall_acknowledgement")
+                .name("recall_acknowledgement")
+                .route(web::post().to(acknowledge_recall)))
+
+            .service(web::resource("/recalls")
+                .name("recalls")
+                .route(web::get().to(list_recalls)))
+
+            .service(web::resource("/recall/{recall_id}")
+                .route(web::get().to(fetch_recall)))
+
+            .service(web::resource("/recalls/custodian/{public_key}")
+                .route(web::get().to(list_custodian_recalls)))
+
 /*
             .service(
                 web::scope("/product")
@@ -4,5 +4,8 @@
 pub mod agents;
 pub mod organizations;
 pub mod products;
+pub mod properties;
+pub mod proposals;
+pub mod recalls;
 pub mod schemas;
 pub mod records;
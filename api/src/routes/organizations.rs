@@ -101,6 +101,7 @@ pub async fn create_org(
     let name = &input_data.name;
     let address = &input_data.address;
     let metadata = retrieve_metadata(&input_data);
+    check_metadata_gs1_company_prefixes(org_id, &metadata).await?;
 /*
     // Creating a Private Key and Signer //
     let private_key_as_hex = &input_data.private_key;
@@ -191,6 +192,7 @@ pub async fn update_org(
     let name = &input_data.name;
     let address = &input_data.address;
     let metadata = retrieve_metadata(&input_data);
+    check_metadata_gs1_company_prefixes(org_id, &metadata).await?;
 /*
     // Creating a Private Key and Signer //
     let private_key_as_hex = &input_data.private_key;
@@ -302,3 +304,230 @@ fn retrieve_metadata(
     }
     return metadata
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct Gs1PrefixData {
+    private_key: String,
+    org_id: String,
+    gs1_company_prefixes: String,
+}
+
+pub async fn fetch_gs1_company_prefixes(
+    org_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    println!("!dgc-network! org_id = {:?}", org_id);
+    let org = retrieve_org(&org_id).await?;
+    let prefixes = parse_gs1_company_prefixes(&org);
+    println!("!dgc-network! gs1_company_prefixes = {:?}", prefixes);
+
+    Ok(HttpResponse::Ok().body(format!(
+        "{{\n  org_id: {:?}, \n  gs1_company_prefixes: {:?} \n}}",
+        org.org_id, prefixes
+    )))
+}
+
+pub async fn add_gs1_company_prefixes(
+    input_data: web::Json<Gs1PrefixData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let org = retrieve_org(&input_data.org_id).await?;
+    let mut prefixes = parse_gs1_company_prefixes(&org);
+    for prefix in input_data.gs1_company_prefixes.split(",") {
+        let prefix = prefix.trim();
+        if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_digit()) {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "GS1 company prefixes only contain numbers: {:?}",
+                prefix
+            )));
+        }
+        if !prefixes.iter().any(|p| p == prefix) {
+            prefixes.push(prefix.to_string());
+        }
+    }
+    check_gs1_company_prefixes_unclaimed(&org.org_id, &prefixes).await?;
+
+    submit_gs1_company_prefixes(&input_data.private_key, &org, prefixes).await
+}
+
+pub async fn remove_gs1_company_prefixes(
+    input_data: web::Json<Gs1PrefixData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let org = retrieve_org(&input_data.org_id).await?;
+    let removed: Vec<&str> = input_data.gs1_company_prefixes
+        .split(",")
+        .map(|prefix| prefix.trim())
+        .collect();
+    let prefixes = parse_gs1_company_prefixes(&org)
+        .into_iter()
+        .filter(|prefix| !removed.contains(&prefix.as_str()))
+        .collect();
+
+    submit_gs1_company_prefixes(&input_data.private_key, &org, prefixes).await
+}
+
+async fn retrieve_org(
+    org_id: &str,
+) -> Result<pike_state::Organization, RestApiResponseError> {
+
+    let address = make_org_address(org_id);
+    let url = format!("http://rest-api:8008/state/{}", address);
+    let res = reqwest::get(&url).await?.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let orgs: pike_state::OrganizationList = match protobuf::parse_from_bytes(&msg){
+        Ok(orgs) => orgs,
+        Err(err) => {
+            return Err(RestApiResponseError::ApplyError(ApplyError::InternalError(format!(
+                "Cannot deserialize organization: {:?}",
+                err,
+            ))))
+        }
+    };
+    match orgs.get_organizations().iter().find(|org| org.org_id == org_id) {
+        Some(org) => Ok(org.clone()),
+        None => Err(RestApiResponseError::NotFoundError(format!(
+            "Organization does not exist: {}",
+            org_id
+        ))),
+    }
+}
+
+fn parse_gs1_company_prefixes(
+    org: &pike_state::Organization,
+) -> Vec<String> {
+    match org.get_metadata().iter().find(|kv| kv.key == "gs1_company_prefixes") {
+        Some(kv) => kv.value
+            .split(",")
+            .map(|prefix| prefix.trim().to_string())
+            .filter(|prefix| !prefix.is_empty())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Checks the GS1 company prefixes in the metadata of an organization that is created or
+/// updated, if it has any
+async fn check_metadata_gs1_company_prefixes(
+    org_id: &str,
+    metadata: &[KeyValueEntry],
+) -> Result<(), RestApiResponseError> {
+    match metadata.iter().find(|kv| kv.key() == "gs1_company_prefixes") {
+        Some(kv) => {
+            let prefixes: Vec<String> = kv.value()
+                .split(",")
+                .map(|prefix| prefix.trim().to_string())
+                .filter(|prefix| !prefix.is_empty())
+                .collect();
+            check_gs1_company_prefixes_unclaimed(org_id, &prefixes).await
+        }
+        None => Ok(()),
+    }
+}
+
+/// Checks that no other organization holds one of the GS1 company prefixes. Company prefixes
+/// vary in length, so a prefix that starts with the prefix of another organization, or that
+/// another organization's prefix starts with, would claim GTINs of that organization too.
+async fn check_gs1_company_prefixes_unclaimed(
+    org_id: &str,
+    prefixes: &[String],
+) -> Result<(), RestApiResponseError> {
+
+    let url = format!("http://rest-api:8008/state?address={}", &get_org_prefix());
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let orgs: pike_state::OrganizationList = match protobuf::parse_from_bytes(&msg){
+            Ok(orgs) => orgs,
+            Err(err) => {
+                return Err(RestApiResponseError::ApplyError(ApplyError::InternalError(format!(
+                    "Cannot deserialize organization: {:?}",
+                    err,
+                ))))
+            }
+        };
+        for other in orgs.get_organizations() {
+            if other.org_id == org_id {
+                continue;
+            }
+            for held in parse_gs1_company_prefixes(other) {
+                if let Some(prefix) = prefixes
+                    .iter()
+                    .find(|prefix| prefix.starts_with(&held) || held.starts_with(prefix.as_str()))
+                {
+                    return Err(RestApiResponseError::BadRequest(format!(
+                        "GS1 company prefix {} overlaps prefix {} of organization {}",
+                        prefix, held, other.org_id
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn submit_gs1_company_prefixes(
+    private_key: &str,
+    org: &pike_state::Organization,
+    prefixes: Vec<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    // Keep every other metadata entry and replace the gs1_company_prefixes one //
+    let mut metadata = Vec::<KeyValueEntry>::new();
+    for kv in org.get_metadata() {
+        if kv.key != "gs1_company_prefixes" {
+            let key_value = KeyValueEntryBuilder::new()
+                .with_key(kv.key.to_string())
+                .with_value(kv.value.to_string())
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+            metadata.push(key_value);
+        }
+    }
+    let key_value = KeyValueEntryBuilder::new()
+        .with_key("gs1_company_prefixes".to_string())
+        .with_value(prefixes.join(","))
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+    metadata.push(key_value);
+
+    let action = UpdateOrganizationActionBuilder::new()
+        .with_org_id(org.org_id.to_string())
+        .with_metadata(metadata)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let payload = PikePayloadBuilder::new()
+        .with_action(Action::UpdateOrganization)
+        .with_update_organization(action)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    // Building the Transaction and Batch //
+    let batch_list = BatchBuilder::new(
+        PIKE_FAMILY_NAME, 
+        PIKE_FAMILY_VERSION, 
+        private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[get_pike_prefix()],
+        &[get_pike_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    println!("============ update_gs1_company_prefixes ============");
+    println!("!dgc-network! res = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
@@ -11,6 +11,7 @@ use std::convert::TryInto;
 
 use crate::transaction::BatchBuilder;
 use crate::error::RestApiResponseError;
+use crate::routes::properties::*;
 use crate::{List, Fetch, split_vec};
 
 use dgc_config::protos::*;
@@ -26,6 +27,46 @@ pub struct ProductData {
     product_type: String,
     owner: String,
     properties: String,
+    #[serde(default)]
+    list_properties: Vec<ListPropertyData>,
+    #[serde(default)]
+    schema: String,
+}
+
+#[derive(Deserialize)]
+pub struct ProductProposalData {
+    private_key: String,
+    product_id: String,
+    receiving_org: String,
+    #[serde(default)]
+    terms: String,
+    #[serde(default)]
+    response: String,
+}
+
+#[derive(Deserialize)]
+pub struct ProductDeleteData {
+    private_key: String,
+    product_id: String,
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Deserialize)]
+pub struct ProductPatchData {
+    private_key: String,
+    product_id: String,
+    patches: Vec<PropertyPatchData>,
+}
+
+#[derive(Deserialize)]
+pub struct PropertyPatchData {
+    operation: String,
+    name: String,
+    #[serde(default)]
+    value: String,
+    #[serde(default)]
+    expected_value: String,
 }
 
 pub async fn list_products(
@@ -51,9 +92,12 @@ pub async fn list_products(
             println!("    product_id: {:?},", product.product_id);
             println!("    product_type: {:?},", product.product_type);
             println!("    owner: {:?},", product.owner);
-            println!("    properties: {:?}", product.properties);
+            println!("    properties: {:?},", product.properties);
+            println!("    schema: {:?},", product.schema);
+            println!("    revision: {:?},", product.revision);
+            println!("    deleted: {:?}", product.deleted);
             
-            response_data = response_data + &format!("\n  {{\n    product_id: {:?}, \n    product_type: {:?}, \n    owner: {:?}, \n    properties: {:?}, \n  }},\n", product.product_id, product.product_type, product.owner, product.properties);
+            response_data = response_data + &format!("\n  {{\n    product_id: {:?}, \n    product_type: {:?}, \n    owner: {:?}, \n    properties: {:?}, \n    schema: {:?}, \n    revision: {:?}, \n    deleted: {:?}, \n    deleted_reason: {:?}, \n  }},\n", product.product_id, product.product_type, product.owner, product.properties, product.schema, product.revision, product.deleted, product.deleted_reason);
         }
     }
     response_data = response_data + &format!("]");
@@ -79,17 +123,77 @@ pub async fn fetch_product(
     };
     let mut response_data = "".to_owned();
     for product in products.get_entries() {
+        // Property values are shown as JSON, with NUMBER values as decimals
+        let definitions = fetch_schema_properties(&product.schema).await?;
+        let properties = product
+            .get_properties()
+            .to_vec()
+            .into_iter()
+            .map(PropertyValue::from_proto)
+            .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?;
+        let properties = properties_to_json(&properties, &definitions);
+
         println!("!dgc-network! response_data: ");
         println!("    product_id: {:?},", product.product_id);
         println!("    product_type: {:?},", product.product_type);
         println!("    owner: {:?},", product.owner);
-        println!("    properties: {:?}", product.properties);
+        println!("    properties: {},", properties);
+        println!("    schema: {:?},", product.schema);
+        println!("    revision: {:?},", product.revision);
+        println!("    deleted: {:?}", product.deleted);
         
-        response_data = response_data + &format!("{{\n  product_id: {:?}, \n  product_type: {:?}, \n  owner: {:?}, \n  properties: {:?}, \n}}", product.product_id, product.product_type, product.owner, product.properties);
+        response_data = response_data + &format!("{{\n  product_id: {:?}, \n  product_type: {:?}, \n  owner: {:?}, \n  properties: {}, \n  schema: {:?}, \n  revision: {:?}, \n  deleted: {:?}, \n  deleted_reason: {:?}, \n}}", product.product_id, product.product_type, product.owner, properties, product.schema, product.revision, product.deleted, product.deleted_reason);
     }
     Ok(HttpResponse::Ok().body(response_data))
 }
 
+pub async fn list_product_revisions(
+    product_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let address = make_product_address(&product_id);
+    let url = format!("http://rest-api:8008/state/{}", address);
+    let res = reqwest::get(&url).await?.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let products: product_state::ProductList = match protobuf::parse_from_bytes(&msg){
+        Ok(products) => products,
+        Err(err) => {
+            return Err(RestApiResponseError::ApplyError(ApplyError::InternalError(format!(
+                "Cannot deserialize data: {:?}",
+                err,
+            ))))
+        }
+    };
+    let product = match products
+        .get_entries()
+        .iter()
+        .find(|product| product.product_id == product_id.as_str())
+    {
+        Some(product) => product,
+        None => {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Product not found: {}",
+                product_id
+            )))
+        }
+    };
+    let mut response_data = "[".to_owned();
+    for revision in product.get_revisions() {
+        println!("!dgc-network! response_data: ");
+        println!("    revision: {:?},", revision.revision);
+        println!("    timestamp: {:?},", revision.timestamp);
+        println!("    agent: {:?},", revision.agent);
+        println!("    change: {:?},", revision.change);
+        println!("    owner: {:?},", revision.owner);
+        println!("    properties: {:?},", revision.properties);
+        println!("    reason: {:?}", revision.reason);
+
+        response_data = response_data + &format!("\n  {{\n    revision: {:?}, \n    timestamp: {:?}, \n    agent: {:?}, \n    change: {:?}, \n    owner: {:?}, \n    properties: {:?}, \n    reason: {:?}, \n  }},\n", revision.revision, revision.timestamp, revision.agent, revision.change, revision.owner, revision.properties, revision.reason);
+    }
+    response_data = response_data + &format!("]");
+    Ok(HttpResponse::Ok().body(response_data))
+}
+
 pub async fn create_product(
     input_data: web::Json<ProductData>,
 ) -> Result<HttpResponse, RestApiResponseError> {
@@ -99,7 +203,9 @@ pub async fn create_product(
     let product_id = &input_data.product_id;
     //let product_type = retrieve_product_type(&input_data);
     let owner = &input_data.owner;
-    let properties = retrieve_property_values(&input_data);
+    let schema = &input_data.schema;
+    let definitions = fetch_schema_properties(schema).await?;
+    let properties = retrieve_property_values(&input_data, &definitions)?;
 
     // Building the Action and Payload//
     let action = ProductCreateActionBuilder::new()
@@ -108,6 +214,7 @@ pub async fn create_product(
         .with_owner(owner.to_string())
         //.with_properties(make_properties())
         .with_properties(properties)
+        .with_schema(schema.to_string())
         .build()
         .unwrap();
 
@@ -124,7 +231,7 @@ pub async fn create_product(
         private_key,
     ).add_transaction(
         &payload.into_proto()?,
-        &[get_product_prefix(), get_pike_prefix()],
+        &[get_product_prefix(), get_pike_prefix(), get_schema_prefix(), get_record_prefix()],
         &[get_product_prefix(), get_pike_prefix()],
     )?.create_batch_list();
 
@@ -155,7 +262,8 @@ pub async fn update_product(
     let product_id = &input_data.product_id;
     //let product_type = retrieve_product_type(&input_data);
     let owner = &input_data.owner;
-    let properties = retrieve_property_values(&input_data);
+    let definitions = fetch_product_schema_properties(product_id).await?;
+    let properties = retrieve_property_values(&input_data, &definitions)?;
 
     // Building the Action and Payload//
     let action = ProductUpdateActionBuilder::new()
@@ -179,7 +287,7 @@ pub async fn update_product(
         private_key,
     ).add_transaction(
         &payload.into_proto()?,
-        &[get_product_prefix(), get_pike_prefix()],
+        &[get_product_prefix(), get_pike_prefix(), get_schema_prefix(), get_record_prefix()],
         &[get_product_prefix(), get_pike_prefix()],
     )?.create_batch_list();
 
@@ -201,12 +309,294 @@ pub async fn update_product(
     Ok(HttpResponse::Ok().body(res))
 }
 
+pub async fn delete_product(
+    input_data: web::Json<ProductDeleteData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    // Building the Action and Payload//
+    let action = ProductDeleteActionBuilder::new()
+        .with_product_id(input_data.product_id.to_string())
+        .with_product_type(ProductType::GS1)
+        .with_reason(input_data.reason.to_string())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let payload = ProductPayloadBuilder::new()
+        .with_action(Action::ProductDelete(action))
+        .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        PRODUCT_FAMILY_NAME, 
+        PRODUCT_FAMILY_VERSION, 
+        &input_data.private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[get_product_prefix(), get_pike_prefix()],
+        &[get_product_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    println!("============ delete_product_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn patch_product(
+    input_data: web::Json<ProductPatchData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    // Creating the Patches //
+    let definitions = fetch_product_schema_properties(&input_data.product_id).await?;
+    let mut patches = Vec::<PropertyPatch>::new();
+    for patch_data in &input_data.patches {
+        let operation = match patch_data.operation.to_lowercase().as_str() {
+            "set" => PatchOperation::Set,
+            "add" => PatchOperation::Add,
+            "remove" => PatchOperation::Remove,
+            _ => {
+                return Err(RestApiResponseError::BadRequest(format!(
+                    "Operation must be one of set, add or remove: {:?}",
+                    patch_data.operation
+                )))
+            }
+        };
+
+        let mut builder = PropertyPatchBuilder::new()
+            .with_operation(operation)
+            .with_name(patch_data.name.to_string());
+        if let Some(value) = parse_property_values(&patch_data.value, &definitions)?.pop() {
+            builder = builder.with_value(value);
+        }
+        if let Some(expected_value) = parse_property_values(&patch_data.expected_value, &definitions)?.pop() {
+            builder = builder.with_expected_value(expected_value);
+        }
+        patches.push(
+            builder
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+
+    // Building the Action and Payload//
+    let action = ProductPatchActionBuilder::new()
+        .with_product_id(input_data.product_id.to_string())
+        .with_product_type(ProductType::GS1)
+        .with_patches(patches)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let payload = ProductPayloadBuilder::new()
+        .with_action(Action::ProductPatch(action))
+        .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        PRODUCT_FAMILY_NAME, 
+        PRODUCT_FAMILY_VERSION, 
+        &input_data.private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[get_product_prefix(), get_pike_prefix(), get_schema_prefix(), get_record_prefix()],
+        &[get_product_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    println!("============ patch_product_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn list_product_proposals(
+    product_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let url = format!(
+        "http://rest-api:8008/state?address={}{}",
+        get_product_proposal_prefix(),
+        hash(&product_id, 36)
+    );
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    let mut response_data = "[".to_owned();
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let proposals: product_state::ProductProposalList = match protobuf::parse_from_bytes(&msg){
+            Ok(proposals) => proposals,
+            Err(err) => {
+                return Err(RestApiResponseError::ApplyError(ApplyError::InternalError(format!(
+                    "Cannot deserialize data: {:?}",
+                    err,
+                ))))
+            }
+        };
+
+        for proposal in proposals.get_entries() {
+            println!("!dgc-network! response_data: ");
+            println!("    product_id: {:?},", proposal.product_id);
+            println!("    issuing_org: {:?},", proposal.issuing_org);
+            println!("    receiving_org: {:?},", proposal.receiving_org);
+            println!("    status: {:?}", proposal.status);
+
+            response_data = response_data + &format!("\n  {{\n    product_id: {:?}, \n    timestamp: {:?}, \n    issuing_agent: {:?}, \n    issuing_org: {:?}, \n    receiving_org: {:?}, \n    status: {:?}, \n    terms: {:?}, \n  }},\n", proposal.product_id, proposal.timestamp, proposal.issuing_agent, proposal.issuing_org, proposal.receiving_org, proposal.status, proposal.terms);
+        }
+    }
+    response_data = response_data + &format!("]");
+    Ok(HttpResponse::Ok().body(response_data))
+}
+
+pub async fn create_product_proposal(
+    input_data: web::Json<ProductProposalData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    // Building the Action and Payload//
+    let action = ProductCreateProposalActionBuilder::new()
+        .with_product_id(input_data.product_id.to_string())
+        .with_product_type(ProductType::GS1)
+        .with_receiving_org(input_data.receiving_org.to_string())
+        .with_terms(input_data.terms.to_string())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let payload = ProductPayloadBuilder::new()
+        .with_action(Action::ProductCreateProposal(action))
+        .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    submit_product_proposal_payload(&input_data.private_key, payload).await
+}
+
+pub async fn answer_product_proposal(
+    input_data: web::Json<ProductProposalData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let response = match input_data.response.to_lowercase().as_str() {
+        "accept" => Response::Accept,
+        "reject" => Response::Reject,
+        "cancel" => Response::Cancel,
+        _ => {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "Response must be one of accept, reject or cancel: {:?}",
+                input_data.response
+            )))
+        }
+    };
+
+    // Building the Action and Payload//
+    let action = ProductAnswerProposalActionBuilder::new()
+        .with_product_id(input_data.product_id.to_string())
+        .with_product_type(ProductType::GS1)
+        .with_receiving_org(input_data.receiving_org.to_string())
+        .with_response(response)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let payload = ProductPayloadBuilder::new()
+        .with_action(Action::ProductAnswerProposal(action))
+        .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    submit_product_proposal_payload(&input_data.private_key, payload).await
+}
+
+async fn submit_product_proposal_payload(
+    private_key: &str,
+    payload: ProductPayload,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        PRODUCT_FAMILY_NAME, 
+        PRODUCT_FAMILY_VERSION, 
+        private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[get_product_prefix(), get_product_proposal_prefix(), get_pike_prefix()],
+        &[get_product_prefix(), get_product_proposal_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    println!("============ product_proposal_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+/// Fetches the property definitions of the schema of a product, if it has one
+async fn fetch_product_schema_properties(
+    product_id: &str,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    let url = format!("http://rest-api:8008/state/{}", make_product_address(product_id));
+    let res = reqwest::get(&url).await?.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let products = ProductList::from_bytes(&msg)?;
+    match products.products().iter().find(|product| product.product_id() == product_id) {
+        Some(product) => fetch_schema_properties(product.schema()).await,
+        None => Err(RestApiResponseError::NotFoundError(format!(
+            "Product {} not found",
+            product_id
+        ))),
+    }
+}
+
 fn retrieve_property_values(
     input_data: &web::Json<ProductData>,
-) -> Vec::<PropertyValue> {
+    definitions: &[PropertyDefinition],
+) -> Result<Vec::<PropertyValue>, RestApiResponseError> {
+    let mut properties = parse_property_values(&input_data.properties, definitions)?;
+    properties.append(&mut retrieve_list_property_values(&input_data.list_properties, definitions)?);
+    Ok(properties)
+}
+
+fn parse_property_values(
+    properties_as_string: &str,
+    definitions: &[PropertyDefinition],
+) -> Result<Vec::<PropertyValue>, RestApiResponseError> {
 
     let mut properties = Vec::<PropertyValue>::new();
-    let properties_as_string = &input_data.properties;
+    if properties_as_string.is_empty() {
+        return Ok(properties)
+    }
     let vec: Vec<&str> = properties_as_string.split(",").collect();
     let key_val_vec = split_vec(vec, 9);
     for key_val in key_val_vec {
@@ -226,6 +616,8 @@ fn retrieve_property_values(
                 else if (value == &"Enum") | (value == &"enum") | (value == &"ENUM") {DataType::Enum}
                 else if (value == &"Struct") | (value == &"struct") | (value == &"STRUCT") {DataType::Struct}
                 else if (value == &"LatLong") | (value == &"LatLong") | (value == &"LATLONG") {DataType::LatLong}
+                else if (value == &"Reference") | (value == &"reference") | (value == &"REFERENCE") {DataType::Reference}
+                else if (value == &"Timestamp") | (value == &"timestamp") | (value == &"TIMESTAMP") {DataType::Timestamp}
                 else {DataType::String},
             None => DataType::String
         };
@@ -263,22 +655,41 @@ fn retrieve_property_values(
             properties.push(property_value.clone());    
         }
 
+        // A number value may be a decimal such as 12.50, which is converted exactly with
+        // the number_exponent of the schema
         if data_type == DataType::Number {
             let string_value = match key_val.get(4) {
                 Some(value) => value.to_string(),
                 None => "0".to_string()
             };    
-            let number_value = string_value.parse::<i64>();
+            let number_value = parse_number_value(&name, &string_value, definitions)?;
 
             let property_value = PropertyValueBuilder::new()
             .with_name(name.clone().into())
             .with_data_type(DataType::Number)
-            .with_number_value(number_value.unwrap())
+            .with_number_value(number_value)
             .build()
             .unwrap();
             properties.push(property_value.clone());    
         }
 
+        // A timestamp value is given in the number_value position, as an RFC 3339 date
+        if data_type == DataType::Timestamp {
+            let string_value = match key_val.get(4) {
+                Some(value) => value.to_string(),
+                None => "0".to_string()
+            };
+            let timestamp_value = parse_timestamp_value(&name, &string_value, definitions)?;
+
+            let property_value = PropertyValueBuilder::new()
+            .with_name(name.clone().into())
+            .with_data_type(DataType::Timestamp)
+            .with_timestamp_value(timestamp_value)
+            .build()
+            .unwrap();
+            properties.push(property_value.clone());
+        }
+
         if data_type == DataType::String {
             let string_value = match key_val.get(5) {
                 Some(value) => value.to_string(),
@@ -310,8 +721,24 @@ fn retrieve_property_values(
             properties.push(property_value.clone());    
         }
 
+        // A reference value is given in the string_value position
+        if data_type == DataType::Reference {
+            let reference_value = match key_val.get(5) {
+                Some(value) => value.to_string(),
+                None => "reference_value is formated incorrectly".to_string()
+            };
+
+            let property_value = PropertyValueBuilder::new()
+            .with_name(name.clone().into())
+            .with_data_type(DataType::Reference)
+            .with_reference_value(reference_value)
+            .build()
+            .unwrap();
+            properties.push(property_value.clone());
+        }
+
     }
-    return properties
+    Ok(properties)
 }
 
 fn make_properties() -> Vec<PropertyValue> {
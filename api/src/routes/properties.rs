@@ -0,0 +1,312 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::RestApiResponseError;
+use crate::Fetch;
+
+use dgc_config::addressing::*;
+use dgc_config::protocol::schema::state::*;
+use dgc_config::protos::*;
+
+/// A LIST property value given as JSON, e.g. `{"name": "readings", "values": ["1.5", "2.25"]}`.
+/// The elements are built with the list element definition of the schema.
+#[derive(Deserialize)]
+pub struct ListPropertyData {
+    pub name: String,
+    pub values: Vec<Value>,
+}
+
+pub fn parse_data_type(value: &str) -> Option<DataType> {
+    match value.to_uppercase().as_str() {
+        "BYTES" => Some(DataType::Bytes),
+        "BOOLEAN" => Some(DataType::Boolean),
+        "NUMBER" => Some(DataType::Number),
+        "STRING" => Some(DataType::String),
+        "ENUM" => Some(DataType::Enum),
+        "STRUCT" => Some(DataType::Struct),
+        "LATLONG" => Some(DataType::LatLong),
+        "REFERENCE" => Some(DataType::Reference),
+        "LIST" => Some(DataType::List),
+        "TIMESTAMP" => Some(DataType::Timestamp),
+        _ => None,
+    }
+}
+
+/// Fetches the property definitions of a schema, used to convert decimal NUMBER and
+/// TIMESTAMP values. An empty schema name has no definitions.
+pub async fn fetch_schema_properties(
+    schema_name: &str,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    if schema_name.is_empty() {
+        return Ok(vec![]);
+    }
+    let url = format!("http://rest-api:8008/state/{}", make_schema_address(schema_name));
+    let res = reqwest::get(&url).await?;
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(RestApiResponseError::NotFoundError(format!(
+            "Schema {} not found",
+            schema_name
+        )));
+    }
+    let res = res.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let schemas = SchemaList::from_bytes(&msg)?;
+    Ok(schemas
+        .schemas()
+        .iter()
+        .find(|schema| schema.name() == schema_name)
+        .map(|schema| schema.properties().to_vec())
+        .unwrap_or_default())
+}
+
+/// Builds LIST property values from their JSON representation. Every element is named
+/// after the list property.
+pub fn retrieve_list_property_values(
+    list_properties: &[ListPropertyData],
+    definitions: &[PropertyDefinition],
+) -> Result<Vec<PropertyValue>, RestApiResponseError> {
+    let mut properties = Vec::<PropertyValue>::new();
+    for list_property in list_properties {
+        let element = definitions
+            .iter()
+            .find(|d| d.name() == list_property.name)
+            .and_then(|d| d.list_element())
+            .ok_or_else(|| {
+                RestApiResponseError::UserError(format!(
+                    "Property {} is not a LIST property of the schema",
+                    list_property.name
+                ))
+            })?;
+
+        let list_values = list_property
+            .values
+            .iter()
+            .map(|value| json_to_property_value(&list_property.name, element, value))
+            .collect::<Result<Vec<PropertyValue>, RestApiResponseError>>()?;
+
+        properties.push(
+            PropertyValueBuilder::new()
+                .with_name(list_property.name.to_string())
+                .with_data_type(DataType::List)
+                .with_list_values(list_values)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+    Ok(properties)
+}
+
+/// Converts a NUMBER value given as a decimal string, e.g. "12.50", into its number_value
+/// using the number_exponent of the named property definition. Properties that are not
+/// defined are treated as having an exponent of 0.
+pub fn parse_number_value(
+    name: &str,
+    value: &str,
+    definitions: &[PropertyDefinition],
+) -> Result<i64, RestApiResponseError> {
+    let number_exponent = definitions
+        .iter()
+        .find(|d| d.name() == name)
+        .map(|d| *d.number_exponent())
+        .unwrap_or(0);
+    decimal_to_number(value, number_exponent).map_err(|err| {
+        RestApiResponseError::UserError(format!("Value provided for {} is invalid: {}", name, err))
+    })
+}
+
+/// Converts a TIMESTAMP value into the time since the UNIX epoch in the precision of the
+/// named property definition. The value is either an RFC 3339 date, e.g.
+/// "2020-01-01T12:00:00.250Z", or an integer already in that precision.
+pub fn parse_timestamp_value(
+    name: &str,
+    value: &str,
+    definitions: &[PropertyDefinition],
+) -> Result<i64, RestApiResponseError> {
+    let precision = definitions
+        .iter()
+        .find(|d| d.name() == name)
+        .map(|d| d.timestamp_precision().clone())
+        .unwrap_or_default();
+    timestamp_to_value(value, &precision).map_err(|err| {
+        RestApiResponseError::UserError(format!("Value provided for {} is invalid: {}", name, err))
+    })
+}
+
+fn timestamp_to_value(timestamp: &str, precision: &TimestampPrecision) -> Result<i64, String> {
+    let timestamp = timestamp.trim();
+    if let Ok(value) = timestamp.parse::<i64>() {
+        return Ok(value);
+    }
+    let date_time = DateTime::parse_from_rfc3339(timestamp)
+        .map_err(|err| format!("{:?} is not an RFC 3339 timestamp: {}", timestamp, err))?;
+    let nanos_per_unit = 1_000_000_000 / precision.units_per_second();
+    let nanos = i64::from(date_time.timestamp_subsec_nanos());
+    if nanos % nanos_per_unit != 0 {
+        return Err(format!(
+            "{:?} is more precise than {:?}",
+            timestamp, precision
+        ));
+    }
+    date_time
+        .timestamp()
+        .checked_mul(precision.units_per_second())
+        .and_then(|value| value.checked_add(nanos / nanos_per_unit))
+        .ok_or_else(|| format!("{:?} is out of range", timestamp))
+}
+
+/// Formats a TIMESTAMP value as an RFC 3339 date in UTC with as many fractional digits as
+/// the precision has, or as the raw integer when it is outside of the dates chrono supports.
+pub fn format_timestamp(value: i64, precision: &TimestampPrecision) -> String {
+    let units_per_second = precision.units_per_second();
+    let seconds = value.div_euclid(units_per_second);
+    let nanos = value.rem_euclid(units_per_second) * (1_000_000_000 / units_per_second);
+    let seconds_format = match precision {
+        TimestampPrecision::Seconds => SecondsFormat::Secs,
+        TimestampPrecision::Milliseconds => SecondsFormat::Millis,
+        TimestampPrecision::Microseconds => SecondsFormat::Micros,
+        TimestampPrecision::Nanoseconds => SecondsFormat::Nanos,
+    };
+    match Utc.timestamp_opt(seconds, nanos as u32).single() {
+        Some(date_time) => date_time.to_rfc3339_opts(seconds_format, true),
+        None => value.to_string(),
+    }
+}
+
+/// Builds a property value for the given definition from a JSON value. NUMBER values
+/// are given as decimal strings and TIMESTAMP values as RFC 3339 dates, though plain
+/// integers are accepted for both. Structs and nested lists can not be given as JSON.
+pub fn json_to_property_value(
+    name: &str,
+    definition: &PropertyDefinition,
+    value: &Value,
+) -> Result<PropertyValue, RestApiResponseError> {
+    let data_type = definition.data_type();
+    let invalid = || {
+        RestApiResponseError::UserError(format!(
+            "Value provided for {} is not a valid {:?}: {}",
+            name, data_type, value
+        ))
+    };
+    // Integers are accepted as strings so that they can be converted exactly
+    let number_string = || match value {
+        Value::String(string) => Ok(string.to_string()),
+        Value::Number(number) if number.is_i64() => Ok(number.to_string()),
+        _ => Err(invalid()),
+    };
+
+    let builder = PropertyValueBuilder::new()
+        .with_name(name.to_string())
+        .with_data_type(data_type.clone());
+    let builder = match data_type {
+        DataType::Bytes => {
+            builder.with_bytes_value(value.as_str().ok_or_else(invalid)?.as_bytes().to_vec())
+        }
+        DataType::Boolean => builder.with_boolean_value(value.as_bool().ok_or_else(invalid)?),
+        DataType::Number => builder.with_number_value(
+            decimal_to_number(&number_string()?, *definition.number_exponent()).map_err(
+                |err| {
+                    RestApiResponseError::UserError(format!(
+                        "Value provided for {} is invalid: {}",
+                        name, err
+                    ))
+                },
+            )?,
+        ),
+        DataType::String => {
+            builder.with_string_value(value.as_str().ok_or_else(invalid)?.to_string())
+        }
+        DataType::Enum => builder.with_enum_value(value.as_u64().ok_or_else(invalid)? as u32),
+        DataType::LatLong => {
+            let latitude = value.get("latitude").and_then(Value::as_i64).ok_or_else(invalid)?;
+            let longitude = value.get("longitude").and_then(Value::as_i64).ok_or_else(invalid)?;
+            builder.with_lat_long_value(
+                LatLongBuilder::new()
+                    .with_lat_long(latitude, longitude)
+                    .build()
+                    .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+            )
+        }
+        DataType::Reference => {
+            builder.with_reference_value(value.as_str().ok_or_else(invalid)?.to_string())
+        }
+        DataType::Timestamp => builder.with_timestamp_value(
+            timestamp_to_value(&number_string()?, definition.timestamp_precision()).map_err(
+                |err| {
+                    RestApiResponseError::UserError(format!(
+                        "Value provided for {} is invalid: {}",
+                        name, err
+                    ))
+                },
+            )?,
+        ),
+        DataType::Struct | DataType::List => {
+            return Err(RestApiResponseError::UserError(format!(
+                "{:?} values can not be provided for {} as JSON",
+                data_type, name
+            )))
+        }
+    };
+
+    builder
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))
+}
+
+/// Converts property values into a JSON object keyed by property name, using the
+/// definitions of the schema to format the values.
+pub fn properties_to_json(values: &[PropertyValue], definitions: &[PropertyDefinition]) -> Value {
+    let mut properties = Map::new();
+    for value in values {
+        let definition = definitions.iter().find(|d| d.name() == value.name());
+        properties.insert(value.name().to_string(), property_value_to_json(value, definition));
+    }
+    Value::Object(properties)
+}
+
+/// Converts a property value into JSON. NUMBER values become decimal strings using the
+/// exponent of the definition and TIMESTAMP values become RFC 3339 dates, struct values
+/// become objects keyed by field name and list values become arrays. Without a
+/// definition, NUMBER values have an exponent of 0 and TIMESTAMP values are in seconds.
+pub fn property_value_to_json(
+    value: &PropertyValue,
+    definition: Option<&PropertyDefinition>,
+) -> Value {
+    match value.data_type() {
+        DataType::Bytes => json!(String::from_utf8_lossy(value.bytes_value())),
+        DataType::Boolean => json!(value.boolean_value()),
+        DataType::Number => json!(number_to_decimal(
+            *value.number_value(),
+            definition.map(|d| *d.number_exponent()).unwrap_or(0)
+        )),
+        DataType::String => json!(value.string_value()),
+        DataType::Enum => json!(value.enum_value()),
+        DataType::Struct => properties_to_json(
+            value.struct_values(),
+            definition.map(|d| d.struct_properties()).unwrap_or(&[]),
+        ),
+        DataType::LatLong => json!({
+            "latitude": value.lat_long_value().latitude(),
+            "longitude": value.lat_long_value().longitude(),
+        }),
+        DataType::Reference => json!(value.reference_value()),
+        DataType::List => Value::Array(
+            value
+                .list_values()
+                .iter()
+                .map(|list_value| {
+                    property_value_to_json(list_value, definition.and_then(|d| d.list_element()))
+                })
+                .collect(),
+        ),
+        DataType::Timestamp => json!(format_timestamp(
+            *value.timestamp_value(),
+            &definition
+                .map(|d| d.timestamp_precision().clone())
+                .unwrap_or_default()
+        )),
+    }
+}
@@ -0,0 +1,654 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use actix_web::*;
+use serde::Deserialize;
+use serde_json::json;
+use protobuf::Message;
+use reqwest;
+use chrono;
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use crate::transaction::BatchBuilder;
+use crate::error::RestApiResponseError;
+use crate::confidential::{record_data_key, wrap_data_key};
+use crate::routes::records::fetch_record_entry;
+use crate::{List, Fetch};
+
+use dgc_config::protos::*;
+use dgc_config::addressing::*;
+use dgc_config::protocol::pike::state::{AgentList, Organization, OrganizationList};
+use dgc_config::protocol::track_and_trace::state::{
+    AssociatedAgent, BulkProposal, BulkProposalList, Condition, ConditionBuilder, ConditionType,
+    Proposal, ProposalList, Role, Status,
+};
+use dgc_config::protocol::track_and_trace::payload::*;
+
+#[derive(Deserialize)]
+pub struct RecordProposalData {
+    private_key: String,
+    record_id: String,
+    receiving_agent: String,
+    role: String,
+    #[serde(default)]
+    properties: Vec<String>,
+    #[serde(default)]
+    terms: String,
+    #[serde(default)]
+    expires_at: u64,
+    #[serde(default)]
+    conditions: Vec<ProposalConditionData>,
+}
+
+/// A condition that must be met before a proposal can be accepted: either every reported value
+/// of a NUMBER property within `min` and `max`, or a counter-signature from an admin of `org_id`
+#[derive(Deserialize)]
+pub struct ProposalConditionData {
+    condition_type: String,
+    #[serde(default)]
+    property_name: String,
+    #[serde(default)]
+    min: i64,
+    #[serde(default)]
+    max: i64,
+    #[serde(default)]
+    org_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct RecordProposalCounterSignatureData {
+    private_key: String,
+    record_id: String,
+    receiving_agent: String,
+    role: String,
+}
+
+#[derive(Deserialize)]
+pub struct RecordProposalAnswerData {
+    private_key: String,
+    record_id: String,
+    receiving_agent: String,
+    role: String,
+    response: String,
+}
+
+#[derive(Deserialize)]
+pub struct BulkProposalData {
+    private_key: String,
+    proposal_id: String,
+    record_ids: Vec<String>,
+    receiving_agent: String,
+    role: String,
+    #[serde(default)]
+    terms: String,
+    #[serde(default)]
+    expires_at: u64,
+}
+
+#[derive(Deserialize)]
+pub struct BulkProposalAnswerData {
+    private_key: String,
+    proposal_id: String,
+    receiving_agent: String,
+    response: String,
+}
+
+/// Lists the proposals of a record, including the proposals to reopen it when it is final
+pub async fn list_record_proposals(
+    record_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let url = format!(
+        "http://rest-api:8008/state?address={}{}",
+        get_proposal_prefix(),
+        hash(&record_id, 36)
+    );
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    let mut response_data = Vec::new();
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let proposals = ProposalList::from_bytes(&msg)?;
+        response_data.extend(
+            proposals
+                .proposals()
+                .iter()
+                .filter(|proposal| proposal.record_id() == record_id.as_str())
+                .map(proposal_to_json),
+        );
+    }
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+/// Returns the chain of ownership and of custody of a record as timelines of intervals.
+/// Each interval names the agent holding the role, the Pike organization it belonged to when
+/// it was handed the role, and the accepted proposal recorded as handing the role over; the
+/// first holders come from the record's creation.
+pub async fn fetch_record_custody_chain(
+    record_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let record = match fetch_record_entry(&record_id).await? {
+        Some(record) => record,
+        None => {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Record {} not found",
+                record_id.as_str()
+            )))
+        }
+    };
+
+    let url = format!(
+        "http://rest-api:8008/state?address={}{}",
+        get_proposal_prefix(),
+        hash(&record_id, 36)
+    );
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    let mut proposals = Vec::new();
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let proposal_list = ProposalList::from_bytes(&msg)?;
+        proposals.extend(
+            proposal_list
+                .proposals()
+                .iter()
+                .filter(|proposal| {
+                    proposal.record_id() == record_id.as_str()
+                        && proposal.status() == &Status::Accepted
+                })
+                .cloned(),
+        );
+    }
+
+    let url = format!("http://rest-api:8008/state?address={}", get_bulk_proposal_prefix());
+    let bulk_proposals = fetch_bulk_proposals(&url)
+        .await?
+        .into_iter()
+        .filter(|proposal| {
+            proposal.status() == &Status::Accepted
+                && proposal.record_ids().iter().any(|id| id == record_id.as_str())
+        })
+        .collect::<Vec<_>>();
+
+    // Entries written before the organization was recorded fall back to the agent's
+    // current organization
+    let mut agent_org_ids = HashMap::new();
+    for associated_agent in record.owners().iter().chain(record.custodians().iter()) {
+        let agent_id = associated_agent.agent_id();
+        if associated_agent.org_id().is_empty() && !agent_org_ids.contains_key(agent_id) {
+            let org_id = fetch_agent_org_id(agent_id).await?;
+            agent_org_ids.insert(agent_id.to_string(), org_id);
+        }
+    }
+    let org_id_of = |associated_agent: &AssociatedAgent| -> Option<String> {
+        if associated_agent.org_id().is_empty() {
+            agent_org_ids
+                .get(associated_agent.agent_id())
+                .cloned()
+                .flatten()
+        } else {
+            Some(associated_agent.org_id().to_string())
+        }
+    };
+
+    let mut organizations = HashMap::new();
+    for associated_agent in record.owners().iter().chain(record.custodians().iter()) {
+        if let Some(org_id) = org_id_of(associated_agent) {
+            if !organizations.contains_key(&org_id) {
+                let organization = fetch_organization(&org_id).await?;
+                organizations.insert(org_id, organization);
+            }
+        }
+    }
+
+    let chain = |associated_agents: &[AssociatedAgent], role: Role| {
+        associated_agents
+            .iter()
+            .enumerate()
+            .map(|(i, associated_agent)| {
+                let org_id = org_id_of(associated_agent);
+                let org_name = org_id
+                    .as_ref()
+                    .and_then(|org_id| organizations.get(org_id))
+                    .and_then(|organization| organization.as_ref())
+                    .map(|organization| organization.name().to_string());
+                let proposal =
+                    find_transfer_proposal(&proposals, &bulk_proposals, &role, associated_agent);
+                json!({
+                    "agent_id": associated_agent.agent_id(),
+                    "org_id": org_id,
+                    "org_name": org_name,
+                    "from": associated_agent.timestamp(),
+                    "to": associated_agents.get(i + 1).map(|next| *next.timestamp()),
+                    "current": i + 1 == associated_agents.len(),
+                    "proposal": proposal,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let response_data = json!({
+        "record_id": record.record_id(),
+        "owners": chain(record.owners(), Role::Owner),
+        "custodians": chain(record.custodians(), Role::Custodian),
+    });
+    println!("!dgc-network! response_data: {}", response_data);
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+/// Finds the accepted proposal, single or bulk, recorded as having handed `role` to an
+/// associated agent. Agents associated when the record was created have none.
+fn find_transfer_proposal(
+    proposals: &[Proposal],
+    bulk_proposals: &[BulkProposal],
+    role: &Role,
+    associated_agent: &AssociatedAgent,
+) -> Option<serde_json::Value> {
+    let agent_id = associated_agent.agent_id();
+    if !associated_agent.bulk_proposal_id().is_empty() {
+        return bulk_proposals
+            .iter()
+            .find(|proposal| {
+                proposal.proposal_id() == associated_agent.bulk_proposal_id()
+                    && proposal.receiving_agent() == agent_id
+            })
+            .map(bulk_proposal_to_json);
+    }
+    if associated_agent.proposal_issuing_agent().is_empty() {
+        return None;
+    }
+    proposals
+        .iter()
+        .find(|proposal| {
+            proposal.role() == role
+                && proposal.receiving_agent() == agent_id
+                && proposal.issuing_agent() == associated_agent.proposal_issuing_agent()
+                && proposal.timestamp() == associated_agent.proposal_timestamp()
+        })
+        .map(proposal_to_json)
+}
+
+/// Fetches the id of the Pike organization an agent currently belongs to, if the agent exists
+async fn fetch_agent_org_id(public_key: &str) -> Result<Option<String>, RestApiResponseError> {
+    let url = format!("http://rest-api:8008/state/{}", make_agent_address(public_key));
+    let res = reqwest::get(&url).await?;
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let res = res.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let agents = AgentList::from_bytes(&msg)?;
+    Ok(agents
+        .agents()
+        .iter()
+        .find(|agent| agent.public_key() == public_key)
+        .map(|agent| agent.org_id().to_string()))
+}
+
+/// Fetches a Pike organization from state, if it exists
+async fn fetch_organization(org_id: &str) -> Result<Option<Organization>, RestApiResponseError> {
+    let url = format!("http://rest-api:8008/state/{}", make_org_address(org_id));
+    let res = reqwest::get(&url).await?;
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let res = res.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let organizations = OrganizationList::from_bytes(&msg)?;
+    Ok(organizations
+        .organizations()
+        .iter()
+        .find(|organization| organization.org_id() == org_id)
+        .cloned())
+}
+
+pub async fn create_record_proposal(
+    input_data: web::Json<RecordProposalData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let role = parse_role(&input_data.role)?;
+    let conditions = input_data
+        .conditions
+        .iter()
+        .map(parse_condition)
+        .collect::<Result<Vec<_>, _>>()?;
+    let wrapped_data_key = wrap_record_data_key(
+        &input_data.record_id,
+        &input_data.receiving_agent,
+        &role,
+        &input_data.private_key,
+    ).await?;
+    let action = CreateProposalActionBuilder::new()
+        .with_record_id(input_data.record_id.to_string())
+        .with_receiving_agent(input_data.receiving_agent.to_string())
+        .with_role(role)
+        .with_properties(input_data.properties.clone())
+        .with_terms(input_data.terms.to_string())
+        .with_expires_at(input_data.expires_at)
+        .with_conditions(conditions)
+        .with_wrapped_data_key(wrapped_data_key)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_proposal_action(&input_data.private_key, Action::CreateProposal(action)).await?;
+
+    println!("============ create_record_proposal_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+/// Wraps the data key of a record with confidential properties for the receiving agent of a
+/// proposal, unless the agent already holds it. The issuing agent must hold the data key.
+async fn wrap_record_data_key(
+    record_id: &str,
+    receiving_agent: &str,
+    role: &Role,
+    private_key: &str,
+) -> Result<Vec<u8>, RestApiResponseError> {
+    let record = match fetch_record_entry(record_id).await? {
+        Some(record) => record,
+        None => return Ok(Vec::new()),
+    };
+    if record.data_keys().is_empty()
+        || record.data_key(receiving_agent).is_some()
+        || *role == Role::Reopen
+    {
+        return Ok(Vec::new());
+    }
+    match record_data_key(&record, private_key)? {
+        Some(data_key) => wrap_data_key(&data_key, receiving_agent),
+        None => Err(RestApiResponseError::UserError(format!(
+            "The data key of record {} has not been shared with the signer",
+            record_id
+        ))),
+    }
+}
+
+pub async fn answer_record_proposal(
+    input_data: web::Json<RecordProposalAnswerData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let role = parse_role(&input_data.role)?;
+    let response = parse_response(&input_data.response)?;
+    let action = AnswerProposalActionBuilder::new()
+        .with_record_id(input_data.record_id.to_string())
+        .with_receiving_agent(input_data.receiving_agent.to_string())
+        .with_role(role)
+        .with_response(response)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_proposal_action(&input_data.private_key, Action::AnswerProposal(action)).await?;
+
+    println!("============ answer_record_proposal_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+/// Counter-signs an open proposal as an admin of an organization named in one of its conditions
+pub async fn counter_sign_record_proposal(
+    input_data: web::Json<RecordProposalCounterSignatureData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let role = parse_role(&input_data.role)?;
+    let action = CounterSignProposalActionBuilder::new()
+        .with_record_id(input_data.record_id.to_string())
+        .with_receiving_agent(input_data.receiving_agent.to_string())
+        .with_role(role)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_proposal_action(
+        &input_data.private_key,
+        Action::CounterSignProposal(action),
+    ).await?;
+
+    println!("============ counter_sign_record_proposal_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn fetch_bulk_proposal(
+    proposal_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let url = format!(
+        "http://rest-api:8008/state?address={}{}",
+        get_bulk_proposal_prefix(),
+        hash(&proposal_id, 36)
+    );
+    let response_data = fetch_bulk_proposals(&url)
+        .await?
+        .iter()
+        .filter(|proposal| proposal.proposal_id() == proposal_id.as_str())
+        .map(bulk_proposal_to_json)
+        .collect::<Vec<_>>();
+    if response_data.is_empty() {
+        return Err(RestApiResponseError::NotFoundError(format!(
+            "Bulk proposal {} not found",
+            proposal_id
+        )));
+    }
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+/// Lists the bulk proposals sent by or to an agent
+pub async fn list_agent_bulk_proposals(
+    public_key: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let url = format!("http://rest-api:8008/state?address={}", get_bulk_proposal_prefix());
+    let response_data = fetch_bulk_proposals(&url)
+        .await?
+        .iter()
+        .filter(|proposal| {
+            proposal.issuing_agent() == public_key.as_str()
+                || proposal.receiving_agent() == public_key.as_str()
+        })
+        .map(bulk_proposal_to_json)
+        .collect::<Vec<_>>();
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+pub async fn create_bulk_proposal(
+    input_data: web::Json<BulkProposalData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let role = parse_role(&input_data.role)?;
+    let action = CreateBulkProposalActionBuilder::new()
+        .with_proposal_id(input_data.proposal_id.to_string())
+        .with_record_ids(input_data.record_ids.clone())
+        .with_receiving_agent(input_data.receiving_agent.to_string())
+        .with_role(role)
+        .with_terms(input_data.terms.to_string())
+        .with_expires_at(input_data.expires_at)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_proposal_action(
+        &input_data.private_key,
+        Action::CreateBulkProposal(action),
+    ).await?;
+
+    println!("============ create_bulk_proposal_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn answer_bulk_proposal(
+    input_data: web::Json<BulkProposalAnswerData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let response = parse_response(&input_data.response)?;
+    let action = AnswerBulkProposalActionBuilder::new()
+        .with_proposal_id(input_data.proposal_id.to_string())
+        .with_receiving_agent(input_data.receiving_agent.to_string())
+        .with_response(response)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_proposal_action(
+        &input_data.private_key,
+        Action::AnswerBulkProposal(action),
+    ).await?;
+
+    println!("============ answer_bulk_proposal_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+fn parse_role(value: &str) -> Result<Role, RestApiResponseError> {
+    match value.to_uppercase().as_str() {
+        "OWNER" => Ok(Role::Owner),
+        "CUSTODIAN" => Ok(Role::Custodian),
+        "REPORTER" => Ok(Role::Reporter),
+        "REOPEN" => Ok(Role::Reopen),
+        _ => Err(RestApiResponseError::UserError(format!(
+            "Invalid proposal role {:?}, expected OWNER, CUSTODIAN, REPORTER or REOPEN",
+            value
+        ))),
+    }
+}
+
+fn parse_response(value: &str) -> Result<Response, RestApiResponseError> {
+    match value.to_uppercase().as_str() {
+        "ACCEPT" => Ok(Response::Accept),
+        "REJECT" => Ok(Response::Reject),
+        "CANCEL" => Ok(Response::Cancel),
+        _ => Err(RestApiResponseError::UserError(format!(
+            "Invalid proposal response {:?}, expected ACCEPT, REJECT or CANCEL",
+            value
+        ))),
+    }
+}
+
+fn parse_condition(data: &ProposalConditionData) -> Result<Condition, RestApiResponseError> {
+    let builder = match data.condition_type.to_uppercase().as_str() {
+        "PROPERTY_RANGE" => ConditionBuilder::new().with_property_range(
+            data.property_name.to_string(),
+            data.min,
+            data.max,
+        ),
+        "COUNTER_SIGNATURE" => ConditionBuilder::new()
+            .with_condition_type(ConditionType::CounterSignature)
+            .with_org_id(data.org_id.to_string()),
+        _ => {
+            return Err(RestApiResponseError::UserError(format!(
+                "Invalid condition type {:?}, expected PROPERTY_RANGE or COUNTER_SIGNATURE",
+                data.condition_type
+            )))
+        }
+    };
+    builder
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))
+}
+
+fn condition_to_json(condition: &Condition) -> serde_json::Value {
+    match condition.condition_type() {
+        ConditionType::PropertyRange => json!({
+            "condition_type": "PROPERTY_RANGE",
+            "property_name": condition.property_name(),
+            "min": condition.min(),
+            "max": condition.max(),
+        }),
+        ConditionType::CounterSignature => json!({
+            "condition_type": "COUNTER_SIGNATURE",
+            "org_id": condition.org_id(),
+        }),
+    }
+}
+
+fn proposal_to_json(proposal: &Proposal) -> serde_json::Value {
+    json!({
+        "record_id": proposal.record_id(),
+        "timestamp": proposal.timestamp(),
+        "issuing_agent": proposal.issuing_agent(),
+        "receiving_agent": proposal.receiving_agent(),
+        "role": format!("{:?}", proposal.role()).to_uppercase(),
+        "properties": proposal.properties(),
+        "status": format!("{:?}", proposal.status()).to_uppercase(),
+        "terms": proposal.terms(),
+        "expires_at": proposal.expires_at(),
+        "conditions": proposal
+            .conditions()
+            .iter()
+            .map(condition_to_json)
+            .collect::<Vec<_>>(),
+        "counter_signatures": proposal
+            .counter_signatures()
+            .iter()
+            .map(|counter_signature| json!({
+                "agent_id": counter_signature.agent_id(),
+                "timestamp": counter_signature.timestamp(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn bulk_proposal_to_json(proposal: &BulkProposal) -> serde_json::Value {
+    json!({
+        "proposal_id": proposal.proposal_id(),
+        "timestamp": proposal.timestamp(),
+        "issuing_agent": proposal.issuing_agent(),
+        "receiving_agent": proposal.receiving_agent(),
+        "role": format!("{:?}", proposal.role()).to_uppercase(),
+        "record_ids": proposal.record_ids(),
+        "status": format!("{:?}", proposal.status()).to_uppercase(),
+        "terms": proposal.terms(),
+        "expires_at": proposal.expires_at(),
+    })
+}
+
+async fn fetch_bulk_proposals(url: &str) -> Result<Vec<BulkProposal>, RestApiResponseError> {
+    let list = reqwest::get(url).await?.json::<List>().await?;
+    let mut proposals = Vec::new();
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let proposal_list = BulkProposalList::from_bytes(&msg)?;
+        proposals.extend(proposal_list.proposals().iter().cloned());
+    }
+    Ok(proposals)
+}
+
+async fn submit_proposal_action(
+    private_key: &str,
+    action: Action,
+) -> Result<String, RestApiResponseError> {
+    let payload = TrackAndTracePayloadBuilder::new()
+        .with_action(action)
+        .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+        .build()
+        .unwrap();
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        TNT_FAMILY_NAME,
+        TNT_FAMILY_VERSION,
+        private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[get_track_and_trace_prefix(), get_pike_prefix(), get_schema_prefix()],
+        &[get_track_and_trace_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    Ok(res)
+}
@@ -0,0 +1,300 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use actix_web::*;
+use serde::Deserialize;
+use serde_json::json;
+use protobuf::Message;
+use reqwest;
+use chrono;
+use std::convert::TryInto;
+
+use crate::transaction::BatchBuilder;
+use crate::error::RestApiResponseError;
+use crate::routes::records::{fetch_record_entry, record_references_product};
+use crate::{List, Fetch};
+
+use dgc_config::protos::*;
+use dgc_config::addressing::*;
+use dgc_config::protocol::track_and_trace::state::{
+    Recall, RecallList, RecallStatus, Record, RecordList, Severity,
+};
+use dgc_config::protocol::track_and_trace::payload::*;
+
+#[derive(Deserialize)]
+pub struct RecallData {
+    private_key: String,
+    recall_id: String,
+    #[serde(default)]
+    product_id: String,
+    #[serde(default)]
+    record_ids: Vec<String>,
+    reason: String,
+    severity: String,
+}
+
+#[derive(Deserialize)]
+pub struct RecallAcknowledgementData {
+    private_key: String,
+    recall_id: String,
+    record_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct RecallCloseData {
+    private_key: String,
+    recall_id: String,
+}
+
+pub async fn list_recalls(
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let recalls = fetch_recalls().await?;
+    let response_data = recalls.iter().map(recall_to_json).collect::<Vec<_>>();
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+pub async fn fetch_recall(
+    recall_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let url = format!("http://rest-api:8008/state/{}", make_recall_address(&recall_id));
+    let res = reqwest::get(&url).await?;
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(RestApiResponseError::NotFoundError(format!(
+            "Recall {} not found",
+            recall_id
+        )));
+    }
+    let res = res.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let recalls = RecallList::from_bytes(&msg)?;
+    match recalls.recalls().iter().find(|recall| recall.recall_id() == recall_id.as_str()) {
+        Some(recall) => Ok(HttpResponse::Ok().json(recall_to_json(recall))),
+        None => Err(RestApiResponseError::NotFoundError(format!(
+            "Recall {} not found",
+            recall_id
+        ))),
+    }
+}
+
+/// Lists the open recalls of records currently in the custody of an agent, with the
+/// records still waiting for the agent's acknowledgement. A product recall also lists the
+/// records that reference the product.
+pub async fn list_custodian_recalls(
+    public_key: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let mut custody: Option<Vec<Record>> = None;
+    let mut response_data = Vec::new();
+    for recall in fetch_recalls().await? {
+        if *recall.status() != RecallStatus::Open {
+            continue;
+        }
+        let mut record_ids = Vec::new();
+        for record_id in recall.record_ids() {
+            let record = match fetch_record_entry(record_id).await? {
+                Some(record) => record,
+                None => continue,
+            };
+            let custodian = record.custodians().last().map(|custodian| custodian.agent_id());
+            if custodian == Some(public_key.as_str()) {
+                record_ids.push(record_id.to_string());
+            }
+        }
+        if !recall.product_id().is_empty() {
+            if custody.is_none() {
+                custody = Some(fetch_custodian_records(&public_key).await?);
+            }
+            for record in custody.iter().flatten() {
+                if record_ids.iter().any(|id| id == record.record_id()) {
+                    continue;
+                }
+                if record_references_product(record, recall.product_id()).await? {
+                    record_ids.push(record.record_id().to_string());
+                }
+            }
+        }
+
+        let records = record_ids
+            .iter()
+            .map(|record_id| {
+                let acknowledged = recall.acknowledgements().iter().any(|ack| {
+                    ack.record_id() == record_id && ack.agent_id() == public_key.as_str()
+                });
+                json!({
+                    "record_id": record_id,
+                    "acknowledged": acknowledged,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !records.is_empty() {
+            let mut recall_data = recall_to_json(&recall);
+            recall_data["custody"] = json!(records);
+            response_data.push(recall_data);
+        }
+    }
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+pub async fn create_recall(
+    input_data: web::Json<RecallData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let severity = parse_severity(&input_data.severity)?;
+    let action = CreateRecallActionBuilder::new()
+        .with_recall_id(input_data.recall_id.to_string())
+        .with_product_id(input_data.product_id.to_string())
+        .with_record_ids(input_data.record_ids.clone())
+        .with_reason(input_data.reason.to_string())
+        .with_severity(severity)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_recall_action(&input_data.private_key, Action::CreateRecall(action)).await?;
+
+    println!("============ create_recall_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn acknowledge_recall(
+    input_data: web::Json<RecallAcknowledgementData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let action = AcknowledgeRecallActionBuilder::new()
+        .with_recall_id(input_data.recall_id.to_string())
+        .with_record_id(input_data.record_id.to_string())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_recall_action(&input_data.private_key, Action::AcknowledgeRecall(action)).await?;
+
+    println!("============ acknowledge_recall_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn close_recall(
+    input_data: web::Json<RecallCloseData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let action = CloseRecallActionBuilder::new()
+        .with_recall_id(input_data.recall_id.to_string())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_recall_action(&input_data.private_key, Action::CloseRecall(action)).await?;
+
+    println!("============ close_recall_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+fn parse_severity(value: &str) -> Result<Severity, RestApiResponseError> {
+    match value.to_uppercase().as_str() {
+        "LOW" => Ok(Severity::Low),
+        "MEDIUM" => Ok(Severity::Medium),
+        "HIGH" => Ok(Severity::High),
+        _ => Err(RestApiResponseError::UserError(format!(
+            "Invalid recall severity {:?}, expected LOW, MEDIUM or HIGH",
+            value
+        ))),
+    }
+}
+
+fn recall_to_json(recall: &Recall) -> serde_json::Value {
+    json!({
+        "recall_id": recall.recall_id(),
+        "issuing_agent": recall.issuing_agent(),
+        "product_id": recall.product_id(),
+        "record_ids": recall.record_ids(),
+        "reason": recall.reason(),
+        "severity": format!("{:?}", recall.severity()).to_uppercase(),
+        "status": format!("{:?}", recall.status()).to_uppercase(),
+        "timestamp": recall.timestamp(),
+        "acknowledgements": recall
+            .acknowledgements()
+            .iter()
+            .map(|ack| json!({
+                "record_id": ack.record_id(),
+                "agent_id": ack.agent_id(),
+                "timestamp": ack.timestamp(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+async fn fetch_recalls() -> Result<Vec<Recall>, RestApiResponseError> {
+    let url = format!("http://rest-api:8008/state?address={}", get_recall_prefix());
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    let mut recalls = Vec::new();
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let recall_list = RecallList::from_bytes(&msg)?;
+        recalls.extend(recall_list.recalls().iter().cloned());
+    }
+    Ok(recalls)
+}
+
+/// Fetches the records an agent is currently the custodian of
+async fn fetch_custodian_records(agent_id: &str) -> Result<Vec<Record>, RestApiResponseError> {
+    let url = format!("http://rest-api:8008/state?address={}", get_record_prefix());
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    let mut records = Vec::new();
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let record_list = RecordList::from_bytes(&msg)?;
+        for record in record_list.records() {
+            let custodian = record.custodians().last().map(|custodian| custodian.agent_id());
+            if custodian == Some(agent_id) {
+                records.push(record.clone());
+            }
+        }
+    }
+    Ok(records)
+}
+
+async fn submit_recall_action(
+    private_key: &str,
+    action: Action,
+) -> Result<String, RestApiResponseError> {
+    let payload = TrackAndTracePayloadBuilder::new()
+        .with_action(action)
+        .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+        .build()
+        .unwrap();
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        TNT_FAMILY_NAME,
+        TNT_FAMILY_VERSION,
+        private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[
+            get_track_and_trace_prefix(),
+            get_schema_prefix(),
+            get_pike_prefix(),
+            get_product_prefix(),
+        ],
+        &[get_recall_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    Ok(res)
+}
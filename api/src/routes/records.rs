@@ -7,15 +7,22 @@ use serde::Deserialize;
 use protobuf::Message;
 use reqwest;
 use chrono;
+use std::collections::HashMap;
 use std::convert::TryInto;
 
 use crate::transaction::BatchBuilder;
 use crate::error::RestApiResponseError;
+use crate::confidential::*;
+use crate::routes::properties::*;
 use crate::{List, Fetch, split_vec};
 
 use dgc_config::protos::*;
 use dgc_config::addressing::*;
 //use dgc_config::protocol::track_and_trace::state::*;
+use dgc_config::protocol::track_and_trace::state::{
+    AssociatedAgent, DataKey, Excursion, Property, PropertyList, PropertyPageList, Record,
+    RecordList, ReportedValue,
+};
 use dgc_config::protocol::track_and_trace::payload::*;
 use dgc_config::protocol::schema::state::*;
 
@@ -25,6 +32,18 @@ pub struct RecordData {
     record_id: String,
     schema: String,
     properties: String,
+    #[serde(default)]
+    list_properties: Vec<ListPropertyData>,
+}
+
+#[derive(Deserialize)]
+pub struct RecordPropertiesData {
+    private_key: String,
+    record_id: String,
+    #[serde(default)]
+    properties: String,
+    #[serde(default)]
+    list_properties: Vec<ListPropertyData>,
 }
 
 pub async fn list_records(
@@ -49,11 +68,12 @@ pub async fn list_records(
             println!("!dgc-network! response_data: ");
             println!("    record_id: {:?},", record.record_id);
             println!("    schema: {:?},", record.schema);
+            println!("    schema_version: {:?},", record.schema_version);
             println!("    owners: {:?},", record.owners);
             println!("    custodians: {:?}", record.custodians);
             println!("    field_final: {:?}", record.field_final);
             
-            response_data = response_data + &format!("\n  {{\n    record_id: {:?}, \n    schema: {:?}, \n    owner: {:?}, \n    custodians: {:?}, \n    field_final: {:?}\n  }},\n", record.record_id, record.schema, record.owners, record.custodians, record.field_final);
+            response_data = response_data + &format!("\n  {{\n    record_id: {:?}, \n    schema: {:?}, \n    schema_version: {:?}, \n    owner: {:?}, \n    custodians: {:?}, \n    field_final: {:?}\n  }},\n", record.record_id, record.schema, record.schema_version, record.owners, record.custodians, record.field_final);
         }
     }
     response_data = response_data + &format!("]");
@@ -82,11 +102,12 @@ pub async fn fetch_record(
         println!("!dgc-network! response_data: ");
         println!("    record_id: {:?},", record.record_id);
         println!("    schema: {:?},", record.schema);
+        println!("    schema_version: {:?},", record.schema_version);
         println!("    owners: {:?},", record.owners);
         println!("    custodians: {:?}", record.custodians);
         println!("    field_final: {:?}", record.field_final);
         
-        response_data = response_data + &format!("{{\n  record_id: {:?}, \n  schema: {:?}, \n  owners: {:?}, \n  custodians: {:?}, \n  field_final: {:?} \n}}", record.record_id, record.schema, record.owners, record.custodians, record.field_final);
+        response_data = response_data + &format!("{{\n  record_id: {:?}, \n  schema: {:?}, \n  schema_version: {:?}, \n  owners: {:?}, \n  custodians: {:?}, \n  field_final: {:?} \n}}", record.record_id, record.schema, record.schema_version, record.owners, record.custodians, record.field_final);
     }
     Ok(HttpResponse::Ok().body(response_data))
 }
@@ -99,13 +120,18 @@ pub async fn create_record(
     let private_key = &input_data.private_key;
     let record_id = &input_data.record_id;
     let schema = &input_data.schema;
-    let properties = retrieve_property_values(&input_data);
+    let definitions = fetch_schema_properties(schema).await?;
+    let mut properties = retrieve_property_values(&input_data.properties, &definitions)?;
+    properties.append(&mut retrieve_list_property_values(&input_data.list_properties, &definitions)?);
+    let (data_key, wrapped_data_key) = new_record_data_key(&definitions, private_key)?;
+    let properties = encrypt_confidential_values(properties, &definitions, data_key.as_ref())?;
 
     // Building the Action and Payload//
     let action = CreateRecordActionBuilder::new()
         .with_record_id(record_id.into())
         .with_schema(schema.into())
         .with_properties(properties)
+        .with_wrapped_data_key(wrapped_data_key)
         .build()
         .unwrap();
 
@@ -122,7 +148,7 @@ pub async fn create_record(
         private_key,
     ).add_transaction(
         &payload.into_proto()?,
-        &[get_record_prefix(), get_schema_prefix(), get_pike_prefix()],
+        &[get_record_prefix(), get_schema_prefix(), get_pike_prefix(), get_product_prefix()],
         &[get_record_prefix(), get_schema_prefix(), get_pike_prefix()],
     )?.create_batch_list();
 
@@ -173,7 +199,7 @@ pub async fn update_record(
         private_key,
     ).add_transaction(
         &payload.into_proto()?,
-        &[get_record_prefix(), get_schema_prefix(), get_pike_prefix()],
+        &[get_record_prefix(), get_schema_prefix(), get_pike_prefix(), get_product_prefix()],
         &[get_record_prefix(), get_schema_prefix(), get_pike_prefix()],
     )?.create_batch_list();
 
@@ -195,9 +221,1115 @@ pub async fn update_record(
     Ok(HttpResponse::Ok().body(res))
 }
 
+/// Fetches a record property with the values reported on its current page. The values of a
+/// confidential property are decrypted when the private key of its current owner, custodian or
+/// an authorized reporter is given in the Private-Key header, and the version of the data key
+/// they were encrypted with has been shared with it. Otherwise they are left out and marked as
+/// encrypted.
+pub async fn fetch_record_property(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let (record_id, property_name) = path.into_inner();
+    let address = make_property_address(&record_id, &property_name, 0);
+    let url = format!("http://rest-api:8008/state/{}", address);
+    let res = reqwest::get(&url).await?.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let properties = PropertyList::from_bytes(&msg)?;
+    let property = match properties
+        .properties()
+        .iter()
+        .find(|property| property.name() == property_name)
+    {
+        Some(property) => property,
+        None => {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Property {} not found for record {}",
+                property_name, record_id
+            )))
+        }
+    };
+
+    let address = make_property_address(&record_id, &property_name, *property.current_page());
+    let url = format!("http://rest-api:8008/state/{}", address);
+    let res = reqwest::get(&url).await?.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let pages = PropertyPageList::from_bytes(&msg)?;
+    let data_keys = fetch_reader_data_keys(property, header_private_key(&req)).await?;
+    let mut reported_values = Vec::new();
+    for page in pages.property_pages() {
+        if page.name() != property_name {
+            continue;
+        }
+        for reported_value in page.reported_values() {
+            let value = readable_value(reported_value.value(), &data_keys)?;
+            reported_values.push(serde_json::json!({
+                "reporter_index": reported_value.reporter_index(),
+                "timestamp": reported_value.timestamp(),
+                "value": value.as_ref().map(|value| {
+                    property_value_to_json(value, Some(property.property_definition()))
+                }),
+                "encrypted": value.is_none(),
+                "geofence_breaches": reported_value.geofence_breaches(),
+            }));
+        }
+    }
+
+    let response_data = serde_json::json!({
+        "record_id": record_id,
+        "name": property_name,
+        "data_type": format!("{:?}", property.property_definition().data_type()),
+        "confidential": property.property_definition().confidential(),
+        "current_page": property.current_page(),
+        "wrapped": property.wrapped(),
+        "page_size": property.property_definition().page_size(),
+        "max_pages": property.property_definition().max_pages(),
+        "retention": format!("{:?}", property.property_definition().retention()),
+        "archive_anchor": property.archive_anchor(),
+        "archived_pages": property.archived_pages(),
+        "reported_values": reported_values,
+    });
+    println!("!dgc-network! response_data: {}", response_data);
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+#[derive(Deserialize)]
+pub struct ReportersQuery {
+    at: Option<u64>,
+}
+
+/// Lists the reporters of a record property with their authorization intervals. Given `at`,
+/// only the reporters that were authorized to report the property at that time are listed.
+pub async fn fetch_record_property_reporters(
+    path: web::Path<(String, String)>,
+    query: web::Query<ReportersQuery>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let (record_id, property_name) = path.into_inner();
+    let property = match fetch_property_entry(&record_id, &property_name).await? {
+        Some(property) => property,
+        None => {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Property {} not found for record {}",
+                property_name, record_id
+            )))
+        }
+    };
+
+    let reporters = property
+        .reporters()
+        .iter()
+        .filter(|reporter| match query.at {
+            Some(at) => reporter.authorized_at(at),
+            None => true,
+        })
+        .map(|reporter| {
+            let authorizations = reporter
+                .authorizations()
+                .iter()
+                .map(|authorization| {
+                    serde_json::json!({
+                        "granted_at": authorization.granted_at(),
+                        "granted_by": authorization.granted_by(),
+                        "revoked_at": authorization.revoked_at(),
+                        "revoked_by": authorization.revoked_by(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            serde_json::json!({
+                "public_key": reporter.public_key(),
+                "index": reporter.index(),
+                "authorized": reporter.authorized(),
+                "authorizations": authorizations,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let response_data = serde_json::json!({
+        "record_id": record_id,
+        "name": property_name,
+        "at": query.at,
+        "reporters": reporters,
+    });
+    println!("!dgc-network! response_data: {}", response_data);
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+pub async fn update_record_properties(
+    input_data: web::Json<RecordPropertiesData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    // Creating the Payload //
+    let private_key = &input_data.private_key;
+    let record_id = &input_data.record_id;
+    let definitions = fetch_record_schema_properties(record_id).await?;
+    let mut properties = retrieve_property_values(&input_data.properties, &definitions)?;
+    properties.append(&mut retrieve_list_property_values(&input_data.list_properties, &definitions)?);
+    let (data_key, data_keys) = fetch_signer_data_key(record_id, &definitions, private_key).await?;
+    let properties = encrypt_confidential_values(properties, &definitions, data_key.as_ref())?;
+
+    // Building the Action and Payload//
+    let action = UpdatePropertiesActionBuilder::new()
+        .with_record_id(record_id.into())
+        .with_properties(properties)
+        .with_data_keys(data_keys)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let payload = TrackAndTracePayloadBuilder::new()
+        .with_action(Action::UpdateProperties(action.clone()))
+        .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+        .build()
+        .unwrap();
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        TNT_FAMILY_NAME, 
+        TNT_FAMILY_VERSION, 
+        private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[
+            get_track_and_trace_prefix(),
+            get_schema_prefix(),
+            get_pike_prefix(),
+            get_product_prefix(),
+        ],
+        &[get_track_and_trace_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    println!("============ update_record_properties_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+/// The most readings submitted in one transaction; larger streams are split into several
+const MAX_READINGS_PER_TRANSACTION: usize = 1000;
+
+/// The header carrying the private key of the caller, for requests without a JSON body
+const PRIVATE_KEY_HEADER: &str = "Private-Key";
+
+/// One line of an NDJSON sensor stream
+#[derive(Deserialize)]
+pub struct SensorReadingData {
+    property_name: String,
+    timestamp: u64,
+    value: serde_json::Value,
+}
+
+/// Ingests a stream of sensor readings for a record, given as one JSON object per line.
+/// Each reading keeps its own timestamp and the readings are submitted in transactions of
+/// at most MAX_READINGS_PER_TRANSACTION readings. The private key of the signer is given in the
+/// Private-Key header.
+pub async fn ingest_record_readings(
+    req: HttpRequest,
+    record_id: web::Path<String>,
+    body: String,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let private_key = header_private_key(&req).ok_or_else(|| {
+        RestApiResponseError::UserError(format!("The {} header is required", PRIVATE_KEY_HEADER))
+    })?;
+    let definitions = fetch_record_schema_properties(&record_id).await?;
+    let (data_key, mut data_keys) =
+        fetch_signer_data_key(&record_id, &definitions, private_key).await?;
+    let mut readings = Vec::new();
+    for (line_number, line) in body.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reading_data = serde_json::from_str::<SensorReadingData>(line).map_err(|err| {
+            RestApiResponseError::UserError(format!(
+                "Invalid reading on line {}: {}",
+                line_number + 1,
+                err
+            ))
+        })?;
+        let definition = definitions
+            .iter()
+            .find(|definition| definition.name() == reading_data.property_name)
+            .ok_or_else(|| {
+                RestApiResponseError::UserError(format!(
+                    "Record {} has no property {}, on line {}",
+                    record_id,
+                    reading_data.property_name,
+                    line_number + 1
+                ))
+            })?;
+        let value =
+            json_to_property_value(&reading_data.property_name, definition, &reading_data.value)?;
+        let value = encrypt_confidential_value(value, &definitions, data_key.as_ref())?;
+        readings.push(
+            SensorReadingBuilder::new()
+                .with_timestamp(reading_data.timestamp)
+                .with_value(value)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+    if readings.is_empty() {
+        return Err(RestApiResponseError::UserError(
+            "The stream does not contain any readings".to_string(),
+        ));
+    }
+
+    // Building the Transactions and Batches//
+    let mut batch_builder = BatchBuilder::new(
+        TNT_FAMILY_NAME,
+        TNT_FAMILY_VERSION,
+        private_key,
+    );
+    for chunk in readings.chunks(MAX_READINGS_PER_TRANSACTION) {
+        // A rotated data key is submitted with the first chunk only
+        let action = IngestReadingsActionBuilder::new()
+            .with_record_id(record_id.to_string())
+            .with_readings(chunk.to_vec())
+            .with_data_keys(std::mem::take(&mut data_keys))
+            .build()
+            .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+        let payload = TrackAndTracePayloadBuilder::new()
+            .with_action(Action::IngestReadings(action))
+            .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+            .build()
+            .unwrap();
+
+        batch_builder.add_transaction(
+            &payload.into_proto()?,
+            &[
+                get_track_and_trace_prefix(),
+                get_schema_prefix(),
+                get_pike_prefix(),
+                get_product_prefix(),
+            ],
+            &[get_track_and_trace_prefix()],
+        )?;
+    }
+
+    let batch_list_bytes = batch_builder
+        .create_batch_list()
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    println!("============ ingest_record_readings_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+#[derive(Deserialize)]
+pub struct TraceQuery {
+    from: Option<u64>,
+    to: Option<u64>,
+}
+
+/// Reads the private key of the caller from the Private-Key header, if it was given
+fn header_private_key(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(PRIVATE_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|private_key| !private_key.is_empty())
+}
+
+/// Traces the route of a record: the locations reported for one of its LAT_LONG properties,
+/// oldest first, optionally limited to the ones reported between `from` and `to`. A
+/// confidential property is only traced for its current owner, custodian or reporters, given
+/// their private key in the Private-Key header.
+pub async fn fetch_record_trace(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    query: web::Query<TraceQuery>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let (record_id, property_name) = path.into_inner();
+    let property = match fetch_property_entry(&record_id, &property_name).await? {
+        Some(property) => property,
+        None => {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Property {} not found for record {}",
+                property_name, record_id
+            )))
+        }
+    };
+    check_lat_long_property(&property)?;
+    let data_keys = fetch_reader_data_keys(&property, header_private_key(&req)).await?;
+    if *property.property_definition().confidential() && data_keys.is_empty() {
+        return Err(RestApiResponseError::UserError(format!(
+            "Property {} of record {} is confidential, the private key of its owner, custodian \
+             or a reporter is required",
+            property_name, record_id
+        )));
+    }
+
+    let mut reported_values = fetch_reported_values(&record_id, &property_name).await?;
+    reported_values.retain(|reported_value| {
+        query.from.map_or(true, |from| *reported_value.timestamp() >= from)
+            && query.to.map_or(true, |to| *reported_value.timestamp() <= to)
+    });
+    reported_values.sort_by_key(|rv| (*rv.timestamp(), *rv.reporter_index()));
+
+    let mut route = Vec::new();
+    for reported_value in &reported_values {
+        let value = match readable_value(reported_value.value(), &data_keys)? {
+            Some(value) => value,
+            None => continue,
+        };
+        let location = value.lat_long_value();
+        route.push(serde_json::json!({
+            "timestamp": reported_value.timestamp(),
+            "reporter_index": reported_value.reporter_index(),
+            "latitude": location.latitude(),
+            "longitude": location.longitude(),
+            "geofence_breaches": reported_value.geofence_breaches(),
+        }));
+    }
+
+    let response_data = serde_json::json!({
+        "record_id": record_id,
+        "name": property_name,
+        "route": route,
+    });
+    println!("!dgc-network! response_data: {}", response_data);
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+/// A bounding box in millionths of a degree, and the LAT_LONG property holding the locations
+#[derive(Deserialize)]
+pub struct BoundingBoxQuery {
+    property: String,
+    min_latitude: i64,
+    min_longitude: i64,
+    max_latitude: i64,
+    max_longitude: i64,
+}
+
+/// Lists the records whose latest reported location is inside of a bounding box. Confidential
+/// locations are not searched.
+pub async fn list_records_within_bounding_box(
+    query: web::Query<BoundingBoxQuery>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    if query.min_latitude > query.max_latitude || query.min_longitude > query.max_longitude {
+        return Err(RestApiResponseError::UserError(
+            "The minimum corner of the bounding box must be south-west of the maximum corner"
+                .to_string(),
+        ));
+    }
+
+    let url = format!("http://rest-api:8008/state?address={}", get_record_prefix());
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    let mut records_within = Vec::new();
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let records = RecordList::from_bytes(&msg)?;
+        for record in records.records() {
+            let property = match fetch_property_entry(record.record_id(), &query.property).await? {
+                Some(property) => property,
+                None => continue,
+            };
+            if check_lat_long_property(&property).is_err()
+                || *property.property_definition().confidential()
+            {
+                continue;
+            }
+            let latest = match fetch_latest_reported_value(&property).await? {
+                Some(latest) => latest,
+                None => continue,
+            };
+
+            let location = latest.value().lat_long_value();
+            if *location.latitude() < query.min_latitude
+                || *location.latitude() > query.max_latitude
+                || *location.longitude() < query.min_longitude
+                || *location.longitude() > query.max_longitude
+            {
+                continue;
+            }
+            records_within.push(serde_json::json!({
+                "record_id": record.record_id(),
+                "schema": record.schema(),
+                "timestamp": latest.timestamp(),
+                "latitude": location.latitude(),
+                "longitude": location.longitude(),
+                "geofence_breaches": latest.geofence_breaches(),
+            }));
+        }
+    }
+
+    let response_data = serde_json::json!(records_within);
+    println!("!dgc-network! response_data: {}", response_data);
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+#[derive(Deserialize)]
+pub struct ExcursionsQuery {
+    #[serde(default)]
+    all: bool,
+}
+
+/// Lists the active excursions of a record from the alert rules of its NUMBER properties;
+/// with `all`, pending and ended excursions are listed too
+pub async fn list_record_excursions(
+    record_id: web::Path<String>,
+    query: web::Query<ExcursionsQuery>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let record = match fetch_record_entry(&record_id).await? {
+        Some(record) => record,
+        None => {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Record {} not found",
+                record_id.as_str()
+            )))
+        }
+    };
+
+    let excursions = record
+        .excursions()
+        .iter()
+        .filter(|excursion| query.all || excursion.is_active())
+        .map(excursion_to_json)
+        .collect::<Vec<_>>();
+
+    let response_data = serde_json::json!({
+        "record_id": record.record_id(),
+        "excursions": excursions,
+    });
+    println!("!dgc-network! response_data: {}", response_data);
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+/// Lists the active excursions of the records an agent is currently the custodian of
+pub async fn list_custodian_excursions(
+    agent_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let url = format!("http://rest-api:8008/state?address={}", get_record_prefix());
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    let mut records_with_excursions = Vec::new();
+    for sub in list.data {
+        let msg = base64::decode(&sub.data).unwrap();
+        let records = RecordList::from_bytes(&msg)?;
+        for record in records.records() {
+            let custodian = record.custodians().last().map(|custodian| custodian.agent_id());
+            if custodian != Some(agent_id.as_str()) {
+                continue;
+            }
+            let excursions = record.active_excursions();
+            if excursions.is_empty() {
+                continue;
+            }
+            records_with_excursions.push(serde_json::json!({
+                "record_id": record.record_id(),
+                "schema": record.schema(),
+                "excursions": excursions
+                    .into_iter()
+                    .map(excursion_to_json)
+                    .collect::<Vec<_>>(),
+            }));
+        }
+    }
+
+    let response_data = serde_json::json!({
+        "custodian": agent_id.as_str(),
+        "records": records_with_excursions,
+    });
+    println!("!dgc-network! response_data: {}", response_data);
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+fn excursion_to_json(excursion: &Excursion) -> serde_json::Value {
+    serde_json::json!({
+        "property_name": excursion.property_name(),
+        "rule_name": excursion.rule_name(),
+        "started_at": excursion.started_at(),
+        "raised_at": excursion.raised_at(),
+        "ended_at": excursion.ended_at(),
+        "last_value": excursion.last_value(),
+        "custodian": excursion.custodian(),
+        "active": excursion.is_active(),
+    })
+}
+
+fn check_lat_long_property(property: &Property) -> Result<(), RestApiResponseError> {
+    if property.property_definition().data_type() != &DataType::LatLong {
+        return Err(RestApiResponseError::UserError(format!(
+            "Property {} of record {} is not a LAT_LONG property",
+            property.name(),
+            property.record_id()
+        )));
+    }
+    Ok(())
+}
+
+/// Fetches every reported value in the history of a record property, in no particular order
+async fn fetch_reported_values(
+    record_id: &str,
+    property_name: &str,
+) -> Result<Vec<ReportedValue>, RestApiResponseError> {
+    // The pages of a property share its address but for the last four characters
+    let property_address = make_property_address(record_id, property_name, 0);
+    let prefix = &property_address[..property_address.len() - 4];
+    let url = format!("http://rest-api:8008/state?address={}", prefix);
+    let list = reqwest::get(&url).await?.json::<List>().await?;
+    let mut reported_values = Vec::new();
+    for sub in list.data {
+        // Page 0 holds the property itself
+        if sub.address == property_address {
+            continue;
+        }
+        let msg = base64::decode(&sub.data).unwrap();
+        let pages = PropertyPageList::from_bytes(&msg)?;
+        for page in pages.property_pages() {
+            if page.name() == property_name && page.record_id() == record_id {
+                reported_values.extend(page.reported_values().iter().cloned());
+            }
+        }
+    }
+    Ok(reported_values)
+}
+
+/// Fetches the most recently reported value of a property, from its current page or, when
+/// that page was just started, from the page before it
+async fn fetch_latest_reported_value(
+    property: &Property,
+) -> Result<Option<ReportedValue>, RestApiResponseError> {
+    let current_page = *property.current_page();
+    let previous_page = match current_page {
+        1 if *property.wrapped() => Some(*property.property_definition().max_pages()),
+        1 => None,
+        page => Some(page - 1),
+    };
+
+    for page_number in std::iter::once(current_page).chain(previous_page) {
+        let address = make_property_address(property.record_id(), property.name(), page_number);
+        let url = format!("http://rest-api:8008/state/{}", address);
+        let res = reqwest::get(&url).await?;
+        if res.status() == reqwest::StatusCode::NOT_FOUND {
+            continue;
+        }
+        let res = res.json::<Fetch>().await?;
+        let msg = base64::decode(&res.data).unwrap();
+        let pages = PropertyPageList::from_bytes(&msg)?;
+        let latest = pages
+            .property_pages()
+            .iter()
+            .filter(|page| {
+                page.name() == property.name() && page.record_id() == property.record_id()
+            })
+            .flat_map(|page| page.reported_values().iter())
+            .max_by_key(|rv| (*rv.timestamp(), *rv.reporter_index()))
+            .cloned();
+        if latest.is_some() {
+            return Ok(latest);
+        }
+    }
+    Ok(None)
+}
+
+/// A parent of a split, merge or transform. A consumed parent is finalized, otherwise it
+/// stays open for the remaining quantity.
+#[derive(Deserialize)]
+pub struct LineageParentData {
+    record_id: String,
+    #[serde(default)]
+    consume: bool,
+}
+
+/// A child record created by a split, merge or transform, given like a new record
+#[derive(Deserialize)]
+pub struct LineageChildData {
+    record_id: String,
+    schema: String,
+    #[serde(default)]
+    properties: String,
+    #[serde(default)]
+    list_properties: Vec<ListPropertyData>,
+}
+
+#[derive(Deserialize)]
+pub struct SplitRecordData {
+    private_key: String,
+    parent: LineageParentData,
+    children: Vec<LineageChildData>,
+}
+
+#[derive(Deserialize)]
+pub struct MergeRecordsData {
+    private_key: String,
+    parents: Vec<LineageParentData>,
+    child: LineageChildData,
+}
+
+#[derive(Deserialize)]
+pub struct TransformRecordData {
+    private_key: String,
+    parent: LineageParentData,
+    child: LineageChildData,
+}
+
+pub async fn split_record(
+    input_data: web::Json<SplitRecordData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let mut children = Vec::new();
+    for child in &input_data.children {
+        children.push(build_lineage_child(child, &input_data.private_key).await?);
+    }
+    let action = SplitRecordActionBuilder::new()
+        .with_parent(build_lineage_parent(&input_data.parent)?)
+        .with_children(children)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_lineage_action(&input_data.private_key, Action::SplitRecord(action)).await?;
+
+    println!("============ split_record_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn merge_records(
+    input_data: web::Json<MergeRecordsData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let parents = input_data
+        .parents
+        .iter()
+        .map(build_lineage_parent)
+        .collect::<Result<Vec<LineageParent>, RestApiResponseError>>()?;
+    let action = MergeRecordsActionBuilder::new()
+        .with_parents(parents)
+        .with_child(build_lineage_child(&input_data.child, &input_data.private_key).await?)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_lineage_action(&input_data.private_key, Action::MergeRecords(action)).await?;
+
+    println!("============ merge_records_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn transform_record(
+    input_data: web::Json<TransformRecordData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let action = TransformRecordActionBuilder::new()
+        .with_parent(build_lineage_parent(&input_data.parent)?)
+        .with_child(build_lineage_child(&input_data.child, &input_data.private_key).await?)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let res = submit_lineage_action(&input_data.private_key, Action::TransformRecord(action)).await?;
+
+    println!("============ transform_record_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+/// Walks the lineage of a record in both directions, returning every ancestor and every
+/// descendant. This is what a recall uses to find all affected records.
+pub async fn fetch_record_genealogy(
+    record_id: web::Path<String>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    let record = match fetch_record_entry(&record_id).await? {
+        Some(record) => record,
+        None => {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Record {} not found",
+                record_id
+            )))
+        }
+    };
+
+    let ancestors = walk_lineage(record.parents().to_vec(), |r| r.parents().to_vec()).await?;
+    let descendants = walk_lineage(record.children().to_vec(), |r| r.children().to_vec()).await?;
+
+    let response_data = serde_json::json!({
+        "record": lineage_to_json(&record),
+        "ancestors": ancestors.iter().map(lineage_to_json).collect::<Vec<_>>(),
+        "descendants": descendants.iter().map(lineage_to_json).collect::<Vec<_>>(),
+    });
+    println!("!dgc-network! response_data: {}", response_data);
+
+    Ok(HttpResponse::Ok().json(response_data))
+}
+
+/// Visits records breadth first starting from `record_ids`, following the links returned
+/// by `next`. Every record is visited once, even where lineages join again.
+async fn walk_lineage<F>(
+    record_ids: Vec<String>,
+    next: F,
+) -> Result<Vec<Record>, RestApiResponseError>
+where
+    F: Fn(&Record) -> Vec<String>,
+{
+    let mut queue: std::collections::VecDeque<String> = record_ids.into();
+    let mut visited = std::collections::HashSet::new();
+    let mut records = Vec::new();
+    while let Some(record_id) = queue.pop_front() {
+        if !visited.insert(record_id.clone()) {
+            continue;
+        }
+        if let Some(record) = fetch_record_entry(&record_id).await? {
+            queue.extend(next(&record));
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+fn lineage_to_json(record: &Record) -> serde_json::Value {
+    serde_json::json!({
+        "record_id": record.record_id(),
+        "schema": record.schema(),
+        "final": record.field_final(),
+        "owner": record.owners().last().map(|owner| owner.agent_id()),
+        "custodian": record.custodians().last().map(|custodian| custodian.agent_id()),
+        "parents": record.parents(),
+        "children": record.children(),
+    })
+}
+
+/// Fetches a record from state, if it exists
+pub async fn fetch_record_entry(record_id: &str) -> Result<Option<Record>, RestApiResponseError> {
+    let url = format!("http://rest-api:8008/state/{}", make_record_address(record_id));
+    let res = reqwest::get(&url).await?;
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let res = res.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let records = RecordList::from_bytes(&msg)?;
+    Ok(records
+        .records()
+        .iter()
+        .find(|record| record.record_id() == record_id)
+        .cloned())
+}
+
+/// Whether the latest value of a non-confidential property of a record references the product,
+/// including REFERENCE values nested in a STRUCT or LIST
+pub async fn record_references_product(
+    record: &Record,
+    product_id: &str,
+) -> Result<bool, RestApiResponseError> {
+    for definition in fetch_schema_properties(record.schema()).await? {
+        if *definition.confidential() {
+            continue;
+        }
+        let latest = fetch_reported_values(record.record_id(), definition.name())
+            .await?
+            .into_iter()
+            .max_by_key(|rv| (*rv.timestamp(), *rv.reporter_index()));
+        if let Some(latest) = latest {
+            if value_references_product(&definition, latest.value(), product_id) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn value_references_product(
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+    product_id: &str,
+) -> bool {
+    match definition.data_type() {
+        DataType::Reference => {
+            definition.reference_kind() == Some(&ReferenceKind::Product)
+                && value.reference_value() == product_id
+        }
+        DataType::Struct => value.struct_values().iter().any(|struct_value| {
+            definition
+                .struct_properties()
+                .iter()
+                .find(|d| d.name() == struct_value.name())
+                .map_or(false, |struct_definition| {
+                    value_references_product(struct_definition, struct_value, product_id)
+                })
+        }),
+        DataType::List => definition.list_element().map_or(false, |element| {
+            value
+                .list_values()
+                .iter()
+                .any(|list_value| value_references_product(element, list_value, product_id))
+        }),
+        _ => false,
+    }
+}
+
+/// Fetches a record property from state, if it exists
+pub async fn fetch_property_entry(
+    record_id: &str,
+    property_name: &str,
+) -> Result<Option<Property>, RestApiResponseError> {
+    let address = make_property_address(record_id, property_name, 0);
+    let url = format!("http://rest-api:8008/state/{}", address);
+    let res = reqwest::get(&url).await?;
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let res = res.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let properties = PropertyList::from_bytes(&msg)?;
+    Ok(properties
+        .properties()
+        .iter()
+        .find(|property| property.name() == property_name)
+        .cloned())
+}
+
+fn build_lineage_parent(parent: &LineageParentData) -> Result<LineageParent, RestApiResponseError> {
+    LineageParentBuilder::new()
+        .with_record_id(parent.record_id.to_string())
+        .with_consume(parent.consume)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))
+}
+
+async fn build_lineage_child(
+    child: &LineageChildData,
+    private_key: &str,
+) -> Result<CreateRecordAction, RestApiResponseError> {
+    let definitions = fetch_schema_properties(&child.schema).await?;
+    let mut properties = retrieve_property_values(&child.properties, &definitions)?;
+    properties.append(&mut retrieve_list_property_values(&child.list_properties, &definitions)?);
+    let (data_key, wrapped_data_key) = new_record_data_key(&definitions, private_key)?;
+    let properties = encrypt_confidential_values(properties, &definitions, data_key.as_ref())?;
+    CreateRecordActionBuilder::new()
+        .with_record_id(child.record_id.to_string())
+        .with_schema(child.schema.to_string())
+        .with_properties(properties)
+        .with_wrapped_data_key(wrapped_data_key)
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))
+}
+
+async fn submit_lineage_action(
+    private_key: &str,
+    action: Action,
+) -> Result<String, RestApiResponseError> {
+    let payload = TrackAndTracePayloadBuilder::new()
+        .with_action(action)
+        .with_timestamp(chrono::offset::Utc::now().timestamp().try_into().unwrap())
+        .build()
+        .unwrap();
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        TNT_FAMILY_NAME, 
+        TNT_FAMILY_VERSION, 
+        private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[
+            get_track_and_trace_prefix(),
+            get_schema_prefix(),
+            get_pike_prefix(),
+            get_product_prefix(),
+        ],
+        &[get_track_and_trace_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    Ok(res)
+}
+
+/// Fetches the property definitions of the schema of a record
+async fn fetch_record_schema_properties(
+    record_id: &str,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    let url = format!("http://rest-api:8008/state/{}", make_record_address(record_id));
+    let res = reqwest::get(&url).await?.json::<Fetch>().await?;
+    let msg = base64::decode(&res.data).unwrap();
+    let records = RecordList::from_bytes(&msg)?;
+    match records.records().iter().find(|record| record.record_id() == record_id) {
+        Some(record) => fetch_schema_properties(record.schema()).await,
+        None => Err(RestApiResponseError::NotFoundError(format!(
+            "Record {} not found",
+            record_id
+        ))),
+    }
+}
+
+/// Generates the data key of a new record when its schema has confidential properties,
+/// returning its first version with the data key wrapped for the signer
+fn new_record_data_key(
+    definitions: &[PropertyDefinition],
+    private_key: &str,
+) -> Result<(Option<(u32, Vec<u8>)>, Vec<u8>), RestApiResponseError> {
+    if !definitions.iter().any(|definition| *definition.confidential()) {
+        return Ok((None, Vec::new()));
+    }
+    let data_key = generate_data_key();
+    let wrapped_data_key = wrap_data_key(&data_key, &public_key_of(private_key)?)?;
+    Ok((Some((0, data_key)), wrapped_data_key))
+}
+
+/// Unwraps the current version of the data key of a record for the signer, when its schema
+/// has confidential properties and the data key has been shared with the signer. When the
+/// data key must be rotated, a new version is generated instead and returned with the new
+/// version wrapped for every agent holding the data key, to be submitted with the write.
+async fn fetch_signer_data_key(
+    record_id: &str,
+    definitions: &[PropertyDefinition],
+    private_key: &str,
+) -> Result<(Option<(u32, Vec<u8>)>, Vec<DataKey>), RestApiResponseError> {
+    if !definitions.iter().any(|definition| *definition.confidential()) {
+        return Ok((None, Vec::new()));
+    }
+    let record = match fetch_record_entry(record_id).await? {
+        Some(record) => record,
+        None => {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Record {} not found",
+                record_id
+            )))
+        }
+    };
+    let public_key = public_key_of(private_key)?;
+    let holds_data_key = record
+        .data_keys()
+        .iter()
+        .any(|data_key| data_key.agent_id() == public_key);
+    if *record.data_key_rotation_required() && holds_data_key {
+        let (data_key, data_keys) = rotate_data_key(&record)?;
+        return Ok((Some((record.data_key_version() + 1, data_key)), data_keys));
+    }
+    let data_key = record_data_key(&record, private_key)?;
+    Ok((data_key.map(|data_key| (*record.data_key_version(), data_key)), Vec::new()))
+}
+
+/// Unwraps the versions of the data key of a record shared with a caller reading one of its
+/// confidential properties, by version. Only the current owner and custodian of the record
+/// and the authorized reporters of the property may read it, even where the data key was
+/// shared with the caller before.
+async fn fetch_reader_data_keys(
+    property: &Property,
+    private_key: Option<&str>,
+) -> Result<HashMap<u32, Vec<u8>>, RestApiResponseError> {
+    let private_key = match private_key {
+        Some(private_key) if *property.property_definition().confidential() => private_key,
+        _ => return Ok(HashMap::new()),
+    };
+    let record = match fetch_record_entry(property.record_id()).await? {
+        Some(record) => record,
+        None => return Ok(HashMap::new()),
+    };
+    let public_key = public_key_of(private_key)?;
+    let is_current = |agents: &[AssociatedAgent]| {
+        agents.last().map_or(false, |agent| agent.agent_id() == public_key)
+    };
+    if !is_current(record.owners())
+        && !is_current(record.custodians())
+        && !property
+            .reporters()
+            .iter()
+            .any(|reporter| reporter.public_key() == public_key && *reporter.authorized())
+    {
+        return Ok(HashMap::new());
+    }
+    record_data_keys(&record, private_key)
+}
+
+/// Encrypts the values of confidential properties with a version of the data key of their
+/// record
+fn encrypt_confidential_values(
+    values: Vec<PropertyValue>,
+    definitions: &[PropertyDefinition],
+    data_key: Option<&(u32, Vec<u8>)>,
+) -> Result<Vec<PropertyValue>, RestApiResponseError> {
+    values
+        .into_iter()
+        .map(|value| encrypt_confidential_value(value, definitions, data_key))
+        .collect()
+}
+
+fn encrypt_confidential_value(
+    value: PropertyValue,
+    definitions: &[PropertyDefinition],
+    data_key: Option<&(u32, Vec<u8>)>,
+) -> Result<PropertyValue, RestApiResponseError> {
+    let confidential = definitions
+        .iter()
+        .any(|definition| definition.name() == value.name() && *definition.confidential());
+    match (confidential, data_key) {
+        (false, _) => Ok(value),
+        (true, Some((key_version, data_key))) => encrypt_value(data_key, *key_version, value),
+        (true, None) => Err(RestApiResponseError::UserError(format!(
+            "Property {} is confidential and the data key of the record has not been shared \
+             with the signer",
+            value.name()
+        ))),
+    }
+}
+
+/// Returns a reported value as it can be read with the given versions of the data key:
+/// decrypted if it is encrypted and the version it was encrypted with is given, or nothing if
+/// it can not be decrypted
+fn readable_value(
+    value: &PropertyValue,
+    data_keys: &HashMap<u32, Vec<u8>>,
+) -> Result<Option<PropertyValue>, RestApiResponseError> {
+    if value.encrypted_value().is_empty() {
+        return Ok(Some(value.clone()));
+    }
+    match data_keys.get(value.key_version()) {
+        Some(data_key) => Ok(Some(decrypt_value(data_key, value)?)),
+        None => Ok(None),
+    }
+}
+
 fn retrieve_property_values(
-    input_data: &web::Json<RecordData>,
-) -> Vec::<PropertyValue> {
+    properties_as_string: &str,
+    definitions: &[PropertyDefinition],
+) -> Result<Vec::<PropertyValue>, RestApiResponseError> {
 /*    
     name: String,
     data_type: DataType,
@@ -211,7 +1343,9 @@ fn retrieve_property_values(
 */
 
     let mut properties = Vec::<PropertyValue>::new();
-    let properties_as_string = &input_data.properties;
+    if properties_as_string.is_empty() {
+        return Ok(properties);
+    }
     let vec: Vec<&str> = properties_as_string.split(",").collect();
     let key_val_vec = split_vec(vec, 9);
     for key_val in key_val_vec {
@@ -231,6 +1365,8 @@ fn retrieve_property_values(
                 else if (value == &"Enum") | (value == &"enum") | (value == &"ENUM") {DataType::Enum}
                 else if (value == &"Struct") | (value == &"struct") | (value == &"STRUCT") {DataType::Struct}
                 else if (value == &"LatLong") | (value == &"LatLong") | (value == &"LATLONG") {DataType::LatLong}
+                else if (value == &"Reference") | (value == &"reference") | (value == &"REFERENCE") {DataType::Reference}
+                else if (value == &"Timestamp") | (value == &"timestamp") | (value == &"TIMESTAMP") {DataType::Timestamp}
                 else {DataType::String},
             None => DataType::String
         };
@@ -268,22 +1404,41 @@ fn retrieve_property_values(
             properties.push(property_value.clone());    
         }
 
+        // A number value may be a decimal such as 12.50, which is converted exactly with
+        // the number_exponent of the schema
         if data_type == DataType::Number {
             let string_value = match key_val.get(4) {
                 Some(value) => value.to_string(),
                 None => "0".to_string()
             };    
-            let number_value = string_value.parse::<i64>();
+            let number_value = parse_number_value(&name, &string_value, definitions)?;
 
             let property_value = PropertyValueBuilder::new()
             .with_name(name.clone().into())
             .with_data_type(DataType::Number)
-            .with_number_value(number_value.unwrap())
+            .with_number_value(number_value)
             .build()
             .unwrap();
             properties.push(property_value.clone());    
         }
 
+        // A timestamp value is given in the number_value position, as an RFC 3339 date
+        if data_type == DataType::Timestamp {
+            let string_value = match key_val.get(4) {
+                Some(value) => value.to_string(),
+                None => "0".to_string()
+            };
+            let timestamp_value = parse_timestamp_value(&name, &string_value, definitions)?;
+
+            let property_value = PropertyValueBuilder::new()
+            .with_name(name.clone().into())
+            .with_data_type(DataType::Timestamp)
+            .with_timestamp_value(timestamp_value)
+            .build()
+            .unwrap();
+            properties.push(property_value.clone());
+        }
+
         if data_type == DataType::String {
             let string_value = match key_val.get(5) {
                 Some(value) => value.to_string(),
@@ -315,6 +1470,22 @@ fn retrieve_property_values(
             properties.push(property_value.clone());    
         }
 
+        // A reference value is given in the string_value position
+        if data_type == DataType::Reference {
+            let reference_value = match key_val.get(5) {
+                Some(value) => value.to_string(),
+                None => "reference_value is formated incorrectly".to_string()
+            };
+
+            let property_value = PropertyValueBuilder::new()
+            .with_name(name.clone().into())
+            .with_data_type(DataType::Reference)
+            .with_reference_value(reference_value)
+            .build()
+            .unwrap();
+            properties.push(property_value.clone());
+        }
+
     }
-    return properties
+    Ok(properties)
 }
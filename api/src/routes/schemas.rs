@@ -9,6 +9,7 @@ use reqwest;
 
 use crate::transaction::BatchBuilder;
 use crate::error::RestApiResponseError;
+use crate::routes::properties::parse_data_type;
 use crate::{List, Fetch, split_vec};
 
 use dgc_config::protos::*;
@@ -21,7 +22,124 @@ pub struct SchemaData {
     private_key: String,
     schema_name: String,
     description: String,
+    #[serde(default)]
     properties: String,
+    #[serde(default)]
+    enum_options_additions: Vec<EnumOptionsAdditionData>,
+    #[serde(default)]
+    deprecated_properties: Vec<String>,
+    #[serde(default)]
+    optional_properties: Vec<String>,
+    #[serde(default)]
+    property_constraints: Vec<PropertyConstraintsData>,
+    #[serde(default)]
+    property_references: Vec<PropertyReferenceData>,
+    #[serde(default)]
+    property_list_elements: Vec<PropertyListElementData>,
+    #[serde(default)]
+    property_paging: Vec<PropertyPagingData>,
+    #[serde(default)]
+    property_geofences: Vec<PropertyGeofenceData>,
+    #[serde(default)]
+    property_alert_rules: Vec<PropertyAlertRuleData>,
+    #[serde(default)]
+    confidential_properties: Vec<String>,
+}
+
+/// How the history of a property is paged; a page_size or max_pages of 0 selects the default
+#[derive(Deserialize)]
+pub struct PropertyPagingData {
+    property_name: String,
+    #[serde(default)]
+    page_size: u32,
+    #[serde(default)]
+    max_pages: u32,
+    #[serde(default)]
+    retention: String,
+}
+
+/// A geofence of a LAT_LONG property, with its vertices as [latitude, longitude] pairs in
+/// millionths of a degree
+#[derive(Deserialize)]
+pub struct PropertyGeofenceData {
+    property_name: String,
+    name: String,
+    vertices: Vec<(i64, i64)>,
+}
+
+/// An alert rule of a NUMBER property, in the units of its number_value; a rate of change of 0
+/// is not checked and a sustained duration of 0 raises an excursion on the first broken value
+#[derive(Deserialize)]
+pub struct PropertyAlertRuleData {
+    property_name: String,
+    name: String,
+    min: Option<i64>,
+    max: Option<i64>,
+    #[serde(default)]
+    max_rate_of_change: u64,
+    #[serde(default)]
+    sustained_duration: u64,
+}
+
+#[derive(Deserialize)]
+pub struct PropertyReferenceData {
+    property_name: String,
+    reference_kind: String,
+    #[serde(default)]
+    reference_schema: String,
+}
+
+#[derive(Deserialize)]
+pub struct PropertyListElementData {
+    property_name: String,
+    element_type: String,
+    #[serde(default)]
+    number_exponent: i32,
+    #[serde(default)]
+    enum_options: Vec<String>,
+    #[serde(default)]
+    reference_kind: String,
+    #[serde(default)]
+    reference_schema: String,
+    #[serde(default)]
+    timestamp_precision: String,
+}
+
+#[derive(Deserialize)]
+pub struct SchemaDeprecateData {
+    private_key: String,
+    schema_name: String,
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Deserialize)]
+pub struct SchemaTransferData {
+    private_key: String,
+    schema_name: String,
+    new_owner: String,
+}
+
+#[derive(Deserialize)]
+pub struct PropertyConstraintsData {
+    property_name: String,
+    number_min: Option<i64>,
+    number_max: Option<i64>,
+    pattern: Option<String>,
+    max_length: Option<u32>,
+    max_bytes: Option<u64>,
+    min_latitude: Option<i64>,
+    min_longitude: Option<i64>,
+    max_latitude: Option<i64>,
+    max_longitude: Option<i64>,
+    #[serde(default)]
+    immutable: bool,
+}
+
+#[derive(Deserialize)]
+pub struct EnumOptionsAdditionData {
+    property_name: String,
+    enum_options: Vec<String>,
 }
 
 pub async fn list_schemas(
@@ -47,9 +165,11 @@ pub async fn list_schemas(
             println!("    schema_name: {:?},", schema.name);
             println!("    description: {:?},", schema.description);
             println!("    owner: {:?},", schema.owner);
+            println!("    version: {:?},", schema.version);
+            println!("    deprecated: {:?},", schema.deprecated);
             println!("    properties: {:?}", schema.properties);
 
-            response_data = response_data + &format!("\n  {{\n    schema_name: {:?}, \n    description: {:?}, \n    owner: {:?}, \n    properties: {:?}, \n  }},\n", schema.name, schema.description, schema.owner, schema.properties);
+            response_data = response_data + &format!("\n  {{\n    schema_name: {:?}, \n    description: {:?}, \n    owner: {:?}, \n    version: {:?}, \n    deprecated: {:?}, \n    deprecated_reason: {:?}, \n    properties: {:?}, \n  }},\n", schema.name, schema.description, schema.owner, schema.version, schema.deprecated, schema.deprecated_reason, schema.properties);
         }
     }
     response_data = response_data + &format!("]");
@@ -80,9 +200,10 @@ pub async fn fetch_schema(
         println!("    schema_name: {:?},", schema.name);
         println!("    description: {:?},", schema.description);
         println!("    owner: {:?},", schema.owner);
+        println!("    version: {:?},", schema.version);
         println!("    properties: {:?}", schema.properties);
         
-        response_data = response_data + &format!("{{\n  schema_name: {:?}, \n  description: {:?}, \n  owner: {:?}, \n  properties: {:?}, \n}}", schema.name, schema.description, schema.owner, schema.properties);
+        response_data = response_data + &format!("{{\n  schema_name: {:?}, \n  description: {:?}, \n  owner: {:?}, \n  version: {:?}, \n  deprecated: {:?}, \n  deprecated_reason: {:?}, \n  properties: {:?}, \n}}", schema.name, schema.description, schema.owner, schema.version, schema.deprecated, schema.deprecated_reason, schema.properties);
     }
     Ok(HttpResponse::Ok().body(response_data))
 }
@@ -95,7 +216,7 @@ pub async fn create_schema(
     let private_key = &input_data.private_key;
     let schema_name = &input_data.schema_name;
     let description = &input_data.description;
-    let properties = retrieve_property_definitions(&input_data);
+    let properties = retrieve_configured_property_definitions(&input_data)?;
 
     // Building the Action and Payload//
     let action = SchemaCreateBuilder::new()
@@ -147,14 +268,31 @@ pub async fn update_schema(
     let private_key = &input_data.private_key;
     let schema_name = &input_data.schema_name;
     let description = &input_data.description;
-    let properties = retrieve_property_definitions(&input_data);
+    let properties = if input_data.properties.is_empty() {
+        Vec::<PropertyDefinition>::new()
+    } else {
+        retrieve_configured_property_definitions(&input_data)?
+    };
+    let mut enum_options_additions = Vec::<EnumOptionsAddition>::new();
+    for addition in &input_data.enum_options_additions {
+        enum_options_additions.push(
+            EnumOptionsAdditionBuilder::new()
+                .with_property_name(addition.property_name.to_string())
+                .with_enum_options(addition.enum_options.clone())
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
 
     // Building the Action and Payload//
     let action = SchemaUpdateBuilder::new()
         .with_schema_name(schema_name.to_string())
         .with_properties(properties)
+        .with_enum_options_additions(enum_options_additions)
+        .with_deprecated_properties(input_data.deprecated_properties.clone())
+        .with_optional_properties(input_data.optional_properties.clone())
         .build()
-        .unwrap();
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
 
     let payload = SchemaPayloadBuilder::new()
         .with_action(Action::SchemaUpdate(action.clone()))
@@ -190,9 +328,446 @@ pub async fn update_schema(
     Ok(HttpResponse::Ok().body(res))
 }
 
+pub async fn deprecate_schema(
+    input_data: web::Json<SchemaDeprecateData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    // Creating the Payload //
+    let private_key = &input_data.private_key;
+    let schema_name = &input_data.schema_name;
+    let reason = &input_data.reason;
+
+    // Building the Action and Payload//
+    let action = SchemaDeprecateBuilder::new()
+        .with_schema_name(schema_name.to_string())
+        .with_reason(reason.to_string())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let payload = SchemaPayloadBuilder::new()
+        .with_action(Action::SchemaDeprecate(action.clone()))
+        .build()
+        .unwrap();
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        SCHEMA_FAMILY_NAME, 
+        SCHEMA_FAMILY_VERSION, 
+        private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[get_schema_prefix(), get_pike_prefix()],
+        &[get_schema_prefix(), get_pike_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    println!("============ deprecate_schema_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+pub async fn transfer_schema(
+    input_data: web::Json<SchemaTransferData>,
+) -> Result<HttpResponse, RestApiResponseError> {
+
+    // Creating the Payload //
+    let private_key = &input_data.private_key;
+    let schema_name = &input_data.schema_name;
+    let new_owner = &input_data.new_owner;
+
+    // Building the Action and Payload//
+    let action = SchemaTransferBuilder::new()
+        .with_schema_name(schema_name.to_string())
+        .with_new_owner(new_owner.to_string())
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+    let payload = SchemaPayloadBuilder::new()
+        .with_action(Action::SchemaTransfer(action.clone()))
+        .build()
+        .unwrap();
+
+    // Building the Transaction and Batch//
+    let batch_list = BatchBuilder::new(
+        SCHEMA_FAMILY_NAME, 
+        SCHEMA_FAMILY_VERSION, 
+        private_key,
+    ).add_transaction(
+        &payload.into_proto()?,
+        &[get_schema_prefix(), get_pike_prefix()],
+        &[get_schema_prefix(), get_pike_prefix()],
+    )?.create_batch_list();
+
+    let batch_list_bytes = batch_list
+        .write_to_bytes()
+        .expect("Error converting batch list to bytes");
+
+    // Submitting Batches to the Validator //
+    let res = reqwest::Client::new()
+        .post("http://rest-api:8008/batches")
+        .header("Content-Type", "application/octet-stream")
+        .body(batch_list_bytes)
+        .send().await?
+        .text().await?;
+
+    println!("============ transfer_schema_link ============");
+    println!("!dgc-network! submit_status = {:?}", res);
+
+    Ok(HttpResponse::Ok().body(res))
+}
+
+/// Retrieves the property definitions of a schema with their constraints, paging, geofences
+/// and alert rules applied
+fn retrieve_configured_property_definitions(
+    input_data: &web::Json<SchemaData>,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    let properties = retrieve_property_definitions(input_data)?;
+    let properties = apply_property_constraints(properties, input_data)?;
+    let properties = apply_property_paging(properties, input_data)?;
+    let properties = apply_property_geofences(properties, input_data)?;
+    let properties = apply_property_alert_rules(properties, input_data)?;
+    apply_confidential_properties(properties, input_data)
+}
+
+fn apply_property_constraints(
+    properties: Vec<PropertyDefinition>,
+    input_data: &web::Json<SchemaData>,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    let mut constrained_properties = Vec::<PropertyDefinition>::new();
+    for property in properties {
+        let constraints_data = match input_data
+            .property_constraints
+            .iter()
+            .find(|c| c.property_name == property.name())
+        {
+            Some(constraints_data) => constraints_data,
+            None => {
+                constrained_properties.push(property);
+                continue;
+            }
+        };
+
+        let mut builder = PropertyConstraintsBuilder::new()
+            .with_immutable(constraints_data.immutable);
+        if let Some(number_min) = constraints_data.number_min {
+            builder = builder.with_number_min(number_min);
+        }
+        if let Some(number_max) = constraints_data.number_max {
+            builder = builder.with_number_max(number_max);
+        }
+        if let Some(pattern) = &constraints_data.pattern {
+            builder = builder.with_pattern(pattern.to_string());
+        }
+        if let Some(max_length) = constraints_data.max_length {
+            builder = builder.with_max_length(max_length);
+        }
+        if let Some(max_bytes) = constraints_data.max_bytes {
+            builder = builder.with_max_bytes(max_bytes);
+        }
+        if let (Some(min_latitude), Some(min_longitude), Some(max_latitude), Some(max_longitude)) = (
+            constraints_data.min_latitude,
+            constraints_data.min_longitude,
+            constraints_data.max_latitude,
+            constraints_data.max_longitude,
+        ) {
+            let lat_long_min = LatLongBuilder::new()
+                .with_lat_long(min_latitude, min_longitude)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+            let lat_long_max = LatLongBuilder::new()
+                .with_lat_long(max_latitude, max_longitude)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+            builder = builder.with_bounding_box(lat_long_min, lat_long_max);
+        }
+        let constraints = builder
+            .build()
+            .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
+
+        constrained_properties.push(
+            property
+                .into_builder()
+                .with_constraints(constraints)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+    Ok(constrained_properties)
+}
+
+fn apply_property_paging(
+    properties: Vec<PropertyDefinition>,
+    input_data: &web::Json<SchemaData>,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    let mut paged_properties = Vec::<PropertyDefinition>::new();
+    for property in properties {
+        let paging_data = match input_data
+            .property_paging
+            .iter()
+            .find(|p| p.property_name == property.name())
+        {
+            Some(paging_data) => paging_data,
+            None => {
+                paged_properties.push(property);
+                continue;
+            }
+        };
+
+        let retention = match paging_data.retention.to_uppercase().as_str() {
+            "" | "WRAP" => RetentionPolicy::Wrap,
+            "KEEP_ALL" => RetentionPolicy::KeepAll,
+            "ARCHIVE" => RetentionPolicy::Archive,
+            _ => {
+                return Err(RestApiResponseError::UserError(format!(
+                    "Invalid retention for property {}: {}, expected WRAP, KEEP_ALL or ARCHIVE",
+                    paging_data.property_name, paging_data.retention
+                )))
+            }
+        };
+        paged_properties.push(
+            property
+                .into_builder()
+                .with_paging(paging_data.page_size, paging_data.max_pages, retention)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+    Ok(paged_properties)
+}
+
+fn apply_property_geofences(
+    properties: Vec<PropertyDefinition>,
+    input_data: &web::Json<SchemaData>,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    let mut fenced_properties = Vec::<PropertyDefinition>::new();
+    for property in properties {
+        let mut geofences = property.geofences().to_vec();
+        for geofence_data in input_data
+            .property_geofences
+            .iter()
+            .filter(|g| g.property_name == property.name())
+        {
+            let vertices = geofence_data
+                .vertices
+                .iter()
+                .map(|(latitude, longitude)| {
+                    LatLongBuilder::new()
+                        .with_lat_long(*latitude, *longitude)
+                        .build()
+                        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))
+                })
+                .collect::<Result<Vec<_>, RestApiResponseError>>()?;
+            geofences.push(
+                GeofenceBuilder::new()
+                    .with_name(geofence_data.name.to_string())
+                    .with_vertices(vertices)
+                    .build()
+                    .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+            );
+        }
+
+        if geofences.is_empty() {
+            fenced_properties.push(property);
+            continue;
+        }
+        fenced_properties.push(
+            property
+                .into_builder()
+                .with_geofences(geofences)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+    Ok(fenced_properties)
+}
+
+fn apply_property_alert_rules(
+    properties: Vec<PropertyDefinition>,
+    input_data: &web::Json<SchemaData>,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    let mut alerting_properties = Vec::<PropertyDefinition>::new();
+    for property in properties {
+        let mut alert_rules = property.alert_rules().to_vec();
+        for rule_data in input_data
+            .property_alert_rules
+            .iter()
+            .filter(|r| r.property_name == property.name())
+        {
+            let mut builder = AlertRuleBuilder::new()
+                .with_name(rule_data.name.to_string())
+                .with_max_rate_of_change(rule_data.max_rate_of_change)
+                .with_sustained_duration(rule_data.sustained_duration);
+            if let Some(min) = rule_data.min {
+                builder = builder.with_min(min);
+            }
+            if let Some(max) = rule_data.max {
+                builder = builder.with_max(max);
+            }
+            alert_rules.push(
+                builder
+                    .build()
+                    .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+            );
+        }
+
+        if alert_rules.is_empty() {
+            alerting_properties.push(property);
+            continue;
+        }
+        alerting_properties.push(
+            property
+                .into_builder()
+                .with_alert_rules(alert_rules)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+    Ok(alerting_properties)
+}
+
+/// Marks the listed properties as confidential, so that their values are stored encrypted
+fn apply_confidential_properties(
+    properties: Vec<PropertyDefinition>,
+    input_data: &web::Json<SchemaData>,
+) -> Result<Vec<PropertyDefinition>, RestApiResponseError> {
+    for name in &input_data.confidential_properties {
+        if !properties.iter().any(|property| property.name() == name) {
+            return Err(RestApiResponseError::UserError(format!(
+                "Confidential property {} is not a property of the schema",
+                name
+            )));
+        }
+    }
+
+    let mut confidential_properties = Vec::<PropertyDefinition>::new();
+    for property in properties {
+        if !input_data.confidential_properties.iter().any(|name| name == property.name()) {
+            confidential_properties.push(property);
+            continue;
+        }
+        confidential_properties.push(
+            property
+                .into_builder()
+                .with_confidential(true)
+                .build()
+                .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?,
+        );
+    }
+    Ok(confidential_properties)
+}
+
+fn retrieve_property_reference(
+    input_data: &web::Json<SchemaData>,
+    property_name: &str,
+) -> Result<(ReferenceKind, String), RestApiResponseError> {
+    let reference_data = input_data
+        .property_references
+        .iter()
+        .find(|r| r.property_name == property_name)
+        .ok_or_else(|| {
+            RestApiResponseError::UserError(format!(
+                "Missing property_references entry for REFERENCE property {}",
+                property_name
+            ))
+        })?;
+
+    let reference_kind = parse_reference_kind(&reference_data.reference_kind, property_name)?;
+
+    Ok((reference_kind, reference_data.reference_schema.to_string()))
+}
+
+fn parse_reference_kind(
+    reference_kind: &str,
+    property_name: &str,
+) -> Result<ReferenceKind, RestApiResponseError> {
+    match reference_kind.to_uppercase().as_str() {
+        "RECORD" => Ok(ReferenceKind::Record),
+        "PRODUCT" => Ok(ReferenceKind::Product),
+        "ORGANIZATION" => Ok(ReferenceKind::Organization),
+        "AGENT" => Ok(ReferenceKind::Agent),
+        _ => Err(RestApiResponseError::UserError(format!(
+            "Invalid reference_kind for property {}: {}",
+            property_name, reference_kind
+        ))),
+    }
+}
+
+fn parse_timestamp_precision(
+    precision: &str,
+    property_name: &str,
+) -> Result<TimestampPrecision, RestApiResponseError> {
+    match precision.to_uppercase().as_str() {
+        "" | "SECONDS" => Ok(TimestampPrecision::Seconds),
+        "MILLISECONDS" => Ok(TimestampPrecision::Milliseconds),
+        "MICROSECONDS" => Ok(TimestampPrecision::Microseconds),
+        "NANOSECONDS" => Ok(TimestampPrecision::Nanoseconds),
+        _ => Err(RestApiResponseError::UserError(format!(
+            "Invalid timestamp precision for property {}: {}",
+            property_name, precision
+        ))),
+    }
+}
+
+/// Builds the element definition of a LIST property from its property_list_elements
+/// entry. The element is named after the list property.
+fn retrieve_property_list_element(
+    input_data: &web::Json<SchemaData>,
+    property_name: &str,
+) -> Result<PropertyDefinition, RestApiResponseError> {
+    let element_data = input_data
+        .property_list_elements
+        .iter()
+        .find(|e| e.property_name == property_name)
+        .ok_or_else(|| {
+            RestApiResponseError::UserError(format!(
+                "Missing property_list_elements entry for LIST property {}",
+                property_name
+            ))
+        })?;
+
+    let element_type = parse_data_type(&element_data.element_type).ok_or_else(|| {
+        RestApiResponseError::UserError(format!(
+            "Invalid element_type for property {}: {}",
+            property_name, element_data.element_type
+        ))
+    })?;
+
+    let mut builder = PropertyDefinitionBuilder::new()
+        .with_name(property_name.to_string())
+        .with_data_type(element_type.clone())
+        .with_number_exponent(element_data.number_exponent)
+        .with_enum_options(element_data.enum_options.clone());
+    if element_type == DataType::Reference {
+        builder = builder
+            .with_reference_kind(parse_reference_kind(&element_data.reference_kind, property_name)?)
+            .with_reference_schema(element_data.reference_schema.to_string());
+    }
+    if element_type == DataType::Timestamp {
+        builder = builder.with_timestamp_precision(parse_timestamp_precision(
+            &element_data.timestamp_precision,
+            property_name,
+        )?);
+    }
+    builder
+        .build()
+        .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))
+}
+
 fn retrieve_property_definitions(
     input_data: &web::Json<SchemaData>,
-) -> Vec::<PropertyDefinition> {
+) -> Result<Vec::<PropertyDefinition>, RestApiResponseError> {
 /*
     name: String,
     data_type: DataType,
@@ -223,6 +798,9 @@ fn retrieve_property_definitions(
                 else if (value == &"Enum") | (value == &"enum") | (value == &"ENUM") {DataType::Enum}
                 else if (value == &"Struct") | (value == &"struct") | (value == &"STRUCT") {DataType::Struct}
                 else if (value == &"LatLong") | (value == &"LatLong") | (value == &"LATLONG") {DataType::LatLong}
+                else if (value == &"Reference") | (value == &"reference") | (value == &"REFERENCE") {DataType::Reference}
+                else if (value == &"List") | (value == &"list") | (value == &"LIST") {DataType::List}
+                else if (value == &"Timestamp") | (value == &"timestamp") | (value == &"TIMESTAMP") {DataType::Timestamp}
                 else {DataType::String},
             None => DataType::String
         };
@@ -253,20 +831,39 @@ fn retrieve_property_definitions(
             .with_description(description.clone().to_string())
             .with_number_exponent(number_exponent.unwrap())
             .build()
-            .unwrap();    
+            .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;
             properties.push(property_definition.clone());
 
         } else {
             
-            let property_definition = PropertyDefinitionBuilder::new()
+            let mut builder = PropertyDefinitionBuilder::new()
             .with_name(name.clone().into())
             .with_data_type(data_type.clone())
             .with_required(required.clone())
-            .with_description(description.clone().to_string())
+            .with_description(description.clone().to_string());
+            if data_type == DataType::Reference {
+                let (reference_kind, reference_schema) =
+                    retrieve_property_reference(input_data, &name)?;
+                builder = builder
+                    .with_reference_kind(reference_kind)
+                    .with_reference_schema(reference_schema);
+            }
+            if data_type == DataType::List {
+                builder = builder.with_list_element(retrieve_property_list_element(input_data, &name)?);
+            }
+            // The precision of a timestamp is given in the number_exponent position
+            if data_type == DataType::Timestamp {
+                let precision = match key_val.get(4) {
+                    Some(value) => value.to_string(),
+                    None => "".to_string()
+                };
+                builder = builder.with_timestamp_precision(parse_timestamp_precision(&precision, &name)?);
+            }
+            let property_definition = builder
             .build()
-            .unwrap();    
+            .map_err(|err| RestApiResponseError::UserError(format!("{}", err)))?;    
             properties.push(property_definition.clone());
         }
     }
-    return properties
+    Ok(properties)
 }
@@ -22,6 +22,7 @@ pub const PIKE_ORG_NAMESPACE: &str = "01";
 pub const PRODUCT_FAMILY_NAME: &str = "grid_product";
 pub const PRODUCT_FAMILY_VERSION: &str = "1.0";
 pub const PRODUCT_GS1_NAMESPACE: &str = "01"; // Indicates GS1 standard
+const PRODUCT_PROPOSAL_NAMESPACE: &str = "aa";
 //pub const PRODUCT_NAMESPACE: &str = "02"; // Indicates product under GS1 standard
 
 pub const SCHEMA_FAMILY_NAME: &str = "grid_schema";
@@ -33,6 +34,8 @@ pub const TNT_FAMILY_VERSION: &str = "1.0";
 const PROPERTY: &str = "ea";
 const PROPOSAL: &str = "aa";
 const RECORD: &str = "ec";
+const RECALL: &str = "ae";
+const BULK_PROPOSAL: &str = "ab";
 
 pub fn hash(to_hash: &str, num: usize) -> String {
     let mut sha = Sha512::new();
@@ -70,6 +73,14 @@ pub fn make_product_address(product_id: &str) -> String {
     get_product_prefix() + &hash(product_id, 62)
 }
 
+pub fn get_product_proposal_prefix() -> String {
+    hash(&PRODUCT_FAMILY_NAME, 6) + PRODUCT_PROPOSAL_NAMESPACE
+}
+
+pub fn make_product_proposal_address(product_id: &str, receiving_org: &str) -> String {
+    get_product_proposal_prefix() + &hash(product_id, 36) + &hash(receiving_org, 26)
+}
+
 pub fn get_schema_prefix() -> String {
     hash(&SCHEMA_FAMILY_NAME, 6) + GRID_SCHEMA_NAMESPACE
 }
@@ -109,3 +120,19 @@ pub fn get_proposal_prefix() -> String {
 pub fn make_proposal_address(record_id: &str, agent_id: &str) -> String {
     get_proposal_prefix() + &hash(record_id, 36) + &hash(agent_id, 26)
 }
+
+pub fn get_recall_prefix() -> String {
+    get_track_and_trace_prefix() + RECALL
+}
+
+pub fn make_recall_address(recall_id: &str) -> String {
+    get_recall_prefix() + &hash(recall_id, 62)
+}
+
+pub fn get_bulk_proposal_prefix() -> String {
+    get_track_and_trace_prefix() + BULK_PROPOSAL
+}
+
+pub fn make_bulk_proposal_address(proposal_id: &str, agent_id: &str) -> String {
+    get_bulk_proposal_prefix() + &hash(proposal_id, 36) + &hash(agent_id, 26)
+}
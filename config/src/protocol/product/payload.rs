@@ -32,6 +32,9 @@ pub enum Action {
     ProductCreate(ProductCreateAction),
     ProductUpdate(ProductUpdateAction),
     ProductDelete(ProductDeleteAction),
+    ProductCreateProposal(ProductCreateProposalAction),
+    ProductAnswerProposal(ProductAnswerProposalAction),
+    ProductPatch(ProductPatchAction),
 }
 
 // Rust native implementation for ProductPayload
@@ -64,6 +67,19 @@ impl FromProto<protos::product_payload::ProductPayload> for ProductPayload {
             ProductPayload_Action::PRODUCT_DELETE => Action::ProductDelete(
                 ProductDeleteAction::from_proto(payload.get_product_delete().clone())?,
             ),
+            ProductPayload_Action::PRODUCT_CREATE_PROPOSAL => Action::ProductCreateProposal(
+                ProductCreateProposalAction::from_proto(
+                    payload.get_product_create_proposal().clone(),
+                )?,
+            ),
+            ProductPayload_Action::PRODUCT_ANSWER_PROPOSAL => Action::ProductAnswerProposal(
+                ProductAnswerProposalAction::from_proto(
+                    payload.get_product_answer_proposal().clone(),
+                )?,
+            ),
+            ProductPayload_Action::PRODUCT_PATCH => Action::ProductPatch(
+                ProductPatchAction::from_proto(payload.get_product_patch().clone())?,
+            ),
             ProductPayload_Action::UNSET_ACTION => {
                 return Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert ProductPayload_Action with type unset".to_string(),
@@ -96,6 +112,18 @@ impl FromNative<ProductPayload> for protos::product_payload::ProductPayload {
                 proto.set_action(ProductPayload_Action::PRODUCT_DELETE);
                 proto.set_product_delete(payload.clone().into_proto()?);
             }
+            Action::ProductCreateProposal(payload) => {
+                proto.set_action(ProductPayload_Action::PRODUCT_CREATE_PROPOSAL);
+                proto.set_product_create_proposal(payload.clone().into_proto()?);
+            }
+            Action::ProductAnswerProposal(payload) => {
+                proto.set_action(ProductPayload_Action::PRODUCT_ANSWER_PROPOSAL);
+                proto.set_product_answer_proposal(payload.clone().into_proto()?);
+            }
+            Action::ProductPatch(payload) => {
+                proto.set_action(ProductPayload_Action::PRODUCT_PATCH);
+                proto.set_product_patch(payload.clone().into_proto()?);
+            }
         }
 
         Ok(proto)
@@ -193,6 +221,7 @@ pub struct ProductCreateAction {
     product_id: String,
     owner: String,
     properties: Vec<PropertyValue>,
+    schema: String,
 }
 
 impl ProductCreateAction {
@@ -211,6 +240,10 @@ impl ProductCreateAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
 }
 
 impl FromProto<product_payload::ProductCreateAction> for ProductCreateAction {
@@ -227,6 +260,7 @@ impl FromProto<product_payload::ProductCreateAction> for ProductCreateAction {
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            schema: proto.get_schema().to_string(),
         })
     }
 }
@@ -246,6 +280,7 @@ impl FromNative<ProductCreateAction> for product_payload::ProductCreateAction {
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_schema(native.schema().to_string());
         Ok(proto)
     }
 }
@@ -283,6 +318,7 @@ pub struct ProductCreateActionBuilder {
     product_id: Option<String>,
     owner: Option<String>,
     properties: Option<Vec<PropertyValue>>,
+    schema: Option<String>,
 }
 
 impl ProductCreateActionBuilder {
@@ -305,6 +341,10 @@ impl ProductCreateActionBuilder {
         self.properties = Some(value);
         self
     }
+    pub fn with_schema(mut self, value: String) -> Self {
+        self.schema = Some(value);
+        self
+    }
     pub fn build(self) -> Result<ProductCreateAction, BuilderError> {
         let product_type = self.product_type.ok_or_else(|| {
             BuilderError::MissingField("'product_type' field is required".to_string())
@@ -318,11 +358,15 @@ impl ProductCreateActionBuilder {
         let properties = self
             .properties
             .ok_or_else(|| BuilderError::MissingField("'properties' field is required".into()))?;
+        let schema = self
+            .schema
+            .ok_or_else(|| BuilderError::MissingField("'schema' field is required".into()))?;
         Ok(ProductCreateAction {
             product_type,
             product_id,
             owner,
             properties,
+            schema,
         })
     }
 }
@@ -471,6 +515,7 @@ impl ProductUpdateActionBuilder {
 pub struct ProductDeleteAction {
     product_type: ProductType,
     product_id: String,
+    reason: String,
 }
 
 /// Native implementation for ProductDeleteAction
@@ -482,6 +527,10 @@ impl ProductDeleteAction {
     pub fn product_id(&self) -> &str {
         &self.product_id
     }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
 }
 
 impl FromProto<protos::product_payload::ProductDeleteAction> for ProductDeleteAction {
@@ -491,6 +540,7 @@ impl FromProto<protos::product_payload::ProductDeleteAction> for ProductDeleteAc
         Ok(ProductDeleteAction {
             product_type: ProductType::from_proto(proto.get_product_type())?,
             product_id: proto.get_product_id().to_string(),
+            reason: proto.get_reason().to_string(),
         })
     }
 }
@@ -500,6 +550,7 @@ impl FromNative<ProductDeleteAction> for protos::product_payload::ProductDeleteA
         let mut proto = protos::product_payload::ProductDeleteAction::new();
         proto.set_product_type(native.product_type().clone().into_proto()?);
         proto.set_product_id(native.product_id().to_string());
+        proto.set_reason(native.reason().to_string());
         Ok(proto)
     }
 }
@@ -536,6 +587,7 @@ impl IntoNative<ProductDeleteAction> for protos::product_payload::ProductDeleteA
 pub struct ProductDeleteActionBuilder {
     product_type: Option<ProductType>,
     product_id: Option<String>,
+    reason: Option<String>,
 }
 
 impl ProductDeleteActionBuilder {
@@ -553,6 +605,11 @@ impl ProductDeleteActionBuilder {
         self
     }
 
+    pub fn with_reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
     pub fn build(self) -> Result<ProductDeleteAction, BuilderError> {
         let product_type = self.product_type.ok_or_else(|| {
             BuilderError::MissingField("'product_type' field is required".to_string())
@@ -562,9 +619,603 @@ impl ProductDeleteActionBuilder {
             BuilderError::MissingField("'product_id' field is required".to_string())
         })?;
 
+        // A reason for the delete is optional
+        let reason = self.reason.unwrap_or_default();
+
         Ok(ProductDeleteAction {
             product_type,
             product_id,
+            reason,
+        })
+    }
+}
+
+/// Native implementation for ProductCreateProposalAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProductCreateProposalAction {
+    product_type: ProductType,
+    product_id: String,
+    receiving_org: String,
+    terms: String,
+}
+
+impl ProductCreateProposalAction {
+    pub fn product_type(&self) -> &ProductType {
+        &self.product_type
+    }
+
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+
+    pub fn receiving_org(&self) -> &str {
+        &self.receiving_org
+    }
+
+    pub fn terms(&self) -> &str {
+        &self.terms
+    }
+}
+
+impl FromProto<product_payload::ProductCreateProposalAction> for ProductCreateProposalAction {
+    fn from_proto(
+        proto: product_payload::ProductCreateProposalAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProductCreateProposalAction {
+            product_type: ProductType::from_proto(proto.get_product_type())?,
+            product_id: proto.get_product_id().to_string(),
+            receiving_org: proto.get_receiving_org().to_string(),
+            terms: proto.get_terms().to_string(),
+        })
+    }
+}
+
+impl FromNative<ProductCreateProposalAction> for product_payload::ProductCreateProposalAction {
+    fn from_native(native: ProductCreateProposalAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = product_payload::ProductCreateProposalAction::new();
+        proto.set_product_type(native.product_type().clone().into_proto()?);
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_receiving_org(native.receiving_org().to_string());
+        proto.set_terms(native.terms().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ProductCreateProposalAction> for ProductCreateProposalAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ProductCreateProposalAction, ProtoConversionError> {
+        let proto: product_payload::ProductCreateProposalAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ProductCreateProposalAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ProductCreateProposalAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ProductCreateProposalAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<product_payload::ProductCreateProposalAction> for ProductCreateProposalAction {}
+impl IntoNative<ProductCreateProposalAction> for product_payload::ProductCreateProposalAction {}
+
+#[derive(Default, Debug)]
+pub struct ProductCreateProposalActionBuilder {
+    product_type: Option<ProductType>,
+    product_id: Option<String>,
+    receiving_org: Option<String>,
+    terms: Option<String>,
+}
+
+impl ProductCreateProposalActionBuilder {
+    pub fn new() -> Self {
+        ProductCreateProposalActionBuilder::default()
+    }
+    pub fn with_product_type(mut self, value: ProductType) -> Self {
+        self.product_type = Some(value);
+        self
+    }
+    pub fn with_product_id(mut self, value: String) -> Self {
+        self.product_id = Some(value);
+        self
+    }
+    pub fn with_receiving_org(mut self, value: String) -> Self {
+        self.receiving_org = Some(value);
+        self
+    }
+    pub fn with_terms(mut self, value: String) -> Self {
+        self.terms = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<ProductCreateProposalAction, BuilderError> {
+        let product_type = self.product_type.ok_or_else(|| {
+            BuilderError::MissingField("'product_type' field is required".to_string())
+        })?;
+        let product_id = self
+            .product_id
+            .ok_or_else(|| BuilderError::MissingField("'product_id' field is required".into()))?;
+        let receiving_org = self.receiving_org.ok_or_else(|| {
+            BuilderError::MissingField("'receiving_org' field is required".into())
+        })?;
+        let terms = self.terms.unwrap_or_default();
+        Ok(ProductCreateProposalAction {
+            product_type,
+            product_id,
+            receiving_org,
+            terms,
+        })
+    }
+}
+
+/// Native implementation for ProductAnswerProposalAction_Response
+#[derive(Debug, Clone, PartialEq)]
+pub enum Response {
+    Accept,
+    Reject,
+    Cancel,
+}
+
+impl Default for Response {
+    fn default() -> Response {
+        Response::Accept
+    }
+}
+
+impl FromProto<product_payload::ProductAnswerProposalAction_Response> for Response {
+    fn from_proto(
+        response: product_payload::ProductAnswerProposalAction_Response,
+    ) -> Result<Self, ProtoConversionError> {
+        match response {
+            product_payload::ProductAnswerProposalAction_Response::ACCEPT => Ok(Response::Accept),
+            product_payload::ProductAnswerProposalAction_Response::REJECT => Ok(Response::Reject),
+            product_payload::ProductAnswerProposalAction_Response::CANCEL => Ok(Response::Cancel),
+        }
+    }
+}
+
+impl FromNative<Response> for product_payload::ProductAnswerProposalAction_Response {
+    fn from_native(response: Response) -> Result<Self, ProtoConversionError> {
+        match response {
+            Response::Accept => Ok(product_payload::ProductAnswerProposalAction_Response::ACCEPT),
+            Response::Reject => Ok(product_payload::ProductAnswerProposalAction_Response::REJECT),
+            Response::Cancel => Ok(product_payload::ProductAnswerProposalAction_Response::CANCEL),
+        }
+    }
+}
+
+impl IntoProto<product_payload::ProductAnswerProposalAction_Response> for Response {}
+impl IntoNative<Response> for product_payload::ProductAnswerProposalAction_Response {}
+
+/// Native implementation for ProductAnswerProposalAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProductAnswerProposalAction {
+    product_type: ProductType,
+    product_id: String,
+    receiving_org: String,
+    response: Response,
+}
+
+impl ProductAnswerProposalAction {
+    pub fn product_type(&self) -> &ProductType {
+        &self.product_type
+    }
+
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+
+    pub fn receiving_org(&self) -> &str {
+        &self.receiving_org
+    }
+
+    pub fn response(&self) -> &Response {
+        &self.response
+    }
+}
+
+impl FromProto<product_payload::ProductAnswerProposalAction> for ProductAnswerProposalAction {
+    fn from_proto(
+        proto: product_payload::ProductAnswerProposalAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProductAnswerProposalAction {
+            product_type: ProductType::from_proto(proto.get_product_type())?,
+            product_id: proto.get_product_id().to_string(),
+            receiving_org: proto.get_receiving_org().to_string(),
+            response: Response::from_proto(proto.get_response())?,
+        })
+    }
+}
+
+impl FromNative<ProductAnswerProposalAction> for product_payload::ProductAnswerProposalAction {
+    fn from_native(native: ProductAnswerProposalAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = product_payload::ProductAnswerProposalAction::new();
+        proto.set_product_type(native.product_type().clone().into_proto()?);
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_receiving_org(native.receiving_org().to_string());
+        proto.set_response(native.response().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ProductAnswerProposalAction> for ProductAnswerProposalAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ProductAnswerProposalAction, ProtoConversionError> {
+        let proto: product_payload::ProductAnswerProposalAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ProductAnswerProposalAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ProductAnswerProposalAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ProductAnswerProposalAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<product_payload::ProductAnswerProposalAction> for ProductAnswerProposalAction {}
+impl IntoNative<ProductAnswerProposalAction> for product_payload::ProductAnswerProposalAction {}
+
+#[derive(Default, Debug)]
+pub struct ProductAnswerProposalActionBuilder {
+    product_type: Option<ProductType>,
+    product_id: Option<String>,
+    receiving_org: Option<String>,
+    response: Option<Response>,
+}
+
+impl ProductAnswerProposalActionBuilder {
+    pub fn new() -> Self {
+        ProductAnswerProposalActionBuilder::default()
+    }
+    pub fn with_product_type(mut self, value: ProductType) -> Self {
+        self.product_type = Some(value);
+        self
+    }
+    pub fn with_product_id(mut self, value: String) -> Self {
+        self.product_id = Some(value);
+        self
+    }
+    pub fn with_receiving_org(mut self, value: String) -> Self {
+        self.receiving_org = Some(value);
+        self
+    }
+    pub fn with_response(mut self, value: Response) -> Self {
+        self.response = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<ProductAnswerProposalAction, BuilderError> {
+        let product_type = self.product_type.ok_or_else(|| {
+            BuilderError::MissingField("'product_type' field is required".to_string())
+        })?;
+        let product_id = self
+            .product_id
+            .ok_or_else(|| BuilderError::MissingField("'product_id' field is required".into()))?;
+        let receiving_org = self.receiving_org.ok_or_else(|| {
+            BuilderError::MissingField("'receiving_org' field is required".into())
+        })?;
+        let response = self
+            .response
+            .ok_or_else(|| BuilderError::MissingField("'response' field is required".into()))?;
+        Ok(ProductAnswerProposalAction {
+            product_type,
+            product_id,
+            receiving_org,
+            response,
+        })
+    }
+}
+
+/// Native implementation for PropertyPatch_Operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOperation {
+    Set,
+    Add,
+    Remove,
+}
+
+impl FromProto<product_payload::PropertyPatch_Operation> for PatchOperation {
+    fn from_proto(
+        operation: product_payload::PropertyPatch_Operation,
+    ) -> Result<Self, ProtoConversionError> {
+        match operation {
+            product_payload::PropertyPatch_Operation::SET => Ok(PatchOperation::Set),
+            product_payload::PropertyPatch_Operation::ADD => Ok(PatchOperation::Add),
+            product_payload::PropertyPatch_Operation::REMOVE => Ok(PatchOperation::Remove),
+            product_payload::PropertyPatch_Operation::UNSET_OPERATION => {
+                Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert PropertyPatch_Operation with type UNSET_OPERATION".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl FromNative<PatchOperation> for product_payload::PropertyPatch_Operation {
+    fn from_native(operation: PatchOperation) -> Result<Self, ProtoConversionError> {
+        match operation {
+            PatchOperation::Set => Ok(product_payload::PropertyPatch_Operation::SET),
+            PatchOperation::Add => Ok(product_payload::PropertyPatch_Operation::ADD),
+            PatchOperation::Remove => Ok(product_payload::PropertyPatch_Operation::REMOVE),
+        }
+    }
+}
+
+impl IntoProto<product_payload::PropertyPatch_Operation> for PatchOperation {}
+impl IntoNative<PatchOperation> for product_payload::PropertyPatch_Operation {}
+
+/// Native implementation for PropertyPatch
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyPatch {
+    operation: PatchOperation,
+    name: String,
+    value: Option<PropertyValue>,
+    expected_value: Option<PropertyValue>,
+}
+
+impl PropertyPatch {
+    pub fn operation(&self) -> &PatchOperation {
+        &self.operation
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> Option<&PropertyValue> {
+        self.value.as_ref()
+    }
+
+    pub fn expected_value(&self) -> Option<&PropertyValue> {
+        self.expected_value.as_ref()
+    }
+}
+
+impl FromProto<product_payload::PropertyPatch> for PropertyPatch {
+    fn from_proto(proto: product_payload::PropertyPatch) -> Result<Self, ProtoConversionError> {
+        let value = if proto.has_value() {
+            Some(PropertyValue::from_proto(proto.get_value().clone())?)
+        } else {
+            None
+        };
+        let expected_value = if proto.has_expected_value() {
+            Some(PropertyValue::from_proto(proto.get_expected_value().clone())?)
+        } else {
+            None
+        };
+        Ok(PropertyPatch {
+            operation: PatchOperation::from_proto(proto.get_operation())?,
+            name: proto.get_name().to_string(),
+            value,
+            expected_value,
+        })
+    }
+}
+
+impl FromNative<PropertyPatch> for product_payload::PropertyPatch {
+    fn from_native(native: PropertyPatch) -> Result<Self, ProtoConversionError> {
+        let mut proto = product_payload::PropertyPatch::new();
+        proto.set_operation(native.operation().clone().into_proto()?);
+        proto.set_name(native.name().to_string());
+        if let Some(value) = native.value() {
+            proto.set_value(value.clone().into_proto()?);
+        }
+        if let Some(expected_value) = native.expected_value() {
+            proto.set_expected_value(expected_value.clone().into_proto()?);
+        }
+        Ok(proto)
+    }
+}
+
+impl IntoProto<product_payload::PropertyPatch> for PropertyPatch {}
+impl IntoNative<PropertyPatch> for product_payload::PropertyPatch {}
+
+/// Builder used to create a PropertyPatch
+#[derive(Default, Clone)]
+pub struct PropertyPatchBuilder {
+    operation: Option<PatchOperation>,
+    name: Option<String>,
+    value: Option<PropertyValue>,
+    expected_value: Option<PropertyValue>,
+}
+
+impl PropertyPatchBuilder {
+    pub fn new() -> Self {
+        PropertyPatchBuilder::default()
+    }
+
+    pub fn with_operation(mut self, operation: PatchOperation) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn with_value(mut self, value: PropertyValue) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn with_expected_value(mut self, expected_value: PropertyValue) -> Self {
+        self.expected_value = Some(expected_value);
+        self
+    }
+
+    pub fn build(self) -> Result<PropertyPatch, BuilderError> {
+        let operation = self.operation.ok_or_else(|| {
+            BuilderError::MissingField("'operation' field is required".to_string())
+        })?;
+
+        let name = self
+            .name
+            .ok_or_else(|| BuilderError::MissingField("'name' field is required".to_string()))?;
+
+        // Set and Add need the new value, Remove does not
+        if operation != PatchOperation::Remove && self.value.is_none() {
+            return Err(BuilderError::MissingField(
+                "'value' field is required".to_string(),
+            ));
+        }
+
+        Ok(PropertyPatch {
+            operation,
+            name,
+            value: self.value,
+            expected_value: self.expected_value,
+        })
+    }
+}
+
+/// Native implementation for ProductPatchAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProductPatchAction {
+    product_type: ProductType,
+    product_id: String,
+    patches: Vec<PropertyPatch>,
+}
+
+impl ProductPatchAction {
+    pub fn product_type(&self) -> &ProductType {
+        &self.product_type
+    }
+
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+
+    pub fn patches(&self) -> &[PropertyPatch] {
+        &self.patches
+    }
+}
+
+impl FromProto<product_payload::ProductPatchAction> for ProductPatchAction {
+    fn from_proto(
+        proto: product_payload::ProductPatchAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProductPatchAction {
+            product_type: ProductType::from_proto(proto.get_product_type())?,
+            product_id: proto.get_product_id().to_string(),
+            patches: proto
+                .get_patches()
+                .to_vec()
+                .into_iter()
+                .map(PropertyPatch::from_proto)
+                .collect::<Result<Vec<PropertyPatch>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<ProductPatchAction> for product_payload::ProductPatchAction {
+    fn from_native(native: ProductPatchAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = product_payload::ProductPatchAction::new();
+        proto.set_product_type(native.product_type().clone().into_proto()?);
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_patches(RepeatedField::from_vec(
+            native
+                .patches()
+                .to_vec()
+                .into_iter()
+                .map(PropertyPatch::into_proto)
+                .collect::<Result<Vec<product_payload::PropertyPatch>, ProtoConversionError>>()?,
+        ));
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ProductPatchAction> for ProductPatchAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ProductPatchAction, ProtoConversionError> {
+        let proto: product_payload::ProductPatchAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ProductPatchAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ProductPatchAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ProductPatchAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<product_payload::ProductPatchAction> for ProductPatchAction {}
+impl IntoNative<ProductPatchAction> for product_payload::ProductPatchAction {}
+
+/// Builder used to create a ProductPatchAction
+#[derive(Default, Clone)]
+pub struct ProductPatchActionBuilder {
+    product_type: Option<ProductType>,
+    product_id: Option<String>,
+    patches: Vec<PropertyPatch>,
+}
+
+impl ProductPatchActionBuilder {
+    pub fn new() -> Self {
+        ProductPatchActionBuilder::default()
+    }
+
+    pub fn with_product_type(mut self, product_type: ProductType) -> Self {
+        self.product_type = Some(product_type);
+        self
+    }
+
+    pub fn with_product_id(mut self, product_id: String) -> Self {
+        self.product_id = Some(product_id);
+        self
+    }
+
+    pub fn with_patches(mut self, patches: Vec<PropertyPatch>) -> Self {
+        self.patches = patches;
+        self
+    }
+
+    pub fn build(self) -> Result<ProductPatchAction, BuilderError> {
+        let product_type = self.product_type.ok_or_else(|| {
+            BuilderError::MissingField("'product_type' field is required".to_string())
+        })?;
+
+        let product_id = self.product_id.ok_or_else(|| {
+            BuilderError::MissingField("'product_id' field is required".to_string())
+        })?;
+
+        if self.patches.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'patches' field is required".to_string(),
+            ));
+        }
+
+        Ok(ProductPatchAction {
+            product_type,
+            product_id,
+            patches: self.patches,
         })
     }
 }
@@ -582,12 +1233,14 @@ mod tests {
             .with_product_id("688955434684".into()) // GTIN-12
             .with_product_type(ProductType::GS1)
             .with_owner("Target".into())
+            .with_schema("gs1_product".into())
             .with_properties(make_properties())
             .build()
             .unwrap();
 
         assert_eq!(action.product_id(), "688955434684");
         assert_eq!(action.owner(), "Target");
+        assert_eq!(action.schema(), "gs1_product");
         assert_eq!(*action.product_type(), ProductType::GS1);
         assert_eq!(action.properties()[0].name(), "description");
         assert_eq!(*action.properties()[0].data_type(), DataType::String);
@@ -607,6 +1260,7 @@ mod tests {
             .with_product_id("688955434684".into()) // GTIN-12
             .with_product_type(ProductType::GS1)
             .with_owner("Target".into())
+            .with_schema("gs1_product".into())
             .with_properties(make_properties())
             .build()
             .unwrap();
@@ -675,6 +1329,75 @@ mod tests {
         test_from_bytes(action, ProductDeleteAction::from_bytes);
     }
 
+    #[test]
+    // Test that a product create proposal action can be converted to bytes and back
+    fn test_product_create_proposal_into_bytes() {
+        let action = ProductCreateProposalActionBuilder::new()
+            .with_product_id("688955434684".into()) // GTIN-12
+            .with_product_type(ProductType::GS1)
+            .with_receiving_org("Cargill".into())
+            .with_terms("brand sale".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.receiving_org(), "Cargill");
+        assert_eq!(action.terms(), "brand sale");
+        test_from_bytes(action, ProductCreateProposalAction::from_bytes);
+    }
+
+    #[test]
+    // Test that a product answer proposal action can be converted to bytes and back
+    fn test_product_answer_proposal_into_bytes() {
+        let action = ProductAnswerProposalActionBuilder::new()
+            .with_product_id("688955434684".into()) // GTIN-12
+            .with_product_type(ProductType::GS1)
+            .with_receiving_org("Cargill".into())
+            .with_response(Response::Accept)
+            .build()
+            .unwrap();
+
+        assert_eq!(*action.response(), Response::Accept);
+        test_from_bytes(action, ProductAnswerProposalAction::from_bytes);
+    }
+
+    #[test]
+    // Test that a product patch action can be converted to bytes and back
+    fn test_product_patch_into_bytes() {
+        let properties = make_properties();
+        let set_patch = PropertyPatchBuilder::new()
+            .with_operation(PatchOperation::Set)
+            .with_name("price".into())
+            .with_value(properties[1].clone())
+            .with_expected_value(properties[1].clone())
+            .build()
+            .unwrap();
+        let remove_patch = PropertyPatchBuilder::new()
+            .with_operation(PatchOperation::Remove)
+            .with_name("description".into())
+            .build()
+            .unwrap();
+        let action = ProductPatchActionBuilder::new()
+            .with_product_id("688955434684".into()) // GTIN-12
+            .with_product_type(ProductType::GS1)
+            .with_patches(vec![set_patch, remove_patch])
+            .build()
+            .unwrap();
+
+        assert_eq!(action.patches().len(), 2);
+        assert_eq!(action.patches()[1].value(), None);
+        test_from_bytes(action, ProductPatchAction::from_bytes);
+    }
+
+    #[test]
+    // Test that a set patch cannot be built without a value
+    fn test_property_patch_set_without_value() {
+        assert!(PropertyPatchBuilder::new()
+            .with_operation(PatchOperation::Set)
+            .with_name("price".into())
+            .build()
+            .is_err());
+    }
+
     #[test]
     // Test that a product payload can be built correctly
     fn test_product_payload_builder() {
@@ -682,6 +1405,7 @@ mod tests {
             .with_product_id("688955434684".into()) // GTIN-12
             .with_product_type(ProductType::GS1)
             .with_owner("Target".into())
+            .with_schema("gs1_product".into())
             .with_properties(make_properties())
             .build()
             .unwrap();
@@ -703,6 +1427,7 @@ mod tests {
             .with_product_id("688955434684".into()) // GTIN-12
             .with_product_type(ProductType::GS1)
             .with_owner("Target".into())
+            .with_schema("gs1_product".into())
             .with_properties(make_properties())
             .build()
             .unwrap();
@@ -23,6 +23,7 @@ use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
 };
 
+use super::errors::BuilderError;
 use crate::protocol::schema::state::PropertyValue;
 
 /// Native implementation of ProductType enum
@@ -70,6 +71,11 @@ pub struct Product {
     product_type: ProductType,
     owner: String,
     properties: Vec<PropertyValue>,
+    schema: String,
+    revision: u64,
+    deleted: bool,
+    deleted_reason: String,
+    revisions: Vec<ProductRevision>,
 }
 
 impl Product {
@@ -89,12 +95,37 @@ impl Product {
         &self.properties
     }
 
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    pub fn revision(&self) -> &u64 {
+        &self.revision
+    }
+
+    pub fn deleted(&self) -> &bool {
+        &self.deleted
+    }
+
+    pub fn deleted_reason(&self) -> &str {
+        &self.deleted_reason
+    }
+
+    pub fn revisions(&self) -> &[ProductRevision] {
+        &self.revisions
+    }
+
     pub fn into_builder(self) -> ProductBuilder {
         ProductBuilder::new()
             .with_product_id(self.product_id)
             .with_product_type(self.product_type)
             .with_owner(self.owner)
             .with_properties(self.properties)
+            .with_schema(self.schema)
+            .with_revision(self.revision)
+            .with_deleted(self.deleted)
+            .with_deleted_reason(self.deleted_reason)
+            .with_revisions(self.revisions)
     }
 }
 
@@ -110,6 +141,16 @@ impl FromProto<protos::product_state::Product> for Product {
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            schema: product.get_schema().to_string(),
+            revision: product.get_revision(),
+            deleted: product.get_deleted(),
+            deleted_reason: product.get_deleted_reason().to_string(),
+            revisions: product
+                .get_revisions()
+                .to_vec()
+                .into_iter()
+                .map(ProductRevision::from_proto)
+                .collect::<Result<Vec<ProductRevision>, ProtoConversionError>>()?,
         })
     }
 }
@@ -128,6 +169,19 @@ impl FromNative<Product> for protos::product_state::Product {
                 .map(PropertyValue::into_proto)
                 .collect::<Result<Vec<schema_state::PropertyValue>, ProtoConversionError>>()?,
         ));
+        proto.set_schema(product.schema().to_string());
+        proto.set_revision(*product.revision());
+        proto.set_deleted(*product.deleted());
+        proto.set_deleted_reason(product.deleted_reason().to_string());
+        proto.set_revisions(RepeatedField::from_vec(
+            product
+                .revisions()
+                .to_vec()
+                .into_iter()
+                .map(ProductRevision::into_proto)
+                .collect::<Result<Vec<protos::product_state::ProductRevision>, ProtoConversionError>>(
+                )?,
+        ));
         Ok(proto)
     }
 }
@@ -195,6 +249,11 @@ pub struct ProductBuilder {
     pub product_type: Option<ProductType>,
     pub owner: Option<String>,
     pub properties: Option<Vec<PropertyValue>>,
+    pub schema: Option<String>,
+    pub revision: Option<u64>,
+    pub deleted: Option<bool>,
+    pub deleted_reason: Option<String>,
+    pub revisions: Option<Vec<ProductRevision>>,
 }
 
 impl ProductBuilder {
@@ -222,6 +281,31 @@ impl ProductBuilder {
         self
     }
 
+    pub fn with_schema(mut self, schema: String) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    pub fn with_revision(mut self, revision: u64) -> Self {
+        self.revision = Some(revision);
+        self
+    }
+
+    pub fn with_deleted(mut self, deleted: bool) -> Self {
+        self.deleted = Some(deleted);
+        self
+    }
+
+    pub fn with_deleted_reason(mut self, deleted_reason: String) -> Self {
+        self.deleted_reason = Some(deleted_reason);
+        self
+    }
+
+    pub fn with_revisions(mut self, revisions: Vec<ProductRevision>) -> Self {
+        self.revisions = Some(revisions);
+        self
+    }
+
     pub fn build(self) -> Result<Product, ProductBuildError> {
         let product_id = self.product_id.ok_or_else(|| {
             ProductBuildError::MissingField("'product_id' field is required".to_string())
@@ -240,11 +324,249 @@ impl ProductBuilder {
             ProductBuildError::MissingField("'properties' field is required".to_string())
         })?;
 
+        let schema = self.schema.ok_or_else(|| {
+            ProductBuildError::MissingField("'schema' field is required".to_string())
+        })?;
+
+        // A new product starts without any history
+        let revision = self.revision.unwrap_or_default();
+        let deleted = self.deleted.unwrap_or_default();
+        let deleted_reason = self.deleted_reason.unwrap_or_default();
+        let revisions = self.revisions.unwrap_or_default();
+
         Ok(Product {
             product_id,
             product_type,
             owner,
             properties,
+            schema,
+            revision,
+            deleted,
+            deleted_reason,
+            revisions,
+        })
+    }
+}
+
+/// Native implementation of ProductRevision_Change enum
+#[derive(Debug, Clone, PartialEq)]
+pub enum RevisionChange {
+    Create,
+    Update,
+    Patch,
+    Transfer,
+    Delete,
+}
+
+impl FromProto<protos::product_state::ProductRevision_Change> for RevisionChange {
+    fn from_proto(
+        change: protos::product_state::ProductRevision_Change,
+    ) -> Result<Self, ProtoConversionError> {
+        match change {
+            protos::product_state::ProductRevision_Change::CREATE => Ok(RevisionChange::Create),
+            protos::product_state::ProductRevision_Change::UPDATE => Ok(RevisionChange::Update),
+            protos::product_state::ProductRevision_Change::PATCH => Ok(RevisionChange::Patch),
+            protos::product_state::ProductRevision_Change::TRANSFER => {
+                Ok(RevisionChange::Transfer)
+            }
+            protos::product_state::ProductRevision_Change::DELETE => Ok(RevisionChange::Delete),
+            protos::product_state::ProductRevision_Change::UNSET_CHANGE => {
+                Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert ProductRevision_Change with type UNSET_CHANGE".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl FromNative<RevisionChange> for protos::product_state::ProductRevision_Change {
+    fn from_native(change: RevisionChange) -> Result<Self, ProtoConversionError> {
+        match change {
+            RevisionChange::Create => Ok(protos::product_state::ProductRevision_Change::CREATE),
+            RevisionChange::Update => Ok(protos::product_state::ProductRevision_Change::UPDATE),
+            RevisionChange::Patch => Ok(protos::product_state::ProductRevision_Change::PATCH),
+            RevisionChange::Transfer => {
+                Ok(protos::product_state::ProductRevision_Change::TRANSFER)
+            }
+            RevisionChange::Delete => Ok(protos::product_state::ProductRevision_Change::DELETE),
+        }
+    }
+}
+
+impl IntoProto<protos::product_state::ProductRevision_Change> for RevisionChange {}
+impl IntoNative<RevisionChange> for protos::product_state::ProductRevision_Change {}
+
+/// Native implementation of ProductRevision
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductRevision {
+    revision: u64,
+    timestamp: u64,
+    agent: String,
+    change: RevisionChange,
+    owner: String,
+    properties: Vec<PropertyValue>,
+    reason: String,
+}
+
+impl ProductRevision {
+    pub fn revision(&self) -> &u64 {
+        &self.revision
+    }
+
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+
+    pub fn agent(&self) -> &str {
+        &self.agent
+    }
+
+    pub fn change(&self) -> &RevisionChange {
+        &self.change
+    }
+
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    pub fn properties(&self) -> &[PropertyValue] {
+        &self.properties
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl FromProto<protos::product_state::ProductRevision> for ProductRevision {
+    fn from_proto(
+        revision: protos::product_state::ProductRevision,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProductRevision {
+            revision: revision.get_revision(),
+            timestamp: revision.get_timestamp(),
+            agent: revision.get_agent().to_string(),
+            change: RevisionChange::from_proto(revision.get_change())?,
+            owner: revision.get_owner().to_string(),
+            properties: revision
+                .get_properties()
+                .to_vec()
+                .into_iter()
+                .map(PropertyValue::from_proto)
+                .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            reason: revision.get_reason().to_string(),
+        })
+    }
+}
+
+impl FromNative<ProductRevision> for protos::product_state::ProductRevision {
+    fn from_native(revision: ProductRevision) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::product_state::ProductRevision::new();
+        proto.set_revision(*revision.revision());
+        proto.set_timestamp(*revision.timestamp());
+        proto.set_agent(revision.agent().to_string());
+        proto.set_change(revision.change().clone().into_proto()?);
+        proto.set_owner(revision.owner().to_string());
+        proto.set_properties(RepeatedField::from_vec(
+            revision
+                .properties()
+                .to_vec()
+                .into_iter()
+                .map(PropertyValue::into_proto)
+                .collect::<Result<Vec<schema_state::PropertyValue>, ProtoConversionError>>()?,
+        ));
+        proto.set_reason(revision.reason().to_string());
+        Ok(proto)
+    }
+}
+
+impl IntoProto<protos::product_state::ProductRevision> for ProductRevision {}
+impl IntoNative<ProductRevision> for protos::product_state::ProductRevision {}
+
+/// Builder used to create a ProductRevision
+#[derive(Default, Clone, PartialEq)]
+pub struct ProductRevisionBuilder {
+    pub revision: Option<u64>,
+    pub timestamp: Option<u64>,
+    pub agent: Option<String>,
+    pub change: Option<RevisionChange>,
+    pub owner: Option<String>,
+    pub properties: Option<Vec<PropertyValue>>,
+    pub reason: Option<String>,
+}
+
+impl ProductRevisionBuilder {
+    pub fn new() -> Self {
+        ProductRevisionBuilder::default()
+    }
+
+    pub fn with_revision(mut self, revision: u64) -> Self {
+        self.revision = Some(revision);
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn with_agent(mut self, agent: String) -> Self {
+        self.agent = Some(agent);
+        self
+    }
+
+    pub fn with_change(mut self, change: RevisionChange) -> Self {
+        self.change = Some(change);
+        self
+    }
+
+    pub fn with_owner(mut self, owner: String) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn with_properties(mut self, properties: Vec<PropertyValue>) -> Self {
+        self.properties = Some(properties);
+        self
+    }
+
+    pub fn with_reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    pub fn build(self) -> Result<ProductRevision, BuilderError> {
+        let revision = self.revision.ok_or_else(|| {
+            BuilderError::MissingField("'revision' field is required".to_string())
+        })?;
+
+        let timestamp = self.timestamp.ok_or_else(|| {
+            BuilderError::MissingField("'timestamp' field is required".to_string())
+        })?;
+
+        let agent = self
+            .agent
+            .ok_or_else(|| BuilderError::MissingField("'agent' field is required".to_string()))?;
+
+        let change = self
+            .change
+            .ok_or_else(|| BuilderError::MissingField("'change' field is required".to_string()))?;
+
+        let owner = self
+            .owner
+            .ok_or_else(|| BuilderError::MissingField("'owner' field is required".to_string()))?;
+
+        let properties = self.properties.unwrap_or_default();
+        let reason = self.reason.unwrap_or_default();
+
+        Ok(ProductRevision {
+            revision,
+            timestamp,
+            agent,
+            change,
+            owner,
+            properties,
+            reason,
         })
     }
 }
@@ -387,6 +709,344 @@ impl ProductListBuilder {
     }
 }
 
+/// Native implementation of ProductProposal_Status enum
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Open,
+    Accepted,
+    Rejected,
+    Canceled,
+}
+
+impl Default for Status {
+    fn default() -> Status {
+        Status::Open
+    }
+}
+
+impl FromProto<protos::product_state::ProductProposal_Status> for Status {
+    fn from_proto(
+        status: protos::product_state::ProductProposal_Status,
+    ) -> Result<Self, ProtoConversionError> {
+        match status {
+            protos::product_state::ProductProposal_Status::OPEN => Ok(Status::Open),
+            protos::product_state::ProductProposal_Status::ACCEPTED => Ok(Status::Accepted),
+            protos::product_state::ProductProposal_Status::REJECTED => Ok(Status::Rejected),
+            protos::product_state::ProductProposal_Status::CANCELED => Ok(Status::Canceled),
+        }
+    }
+}
+
+impl FromNative<Status> for protos::product_state::ProductProposal_Status {
+    fn from_native(status: Status) -> Result<Self, ProtoConversionError> {
+        match status {
+            Status::Open => Ok(protos::product_state::ProductProposal_Status::OPEN),
+            Status::Accepted => Ok(protos::product_state::ProductProposal_Status::ACCEPTED),
+            Status::Rejected => Ok(protos::product_state::ProductProposal_Status::REJECTED),
+            Status::Canceled => Ok(protos::product_state::ProductProposal_Status::CANCELED),
+        }
+    }
+}
+
+impl IntoProto<protos::product_state::ProductProposal_Status> for Status {}
+impl IntoNative<Status> for protos::product_state::ProductProposal_Status {}
+
+/// Native implementation of ProductProposal
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductProposal {
+    product_id: String,
+    timestamp: u64,
+    issuing_agent: String,
+    issuing_org: String,
+    receiving_org: String,
+    status: Status,
+    terms: String,
+}
+
+impl ProductProposal {
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+
+    pub fn issuing_agent(&self) -> &str {
+        &self.issuing_agent
+    }
+
+    pub fn issuing_org(&self) -> &str {
+        &self.issuing_org
+    }
+
+    pub fn receiving_org(&self) -> &str {
+        &self.receiving_org
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    pub fn terms(&self) -> &str {
+        &self.terms
+    }
+
+    pub fn into_builder(self) -> ProductProposalBuilder {
+        ProductProposalBuilder::new()
+            .with_product_id(self.product_id)
+            .with_timestamp(self.timestamp)
+            .with_issuing_agent(self.issuing_agent)
+            .with_issuing_org(self.issuing_org)
+            .with_receiving_org(self.receiving_org)
+            .with_status(self.status)
+            .with_terms(self.terms)
+    }
+}
+
+impl FromProto<protos::product_state::ProductProposal> for ProductProposal {
+    fn from_proto(
+        proposal: protos::product_state::ProductProposal,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProductProposal {
+            product_id: proposal.get_product_id().to_string(),
+            timestamp: proposal.get_timestamp(),
+            issuing_agent: proposal.get_issuing_agent().to_string(),
+            issuing_org: proposal.get_issuing_org().to_string(),
+            receiving_org: proposal.get_receiving_org().to_string(),
+            status: Status::from_proto(proposal.get_status())?,
+            terms: proposal.get_terms().to_string(),
+        })
+    }
+}
+
+impl FromNative<ProductProposal> for protos::product_state::ProductProposal {
+    fn from_native(proposal: ProductProposal) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::product_state::ProductProposal::new();
+        proto.set_product_id(proposal.product_id().to_string());
+        proto.set_timestamp(*proposal.timestamp());
+        proto.set_issuing_agent(proposal.issuing_agent().to_string());
+        proto.set_issuing_org(proposal.issuing_org().to_string());
+        proto.set_receiving_org(proposal.receiving_org().to_string());
+        proto.set_status(proposal.status().clone().into_proto()?);
+        proto.set_terms(proposal.terms().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ProductProposal> for ProductProposal {
+    fn from_bytes(bytes: &[u8]) -> Result<ProductProposal, ProtoConversionError> {
+        let proto: protos::product_state::ProductProposal = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ProductProposal from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ProductProposal {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ProductProposal".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::product_state::ProductProposal> for ProductProposal {}
+impl IntoNative<ProductProposal> for protos::product_state::ProductProposal {}
+
+/// Builder used to create a ProductProposal
+#[derive(Default, Clone, PartialEq)]
+pub struct ProductProposalBuilder {
+    pub product_id: Option<String>,
+    pub timestamp: Option<u64>,
+    pub issuing_agent: Option<String>,
+    pub issuing_org: Option<String>,
+    pub receiving_org: Option<String>,
+    pub status: Option<Status>,
+    pub terms: Option<String>,
+}
+
+impl ProductProposalBuilder {
+    pub fn new() -> Self {
+        ProductProposalBuilder::default()
+    }
+
+    pub fn with_product_id(mut self, product_id: String) -> Self {
+        self.product_id = Some(product_id);
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn with_issuing_agent(mut self, issuing_agent: String) -> Self {
+        self.issuing_agent = Some(issuing_agent);
+        self
+    }
+
+    pub fn with_issuing_org(mut self, issuing_org: String) -> Self {
+        self.issuing_org = Some(issuing_org);
+        self
+    }
+
+    pub fn with_receiving_org(mut self, receiving_org: String) -> Self {
+        self.receiving_org = Some(receiving_org);
+        self
+    }
+
+    pub fn with_status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn with_terms(mut self, terms: String) -> Self {
+        self.terms = Some(terms);
+        self
+    }
+
+    pub fn build(self) -> Result<ProductProposal, BuilderError> {
+        let product_id = self.product_id.ok_or_else(|| {
+            BuilderError::MissingField("'product_id' field is required".to_string())
+        })?;
+
+        let timestamp = self.timestamp.ok_or_else(|| {
+            BuilderError::MissingField("'timestamp' field is required".to_string())
+        })?;
+
+        let issuing_agent = self.issuing_agent.ok_or_else(|| {
+            BuilderError::MissingField("'issuing_agent' field is required".to_string())
+        })?;
+
+        let issuing_org = self.issuing_org.ok_or_else(|| {
+            BuilderError::MissingField("'issuing_org' field is required".to_string())
+        })?;
+
+        let receiving_org = self.receiving_org.ok_or_else(|| {
+            BuilderError::MissingField("'receiving_org' field is required".to_string())
+        })?;
+
+        let status = self.status.unwrap_or_default();
+        let terms = self.terms.unwrap_or_default();
+
+        Ok(ProductProposal {
+            product_id,
+            timestamp,
+            issuing_agent,
+            issuing_org,
+            receiving_org,
+            status,
+            terms,
+        })
+    }
+}
+
+/// Native implementation of ProductProposalList
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductProposalList {
+    proposals: Vec<ProductProposal>,
+}
+
+impl ProductProposalList {
+    pub fn proposals(&self) -> &[ProductProposal] {
+        &self.proposals
+    }
+
+    pub fn into_builder(self) -> ProductProposalListBuilder {
+        ProductProposalListBuilder::new().with_proposals(self.proposals)
+    }
+}
+
+impl FromProto<protos::product_state::ProductProposalList> for ProductProposalList {
+    fn from_proto(
+        proposal_list: protos::product_state::ProductProposalList,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProductProposalList {
+            proposals: proposal_list
+                .get_entries()
+                .to_vec()
+                .into_iter()
+                .map(ProductProposal::from_proto)
+                .collect::<Result<Vec<ProductProposal>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<ProductProposalList> for protos::product_state::ProductProposalList {
+    fn from_native(proposal_list: ProductProposalList) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::product_state::ProductProposalList::new();
+        proto.set_entries(RepeatedField::from_vec(
+            proposal_list
+                .proposals()
+                .to_vec()
+                .into_iter()
+                .map(ProductProposal::into_proto)
+                .collect::<Result<Vec<protos::product_state::ProductProposal>, ProtoConversionError>>()?,
+        ));
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ProductProposalList> for ProductProposalList {
+    fn from_bytes(bytes: &[u8]) -> Result<ProductProposalList, ProtoConversionError> {
+        let proto: protos::product_state::ProductProposalList = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ProductProposalList from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ProductProposalList {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ProductProposalList".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::product_state::ProductProposalList> for ProductProposalList {}
+impl IntoNative<ProductProposalList> for protos::product_state::ProductProposalList {}
+
+/// Builder used to create a ProductProposalList
+#[derive(Default, Clone)]
+pub struct ProductProposalListBuilder {
+    pub proposals: Option<Vec<ProductProposal>>,
+}
+
+impl ProductProposalListBuilder {
+    pub fn new() -> Self {
+        ProductProposalListBuilder::default()
+    }
+
+    pub fn with_proposals(mut self, proposals: Vec<ProductProposal>) -> ProductProposalListBuilder {
+        self.proposals = Some(proposals);
+        self
+    }
+
+    pub fn build(self) -> Result<ProductProposalList, BuilderError> {
+        let proposals = self.proposals.ok_or_else(|| {
+            BuilderError::MissingField("'proposals' field is required".to_string())
+        })?;
+
+        Ok(ProductProposalList { proposals })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -423,6 +1083,7 @@ mod tests {
         assert_eq!(builder.product_type, Some(ProductType::GS1));
         assert_eq!(builder.owner, Some("Target".to_string()));
         assert_eq!(builder.properties, Some(make_properties()));
+        assert_eq!(builder.schema, Some("gs1_product".to_string()));
     }
 
     #[test]
@@ -433,6 +1094,7 @@ mod tests {
             .with_product_id("688955434684".into())
             .with_product_type(ProductType::GS1)
             .with_owner("Target".into())
+            .with_schema("gs1_product".into())
             .with_properties(make_properties())
             .build()
             .unwrap();
@@ -440,6 +1102,33 @@ mod tests {
         test_from_bytes(original, Product::from_bytes);
     }
 
+    #[test]
+    // Test that a deleted product with revisions can be converted to bytes and back
+    fn test_product_with_revisions_into_bytes() {
+        let revision = ProductRevisionBuilder::new()
+            .with_revision(2)
+            .with_timestamp(1)
+            .with_agent("agent_public_key".into())
+            .with_change(RevisionChange::Delete)
+            .with_owner("Target".into())
+            .with_properties(make_properties())
+            .with_reason("discontinued".into())
+            .build()
+            .unwrap();
+        let original = build_product()
+            .into_builder()
+            .with_revision(2)
+            .with_deleted(true)
+            .with_deleted_reason("discontinued".into())
+            .with_revisions(vec![revision])
+            .build()
+            .unwrap();
+
+        assert_eq!(*original.revision(), 2);
+        assert_eq!(*original.revisions()[0].change(), RevisionChange::Delete);
+        test_from_bytes(original, Product::from_bytes);
+    }
+
     #[test]
     // Test that a product list can be built correctly
     fn test_product_list_builder() {
@@ -513,11 +1202,56 @@ mod tests {
         test_from_bytes(original, ProductList::from_bytes);
     }
 
+    #[test]
+    // Test that a product proposal can be built correctly
+    fn test_product_proposal_builder() {
+        let proposal = build_product_proposal();
+
+        assert_eq!(proposal.product_id(), "688955434684");
+        assert_eq!(*proposal.timestamp(), 1);
+        assert_eq!(proposal.issuing_agent(), "agent_public_key");
+        assert_eq!(proposal.issuing_org(), "Target");
+        assert_eq!(proposal.receiving_org(), "Cargill");
+        assert_eq!(*proposal.status(), Status::Open);
+        assert_eq!(proposal.terms(), "brand sale");
+    }
+
+    #[test]
+    // Test that a product proposal can be converted to bytes and back
+    fn test_product_proposal_into_bytes() {
+        test_from_bytes(build_product_proposal(), ProductProposal::from_bytes);
+    }
+
+    #[test]
+    // Test that a product proposal list can be converted to bytes and back
+    fn test_product_proposal_list_into_bytes() {
+        let original = ProductProposalListBuilder::new()
+            .with_proposals(vec![build_product_proposal()])
+            .build()
+            .unwrap();
+
+        test_from_bytes(original, ProductProposalList::from_bytes);
+    }
+
+    fn build_product_proposal() -> ProductProposal {
+        ProductProposalBuilder::new()
+            .with_product_id("688955434684".into())
+            .with_timestamp(1)
+            .with_issuing_agent("agent_public_key".into())
+            .with_issuing_org("Target".into())
+            .with_receiving_org("Cargill".into())
+            .with_status(Status::Open)
+            .with_terms("brand sale".into())
+            .build()
+            .expect("Failed to build test product proposal")
+    }
+
     fn build_product() -> Product {
         ProductBuilder::new()
             .with_product_id("688955434684".into()) // GTIN-12
             .with_product_type(ProductType::GS1)
             .with_owner("Target".into())
+            .with_schema("gs1_product".into())
             .with_properties(make_properties())
             .build()
             .expect("Failed to build test product")
@@ -556,6 +1290,7 @@ mod tests {
                 .with_product_id("688955434684".into()) // GTIN-12
                 .with_product_type(ProductType::GS1)
                 .with_owner("Target".into())
+                .with_schema("gs1_product".into())
                 .with_properties(make_properties())
                 .build()
                 .expect("Failed to build test product"),
@@ -563,6 +1298,7 @@ mod tests {
                 .with_product_id("688955434685".into()) // GTIN-12
                 .with_product_type(ProductType::GS1)
                 .with_owner("Cargill".into())
+                .with_schema("gs1_product".into())
                 .with_properties(make_properties())
                 .build()
                 .expect("Failed to build test product"),
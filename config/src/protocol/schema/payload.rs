@@ -28,6 +28,8 @@ use crate::protos::{
 pub enum Action {
     SchemaCreate(SchemaCreateAction),
     SchemaUpdate(SchemaUpdateAction),
+    SchemaDeprecate(SchemaDeprecateAction),
+    SchemaTransfer(SchemaTransferAction),
 }
 
 /// Native implementation for SchemaPayload
@@ -53,6 +55,16 @@ impl FromProto<protos::schema_payload::SchemaPayload> for SchemaPayload {
             protos::schema_payload::SchemaPayload_Action::SCHEMA_UPDATE => Action::SchemaUpdate(
                 SchemaUpdateAction::from_proto(payload.get_schema_update().clone())?,
             ),
+            protos::schema_payload::SchemaPayload_Action::SCHEMA_DEPRECATE => {
+                Action::SchemaDeprecate(SchemaDeprecateAction::from_proto(
+                    payload.get_schema_deprecate().clone(),
+                )?)
+            }
+            protos::schema_payload::SchemaPayload_Action::SCHEMA_TRANSFER => {
+                Action::SchemaTransfer(SchemaTransferAction::from_proto(
+                    payload.get_schema_transfer().clone(),
+                )?)
+            }
             protos::schema_payload::SchemaPayload_Action::UNSET_ACTION => {
                 return Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert SchemaPayload_Action with type unset.".to_string(),
@@ -77,6 +89,16 @@ impl FromNative<SchemaPayload> for protos::schema_payload::SchemaPayload {
                     .set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_UPDATE);
                 proto_payload.set_schema_update(payload.clone().into_proto()?);
             }
+            Action::SchemaDeprecate(payload) => {
+                proto_payload
+                    .set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_DEPRECATE);
+                proto_payload.set_schema_deprecate(payload.clone().into_proto()?);
+            }
+            Action::SchemaTransfer(payload) => {
+                proto_payload
+                    .set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_TRANSFER);
+                proto_payload.set_schema_transfer(payload.clone().into_proto()?);
+            }
         }
         Ok(proto_payload)
     }
@@ -326,6 +348,9 @@ impl SchemaCreateBuilder {
 pub struct SchemaUpdateAction {
     schema_name: String,
     properties: Vec<PropertyDefinition>,
+    enum_options_additions: Vec<EnumOptionsAddition>,
+    deprecated_properties: Vec<String>,
+    optional_properties: Vec<String>,
 }
 
 /// Native implementation for SchemaUpdateAction
@@ -337,6 +362,26 @@ impl SchemaUpdateAction {
     pub fn properties(&self) -> &[PropertyDefinition] {
         &self.properties
     }
+
+    pub fn enum_options_additions(&self) -> &[EnumOptionsAddition] {
+        &self.enum_options_additions
+    }
+
+    pub fn deprecated_properties(&self) -> &[String] {
+        &self.deprecated_properties
+    }
+
+    pub fn optional_properties(&self) -> &[String] {
+        &self.optional_properties
+    }
+
+    /// Returns true if the update does not contain any change
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+            && self.enum_options_additions.is_empty()
+            && self.deprecated_properties.is_empty()
+            && self.optional_properties.is_empty()
+    }
 }
 
 impl FromProto<protos::schema_payload::SchemaUpdateAction> for SchemaUpdateAction {
@@ -351,6 +396,14 @@ impl FromProto<protos::schema_payload::SchemaUpdateAction> for SchemaUpdateActio
                 .into_iter()
                 .map(PropertyDefinition::from_proto)
                 .collect::<Result<Vec<PropertyDefinition>, ProtoConversionError>>()?,
+            enum_options_additions: schema_update
+                .get_enum_options_additions()
+                .to_vec()
+                .into_iter()
+                .map(EnumOptionsAddition::from_proto)
+                .collect::<Result<Vec<EnumOptionsAddition>, ProtoConversionError>>()?,
+            deprecated_properties: schema_update.get_deprecated_properties().to_vec(),
+            optional_properties: schema_update.get_optional_properties().to_vec(),
         })
     }
 }
@@ -365,6 +418,20 @@ impl FromNative<SchemaUpdateAction> for protos::schema_payload::SchemaUpdateActi
             schema_update.properties().to_vec().into_iter()
             .map(PropertyDefinition::into_proto)
             .collect::<Result<Vec<protos::schema_state::PropertyDefinition>, ProtoConversionError>>()?,));
+        proto_schema_update.set_enum_options_additions(RepeatedField::from_vec(
+            schema_update
+                .enum_options_additions()
+                .to_vec()
+                .into_iter()
+                .map(EnumOptionsAddition::into_proto)
+                .collect::<Result<Vec<protos::schema_payload::EnumOptionsAddition>, ProtoConversionError>>()?,
+        ));
+        proto_schema_update.set_deprecated_properties(RepeatedField::from_vec(
+            schema_update.deprecated_properties().to_vec(),
+        ));
+        proto_schema_update.set_optional_properties(RepeatedField::from_vec(
+            schema_update.optional_properties().to_vec(),
+        ));
 
         Ok(proto_schema_update)
     }
@@ -430,6 +497,9 @@ pub struct SchemaUpdateBuilder {
     schema_name: Option<String>,
     description: Option<String>,
     properties: Vec<PropertyDefinition>,
+    enum_options_additions: Vec<EnumOptionsAddition>,
+    deprecated_properties: Vec<String>,
+    optional_properties: Vec<String>,
 }
 
 impl SchemaUpdateBuilder {
@@ -447,24 +517,385 @@ impl SchemaUpdateBuilder {
         self
     }
 
+    pub fn with_enum_options_additions(
+        mut self,
+        enum_options_additions: Vec<EnumOptionsAddition>,
+    ) -> SchemaUpdateBuilder {
+        self.enum_options_additions = enum_options_additions;
+        self
+    }
+
+    pub fn with_deprecated_properties(
+        mut self,
+        deprecated_properties: Vec<String>,
+    ) -> SchemaUpdateBuilder {
+        self.deprecated_properties = deprecated_properties;
+        self
+    }
+
+    pub fn with_optional_properties(
+        mut self,
+        optional_properties: Vec<String>,
+    ) -> SchemaUpdateBuilder {
+        self.optional_properties = optional_properties;
+        self
+    }
+
     pub fn build(self) -> Result<SchemaUpdateAction, SchemaUpdateBuildError> {
         let schema_name = self.schema_name.ok_or_else(|| {
             SchemaUpdateBuildError::MissingField("'schema field is required".to_string())
         })?;
 
-        let properties = {
-            if !self.properties.is_empty() {
-                self.properties
-            } else {
-                return Err(SchemaUpdateBuildError::MissingField(
-                    "'properties' field is required".to_string(),
-                ));
-            }
+        let update = SchemaUpdateAction {
+            schema_name,
+            properties: self.properties,
+            enum_options_additions: self.enum_options_additions,
+            deprecated_properties: self.deprecated_properties,
+            optional_properties: self.optional_properties,
         };
 
-        Ok(SchemaUpdateAction {
+        if update.is_empty() {
+            return Err(SchemaUpdateBuildError::MissingField(
+                "at least one schema change is required".to_string(),
+            ));
+        }
+
+        Ok(update)
+    }
+}
+
+/// Native implementation for EnumOptionsAddition
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumOptionsAddition {
+    property_name: String,
+    enum_options: Vec<String>,
+}
+
+impl EnumOptionsAddition {
+    pub fn property_name(&self) -> &str {
+        &self.property_name
+    }
+
+    pub fn enum_options(&self) -> &[String] {
+        &self.enum_options
+    }
+}
+
+impl FromProto<protos::schema_payload::EnumOptionsAddition> for EnumOptionsAddition {
+    fn from_proto(
+        addition: protos::schema_payload::EnumOptionsAddition,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(EnumOptionsAddition {
+            property_name: addition.get_property_name().to_string(),
+            enum_options: addition.get_enum_options().to_vec(),
+        })
+    }
+}
+
+impl FromNative<EnumOptionsAddition> for protos::schema_payload::EnumOptionsAddition {
+    fn from_native(addition: EnumOptionsAddition) -> Result<Self, ProtoConversionError> {
+        let mut proto_addition = protos::schema_payload::EnumOptionsAddition::new();
+        proto_addition.set_property_name(addition.property_name().to_string());
+        proto_addition.set_enum_options(RepeatedField::from_vec(addition.enum_options().to_vec()));
+        Ok(proto_addition)
+    }
+}
+
+impl IntoProto<protos::schema_payload::EnumOptionsAddition> for EnumOptionsAddition {}
+impl IntoNative<EnumOptionsAddition> for protos::schema_payload::EnumOptionsAddition {}
+
+/// Builder used to create an EnumOptionsAddition
+#[derive(Default, Clone)]
+pub struct EnumOptionsAdditionBuilder {
+    property_name: Option<String>,
+    enum_options: Vec<String>,
+}
+
+impl EnumOptionsAdditionBuilder {
+    pub fn new() -> Self {
+        EnumOptionsAdditionBuilder::default()
+    }
+
+    pub fn with_property_name(mut self, property_name: String) -> EnumOptionsAdditionBuilder {
+        self.property_name = Some(property_name);
+        self
+    }
+
+    pub fn with_enum_options(mut self, enum_options: Vec<String>) -> EnumOptionsAdditionBuilder {
+        self.enum_options = enum_options;
+        self
+    }
+
+    pub fn build(self) -> Result<EnumOptionsAddition, SchemaUpdateBuildError> {
+        let property_name = self.property_name.ok_or_else(|| {
+            SchemaUpdateBuildError::MissingField("'property_name' field is required".to_string())
+        })?;
+
+        if self.enum_options.is_empty() {
+            return Err(SchemaUpdateBuildError::MissingField(
+                "'enum_options' field is required".to_string(),
+            ));
+        }
+
+        Ok(EnumOptionsAddition {
+            property_name,
+            enum_options: self.enum_options,
+        })
+    }
+}
+
+/// Native implementation for SchemaDeprecateAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SchemaDeprecateAction {
+    schema_name: String,
+    reason: String,
+}
+
+impl SchemaDeprecateAction {
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl FromProto<protos::schema_payload::SchemaDeprecateAction> for SchemaDeprecateAction {
+    fn from_proto(
+        schema_deprecate: protos::schema_payload::SchemaDeprecateAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(SchemaDeprecateAction {
+            schema_name: schema_deprecate.get_schema_name().to_string(),
+            reason: schema_deprecate.get_reason().to_string(),
+        })
+    }
+}
+
+impl FromNative<SchemaDeprecateAction> for protos::schema_payload::SchemaDeprecateAction {
+    fn from_native(schema_deprecate: SchemaDeprecateAction) -> Result<Self, ProtoConversionError> {
+        let mut proto_schema_deprecate = protos::schema_payload::SchemaDeprecateAction::new();
+        proto_schema_deprecate.set_schema_name(schema_deprecate.schema_name().to_string());
+        proto_schema_deprecate.set_reason(schema_deprecate.reason().to_string());
+        Ok(proto_schema_deprecate)
+    }
+}
+
+impl FromBytes<SchemaDeprecateAction> for SchemaDeprecateAction {
+    fn from_bytes(bytes: &[u8]) -> Result<SchemaDeprecateAction, ProtoConversionError> {
+        let proto: protos::schema_payload::SchemaDeprecateAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get SchemaDeprecateAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for SchemaDeprecateAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from SchemaDeprecateAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::schema_payload::SchemaDeprecateAction> for SchemaDeprecateAction {}
+impl IntoNative<SchemaDeprecateAction> for protos::schema_payload::SchemaDeprecateAction {}
+
+#[derive(Debug)]
+pub enum SchemaDeprecateBuildError {
+    MissingField(String),
+}
+
+impl StdError for SchemaDeprecateBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            SchemaDeprecateBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            SchemaDeprecateBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaDeprecateBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            SchemaDeprecateBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a SchemaDeprecateAction
+#[derive(Default, Clone)]
+pub struct SchemaDeprecateBuilder {
+    schema_name: Option<String>,
+    reason: Option<String>,
+}
+
+impl SchemaDeprecateBuilder {
+    pub fn new() -> Self {
+        SchemaDeprecateBuilder::default()
+    }
+
+    pub fn with_schema_name(mut self, schema_name: String) -> SchemaDeprecateBuilder {
+        self.schema_name = Some(schema_name);
+        self
+    }
+
+    pub fn with_reason(mut self, reason: String) -> SchemaDeprecateBuilder {
+        self.reason = Some(reason);
+        self
+    }
+
+    pub fn build(self) -> Result<SchemaDeprecateAction, SchemaDeprecateBuildError> {
+        let schema_name = self.schema_name.ok_or_else(|| {
+            SchemaDeprecateBuildError::MissingField("'schema_name' field is required".to_string())
+        })?;
+
+        let reason = self.reason.unwrap_or_default();
+
+        Ok(SchemaDeprecateAction {
             schema_name,
-            properties,
+            reason,
+        })
+    }
+}
+
+/// Native implementation for SchemaTransferAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SchemaTransferAction {
+    schema_name: String,
+    new_owner: String,
+}
+
+impl SchemaTransferAction {
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    pub fn new_owner(&self) -> &str {
+        &self.new_owner
+    }
+}
+
+impl FromProto<protos::schema_payload::SchemaTransferAction> for SchemaTransferAction {
+    fn from_proto(
+        schema_transfer: protos::schema_payload::SchemaTransferAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(SchemaTransferAction {
+            schema_name: schema_transfer.get_schema_name().to_string(),
+            new_owner: schema_transfer.get_new_owner().to_string(),
+        })
+    }
+}
+
+impl FromNative<SchemaTransferAction> for protos::schema_payload::SchemaTransferAction {
+    fn from_native(schema_transfer: SchemaTransferAction) -> Result<Self, ProtoConversionError> {
+        let mut proto_schema_transfer = protos::schema_payload::SchemaTransferAction::new();
+        proto_schema_transfer.set_schema_name(schema_transfer.schema_name().to_string());
+        proto_schema_transfer.set_new_owner(schema_transfer.new_owner().to_string());
+        Ok(proto_schema_transfer)
+    }
+}
+
+impl FromBytes<SchemaTransferAction> for SchemaTransferAction {
+    fn from_bytes(bytes: &[u8]) -> Result<SchemaTransferAction, ProtoConversionError> {
+        let proto: protos::schema_payload::SchemaTransferAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get SchemaTransferAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for SchemaTransferAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from SchemaTransferAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::schema_payload::SchemaTransferAction> for SchemaTransferAction {}
+impl IntoNative<SchemaTransferAction> for protos::schema_payload::SchemaTransferAction {}
+
+#[derive(Debug)]
+pub enum SchemaTransferBuildError {
+    MissingField(String),
+}
+
+impl StdError for SchemaTransferBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            SchemaTransferBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            SchemaTransferBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaTransferBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            SchemaTransferBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a SchemaTransferAction
+#[derive(Default, Clone)]
+pub struct SchemaTransferBuilder {
+    schema_name: Option<String>,
+    new_owner: Option<String>,
+}
+
+impl SchemaTransferBuilder {
+    pub fn new() -> Self {
+        SchemaTransferBuilder::default()
+    }
+
+    pub fn with_schema_name(mut self, schema_name: String) -> SchemaTransferBuilder {
+        self.schema_name = Some(schema_name);
+        self
+    }
+
+    pub fn with_new_owner(mut self, new_owner: String) -> SchemaTransferBuilder {
+        self.new_owner = Some(new_owner);
+        self
+    }
+
+    pub fn build(self) -> Result<SchemaTransferAction, SchemaTransferBuildError> {
+        let schema_name = self.schema_name.ok_or_else(|| {
+            SchemaTransferBuildError::MissingField("'schema_name' field is required".to_string())
+        })?;
+
+        let new_owner = self.new_owner.ok_or_else(|| {
+            SchemaTransferBuildError::MissingField("'new_owner' field is required".to_string())
+        })?;
+
+        Ok(SchemaTransferAction {
+            schema_name,
+            new_owner,
         })
     }
 }
@@ -569,6 +1000,74 @@ mod tests {
         assert_eq!(update, original);
     }
 
+    #[test]
+    // check that a schema update with only compatible evolution changes can be converted to
+    // bytes and back
+    fn check_schema_update_evolution_bytes() {
+        let addition = EnumOptionsAdditionBuilder::new()
+            .with_property_name("color".to_string())
+            .with_enum_options(vec!["green".to_string()])
+            .build()
+            .unwrap();
+
+        let original = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_enum_options_additions(vec![addition])
+            .with_deprecated_properties(vec!["weight".to_string()])
+            .with_optional_properties(vec!["size".to_string()])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let update = SchemaUpdateAction::from_bytes(&bytes).unwrap();
+        assert_eq!(update, original);
+    }
+
+    #[test]
+    // check that a schema update without any change cannot be built
+    fn check_schema_update_without_changes() {
+        assert!(SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that a schema deprecate action can be converted to bytes and back
+    fn check_schema_deprecate_bytes() {
+        let original = SchemaDeprecateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_reason("superseded".to_string())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let deprecate = SchemaDeprecateAction::from_bytes(&bytes).unwrap();
+        assert_eq!(deprecate, original);
+    }
+
+    #[test]
+    // check that a schema payload with a transfer action can be converted to bytes and back
+    fn check_schema_transfer_payload_bytes() {
+        let action = SchemaTransferBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_new_owner("new_org".to_string())
+            .build()
+            .unwrap();
+
+        let original = SchemaPayloadBuilder::new()
+            .with_action(Action::SchemaTransfer(action))
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let payload = SchemaPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(payload, original);
+    }
+
     #[test]
     // check that a schema payload with create action is built correctly
     fn check_schema_create_action_payload() {
@@ -14,6 +14,7 @@
 
 use protobuf::Message;
 use protobuf::RepeatedField;
+use regex::Regex;
 
 use std::error::Error as StdError;
 
@@ -32,6 +33,9 @@ pub enum DataType {
     Enum,
     Struct,
     LatLong,
+    Reference,
+    List,
+    Timestamp,
 }
 
 impl FromProto<protos::schema_state::PropertyDefinition_DataType> for DataType {
@@ -46,6 +50,9 @@ impl FromProto<protos::schema_state::PropertyDefinition_DataType> for DataType {
             protos::schema_state::PropertyDefinition_DataType::ENUM => Ok(DataType::Enum),
             protos::schema_state::PropertyDefinition_DataType::STRUCT => Ok(DataType::Struct),
             protos::schema_state::PropertyDefinition_DataType::LAT_LONG => Ok(DataType::LatLong),
+            protos::schema_state::PropertyDefinition_DataType::REFERENCE => Ok(DataType::Reference),
+            protos::schema_state::PropertyDefinition_DataType::LIST => Ok(DataType::List),
+            protos::schema_state::PropertyDefinition_DataType::TIMESTAMP => Ok(DataType::Timestamp),
             protos::schema_state::PropertyDefinition_DataType::UNSET_DATA_TYPE => {
                 Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert PropertyDefinition_DataType with type unset.".to_string(),
@@ -65,6 +72,13 @@ impl FromNative<DataType> for protos::schema_state::PropertyDefinition_DataType
             DataType::Enum => Ok(protos::schema_state::PropertyDefinition_DataType::ENUM),
             DataType::Struct => Ok(protos::schema_state::PropertyDefinition_DataType::STRUCT),
             DataType::LatLong => Ok(protos::schema_state::PropertyDefinition_DataType::LAT_LONG),
+            DataType::Reference => {
+                Ok(protos::schema_state::PropertyDefinition_DataType::REFERENCE)
+            }
+            DataType::List => Ok(protos::schema_state::PropertyDefinition_DataType::LIST),
+            DataType::Timestamp => {
+                Ok(protos::schema_state::PropertyDefinition_DataType::TIMESTAMP)
+            }
         }
     }
 }
@@ -72,6 +86,278 @@ impl FromNative<DataType> for protos::schema_state::PropertyDefinition_DataType
 impl IntoProto<protos::schema_state::PropertyDefinition_DataType> for DataType {}
 impl IntoNative<DataType> for protos::schema_state::PropertyDefinition_DataType {}
 
+/// Native implementation of ReferenceKind enum
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReferenceKind {
+    Record,
+    Product,
+    Organization,
+    Agent,
+}
+
+impl FromProto<protos::schema_state::PropertyDefinition_ReferenceKind> for ReferenceKind {
+    fn from_proto(
+        reference_kind: protos::schema_state::PropertyDefinition_ReferenceKind,
+    ) -> Result<Self, ProtoConversionError> {
+        match reference_kind {
+            protos::schema_state::PropertyDefinition_ReferenceKind::RECORD => {
+                Ok(ReferenceKind::Record)
+            }
+            protos::schema_state::PropertyDefinition_ReferenceKind::PRODUCT => {
+                Ok(ReferenceKind::Product)
+            }
+            protos::schema_state::PropertyDefinition_ReferenceKind::ORGANIZATION => {
+                Ok(ReferenceKind::Organization)
+            }
+            protos::schema_state::PropertyDefinition_ReferenceKind::AGENT => {
+                Ok(ReferenceKind::Agent)
+            }
+            protos::schema_state::PropertyDefinition_ReferenceKind::UNSET_REFERENCE_KIND => {
+                Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert PropertyDefinition_ReferenceKind with type unset.".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl FromNative<ReferenceKind> for protos::schema_state::PropertyDefinition_ReferenceKind {
+    fn from_native(reference_kind: ReferenceKind) -> Result<Self, ProtoConversionError> {
+        match reference_kind {
+            ReferenceKind::Record => {
+                Ok(protos::schema_state::PropertyDefinition_ReferenceKind::RECORD)
+            }
+            ReferenceKind::Product => {
+                Ok(protos::schema_state::PropertyDefinition_ReferenceKind::PRODUCT)
+            }
+            ReferenceKind::Organization => {
+                Ok(protos::schema_state::PropertyDefinition_ReferenceKind::ORGANIZATION)
+            }
+            ReferenceKind::Agent => {
+                Ok(protos::schema_state::PropertyDefinition_ReferenceKind::AGENT)
+            }
+        }
+    }
+}
+
+/// Native implementation of TimestampPrecision enum
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimestampPrecision {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl Default for TimestampPrecision {
+    fn default() -> Self {
+        TimestampPrecision::Seconds
+    }
+}
+
+impl TimestampPrecision {
+    /// The number of units of this precision in one second
+    pub fn units_per_second(&self) -> i64 {
+        match self {
+            TimestampPrecision::Seconds => 1,
+            TimestampPrecision::Milliseconds => 1_000,
+            TimestampPrecision::Microseconds => 1_000_000,
+            TimestampPrecision::Nanoseconds => 1_000_000_000,
+        }
+    }
+}
+
+impl FromProto<protos::schema_state::PropertyDefinition_TimestampPrecision>
+    for TimestampPrecision
+{
+    fn from_proto(
+        precision: protos::schema_state::PropertyDefinition_TimestampPrecision,
+    ) -> Result<Self, ProtoConversionError> {
+        match precision {
+            protos::schema_state::PropertyDefinition_TimestampPrecision::SECONDS => {
+                Ok(TimestampPrecision::Seconds)
+            }
+            protos::schema_state::PropertyDefinition_TimestampPrecision::MILLISECONDS => {
+                Ok(TimestampPrecision::Milliseconds)
+            }
+            protos::schema_state::PropertyDefinition_TimestampPrecision::MICROSECONDS => {
+                Ok(TimestampPrecision::Microseconds)
+            }
+            protos::schema_state::PropertyDefinition_TimestampPrecision::NANOSECONDS => {
+                Ok(TimestampPrecision::Nanoseconds)
+            }
+        }
+    }
+}
+
+impl FromNative<TimestampPrecision>
+    for protos::schema_state::PropertyDefinition_TimestampPrecision
+{
+    fn from_native(precision: TimestampPrecision) -> Result<Self, ProtoConversionError> {
+        match precision {
+            TimestampPrecision::Seconds => {
+                Ok(protos::schema_state::PropertyDefinition_TimestampPrecision::SECONDS)
+            }
+            TimestampPrecision::Milliseconds => {
+                Ok(protos::schema_state::PropertyDefinition_TimestampPrecision::MILLISECONDS)
+            }
+            TimestampPrecision::Microseconds => {
+                Ok(protos::schema_state::PropertyDefinition_TimestampPrecision::MICROSECONDS)
+            }
+            TimestampPrecision::Nanoseconds => {
+                Ok(protos::schema_state::PropertyDefinition_TimestampPrecision::NANOSECONDS)
+            }
+        }
+    }
+}
+
+impl IntoProto<protos::schema_state::PropertyDefinition_TimestampPrecision>
+    for TimestampPrecision
+{
+}
+impl IntoNative<TimestampPrecision>
+    for protos::schema_state::PropertyDefinition_TimestampPrecision
+{
+}
+
+/// Native implementation of RetentionPolicy enum
+#[derive(Debug, Clone, PartialEq)]
+pub enum RetentionPolicy {
+    Wrap,
+    KeepAll,
+    Archive,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy::Wrap
+    }
+}
+
+impl FromProto<protos::schema_state::PropertyDefinition_RetentionPolicy> for RetentionPolicy {
+    fn from_proto(
+        retention: protos::schema_state::PropertyDefinition_RetentionPolicy,
+    ) -> Result<Self, ProtoConversionError> {
+        match retention {
+            protos::schema_state::PropertyDefinition_RetentionPolicy::WRAP => {
+                Ok(RetentionPolicy::Wrap)
+            }
+            protos::schema_state::PropertyDefinition_RetentionPolicy::KEEP_ALL => {
+                Ok(RetentionPolicy::KeepAll)
+            }
+            protos::schema_state::PropertyDefinition_RetentionPolicy::ARCHIVE => {
+                Ok(RetentionPolicy::Archive)
+            }
+        }
+    }
+}
+
+impl FromNative<RetentionPolicy> for protos::schema_state::PropertyDefinition_RetentionPolicy {
+    fn from_native(retention: RetentionPolicy) -> Result<Self, ProtoConversionError> {
+        match retention {
+            RetentionPolicy::Wrap => {
+                Ok(protos::schema_state::PropertyDefinition_RetentionPolicy::WRAP)
+            }
+            RetentionPolicy::KeepAll => {
+                Ok(protos::schema_state::PropertyDefinition_RetentionPolicy::KEEP_ALL)
+            }
+            RetentionPolicy::Archive => {
+                Ok(protos::schema_state::PropertyDefinition_RetentionPolicy::ARCHIVE)
+            }
+        }
+    }
+}
+
+impl IntoProto<protos::schema_state::PropertyDefinition_RetentionPolicy> for RetentionPolicy {}
+impl IntoNative<RetentionPolicy> for protos::schema_state::PropertyDefinition_RetentionPolicy {}
+
+/// The number of reported values in a page of a property's history when the property
+/// definition does not set a page_size.
+pub const DEFAULT_PAGE_SIZE: u32 = 256;
+
+/// The number of pages of a property's history when the property definition does not set
+/// max_pages. Page numbers are the last four hex characters of a page's address and page 0
+/// holds the property itself, so this is also the largest max_pages allowed.
+pub const MAX_PAGES: u32 = 0xffff;
+
+/// The largest magnitude of a NUMBER property's number_exponent; 10^18 is the largest
+/// power of ten that fits in an i64.
+pub const MAX_NUMBER_EXPONENT: i32 = 18;
+
+/// Converts a decimal string such as "12.50" into the number_value of a NUMBER property
+/// with the given number_exponent, so that number_value * 10^number_exponent equals the
+/// decimal exactly. Decimals that are more precise than the exponent allows, or that do
+/// not fit in an i64, are rejected rather than rounded.
+pub fn decimal_to_number(decimal: &str, number_exponent: i32) -> Result<i64, String> {
+    let decimal = decimal.trim();
+    let (negative, unsigned) = if let Some(unsigned) = decimal.strip_prefix('-') {
+        (true, unsigned)
+    } else {
+        (false, decimal.strip_prefix('+').unwrap_or(decimal))
+    };
+    let (integer_part, fraction_part) = match unsigned.find('.') {
+        Some(index) => (&unsigned[..index], &unsigned[index + 1..]),
+        None => (unsigned, ""),
+    };
+    if (integer_part.is_empty() && fraction_part.is_empty())
+        || !integer_part.chars().all(|c| c.is_ascii_digit())
+        || !fraction_part.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("{:?} is not a decimal number", decimal));
+    }
+
+    // The decimal is digits * 10^-scale, so the number is digits * 10^shift
+    let mut digits = format!("{}{}", integer_part, fraction_part);
+    let shift = -(fraction_part.len() as i64) - i64::from(number_exponent);
+    if shift >= 0 {
+        if shift > i64::from(MAX_NUMBER_EXPONENT) + 1 {
+            return Err(format!("{:?} is too large", decimal));
+        }
+        digits.push_str(&"0".repeat(shift as usize));
+    } else {
+        let dropped = (-shift) as usize;
+        let keep = digits.len().saturating_sub(dropped);
+        if digits[keep..].chars().any(|c| c != '0') {
+            return Err(format!(
+                "{:?} has more decimal places than the exponent {} allows",
+                decimal, number_exponent
+            ));
+        }
+        digits.truncate(keep);
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    if negative {
+        digits.insert(0, '-');
+    }
+    digits
+        .parse::<i64>()
+        .map_err(|_| format!("{:?} is too large", decimal))
+}
+
+/// Converts the number_value of a NUMBER property with the given number_exponent into a
+/// decimal string, e.g. 1250 with an exponent of -2 becomes "12.50".
+pub fn number_to_decimal(number: i64, number_exponent: i32) -> String {
+    let sign = if number < 0 { "-" } else { "" };
+    let mut digits = i128::from(number).abs().to_string();
+    if number_exponent >= 0 {
+        if number != 0 {
+            digits.push_str(&"0".repeat(number_exponent as usize));
+        }
+        return format!("{}{}", sign, digits);
+    }
+    let scale = (-number_exponent) as usize;
+    if digits.len() <= scale {
+        digits = format!("{}{}", "0".repeat(scale + 1 - digits.len()), digits);
+    }
+    let (integer_part, fraction_part) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, integer_part, fraction_part)
+}
+
+impl IntoProto<protos::schema_state::PropertyDefinition_ReferenceKind> for ReferenceKind {}
+impl IntoNative<ReferenceKind> for protos::schema_state::PropertyDefinition_ReferenceKind {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct LatLong {
     latitude: i64,
@@ -171,6 +457,357 @@ impl LatLongBuilder {
     }
 }
 
+/// Native implementation of Geofence
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geofence {
+    name: String,
+    vertices: Vec<LatLong>,
+}
+
+impl Geofence {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn vertices(&self) -> &[LatLong] {
+        &self.vertices
+    }
+
+    /// Whether a location is inside of the polygon; locations on an edge are inside
+    pub fn contains(&self, location: &LatLong) -> bool {
+        let y = i128::from(*location.latitude());
+        let x = i128::from(*location.longitude());
+        let mut inside = false;
+        for (i, a) in self.vertices.iter().enumerate() {
+            let b = &self.vertices[(i + 1) % self.vertices.len()];
+            let (ay, ax) = (i128::from(*a.latitude()), i128::from(*a.longitude()));
+            let (by, bx) = (i128::from(*b.latitude()), i128::from(*b.longitude()));
+
+            let on_line = (bx - ax) * (y - ay) == (by - ay) * (x - ax);
+            if on_line && x >= ax.min(bx) && x <= ax.max(bx) && y >= ay.min(by) && y <= ay.max(by)
+            {
+                return true;
+            }
+
+            // Count the edges crossed by a ray from the location towards increasing longitude
+            if (ay > y) != (by > y) {
+                let offset = (x - ax) * (by - ay);
+                let edge_offset = (y - ay) * (bx - ax);
+                if (by > ay && offset < edge_offset) || (by < ay && offset > edge_offset) {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    pub fn into_builder(self) -> GeofenceBuilder {
+        GeofenceBuilder::new()
+            .with_name(self.name)
+            .with_vertices(self.vertices)
+    }
+}
+
+impl FromProto<protos::schema_state::Geofence> for Geofence {
+    fn from_proto(geofence: protos::schema_state::Geofence) -> Result<Self, ProtoConversionError> {
+        Ok(Geofence {
+            name: geofence.get_name().to_string(),
+            vertices: geofence
+                .get_vertices()
+                .to_vec()
+                .into_iter()
+                .map(LatLong::from_proto)
+                .collect::<Result<Vec<LatLong>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<Geofence> for protos::schema_state::Geofence {
+    fn from_native(geofence: Geofence) -> Result<Self, ProtoConversionError> {
+        let mut proto_geofence = protos::schema_state::Geofence::new();
+        proto_geofence.set_name(geofence.name().to_string());
+        proto_geofence.set_vertices(RepeatedField::from_vec(
+            geofence
+                .vertices()
+                .to_vec()
+                .into_iter()
+                .map(LatLong::into_proto)
+                .collect::<Result<Vec<protos::schema_state::LatLong>, ProtoConversionError>>()?,
+        ));
+        Ok(proto_geofence)
+    }
+}
+
+impl IntoProto<protos::schema_state::Geofence> for Geofence {}
+impl IntoNative<Geofence> for protos::schema_state::Geofence {}
+
+#[derive(Debug)]
+pub enum GeofenceBuildError {
+    MissingField(String),
+    InvalidField(String),
+}
+
+impl StdError for GeofenceBuildError {}
+
+impl std::fmt::Display for GeofenceBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            GeofenceBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+            GeofenceBuildError::InvalidField(ref s) => write!(f, "InvalidField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a Geofence
+#[derive(Default, Clone, PartialEq)]
+pub struct GeofenceBuilder {
+    pub name: Option<String>,
+    pub vertices: Vec<LatLong>,
+}
+
+impl GeofenceBuilder {
+    pub fn new() -> Self {
+        GeofenceBuilder::default()
+    }
+
+    pub fn with_name(mut self, name: String) -> GeofenceBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn with_vertices(mut self, vertices: Vec<LatLong>) -> GeofenceBuilder {
+        self.vertices = vertices;
+        self
+    }
+
+    pub fn build(self) -> Result<Geofence, GeofenceBuildError> {
+        let name = self.name.ok_or_else(|| {
+            GeofenceBuildError::MissingField("'name' field is required".to_string())
+        })?;
+        if name.is_empty() {
+            return Err(GeofenceBuildError::InvalidField(
+                "'name' cannot be empty".to_string(),
+            ));
+        }
+
+        if self.vertices.len() < 3 {
+            return Err(GeofenceBuildError::InvalidField(format!(
+                "geofence {} must have at least 3 vertices",
+                name
+            )));
+        }
+
+        Ok(Geofence {
+            name,
+            vertices: self.vertices,
+        })
+    }
+}
+
+/// Native implementation of AlertRule
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    name: String,
+    min: Option<i64>,
+    max: Option<i64>,
+    max_rate_of_change: u64,
+    sustained_duration: u64,
+}
+
+impl AlertRule {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn min(&self) -> Option<&i64> {
+        self.min.as_ref()
+    }
+
+    pub fn max(&self) -> Option<&i64> {
+        self.max.as_ref()
+    }
+
+    pub fn max_rate_of_change(&self) -> &u64 {
+        &self.max_rate_of_change
+    }
+
+    pub fn sustained_duration(&self) -> &u64 {
+        &self.sustained_duration
+    }
+
+    /// Whether a value is outside of the allowed range
+    pub fn is_out_of_range(&self, value: i64) -> bool {
+        self.min.map_or(false, |min| value < min) || self.max.map_or(false, |max| value > max)
+    }
+
+    /// Whether the change from a previous value is faster than allowed; changes within the
+    /// same second are held to the rate of one second
+    pub fn exceeds_rate_of_change(
+        &self,
+        previous_value: i64,
+        previous_timestamp: u64,
+        value: i64,
+        timestamp: u64,
+    ) -> bool {
+        if self.max_rate_of_change == 0 {
+            return false;
+        }
+        let change = (i128::from(value) - i128::from(previous_value)).abs();
+        let elapsed = if timestamp > previous_timestamp {
+            timestamp - previous_timestamp
+        } else {
+            previous_timestamp - timestamp
+        };
+        let elapsed = i128::from(elapsed.max(1));
+        change > i128::from(self.max_rate_of_change) * elapsed
+    }
+
+    pub fn into_builder(self) -> AlertRuleBuilder {
+        AlertRuleBuilder {
+            name: Some(self.name),
+            min: self.min,
+            max: self.max,
+            max_rate_of_change: Some(self.max_rate_of_change),
+            sustained_duration: Some(self.sustained_duration),
+        }
+    }
+}
+
+impl FromProto<protos::schema_state::AlertRule> for AlertRule {
+    fn from_proto(rule: protos::schema_state::AlertRule) -> Result<Self, ProtoConversionError> {
+        Ok(AlertRule {
+            name: rule.get_name().to_string(),
+            min: if rule.get_has_min() {
+                Some(rule.get_min())
+            } else {
+                None
+            },
+            max: if rule.get_has_max() {
+                Some(rule.get_max())
+            } else {
+                None
+            },
+            max_rate_of_change: rule.get_max_rate_of_change(),
+            sustained_duration: rule.get_sustained_duration(),
+        })
+    }
+}
+
+impl FromNative<AlertRule> for protos::schema_state::AlertRule {
+    fn from_native(rule: AlertRule) -> Result<Self, ProtoConversionError> {
+        let mut proto_rule = protos::schema_state::AlertRule::new();
+        proto_rule.set_name(rule.name().to_string());
+        if let Some(min) = rule.min() {
+            proto_rule.set_has_min(true);
+            proto_rule.set_min(*min);
+        }
+        if let Some(max) = rule.max() {
+            proto_rule.set_has_max(true);
+            proto_rule.set_max(*max);
+        }
+        proto_rule.set_max_rate_of_change(*rule.max_rate_of_change());
+        proto_rule.set_sustained_duration(*rule.sustained_duration());
+        Ok(proto_rule)
+    }
+}
+
+impl IntoProto<protos::schema_state::AlertRule> for AlertRule {}
+impl IntoNative<AlertRule> for protos::schema_state::AlertRule {}
+
+#[derive(Debug)]
+pub enum AlertRuleBuildError {
+    MissingField(String),
+    InvalidField(String),
+}
+
+impl StdError for AlertRuleBuildError {}
+
+impl std::fmt::Display for AlertRuleBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            AlertRuleBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+            AlertRuleBuildError::InvalidField(ref s) => write!(f, "InvalidField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create an AlertRule
+#[derive(Default, Clone, PartialEq)]
+pub struct AlertRuleBuilder {
+    pub name: Option<String>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub max_rate_of_change: Option<u64>,
+    pub sustained_duration: Option<u64>,
+}
+
+impl AlertRuleBuilder {
+    pub fn new() -> Self {
+        AlertRuleBuilder::default()
+    }
+
+    pub fn with_name(mut self, name: String) -> AlertRuleBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn with_min(mut self, min: i64) -> AlertRuleBuilder {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn with_max(mut self, max: i64) -> AlertRuleBuilder {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn with_max_rate_of_change(mut self, max_rate_of_change: u64) -> AlertRuleBuilder {
+        self.max_rate_of_change = Some(max_rate_of_change);
+        self
+    }
+
+    pub fn with_sustained_duration(mut self, sustained_duration: u64) -> AlertRuleBuilder {
+        self.sustained_duration = Some(sustained_duration);
+        self
+    }
+
+    pub fn build(self) -> Result<AlertRule, AlertRuleBuildError> {
+        let name = self.name.ok_or_else(|| {
+            AlertRuleBuildError::MissingField("'name' field is required".to_string())
+        })?;
+        if name.is_empty() {
+            return Err(AlertRuleBuildError::InvalidField(
+                "'name' cannot be empty".to_string(),
+            ));
+        }
+
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(AlertRuleBuildError::InvalidField(format!(
+                    "alert rule {} has a min {} greater than its max {}",
+                    name, min, max
+                )));
+            }
+        }
+
+        let max_rate_of_change = self.max_rate_of_change.unwrap_or_default();
+        if self.min.is_none() && self.max.is_none() && max_rate_of_change == 0 {
+            return Err(AlertRuleBuildError::InvalidField(format!(
+                "alert rule {} needs a min, a max or a max_rate_of_change",
+                name
+            )));
+        }
+
+        Ok(AlertRule {
+            name,
+            min: self.min,
+            max: self.max,
+            max_rate_of_change,
+            sustained_duration: self.sustained_duration.unwrap_or_default(),
+        })
+    }
+}
+
 /// Native implementation of PropertyDefinition
 #[derive(Debug, Clone, PartialEq)]
 pub struct PropertyDefinition {
@@ -178,9 +815,21 @@ pub struct PropertyDefinition {
     data_type: DataType,
     required: bool,
     description: String,
+    deprecated: bool,
     number_exponent: i32,
     enum_options: Vec<String>,
     struct_properties: Vec<PropertyDefinition>,
+    constraints: PropertyConstraints,
+    reference_kind: Option<ReferenceKind>,
+    reference_schema: String,
+    list_element: Option<Box<PropertyDefinition>>,
+    timestamp_precision: TimestampPrecision,
+    page_size: u32,
+    max_pages: u32,
+    retention: RetentionPolicy,
+    geofences: Vec<Geofence>,
+    alert_rules: Vec<AlertRule>,
+    confidential: bool,
 }
 
 impl PropertyDefinition {
@@ -200,6 +849,10 @@ impl PropertyDefinition {
         &self.description
     }
 
+    pub fn deprecated(&self) -> &bool {
+        &self.deprecated
+    }
+
     pub fn number_exponent(&self) -> &i32 {
         &self.number_exponent
     }
@@ -211,6 +864,89 @@ impl PropertyDefinition {
     pub fn struct_properties(&self) -> &[PropertyDefinition] {
         &self.struct_properties
     }
+
+    pub fn constraints(&self) -> &PropertyConstraints {
+        &self.constraints
+    }
+
+    pub fn reference_kind(&self) -> Option<&ReferenceKind> {
+        self.reference_kind.as_ref()
+    }
+
+    pub fn reference_schema(&self) -> &str {
+        &self.reference_schema
+    }
+
+    pub fn list_element(&self) -> Option<&PropertyDefinition> {
+        self.list_element.as_deref()
+    }
+
+    pub fn timestamp_precision(&self) -> &TimestampPrecision {
+        &self.timestamp_precision
+    }
+
+    /// The number of reported values in each page of the property's history
+    pub fn page_size(&self) -> &u32 {
+        &self.page_size
+    }
+
+    /// The number of pages of the property's history
+    pub fn max_pages(&self) -> &u32 {
+        &self.max_pages
+    }
+
+    pub fn retention(&self) -> &RetentionPolicy {
+        &self.retention
+    }
+
+    pub fn geofences(&self) -> &[Geofence] {
+        &self.geofences
+    }
+
+    pub fn alert_rules(&self) -> &[AlertRule] {
+        &self.alert_rules
+    }
+
+    /// Whether the values of the property are encrypted with the data key of the record
+    pub fn confidential(&self) -> &bool {
+        &self.confidential
+    }
+
+    /// The names of the geofences that a LAT_LONG value is outside of
+    pub fn geofence_breaches(&self, value: &PropertyValue) -> Vec<String> {
+        if value.data_type() != &DataType::LatLong {
+            return vec![];
+        }
+        self.geofences
+            .iter()
+            .filter(|geofence| !geofence.contains(value.lat_long_value()))
+            .map(|geofence| geofence.name().to_string())
+            .collect()
+    }
+
+    pub fn into_builder(self) -> PropertyDefinitionBuilder {
+        let mut builder = PropertyDefinitionBuilder::new()
+            .with_name(self.name)
+            .with_data_type(self.data_type)
+            .with_required(self.required)
+            .with_description(self.description)
+            .with_deprecated(self.deprecated)
+            .with_number_exponent(self.number_exponent)
+            .with_enum_options(self.enum_options)
+            .with_struct_properties(self.struct_properties)
+            .with_constraints(self.constraints)
+            .with_reference_schema(self.reference_schema)
+            .with_geofences(self.geofences)
+            .with_alert_rules(self.alert_rules)
+            .with_confidential(self.confidential);
+        builder.reference_kind = self.reference_kind;
+        builder.list_element = self.list_element.map(|element| *element);
+        builder.timestamp_precision = Some(self.timestamp_precision);
+        builder.page_size = Some(self.page_size);
+        builder.max_pages = Some(self.max_pages);
+        builder.retention = Some(self.retention);
+        builder
+    }
 }
 
 impl FromProto<protos::schema_state::PropertyDefinition> for PropertyDefinition {
@@ -222,6 +958,7 @@ impl FromProto<protos::schema_state::PropertyDefinition> for PropertyDefinition
             data_type: DataType::from_proto(property_definition.get_data_type())?,
             required: property_definition.get_required(),
             description: property_definition.get_description().to_string(),
+            deprecated: property_definition.get_deprecated(),
             number_exponent: property_definition.get_number_exponent(),
             enum_options: property_definition.get_enum_options().to_vec(),
             struct_properties: property_definition
@@ -230,6 +967,50 @@ impl FromProto<protos::schema_state::PropertyDefinition> for PropertyDefinition
                 .into_iter()
                 .map(PropertyDefinition::from_proto)
                 .collect::<Result<Vec<PropertyDefinition>, ProtoConversionError>>()?,
+            constraints: if property_definition.has_constraints() {
+                PropertyConstraints::from_proto(property_definition.get_constraints().clone())?
+            } else {
+                PropertyConstraints::default()
+            },
+            reference_kind: match property_definition.get_reference_kind() {
+                protos::schema_state::PropertyDefinition_ReferenceKind::UNSET_REFERENCE_KIND => {
+                    None
+                }
+                reference_kind => Some(ReferenceKind::from_proto(reference_kind)?),
+            },
+            reference_schema: property_definition.get_reference_schema().to_string(),
+            list_element: if property_definition.has_list_element() {
+                Some(Box::new(PropertyDefinition::from_proto(
+                    property_definition.get_list_element().clone(),
+                )?))
+            } else {
+                None
+            },
+            timestamp_precision: TimestampPrecision::from_proto(
+                property_definition.get_timestamp_precision(),
+            )?,
+            page_size: match property_definition.get_page_size() {
+                0 => DEFAULT_PAGE_SIZE,
+                page_size => page_size,
+            },
+            max_pages: match property_definition.get_max_pages() {
+                0 => MAX_PAGES,
+                max_pages => max_pages,
+            },
+            retention: RetentionPolicy::from_proto(property_definition.get_retention())?,
+            geofences: property_definition
+                .get_geofences()
+                .to_vec()
+                .into_iter()
+                .map(Geofence::from_proto)
+                .collect::<Result<Vec<Geofence>, ProtoConversionError>>()?,
+            alert_rules: property_definition
+                .get_alert_rules()
+                .to_vec()
+                .into_iter()
+                .map(AlertRule::from_proto)
+                .collect::<Result<Vec<AlertRule>, ProtoConversionError>>()?,
+            confidential: property_definition.get_confidential(),
         })
     }
 }
@@ -242,6 +1023,7 @@ impl FromNative<PropertyDefinition> for protos::schema_state::PropertyDefinition
             .set_data_type(property_definition.data_type().clone().into_proto()?);
         proto_property_definition.set_required(*property_definition.required());
         proto_property_definition.set_description(property_definition.description().to_string());
+        proto_property_definition.set_deprecated(*property_definition.deprecated());
         proto_property_definition.set_number_exponent(*property_definition.number_exponent());
         proto_property_definition.set_enum_options(RepeatedField::from_vec(
             property_definition.enum_options().to_vec(),
@@ -251,6 +1033,41 @@ impl FromNative<PropertyDefinition> for protos::schema_state::PropertyDefinition
             property_definition.struct_properties().to_vec().into_iter()
             .map(PropertyDefinition::into_proto)
             .collect::<Result<Vec<protos::schema_state::PropertyDefinition>, ProtoConversionError>>()?,));
+        if property_definition.constraints() != &PropertyConstraints::default() {
+            proto_property_definition
+                .set_constraints(property_definition.constraints().clone().into_proto()?);
+        }
+        if let Some(reference_kind) = property_definition.reference_kind() {
+            proto_property_definition.set_reference_kind(reference_kind.clone().into_proto()?);
+        }
+        proto_property_definition
+            .set_reference_schema(property_definition.reference_schema().to_string());
+        if let Some(list_element) = property_definition.list_element() {
+            proto_property_definition.set_list_element(list_element.clone().into_proto()?);
+        }
+        proto_property_definition
+            .set_timestamp_precision(property_definition.timestamp_precision().clone().into_proto()?);
+        proto_property_definition.set_page_size(*property_definition.page_size());
+        proto_property_definition.set_max_pages(*property_definition.max_pages());
+        proto_property_definition
+            .set_retention(property_definition.retention().clone().into_proto()?);
+        proto_property_definition.set_geofences(RepeatedField::from_vec(
+            property_definition
+                .geofences()
+                .to_vec()
+                .into_iter()
+                .map(Geofence::into_proto)
+                .collect::<Result<Vec<protos::schema_state::Geofence>, ProtoConversionError>>()?,
+        ));
+        proto_property_definition.set_alert_rules(RepeatedField::from_vec(
+            property_definition
+                .alert_rules()
+                .to_vec()
+                .into_iter()
+                .map(AlertRule::into_proto)
+                .collect::<Result<Vec<protos::schema_state::AlertRule>, ProtoConversionError>>()?,
+        ));
+        proto_property_definition.set_confidential(*property_definition.confidential());
         Ok(proto_property_definition)
     }
 }
@@ -286,6 +1103,7 @@ impl IntoNative<PropertyDefinition> for protos::schema_state::PropertyDefinition
 pub enum PropertyDefinitionBuildError {
     MissingField(String),
     EmptyVec(String),
+    InvalidField(String),
 }
 
 impl StdError for PropertyDefinitionBuildError {
@@ -293,6 +1111,7 @@ impl StdError for PropertyDefinitionBuildError {
         match *self {
             PropertyDefinitionBuildError::MissingField(ref msg) => msg,
             PropertyDefinitionBuildError::EmptyVec(ref msg) => msg,
+            PropertyDefinitionBuildError::InvalidField(ref msg) => msg,
         }
     }
 
@@ -300,6 +1119,7 @@ impl StdError for PropertyDefinitionBuildError {
         match *self {
             PropertyDefinitionBuildError::MissingField(_) => None,
             PropertyDefinitionBuildError::EmptyVec(_) => None,
+            PropertyDefinitionBuildError::InvalidField(_) => None,
         }
     }
 }
@@ -309,6 +1129,7 @@ impl std::fmt::Display for PropertyDefinitionBuildError {
         match *self {
             PropertyDefinitionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
             PropertyDefinitionBuildError::EmptyVec(ref s) => write!(f, "EmptyVec: {}", s),
+            PropertyDefinitionBuildError::InvalidField(ref s) => write!(f, "InvalidField: {}", s),
         }
     }
 }
@@ -320,9 +1141,21 @@ pub struct PropertyDefinitionBuilder {
     pub data_type: Option<DataType>,
     pub required: Option<bool>,
     pub description: Option<String>,
+    pub deprecated: Option<bool>,
     pub number_exponent: Option<i32>,
     pub enum_options: Vec<String>,
     pub struct_properties: Vec<PropertyDefinition>,
+    pub constraints: Option<PropertyConstraints>,
+    pub reference_kind: Option<ReferenceKind>,
+    pub reference_schema: Option<String>,
+    pub list_element: Option<PropertyDefinition>,
+    pub timestamp_precision: Option<TimestampPrecision>,
+    pub page_size: Option<u32>,
+    pub max_pages: Option<u32>,
+    pub retention: Option<RetentionPolicy>,
+    pub geofences: Vec<Geofence>,
+    pub alert_rules: Vec<AlertRule>,
+    pub confidential: Option<bool>,
 }
 
 impl PropertyDefinitionBuilder {
@@ -350,6 +1183,11 @@ impl PropertyDefinitionBuilder {
         self
     }
 
+    pub fn with_deprecated(mut self, deprecated: bool) -> PropertyDefinitionBuilder {
+        self.deprecated = Some(deprecated);
+        self
+    }
+
     pub fn with_number_exponent(mut self, number_exponent: i32) -> PropertyDefinitionBuilder {
         self.number_exponent = Some(number_exponent);
         self
@@ -368,6 +1206,69 @@ impl PropertyDefinitionBuilder {
         self
     }
 
+    pub fn with_constraints(
+        mut self,
+        constraints: PropertyConstraints,
+    ) -> PropertyDefinitionBuilder {
+        self.constraints = Some(constraints);
+        self
+    }
+
+    pub fn with_reference_kind(
+        mut self,
+        reference_kind: ReferenceKind,
+    ) -> PropertyDefinitionBuilder {
+        self.reference_kind = Some(reference_kind);
+        self
+    }
+
+    pub fn with_reference_schema(mut self, reference_schema: String) -> PropertyDefinitionBuilder {
+        self.reference_schema = Some(reference_schema);
+        self
+    }
+
+    pub fn with_list_element(mut self, list_element: PropertyDefinition) -> PropertyDefinitionBuilder {
+        self.list_element = Some(list_element);
+        self
+    }
+
+    pub fn with_timestamp_precision(
+        mut self,
+        timestamp_precision: TimestampPrecision,
+    ) -> PropertyDefinitionBuilder {
+        self.timestamp_precision = Some(timestamp_precision);
+        self
+    }
+
+    /// Sets the paging of the property's history; a page_size or max_pages of 0 selects the
+    /// default
+    pub fn with_paging(
+        mut self,
+        page_size: u32,
+        max_pages: u32,
+        retention: RetentionPolicy,
+    ) -> PropertyDefinitionBuilder {
+        self.page_size = Some(page_size);
+        self.max_pages = Some(max_pages);
+        self.retention = Some(retention);
+        self
+    }
+
+    pub fn with_geofences(mut self, geofences: Vec<Geofence>) -> PropertyDefinitionBuilder {
+        self.geofences = geofences;
+        self
+    }
+
+    pub fn with_alert_rules(mut self, alert_rules: Vec<AlertRule>) -> PropertyDefinitionBuilder {
+        self.alert_rules = alert_rules;
+        self
+    }
+
+    pub fn with_confidential(mut self, confidential: bool) -> PropertyDefinitionBuilder {
+        self.confidential = Some(confidential);
+        self
+    }
+
     pub fn build(self) -> Result<PropertyDefinition, PropertyDefinitionBuildError> {
         let name = self.name.ok_or_else(|| {
             PropertyDefinitionBuildError::MissingField("'name' field is required".to_string())
@@ -377,7 +1278,9 @@ impl PropertyDefinitionBuilder {
             PropertyDefinitionBuildError::MissingField("'data_type' field is required".to_string())
         })?;
 
-        let required = self.required.unwrap_or_else(|| false);
+        let deprecated = self.deprecated.unwrap_or_default();
+        // A deprecated property can no longer be required
+        let required = self.required.unwrap_or_else(|| false) && !deprecated;
         let description = self.description.unwrap_or_default();
 
         let number_exponent = {
@@ -391,6 +1294,12 @@ impl PropertyDefinitionBuilder {
                 0 as i32
             }
         };
+        if number_exponent.abs() > MAX_NUMBER_EXPONENT {
+            return Err(PropertyDefinitionBuildError::InvalidField(format!(
+                "'number_exponent' must be between -{} and {}",
+                MAX_NUMBER_EXPONENT, MAX_NUMBER_EXPONENT
+            )));
+        }
 
         let enum_options = {
             if data_type == DataType::Enum {
@@ -420,14 +1329,472 @@ impl PropertyDefinitionBuilder {
             }
         };
 
-        Ok(PropertyDefinition {
-            name,
-            data_type,
-            required,
-            description,
-            number_exponent,
-            enum_options,
-            struct_properties,
+        let constraints = self.constraints.unwrap_or_default();
+
+        let reference_kind = {
+            if data_type == DataType::Reference {
+                Some(self.reference_kind.ok_or_else(|| {
+                    PropertyDefinitionBuildError::MissingField(
+                        "'reference_kind' field is required".to_string(),
+                    )
+                })?)
+            } else {
+                self.reference_kind
+            }
+        };
+        let reference_schema = self.reference_schema.unwrap_or_default();
+
+        let list_element = {
+            if data_type == DataType::List {
+                let list_element = self.list_element.ok_or_else(|| {
+                    PropertyDefinitionBuildError::MissingField(
+                        "'list_element' field is required".to_string(),
+                    )
+                })?;
+                // Lists of lists are not supported, a LIST of STRUCT can be used instead
+                if list_element.data_type() == &DataType::List {
+                    return Err(PropertyDefinitionBuildError::MissingField(
+                        "'list_element' cannot be a LIST".to_string(),
+                    ));
+                }
+                Some(Box::new(list_element))
+            } else {
+                None
+            }
+        };
+
+        let page_size = match self.page_size.unwrap_or_default() {
+            0 => DEFAULT_PAGE_SIZE,
+            page_size => page_size,
+        };
+        let max_pages = match self.max_pages.unwrap_or_default() {
+            0 => MAX_PAGES,
+            max_pages => max_pages,
+        };
+        if max_pages > MAX_PAGES {
+            return Err(PropertyDefinitionBuildError::InvalidField(format!(
+                "'max_pages' cannot be greater than {}",
+                MAX_PAGES
+            )));
+        }
+
+        if !self.geofences.is_empty() {
+            if data_type != DataType::LatLong {
+                return Err(PropertyDefinitionBuildError::InvalidField(
+                    "'geofences' can only be set on LAT_LONG properties".to_string(),
+                ));
+            }
+            for (i, geofence) in self.geofences.iter().enumerate() {
+                if self.geofences[..i]
+                    .iter()
+                    .any(|other| other.name() == geofence.name())
+                {
+                    return Err(PropertyDefinitionBuildError::InvalidField(format!(
+                        "geofence {} is defined more than once",
+                        geofence.name()
+                    )));
+                }
+            }
+        }
+
+        if !self.alert_rules.is_empty() {
+            if data_type != DataType::Number {
+                return Err(PropertyDefinitionBuildError::InvalidField(
+                    "'alert_rules' can only be set on NUMBER properties".to_string(),
+                ));
+            }
+            for (i, rule) in self.alert_rules.iter().enumerate() {
+                if self.alert_rules[..i]
+                    .iter()
+                    .any(|other| other.name() == rule.name())
+                {
+                    return Err(PropertyDefinitionBuildError::InvalidField(format!(
+                        "alert rule {} is defined more than once",
+                        rule.name()
+                    )));
+                }
+            }
+        }
+
+        // Encrypted values can not be checked, so nothing but immutability may be asked of them
+        let confidential = self.confidential.unwrap_or_default();
+        if confidential {
+            let mut checked_constraints = constraints.clone();
+            checked_constraints.immutable = false;
+            if checked_constraints != PropertyConstraints::default() {
+                return Err(PropertyDefinitionBuildError::InvalidField(
+                    "'constraints' other than immutable can not be set on confidential \
+                     properties"
+                        .to_string(),
+                ));
+            }
+            if !self.geofences.is_empty() || !self.alert_rules.is_empty() {
+                return Err(PropertyDefinitionBuildError::InvalidField(
+                    "'geofences' and 'alert_rules' can not be set on confidential properties"
+                        .to_string(),
+                ));
+            }
+        }
+        if struct_properties.iter().any(|field| *field.confidential())
+            || list_element.as_ref().map_or(false, |element| *element.confidential())
+        {
+            return Err(PropertyDefinitionBuildError::InvalidField(
+                "struct fields and list elements can not be confidential".to_string(),
+            ));
+        }
+
+        Ok(PropertyDefinition {
+            name,
+            data_type,
+            required,
+            description,
+            deprecated,
+            number_exponent,
+            enum_options,
+            struct_properties,
+            constraints,
+            reference_kind,
+            reference_schema,
+            list_element,
+            timestamp_precision: self.timestamp_precision.unwrap_or_default(),
+            page_size,
+            max_pages,
+            retention: self.retention.unwrap_or_default(),
+            geofences: self.geofences,
+            alert_rules: self.alert_rules,
+            confidential,
+        })
+    }
+}
+
+/// Native implementation of PropertyConstraints
+#[derive(Debug, Default, Clone)]
+pub struct PropertyConstraints {
+    number_min: Option<i64>,
+    number_max: Option<i64>,
+    pattern: String,
+    // The pattern compiled once, None when it is empty or is not a valid regex
+    pattern_regex: Option<Regex>,
+    max_length: u32,
+    max_bytes: u64,
+    lat_long_min: Option<LatLong>,
+    lat_long_max: Option<LatLong>,
+    immutable: bool,
+}
+
+impl PropertyConstraints {
+    pub fn number_min(&self) -> Option<&i64> {
+        self.number_min.as_ref()
+    }
+
+    pub fn number_max(&self) -> Option<&i64> {
+        self.number_max.as_ref()
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn max_length(&self) -> &u32 {
+        &self.max_length
+    }
+
+    pub fn max_bytes(&self) -> &u64 {
+        &self.max_bytes
+    }
+
+    pub fn lat_long_min(&self) -> Option<&LatLong> {
+        self.lat_long_min.as_ref()
+    }
+
+    pub fn lat_long_max(&self) -> Option<&LatLong> {
+        self.lat_long_max.as_ref()
+    }
+
+    pub fn immutable(&self) -> &bool {
+        &self.immutable
+    }
+
+    pub fn into_builder(self) -> PropertyConstraintsBuilder {
+        PropertyConstraintsBuilder {
+            number_min: self.number_min,
+            number_max: self.number_max,
+            pattern: Some(self.pattern),
+            max_length: Some(self.max_length),
+            max_bytes: Some(self.max_bytes),
+            lat_long_min: self.lat_long_min,
+            lat_long_max: self.lat_long_max,
+            immutable: Some(self.immutable),
+        }
+    }
+
+    /// Checks a value against the constraints, returning a description of the first
+    /// constraint that is violated. The value is expected to already have the data type
+    /// of the property definition.
+    pub fn check_value(&self, value: &PropertyValue) -> Result<(), String> {
+        match value.data_type() {
+            DataType::Number => {
+                let number = *value.number_value();
+                if let Some(min) = self.number_min() {
+                    if number < *min {
+                        return Err(format!("{} is less than the minimum {}", number, min));
+                    }
+                }
+                if let Some(max) = self.number_max() {
+                    if number > *max {
+                        return Err(format!("{} is greater than the maximum {}", number, max));
+                    }
+                }
+            }
+            DataType::String => {
+                let string = value.string_value();
+                let length = string.chars().count();
+                if self.max_length > 0 && length > self.max_length as usize {
+                    return Err(format!(
+                        "length {} is greater than the maximum length {}",
+                        length, self.max_length
+                    ));
+                }
+                if !self.pattern.is_empty() {
+                    let pattern = self
+                        .pattern_regex
+                        .as_ref()
+                        .ok_or_else(|| format!("invalid pattern {}", self.pattern))?;
+                    if !pattern.is_match(string) {
+                        return Err(format!("{:?} does not match pattern {}", string, self.pattern));
+                    }
+                }
+            }
+            DataType::Bytes => {
+                let size = value.bytes_value().len();
+                if self.max_bytes > 0 && size as u64 > self.max_bytes {
+                    return Err(format!(
+                        "size {} is greater than the maximum size {}",
+                        size, self.max_bytes
+                    ));
+                }
+            }
+            DataType::LatLong => {
+                if let (Some(min), Some(max)) = (self.lat_long_min(), self.lat_long_max()) {
+                    let lat_long = value.lat_long_value();
+                    if lat_long.latitude() < min.latitude()
+                        || lat_long.latitude() > max.latitude()
+                        || lat_long.longitude() < min.longitude()
+                        || lat_long.longitude() > max.longitude()
+                    {
+                        return Err(format!(
+                            "({}, {}) is outside of the bounding box ({}, {}) - ({}, {})",
+                            lat_long.latitude(),
+                            lat_long.longitude(),
+                            min.latitude(),
+                            min.longitude(),
+                            max.latitude(),
+                            max.longitude()
+                        ));
+                    }
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+// The compiled pattern follows from the pattern, and regexes can not be compared
+impl PartialEq for PropertyConstraints {
+    fn eq(&self, other: &Self) -> bool {
+        self.number_min == other.number_min
+            && self.number_max == other.number_max
+            && self.pattern == other.pattern
+            && self.max_length == other.max_length
+            && self.max_bytes == other.max_bytes
+            && self.lat_long_min == other.lat_long_min
+            && self.lat_long_max == other.lat_long_max
+            && self.immutable == other.immutable
+    }
+}
+
+impl FromProto<protos::schema_state::PropertyConstraints> for PropertyConstraints {
+    fn from_proto(
+        constraints: protos::schema_state::PropertyConstraints,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PropertyConstraints {
+            number_min: if constraints.get_has_number_min() {
+                Some(constraints.get_number_min())
+            } else {
+                None
+            },
+            number_max: if constraints.get_has_number_max() {
+                Some(constraints.get_number_max())
+            } else {
+                None
+            },
+            pattern: constraints.get_pattern().to_string(),
+            // A pattern that was stored before patterns were validated fails every value
+            pattern_regex: match constraints.get_pattern() {
+                "" => None,
+                pattern => Regex::new(pattern).ok(),
+            },
+            max_length: constraints.get_max_length(),
+            max_bytes: constraints.get_max_bytes(),
+            lat_long_min: if constraints.has_lat_long_min() {
+                Some(LatLong::from_proto(constraints.get_lat_long_min().clone())?)
+            } else {
+                None
+            },
+            lat_long_max: if constraints.has_lat_long_max() {
+                Some(LatLong::from_proto(constraints.get_lat_long_max().clone())?)
+            } else {
+                None
+            },
+            immutable: constraints.get_immutable(),
+        })
+    }
+}
+
+impl FromNative<PropertyConstraints> for protos::schema_state::PropertyConstraints {
+    fn from_native(constraints: PropertyConstraints) -> Result<Self, ProtoConversionError> {
+        let mut proto_constraints = protos::schema_state::PropertyConstraints::new();
+        if let Some(min) = constraints.number_min() {
+            proto_constraints.set_has_number_min(true);
+            proto_constraints.set_number_min(*min);
+        }
+        if let Some(max) = constraints.number_max() {
+            proto_constraints.set_has_number_max(true);
+            proto_constraints.set_number_max(*max);
+        }
+        proto_constraints.set_pattern(constraints.pattern().to_string());
+        proto_constraints.set_max_length(*constraints.max_length());
+        proto_constraints.set_max_bytes(*constraints.max_bytes());
+        if let Some(min) = constraints.lat_long_min() {
+            proto_constraints.set_lat_long_min(min.clone().into_proto()?);
+        }
+        if let Some(max) = constraints.lat_long_max() {
+            proto_constraints.set_lat_long_max(max.clone().into_proto()?);
+        }
+        proto_constraints.set_immutable(*constraints.immutable());
+        Ok(proto_constraints)
+    }
+}
+
+impl IntoProto<protos::schema_state::PropertyConstraints> for PropertyConstraints {}
+impl IntoNative<PropertyConstraints> for protos::schema_state::PropertyConstraints {}
+
+#[derive(Debug)]
+pub enum PropertyConstraintsBuildError {
+    InvalidConstraint(String),
+}
+
+impl StdError for PropertyConstraintsBuildError {}
+
+impl std::fmt::Display for PropertyConstraintsBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            PropertyConstraintsBuildError::InvalidConstraint(ref s) => {
+                write!(f, "InvalidConstraint: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create PropertyConstraints
+#[derive(Default, Clone, PartialEq)]
+pub struct PropertyConstraintsBuilder {
+    pub number_min: Option<i64>,
+    pub number_max: Option<i64>,
+    pub pattern: Option<String>,
+    pub max_length: Option<u32>,
+    pub max_bytes: Option<u64>,
+    pub lat_long_min: Option<LatLong>,
+    pub lat_long_max: Option<LatLong>,
+    pub immutable: Option<bool>,
+}
+
+impl PropertyConstraintsBuilder {
+    pub fn new() -> Self {
+        PropertyConstraintsBuilder::default()
+    }
+
+    pub fn with_number_min(mut self, number_min: i64) -> PropertyConstraintsBuilder {
+        self.number_min = Some(number_min);
+        self
+    }
+
+    pub fn with_number_max(mut self, number_max: i64) -> PropertyConstraintsBuilder {
+        self.number_max = Some(number_max);
+        self
+    }
+
+    pub fn with_pattern(mut self, pattern: String) -> PropertyConstraintsBuilder {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    pub fn with_max_length(mut self, max_length: u32) -> PropertyConstraintsBuilder {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> PropertyConstraintsBuilder {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn with_bounding_box(
+        mut self,
+        lat_long_min: LatLong,
+        lat_long_max: LatLong,
+    ) -> PropertyConstraintsBuilder {
+        self.lat_long_min = Some(lat_long_min);
+        self.lat_long_max = Some(lat_long_max);
+        self
+    }
+
+    pub fn with_immutable(mut self, immutable: bool) -> PropertyConstraintsBuilder {
+        self.immutable = Some(immutable);
+        self
+    }
+
+    pub fn build(self) -> Result<PropertyConstraints, PropertyConstraintsBuildError> {
+        if let (Some(min), Some(max)) = (self.number_min, self.number_max) {
+            if min > max {
+                return Err(PropertyConstraintsBuildError::InvalidConstraint(format!(
+                    "number_min {} is greater than number_max {}",
+                    min, max
+                )));
+            }
+        }
+
+        let pattern = self.pattern.unwrap_or_default();
+        let pattern_regex = if pattern.is_empty() {
+            None
+        } else {
+            Some(Regex::new(&pattern).map_err(|err| {
+                PropertyConstraintsBuildError::InvalidConstraint(format!(
+                    "invalid pattern {}: {}",
+                    pattern, err
+                ))
+            })?)
+        };
+
+        if let (Some(min), Some(max)) = (&self.lat_long_min, &self.lat_long_max) {
+            if min.latitude() > max.latitude() || min.longitude() > max.longitude() {
+                return Err(PropertyConstraintsBuildError::InvalidConstraint(
+                    "lat_long_min must be south-west of lat_long_max".to_string(),
+                ));
+            }
+        }
+
+        Ok(PropertyConstraints {
+            number_min: self.number_min,
+            number_max: self.number_max,
+            pattern,
+            pattern_regex,
+            max_length: self.max_length.unwrap_or_default(),
+            max_bytes: self.max_bytes.unwrap_or_default(),
+            lat_long_min: self.lat_long_min,
+            lat_long_max: self.lat_long_max,
+            immutable: self.immutable.unwrap_or_default(),
         })
     }
 }
@@ -438,6 +1805,9 @@ pub struct Schema {
     name: String,
     description: String,
     owner: String,
+    version: u32,
+    deprecated: bool,
+    deprecated_reason: String,
     properties: Vec<PropertyDefinition>,
 }
 
@@ -454,9 +1824,32 @@ impl Schema {
         &self.owner
     }
 
+    pub fn version(&self) -> &u32 {
+        &self.version
+    }
+
+    pub fn deprecated(&self) -> &bool {
+        &self.deprecated
+    }
+
+    pub fn deprecated_reason(&self) -> &str {
+        &self.deprecated_reason
+    }
+
     pub fn properties(&self) -> &[PropertyDefinition] {
         &self.properties
     }
+
+    pub fn into_builder(self) -> SchemaBuilder {
+        SchemaBuilder::new()
+            .with_name(self.name)
+            .with_description(self.description)
+            .with_owner(self.owner)
+            .with_version(self.version)
+            .with_deprecated(self.deprecated)
+            .with_deprecated_reason(self.deprecated_reason)
+            .with_properties(self.properties)
+    }
 }
 
 impl FromProto<protos::schema_state::Schema> for Schema {
@@ -465,6 +1858,9 @@ impl FromProto<protos::schema_state::Schema> for Schema {
             name: schema.get_name().to_string(),
             description: schema.get_description().to_string(),
             owner: schema.get_owner().to_string(),
+            version: schema.get_version(),
+            deprecated: schema.get_deprecated(),
+            deprecated_reason: schema.get_deprecated_reason().to_string(),
             properties: schema
                 .get_properties()
                 .to_vec()
@@ -481,6 +1877,9 @@ impl FromNative<Schema> for protos::schema_state::Schema {
         proto_schema.set_name(schema.name().to_string());
         proto_schema.set_description(schema.description().to_string());
         proto_schema.set_owner(schema.owner().to_string());
+        proto_schema.set_version(*schema.version());
+        proto_schema.set_deprecated(*schema.deprecated());
+        proto_schema.set_deprecated_reason(schema.deprecated_reason().to_string());
         proto_schema.set_properties(RepeatedField::from_vec(
             schema
                 .properties()
@@ -551,6 +1950,9 @@ pub struct SchemaBuilder {
     pub name: Option<String>,
     pub description: Option<String>,
     pub owner: Option<String>,
+    pub version: Option<u32>,
+    pub deprecated: Option<bool>,
+    pub deprecated_reason: Option<String>,
     pub properties: Vec<PropertyDefinition>,
 }
 
@@ -574,6 +1976,21 @@ impl SchemaBuilder {
         self
     }
 
+    pub fn with_version(mut self, version: u32) -> SchemaBuilder {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn with_deprecated(mut self, deprecated: bool) -> SchemaBuilder {
+        self.deprecated = Some(deprecated);
+        self
+    }
+
+    pub fn with_deprecated_reason(mut self, deprecated_reason: String) -> SchemaBuilder {
+        self.deprecated_reason = Some(deprecated_reason);
+        self
+    }
+
     pub fn with_properties(mut self, properties: Vec<PropertyDefinition>) -> SchemaBuilder {
         self.properties = properties;
         self
@@ -589,6 +2006,10 @@ impl SchemaBuilder {
         })?;
 
         let description = self.description.unwrap_or_else(|| "".to_string());
+        // New schemas start at version 1
+        let version = self.version.unwrap_or(1);
+        let deprecated = self.deprecated.unwrap_or_default();
+        let deprecated_reason = self.deprecated_reason.unwrap_or_default();
         let properties = {
             if !self.properties.is_empty() {
                 self.properties
@@ -603,6 +2024,9 @@ impl SchemaBuilder {
             name,
             description,
             owner,
+            version,
+            deprecated,
+            deprecated_reason,
             properties,
         })
     }
@@ -749,6 +2173,11 @@ pub struct PropertyValue {
     enum_value: u32,
     struct_values: Vec<PropertyValue>,
     lat_long_value: LatLong,
+    reference_value: String,
+    list_values: Vec<PropertyValue>,
+    timestamp_value: i64,
+    encrypted_value: Vec<u8>,
+    key_version: u32,
 }
 
 impl PropertyValue {
@@ -787,6 +2216,28 @@ impl PropertyValue {
     pub fn lat_long_value(&self) -> &LatLong {
         &self.lat_long_value
     }
+
+    pub fn reference_value(&self) -> &str {
+        &self.reference_value
+    }
+
+    pub fn list_values(&self) -> &[PropertyValue] {
+        &self.list_values
+    }
+
+    pub fn timestamp_value(&self) -> &i64 {
+        &self.timestamp_value
+    }
+
+    /// The encrypted value of a confidential property; empty for other properties
+    pub fn encrypted_value(&self) -> &[u8] {
+        &self.encrypted_value
+    }
+
+    /// The version of the record's data key that the encrypted value is encrypted with
+    pub fn key_version(&self) -> &u32 {
+        &self.key_version
+    }
 }
 
 impl FromProto<protos::schema_state::PropertyValue> for PropertyValue {
@@ -808,6 +2259,16 @@ impl FromProto<protos::schema_state::PropertyValue> for PropertyValue {
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
             lat_long_value: property_value.get_lat_long_value().clone().into_native()?,
+            reference_value: property_value.get_reference_value().to_string(),
+            list_values: property_value
+                .get_list_values()
+                .to_vec()
+                .into_iter()
+                .map(PropertyValue::from_proto)
+                .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            timestamp_value: property_value.get_timestamp_value(),
+            encrypted_value: property_value.get_encrypted_value().to_vec(),
+            key_version: property_value.get_key_version(),
         })
     }
 }
@@ -833,6 +2294,19 @@ impl FromNative<PropertyValue> for protos::schema_state::PropertyValue {
         ));
         proto_property_value
             .set_lat_long_value(property_value.lat_long_value().clone().into_proto()?);
+        proto_property_value.set_reference_value(property_value.reference_value().to_string());
+        proto_property_value.set_list_values(RepeatedField::from_vec(
+            property_value
+                .list_values()
+                .to_vec()
+                .into_iter()
+                .map(PropertyValue::into_proto)
+                .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
+                )?,
+        ));
+        proto_property_value.set_timestamp_value(*property_value.timestamp_value());
+        proto_property_value.set_encrypted_value(property_value.encrypted_value().to_vec());
+        proto_property_value.set_key_version(*property_value.key_version());
         Ok(proto_property_value)
     }
 }
@@ -903,6 +2377,11 @@ pub struct PropertyValueBuilder {
     pub enum_value: Option<u32>,
     pub struct_values: Vec<PropertyValue>,
     pub lat_long_value: Option<LatLong>,
+    pub reference_value: Option<String>,
+    pub list_values: Vec<PropertyValue>,
+    pub timestamp_value: Option<i64>,
+    pub encrypted_value: Option<Vec<u8>>,
+    pub key_version: Option<u32>,
 }
 
 impl PropertyValueBuilder {
@@ -955,6 +2434,31 @@ impl PropertyValueBuilder {
         self
     }
 
+    pub fn with_reference_value(mut self, reference_value: String) -> PropertyValueBuilder {
+        self.reference_value = Some(reference_value);
+        self
+    }
+
+    pub fn with_list_values(mut self, list_values: Vec<PropertyValue>) -> PropertyValueBuilder {
+        self.list_values = list_values;
+        self
+    }
+
+    pub fn with_timestamp_value(mut self, timestamp_value: i64) -> PropertyValueBuilder {
+        self.timestamp_value = Some(timestamp_value);
+        self
+    }
+
+    pub fn with_encrypted_value(mut self, encrypted_value: Vec<u8>) -> PropertyValueBuilder {
+        self.encrypted_value = Some(encrypted_value);
+        self
+    }
+
+    pub fn with_key_version(mut self, key_version: u32) -> PropertyValueBuilder {
+        self.key_version = Some(key_version);
+        self
+    }
+
     pub fn build(self) -> Result<PropertyValue, PropertyValueBuildError> {
         let name = self.name.ok_or_else(|| {
             PropertyValueBuildError::MissingField("'name' field is required".to_string())
@@ -964,6 +2468,29 @@ impl PropertyValueBuilder {
             PropertyValueBuildError::MissingField("'data_type' field is required".to_string())
         })?;
 
+        // An encrypted value carries its value field inside of the encryption
+        if let Some(encrypted_value) = self.encrypted_value {
+            return Ok(PropertyValue {
+                name,
+                data_type,
+                bytes_value: vec![],
+                boolean_value: false,
+                number_value: 0,
+                string_value: "".to_string(),
+                enum_value: 0,
+                struct_values: vec![],
+                lat_long_value: LatLong {
+                    latitude: 0,
+                    longitude: 0,
+                },
+                reference_value: "".to_string(),
+                list_values: vec![],
+                timestamp_value: 0,
+                encrypted_value,
+                key_version: self.key_version.unwrap_or_default(),
+            });
+        }
+
         let bytes_value = {
             if data_type == DataType::Bytes {
                 self.bytes_value.ok_or_else(|| {
@@ -1053,6 +2580,30 @@ impl PropertyValueBuilder {
             }
         };
 
+        let reference_value = {
+            if data_type == DataType::Reference {
+                self.reference_value.ok_or_else(|| {
+                    PropertyValueBuildError::MissingField(
+                        "'reference_value' field is required".to_string(),
+                    )
+                })?
+            } else {
+                "".to_string()
+            }
+        };
+
+        let timestamp_value = {
+            if data_type == DataType::Timestamp {
+                self.timestamp_value.ok_or_else(|| {
+                    PropertyValueBuildError::MissingField(
+                        "'timestamp_value' field is required".to_string(),
+                    )
+                })?
+            } else {
+                0 as i64
+            }
+        };
+
         Ok(PropertyValue {
             name,
             data_type,
@@ -1063,6 +2614,11 @@ impl PropertyValueBuilder {
             enum_value,
             struct_values,
             lat_long_value,
+            reference_value,
+            list_values: self.list_values,
+            timestamp_value,
+            encrypted_value: vec![],
+            key_version: 0,
         })
     }
 }
@@ -1160,6 +2716,419 @@ mod tests {
         assert_eq!(property_definition, original);
     }
 
+    #[test]
+    // check that a property definition with constraints can be converted to bytes and back
+    fn check_property_definition_with_constraints_bytes() {
+        let constraints = PropertyConstraintsBuilder::new()
+            .with_number_min(0)
+            .with_number_max(100)
+            .with_immutable(true)
+            .build()
+            .unwrap();
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_description("Optional".to_string())
+            .with_constraints(constraints)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition, original);
+        assert_eq!(property_definition.constraints().number_max(), Some(&100));
+        assert!(property_definition.constraints().immutable());
+    }
+
+    #[test]
+    // check that a reference property definition requires a reference kind and can be
+    // converted to bytes and back
+    fn check_property_definition_reference_bytes() {
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("contains".to_string())
+            .with_data_type(DataType::Reference)
+            .build()
+            .is_err());
+
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("contains".to_string())
+            .with_data_type(DataType::Reference)
+            .with_reference_kind(ReferenceKind::Record)
+            .with_reference_schema("case".to_string())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition, original);
+        assert_eq!(property_definition.reference_kind(), Some(&ReferenceKind::Record));
+    }
+
+    #[test]
+    // check that a list property definition and value are built correctly and
+    // survive a round trip through bytes
+    fn check_property_list_bytes() {
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("readings".to_string())
+            .with_data_type(DataType::List)
+            .build()
+            .is_err());
+
+        let element = PropertyDefinitionBuilder::new()
+            .with_name("reading".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .build()
+            .unwrap();
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("readings".to_string())
+            .with_data_type(DataType::List)
+            .with_list_element(element.clone())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition, original);
+        assert_eq!(property_definition.list_element(), Some(&element));
+
+        let elements = vec![1, 2, 3]
+            .into_iter()
+            .map(|number| {
+                PropertyValueBuilder::new()
+                    .with_name("reading".to_string())
+                    .with_data_type(DataType::Number)
+                    .with_number_value(number)
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<PropertyValue>>();
+        let original = PropertyValueBuilder::new()
+            .with_name("readings".to_string())
+            .with_data_type(DataType::List)
+            .with_list_values(elements.clone())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_value = PropertyValue::from_bytes(&bytes).unwrap();
+        assert_eq!(property_value, original);
+        assert_eq!(property_value.list_values(), &elements[..]);
+    }
+
+    #[test]
+    // check that the paging of a property definition survives a round trip through bytes and
+    // that unset paging falls back to the defaults
+    fn check_property_paging_bytes() {
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("temperature".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_paging(1024, 16, RetentionPolicy::Archive)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition, original);
+        assert_eq!(property_definition.page_size(), &1024);
+        assert_eq!(property_definition.max_pages(), &16);
+        assert_eq!(property_definition.retention(), &RetentionPolicy::Archive);
+
+        let default_paging = PropertyDefinitionBuilder::new()
+            .with_name("status".to_string())
+            .with_data_type(DataType::String)
+            .build()
+            .unwrap();
+        assert_eq!(default_paging.page_size(), &DEFAULT_PAGE_SIZE);
+        assert_eq!(default_paging.max_pages(), &MAX_PAGES);
+        assert_eq!(default_paging.retention(), &RetentionPolicy::Wrap);
+
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("status".to_string())
+            .with_data_type(DataType::String)
+            .with_paging(0, MAX_PAGES + 1, RetentionPolicy::Wrap)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that the geofences of a lat long property survive a round trip through bytes and
+    // that values outside of a geofence are reported as breaches of it
+    fn check_property_geofence_bytes() {
+        let lat_long = |latitude, longitude| {
+            LatLongBuilder::new()
+                .with_lat_long(latitude, longitude)
+                .build()
+                .unwrap()
+        };
+        // An L shaped warehouse yard
+        let yard = GeofenceBuilder::new()
+            .with_name("yard".to_string())
+            .with_vertices(vec![
+                lat_long(0, 0),
+                lat_long(0, 2_000_000),
+                lat_long(1_000_000, 2_000_000),
+                lat_long(1_000_000, 1_000_000),
+                lat_long(2_000_000, 1_000_000),
+                lat_long(2_000_000, 0),
+            ])
+            .build()
+            .unwrap();
+
+        assert!(yard.contains(&lat_long(500_000, 1_500_000)));
+        assert!(yard.contains(&lat_long(1_500_000, 500_000)));
+        assert!(yard.contains(&lat_long(2_000_000, 500_000)));
+        assert!(!yard.contains(&lat_long(1_500_000, 1_500_000)));
+        assert!(!yard.contains(&lat_long(-1, 0)));
+
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("location".to_string())
+            .with_data_type(DataType::LatLong)
+            .with_geofences(vec![yard.clone()])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition, original);
+
+        let outside = PropertyValueBuilder::new()
+            .with_name("location".to_string())
+            .with_data_type(DataType::LatLong)
+            .with_lat_long_value(lat_long(1_500_000, 1_500_000))
+            .build()
+            .unwrap();
+        assert_eq!(
+            property_definition.geofence_breaches(&outside),
+            vec!["yard".to_string()]
+        );
+
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("status".to_string())
+            .with_data_type(DataType::String)
+            .with_geofences(vec![yard.clone()])
+            .build()
+            .is_err());
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("location".to_string())
+            .with_data_type(DataType::LatLong)
+            .with_geofences(vec![yard.clone(), yard])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that the alert rules of a number property survive a round trip through bytes and
+    // that they are broken by values out of range or changing too quickly
+    fn check_property_alert_rules_bytes() {
+        let cold_chain = AlertRuleBuilder::new()
+            .with_name("cold_chain".to_string())
+            .with_min(2)
+            .with_max(8)
+            .with_max_rate_of_change(1)
+            .with_sustained_duration(600)
+            .build()
+            .unwrap();
+
+        assert!(!cold_chain.is_out_of_range(2));
+        assert!(cold_chain.is_out_of_range(9));
+        assert!(!cold_chain.exceeds_rate_of_change(3, 100, 7, 104));
+        assert!(cold_chain.exceeds_rate_of_change(3, 100, 7, 103));
+        assert!(cold_chain.exceeds_rate_of_change(3, 100, 5, 100));
+
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("temperature".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_alert_rules(vec![cold_chain.clone()])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition, original);
+
+        assert!(AlertRuleBuilder::new()
+            .with_name("empty".to_string())
+            .build()
+            .is_err());
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("status".to_string())
+            .with_data_type(DataType::String)
+            .with_alert_rules(vec![cold_chain])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that a confidential property definition and an encrypted value survive a round
+    // trip through bytes, and that values can not be checked on confidential properties
+    fn check_property_confidential_bytes() {
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("price".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(-2)
+            .with_confidential(true)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition, original);
+        assert!(*property_definition.confidential());
+
+        let value = PropertyValueBuilder::new()
+            .with_name("price".to_string())
+            .with_data_type(DataType::Number)
+            .with_encrypted_value(vec![0xde, 0xad, 0xbe, 0xef])
+            .with_key_version(2)
+            .build()
+            .unwrap();
+        let bytes = value.clone().into_bytes().unwrap();
+        assert_eq!(PropertyValue::from_bytes(&bytes).unwrap(), value);
+        assert_eq!(value.encrypted_value(), &[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(*value.key_version(), 2);
+
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("price".to_string())
+            .with_data_type(DataType::Number)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_number_min(0)
+                    .build()
+                    .unwrap(),
+            )
+            .with_confidential(true)
+            .build()
+            .is_err());
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("invoice".to_string())
+            .with_data_type(DataType::Struct)
+            .with_struct_properties(vec![original])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that a timestamp property definition and value survive a round trip
+    // through bytes with their precision
+    fn check_property_timestamp_bytes() {
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("shipped_at".to_string())
+            .with_data_type(DataType::Timestamp)
+            .with_timestamp_precision(TimestampPrecision::Milliseconds)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition, original);
+        assert_eq!(
+            property_definition.timestamp_precision(),
+            &TimestampPrecision::Milliseconds
+        );
+
+        assert!(PropertyValueBuilder::new()
+            .with_name("shipped_at".to_string())
+            .with_data_type(DataType::Timestamp)
+            .build()
+            .is_err());
+
+        let original = PropertyValueBuilder::new()
+            .with_name("shipped_at".to_string())
+            .with_data_type(DataType::Timestamp)
+            .with_timestamp_value(1_577_836_800_123)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_value = PropertyValue::from_bytes(&bytes).unwrap();
+        assert_eq!(property_value, original);
+        assert_eq!(property_value.timestamp_value(), &1_577_836_800_123);
+    }
+
+    #[test]
+    // check that decimal strings are converted to and from number values exactly
+    fn check_decimal_conversion() {
+        assert_eq!(decimal_to_number("12.50", -2), Ok(1250));
+        assert_eq!(decimal_to_number("12.5", -2), Ok(1250));
+        assert_eq!(decimal_to_number("-0.07", -2), Ok(-7));
+        assert_eq!(decimal_to_number("12000", 3), Ok(12));
+        assert_eq!(decimal_to_number("12", 0), Ok(12));
+        assert!(decimal_to_number("12.505", -2).is_err());
+        assert!(decimal_to_number("12500", 3).is_err());
+        assert!(decimal_to_number("1.2.3", -2).is_err());
+        assert!(decimal_to_number("", 0).is_err());
+        assert!(decimal_to_number("99999999999999999999", 0).is_err());
+
+        assert_eq!(number_to_decimal(1250, -2), "12.50");
+        assert_eq!(number_to_decimal(-7, -2), "-0.07");
+        assert_eq!(number_to_decimal(12, 3), "12000");
+        assert_eq!(number_to_decimal(0, 3), "0");
+        assert_eq!(number_to_decimal(i64::min_value(), 0), i64::min_value().to_string());
+
+        assert!(PropertyDefinitionBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(19)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that values are checked against string constraints
+    fn check_property_constraints_string() {
+        let constraints = PropertyConstraintsBuilder::new()
+            .with_pattern("^[A-Z]{3}-[0-9]+$".to_string())
+            .with_max_length(8)
+            .build()
+            .unwrap();
+
+        let valid = PropertyValueBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("ABC-123".to_string())
+            .build()
+            .unwrap();
+        assert!(constraints.check_value(&valid).is_ok());
+
+        let too_long = PropertyValueBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("ABC-12345".to_string())
+            .build()
+            .unwrap();
+        assert!(constraints
+            .check_value(&too_long)
+            .unwrap_err()
+            .contains("greater than the maximum length"));
+
+        let mismatch = PropertyValueBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("abc-1".to_string())
+            .build()
+            .unwrap();
+        assert!(constraints
+            .check_value(&mismatch)
+            .unwrap_err()
+            .contains("does not match pattern"));
+    }
+
+    #[test]
+    // check that constraints with an invalid range are not built
+    fn check_property_constraints_invalid_range() {
+        let result = PropertyConstraintsBuilder::new()
+            .with_number_min(10)
+            .with_number_max(1)
+            .build();
+        assert!(result.is_err());
+    }
+
     #[test]
     // check that a schema with a enum property is built correctly
     fn check_schema_builder() {
@@ -0,0 +1,530 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validation of property values against the property definitions of a schema.
+//!
+//! Errors carry the path of the offending value, with struct fields separated by `.` and
+//! list elements given by index, e.g. `shipment.dimensions.width` or `readings[2]`.
+
+use std::error::Error as StdError;
+
+use super::state::{DataType, PropertyDefinition, PropertyValue};
+
+const MAX_LATITUDE: i64 = 90_000_000;
+const MAX_LONGITUDE: i64 = 180_000_000;
+/// Seconds since the epoch of 0000-01-01T00:00:00Z and 9999-12-31T23:59:59Z, the range of
+/// dates that can be written in RFC 3339
+const MIN_TIMESTAMP_SECONDS: i64 = -62_167_219_200;
+const MAX_TIMESTAMP_SECONDS: i64 = 253_402_300_799;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValidationError {
+    /// A required property was not provided
+    MissingProperty(String),
+    /// A property was provided that is not defined
+    UnknownProperty(String),
+    /// The value does not have the data type of its definition
+    WrongType {
+        path: String,
+        expected: DataType,
+        provided: DataType,
+    },
+    /// An enum value is not an index into the enum options
+    EnumOutOfRange {
+        path: String,
+        value: u32,
+        options: usize,
+    },
+    /// A lat/long value is outside of ±90 degrees latitude or ±180 degrees longitude
+    LatLongOutOfRange {
+        path: String,
+        latitude: i64,
+        longitude: i64,
+    },
+    /// A timestamp value is before the year 0 or after the year 9999
+    TimestampOutOfRange { path: String, value: i64 },
+    /// A LIST definition without an element definition
+    MissingListElement(String),
+    /// The value violates the constraints of its definition
+    ConstraintViolation { path: String, reason: String },
+    /// The value of a confidential property is not encrypted
+    NotEncrypted(String),
+    /// An encrypted value is provided for a property that is not confidential
+    UnexpectedEncryption(String),
+}
+
+impl PropertyValidationError {
+    /// The path of the value that failed validation
+    pub fn path(&self) -> &str {
+        match self {
+            PropertyValidationError::MissingProperty(path)
+            | PropertyValidationError::UnknownProperty(path)
+            | PropertyValidationError::MissingListElement(path)
+            | PropertyValidationError::NotEncrypted(path)
+            | PropertyValidationError::UnexpectedEncryption(path) => path,
+            PropertyValidationError::WrongType { path, .. }
+            | PropertyValidationError::EnumOutOfRange { path, .. }
+            | PropertyValidationError::LatLongOutOfRange { path, .. }
+            | PropertyValidationError::TimestampOutOfRange { path, .. }
+            | PropertyValidationError::ConstraintViolation { path, .. } => path,
+        }
+    }
+}
+
+impl StdError for PropertyValidationError {}
+
+impl std::fmt::Display for PropertyValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PropertyValidationError::MissingProperty(path) => {
+                write!(f, "Required property {} not provided", path)
+            }
+            PropertyValidationError::UnknownProperty(path) => {
+                write!(f, "Provided property {} is not in schema", path)
+            }
+            PropertyValidationError::WrongType {
+                path,
+                expected,
+                provided,
+            } => write!(
+                f,
+                "Value provided for {} is the wrong type: {:?} != {:?}",
+                path, provided, expected
+            ),
+            PropertyValidationError::EnumOutOfRange {
+                path,
+                value,
+                options,
+            } => write!(
+                f,
+                "Enum value provided for {} is out of range: {} >= {}",
+                path, value, options
+            ),
+            PropertyValidationError::LatLongOutOfRange {
+                path,
+                latitude,
+                longitude,
+            } => write!(
+                f,
+                "Lat/long provided for {} is out of range: ({}, {})",
+                path, latitude, longitude
+            ),
+            PropertyValidationError::TimestampOutOfRange { path, value } => write!(
+                f,
+                "Timestamp provided for {} is out of range: {}",
+                path, value
+            ),
+            PropertyValidationError::MissingListElement(path) => {
+                write!(f, "Property {} does not define a list element", path)
+            }
+            PropertyValidationError::ConstraintViolation { path, reason } => write!(
+                f,
+                "Value provided for {} violates the schema constraints: {}",
+                path, reason
+            ),
+            PropertyValidationError::NotEncrypted(path) => {
+                write!(f, "Value provided for confidential property {} is not encrypted", path)
+            }
+            PropertyValidationError::UnexpectedEncryption(path) => {
+                write!(f, "Value provided for {} is encrypted but not confidential", path)
+            }
+        }
+    }
+}
+
+/// Validates a set of property values against the definitions they are provided for, e.g.
+/// the properties of a schema or the fields of a struct. Every required property that is
+/// not deprecated must be provided and every provided property must be defined and valid.
+pub fn validate_property_values(
+    definitions: &[PropertyDefinition],
+    values: &[PropertyValue],
+) -> Result<(), PropertyValidationError> {
+    validate_values(definitions, values, "")
+}
+
+/// Validates a single property value against its definition, including the values
+/// nested in a STRUCT or LIST. The path of errors starts with the name of the value.
+pub fn validate_property_value(
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+) -> Result<(), PropertyValidationError> {
+    validate_value(definition, value, value.name())
+}
+
+fn validate_values(
+    definitions: &[PropertyDefinition],
+    values: &[PropertyValue],
+    prefix: &str,
+) -> Result<(), PropertyValidationError> {
+    for definition in definitions {
+        if *definition.required()
+            && !*definition.deprecated()
+            && !values.iter().any(|v| v.name() == definition.name())
+        {
+            return Err(PropertyValidationError::MissingProperty(format!(
+                "{}{}",
+                prefix,
+                definition.name()
+            )));
+        }
+    }
+
+    for value in values {
+        let path = format!("{}{}", prefix, value.name());
+        match definitions.iter().find(|d| d.name() == value.name()) {
+            Some(definition) => validate_value(definition, value, &path)?,
+            None => return Err(PropertyValidationError::UnknownProperty(path)),
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_value(
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+    path: &str,
+) -> Result<(), PropertyValidationError> {
+    if value.data_type() != definition.data_type() {
+        return Err(PropertyValidationError::WrongType {
+            path: path.to_string(),
+            expected: definition.data_type().clone(),
+            provided: value.data_type().clone(),
+        });
+    }
+
+    // Only the data type of an encrypted value can be checked
+    if *definition.confidential() {
+        if value.encrypted_value().is_empty() {
+            return Err(PropertyValidationError::NotEncrypted(path.to_string()));
+        }
+        return Ok(());
+    } else if !value.encrypted_value().is_empty() {
+        return Err(PropertyValidationError::UnexpectedEncryption(path.to_string()));
+    }
+
+    match value.data_type() {
+        DataType::Enum => {
+            if *value.enum_value() as usize >= definition.enum_options().len() {
+                return Err(PropertyValidationError::EnumOutOfRange {
+                    path: path.to_string(),
+                    value: *value.enum_value(),
+                    options: definition.enum_options().len(),
+                });
+            }
+        }
+        DataType::LatLong => {
+            let latitude = *value.lat_long_value().latitude();
+            let longitude = *value.lat_long_value().longitude();
+            if latitude.abs() > MAX_LATITUDE || longitude.abs() > MAX_LONGITUDE {
+                return Err(PropertyValidationError::LatLongOutOfRange {
+                    path: path.to_string(),
+                    latitude,
+                    longitude,
+                });
+            }
+        }
+        DataType::Timestamp => {
+            let timestamp = *value.timestamp_value();
+            let seconds =
+                timestamp.div_euclid(definition.timestamp_precision().units_per_second());
+            if seconds < MIN_TIMESTAMP_SECONDS || seconds > MAX_TIMESTAMP_SECONDS {
+                return Err(PropertyValidationError::TimestampOutOfRange {
+                    path: path.to_string(),
+                    value: timestamp,
+                });
+            }
+        }
+        DataType::Struct => validate_values(
+            definition.struct_properties(),
+            value.struct_values(),
+            &format!("{}.", path),
+        )?,
+        DataType::List => {
+            let element = definition
+                .list_element()
+                .ok_or_else(|| PropertyValidationError::MissingListElement(path.to_string()))?;
+            for (index, list_value) in value.list_values().iter().enumerate() {
+                validate_value(element, list_value, &format!("{}[{}]", path, index))?;
+            }
+        }
+        _ => (),
+    }
+
+    definition
+        .constraints()
+        .check_value(value)
+        .map_err(|reason| PropertyValidationError::ConstraintViolation {
+            path: path.to_string(),
+            reason,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::protocol::schema::state::{
+        LatLongBuilder, PropertyConstraintsBuilder, PropertyDefinitionBuilder,
+        PropertyValueBuilder, TimestampPrecision,
+    };
+    use crate::protos::{self, FromProto};
+
+    fn number_definition(name: &str) -> PropertyDefinition {
+        PropertyDefinitionBuilder::new()
+            .with_name(name.to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_required(true)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_number_min(0)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn number_value(name: &str, number: i64) -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(name.to_string())
+            .with_data_type(DataType::Number)
+            .with_number_value(number)
+            .build()
+            .unwrap()
+    }
+
+    fn struct_definition(name: &str, fields: Vec<PropertyDefinition>) -> PropertyDefinition {
+        PropertyDefinitionBuilder::new()
+            .with_name(name.to_string())
+            .with_data_type(DataType::Struct)
+            .with_struct_properties(fields)
+            .build()
+            .unwrap()
+    }
+
+    fn struct_value(name: &str, fields: Vec<PropertyValue>) -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(name.to_string())
+            .with_data_type(DataType::Struct)
+            .with_struct_values(fields)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    // check that errors in nested structs are reported with the full path of the value
+    fn check_nested_struct_paths() {
+        let shipment = struct_definition(
+            "shipment",
+            vec![struct_definition(
+                "dimensions",
+                vec![number_definition("width"), number_definition("height")],
+            )],
+        );
+
+        let valid = struct_value(
+            "shipment",
+            vec![struct_value(
+                "dimensions",
+                vec![number_value("width", 10), number_value("height", 20)],
+            )],
+        );
+        assert!(validate_property_value(&shipment, &valid).is_ok());
+
+        let negative_width = struct_value(
+            "shipment",
+            vec![struct_value(
+                "dimensions",
+                vec![number_value("width", -10), number_value("height", 20)],
+            )],
+        );
+        let err = validate_property_value(&shipment, &negative_width).unwrap_err();
+        assert_eq!(err.path(), "shipment.dimensions.width");
+
+        let missing_height = struct_value(
+            "shipment",
+            vec![struct_value("dimensions", vec![number_value("width", 10)])],
+        );
+        assert_eq!(
+            validate_property_value(&shipment, &missing_height),
+            Err(PropertyValidationError::MissingProperty(
+                "shipment.dimensions.height".to_string()
+            ))
+        );
+
+        let unknown_field = struct_value(
+            "shipment",
+            vec![struct_value(
+                "dimensions",
+                vec![
+                    number_value("width", 10),
+                    number_value("height", 20),
+                    number_value("depth", 30),
+                ],
+            )],
+        );
+        assert_eq!(
+            validate_property_value(&shipment, &unknown_field),
+            Err(PropertyValidationError::UnknownProperty(
+                "shipment.dimensions.depth".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    // check that enum values must be within the enum options
+    fn check_enum_range() {
+        let color = PropertyDefinitionBuilder::new()
+            .with_name("color".to_string())
+            .with_data_type(DataType::Enum)
+            .with_enum_options(vec!["red".to_string(), "green".to_string()])
+            .build()
+            .unwrap();
+        let value = |enum_value: u32| {
+            PropertyValueBuilder::new()
+                .with_name("color".to_string())
+                .with_data_type(DataType::Enum)
+                .with_enum_value(enum_value)
+                .build()
+                .unwrap()
+        };
+
+        assert!(validate_property_value(&color, &value(1)).is_ok());
+        assert_eq!(
+            validate_property_value(&color, &value(2)),
+            Err(PropertyValidationError::EnumOutOfRange {
+                path: "color".to_string(),
+                value: 2,
+                options: 2,
+            })
+        );
+    }
+
+    #[test]
+    // check that lat/long values outside of ±90/±180 degrees are rejected, including
+    // values that bypassed the LatLongBuilder
+    fn check_lat_long_range() {
+        let location = PropertyDefinitionBuilder::new()
+            .with_name("location".to_string())
+            .with_data_type(DataType::LatLong)
+            .build()
+            .unwrap();
+        let value = PropertyValueBuilder::new()
+            .with_name("location".to_string())
+            .with_data_type(DataType::LatLong)
+            .with_lat_long_value(
+                LatLongBuilder::new()
+                    .with_lat_long(45_000_000, -120_000_000)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        assert!(validate_property_value(&location, &value).is_ok());
+
+        let mut proto = protos::schema_state::PropertyValue::new();
+        proto.set_name("location".to_string());
+        proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::LAT_LONG);
+        proto.mut_lat_long_value().set_latitude(91_000_000);
+        let value = PropertyValue::from_proto(proto).unwrap();
+        assert_eq!(
+            validate_property_value(&location, &value),
+            Err(PropertyValidationError::LatLongOutOfRange {
+                path: "location".to_string(),
+                latitude: 91_000_000,
+                longitude: 0,
+            })
+        );
+    }
+
+    #[test]
+    // check that timestamps outside of the years 0 to 9999 are rejected, in the units of the
+    // precision of the definition
+    fn check_timestamp_range() {
+        let shipped_at = PropertyDefinitionBuilder::new()
+            .with_name("shipped_at".to_string())
+            .with_data_type(DataType::Timestamp)
+            .with_timestamp_precision(TimestampPrecision::Milliseconds)
+            .build()
+            .unwrap();
+        let value = |timestamp: i64| {
+            PropertyValueBuilder::new()
+                .with_name("shipped_at".to_string())
+                .with_data_type(DataType::Timestamp)
+                .with_timestamp_value(timestamp)
+                .build()
+                .unwrap()
+        };
+
+        assert!(validate_property_value(&shipped_at, &value(1_600_000_000_000)).is_ok());
+        assert!(validate_property_value(&shipped_at, &value(-62_167_219_200_000)).is_ok());
+        assert!(validate_property_value(&shipped_at, &value(253_402_300_799_999)).is_ok());
+        assert_eq!(
+            validate_property_value(&shipped_at, &value(253_402_300_800_000)),
+            Err(PropertyValidationError::TimestampOutOfRange {
+                path: "shipped_at".to_string(),
+                value: 253_402_300_800_000,
+            })
+        );
+        assert!(validate_property_value(&shipped_at, &value(i64::MIN)).is_err());
+    }
+
+    #[test]
+    // check that list elements are validated against the element definition, with the
+    // index of the element in the path
+    fn check_list_elements() {
+        let readings = PropertyDefinitionBuilder::new()
+            .with_name("readings".to_string())
+            .with_data_type(DataType::List)
+            .with_list_element(number_definition("reading"))
+            .build()
+            .unwrap();
+        let value = |numbers: Vec<i64>| {
+            PropertyValueBuilder::new()
+                .with_name("readings".to_string())
+                .with_data_type(DataType::List)
+                .with_list_values(
+                    numbers
+                        .into_iter()
+                        .map(|number| number_value("reading", number))
+                        .collect(),
+                )
+                .build()
+                .unwrap()
+        };
+
+        assert!(validate_property_value(&readings, &value(vec![1, 2, 3])).is_ok());
+        let err = validate_property_value(&readings, &value(vec![1, -2, 3])).unwrap_err();
+        assert_eq!(err.path(), "readings[1]");
+    }
+
+    #[test]
+    // check that confidential properties take encrypted values only, and that other
+    // properties take plain values only
+    fn check_confidential_values() {
+        let price = PropertyDefinitionBuilder::new()
+            .with_name("price".to_string())
+            .with_data_type(DataType::Number)
+            .with_confidential(true)
+            .build()
+            .unwrap();
+        let encrypted = PropertyValueBuilder::new()
+            .with_name("price".to_string())
+            .with_data_type(DataType::Number)
+            .with_encrypted_value(vec![1, 2, 3])
+            .build()
+            .unwrap();
+
+        assert!(validate_property_value(&price, &encrypted).is_ok());
+        assert_eq!(
+            validate_property_value(&price, &number_value("price", 12)),
+            Err(PropertyValidationError::NotEncrypted("price".to_string()))
+        );
+        assert_eq!(
+            validate_property_value(&number_definition("price"), &encrypted),
+            Err(PropertyValidationError::UnexpectedEncryption("price".to_string()))
+        );
+    }
+}
@@ -18,7 +18,10 @@ use protobuf::RepeatedField;
 use std::default::Default;
 
 use super::errors::BuilderError;
-use crate::protocol::{schema::state::PropertyValue, track_and_trace::state::Role};
+use crate::protocol::{
+    schema::state::PropertyValue,
+    track_and_trace::state::{Condition, DataKey, Role, Severity},
+};
 use crate::protos;
 use crate::protos::{
     track_and_trace_payload, track_and_trace_payload::TrackAndTracePayload_Action,
@@ -32,6 +35,7 @@ pub struct CreateRecordAction {
     record_id: String,
     schema: String,
     properties: Vec<PropertyValue>,
+    wrapped_data_key: Vec<u8>,
 }
 
 impl CreateRecordAction {
@@ -44,6 +48,9 @@ impl CreateRecordAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+    pub fn wrapped_data_key(&self) -> &[u8] {
+        &self.wrapped_data_key
+    }
 }
 
 #[derive(Default, Debug)]
@@ -51,6 +58,7 @@ pub struct CreateRecordActionBuilder {
     record_id: Option<String>,
     schema: Option<String>,
     properties: Option<Vec<PropertyValue>>,
+    wrapped_data_key: Option<Vec<u8>>,
 }
 
 impl CreateRecordActionBuilder {
@@ -69,6 +77,10 @@ impl CreateRecordActionBuilder {
         self.properties = Some(value);
         self
     }
+    pub fn with_wrapped_data_key(mut self, value: Vec<u8>) -> Self {
+        self.wrapped_data_key = Some(value);
+        self
+    }
     pub fn build(self) -> Result<CreateRecordAction, BuilderError> {
         let record_id = self
             .record_id
@@ -79,10 +91,12 @@ impl CreateRecordActionBuilder {
         let properties = self
             .properties
             .ok_or_else(|| BuilderError::MissingField("properties".into()))?;
+        let wrapped_data_key = self.wrapped_data_key.unwrap_or_default();
         Ok(CreateRecordAction {
             record_id,
             schema,
             properties,
+            wrapped_data_key,
         })
     }
 }
@@ -100,6 +114,7 @@ impl FromProto<track_and_trace_payload::CreateRecordAction> for CreateRecordActi
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            wrapped_data_key: proto.get_wrapped_data_key().to_vec(),
         })
     }
 }
@@ -118,6 +133,7 @@ impl FromNative<CreateRecordAction> for track_and_trace_payload::CreateRecordAct
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_wrapped_data_key(create_record_action.wrapped_data_key().to_vec());
 
         Ok(proto)
     }
@@ -227,6 +243,7 @@ impl IntoNative<FinalizeRecordAction> for track_and_trace_payload::FinalizeRecor
 pub struct UpdatePropertiesAction {
     record_id: String,
     properties: Vec<PropertyValue>,
+    data_keys: Vec<DataKey>,
 }
 
 impl UpdatePropertiesAction {
@@ -236,12 +253,17 @@ impl UpdatePropertiesAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+    /// The rotated data key of the record, wrapped for every agent holding it
+    pub fn data_keys(&self) -> &[DataKey] {
+        &self.data_keys
+    }
 }
 
 #[derive(Default, Debug)]
 pub struct UpdatePropertiesActionBuilder {
     record_id: Option<String>,
     properties: Option<Vec<PropertyValue>>,
+    data_keys: Option<Vec<DataKey>>,
 }
 
 impl UpdatePropertiesActionBuilder {
@@ -256,6 +278,10 @@ impl UpdatePropertiesActionBuilder {
         self.properties = Some(value);
         self
     }
+    pub fn with_data_keys(mut self, value: Vec<DataKey>) -> Self {
+        self.data_keys = Some(value);
+        self
+    }
     pub fn build(self) -> Result<UpdatePropertiesAction, BuilderError> {
         let record_id = self
             .record_id
@@ -263,9 +289,11 @@ impl UpdatePropertiesActionBuilder {
         let properties = self
             .properties
             .ok_or_else(|| BuilderError::MissingField("properties".into()))?;
+        let data_keys = self.data_keys.unwrap_or_default();
         Ok(UpdatePropertiesAction {
             record_id,
             properties,
+            data_keys,
         })
     }
 }
@@ -282,6 +310,12 @@ impl FromProto<track_and_trace_payload::UpdatePropertiesAction> for UpdateProper
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            data_keys: proto
+                .get_data_keys()
+                .to_vec()
+                .into_iter()
+                .map(DataKey::from_proto)
+                .collect::<Result<Vec<DataKey>, ProtoConversionError>>()?,
         })
     }
 }
@@ -299,6 +333,14 @@ impl FromNative<UpdatePropertiesAction> for track_and_trace_payload::UpdatePrope
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_data_keys(RepeatedField::from_vec(
+            native
+                .data_keys()
+                .to_vec()
+                .into_iter()
+                .map(DataKey::into_proto)
+                .collect::<Result<Vec<_>, ProtoConversionError>>()?,
+        ));
         Ok(proto)
     }
 }
@@ -335,6 +377,9 @@ pub struct CreateProposalAction {
     role: Role,
     properties: Vec<String>,
     terms: String,
+    expires_at: u64,
+    conditions: Vec<Condition>,
+    wrapped_data_key: Vec<u8>,
 }
 
 impl CreateProposalAction {
@@ -353,6 +398,15 @@ impl CreateProposalAction {
     pub fn terms(&self) -> &str {
         &self.terms
     }
+    pub fn expires_at(&self) -> &u64 {
+        &self.expires_at
+    }
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+    pub fn wrapped_data_key(&self) -> &[u8] {
+        &self.wrapped_data_key
+    }
 }
 
 #[derive(Default, Debug)]
@@ -362,6 +416,9 @@ pub struct CreateProposalActionBuilder {
     role: Option<Role>,
     properties: Option<Vec<String>>,
     terms: Option<String>,
+    expires_at: Option<u64>,
+    conditions: Option<Vec<Condition>>,
+    wrapped_data_key: Option<Vec<u8>>,
 }
 
 impl CreateProposalActionBuilder {
@@ -388,6 +445,18 @@ impl CreateProposalActionBuilder {
         self.terms = Some(value);
         self
     }
+    pub fn with_expires_at(mut self, value: u64) -> Self {
+        self.expires_at = Some(value);
+        self
+    }
+    pub fn with_conditions(mut self, value: Vec<Condition>) -> Self {
+        self.conditions = Some(value);
+        self
+    }
+    pub fn with_wrapped_data_key(mut self, value: Vec<u8>) -> Self {
+        self.wrapped_data_key = Some(value);
+        self
+    }
     pub fn build(self) -> Result<CreateProposalAction, BuilderError> {
         let record_id = self
             .record_id
@@ -404,12 +473,18 @@ impl CreateProposalActionBuilder {
         let terms = self
             .terms
             .ok_or_else(|| BuilderError::MissingField("terms".into()))?;
+        let expires_at = self.expires_at.unwrap_or_default();
+        let conditions = self.conditions.unwrap_or_default();
+        let wrapped_data_key = self.wrapped_data_key.unwrap_or_default();
         Ok(CreateProposalAction {
             record_id,
             receiving_agent,
             role,
             properties,
             terms,
+            expires_at,
+            conditions,
+            wrapped_data_key,
         })
     }
 }
@@ -429,6 +504,14 @@ impl FromProto<track_and_trace_payload::CreateProposalAction> for CreateProposal
                 .map(String::from)
                 .collect(),
             terms: proto.get_terms().to_string(),
+            expires_at: proto.get_expires_at(),
+            conditions: proto
+                .get_conditions()
+                .to_vec()
+                .into_iter()
+                .map(Condition::from_proto)
+                .collect::<Result<Vec<Condition>, ProtoConversionError>>()?,
+            wrapped_data_key: proto.get_wrapped_data_key().to_vec(),
         })
     }
 }
@@ -441,6 +524,19 @@ impl FromNative<CreateProposalAction> for track_and_trace_payload::CreateProposa
         proto.set_role(native.role().clone().into_proto()?);
         proto.set_properties(RepeatedField::from_vec(native.properties().to_vec()));
         proto.set_terms(native.terms().to_string());
+        proto.set_expires_at(*native.expires_at());
+        proto.set_conditions(RepeatedField::from_vec(
+            native
+                .conditions()
+                .to_vec()
+                .into_iter()
+                .map(Condition::into_proto)
+                .collect::<Result<
+                    Vec<protos::track_and_trace_state::Proposal_Condition>,
+                    ProtoConversionError,
+                >>()?,
+        ));
+        proto.set_wrapped_data_key(native.wrapped_data_key().to_vec());
         Ok(proto)
     }
 }
@@ -745,233 +841,1579 @@ impl IntoProto<track_and_trace_payload::RevokeReporterAction> for RevokeReporter
 impl IntoNative<RevokeReporterAction> for track_and_trace_payload::RevokeReporterAction {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum Action {
-    CreateRecord(CreateRecordAction),
-    FinalizeRecord(FinalizeRecordAction),
-    UpdateProperties(UpdatePropertiesAction),
-    CreateProposal(CreateProposalAction),
-    AnswerProposal(AnswerProposalAction),
-    RevokeReporter(RevokeReporterAction),
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct TrackAndTracePayload {
-    action: Action,
-    timestamp: u64,
+pub struct LineageParent {
+    record_id: String,
+    consume: bool,
 }
 
-impl TrackAndTracePayload {
-    pub fn action(&self) -> &Action {
-        &self.action
+impl LineageParent {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
     }
-    pub fn timestamp(&self) -> &u64 {
-        &self.timestamp
+    pub fn consume(&self) -> &bool {
+        &self.consume
     }
 }
 
 #[derive(Default, Debug)]
-pub struct TrackAndTracePayloadBuilder {
-    action: Option<Action>,
-    timestamp: Option<u64>,
+pub struct LineageParentBuilder {
+    record_id: Option<String>,
+    consume: Option<bool>,
 }
 
-impl TrackAndTracePayloadBuilder {
+impl LineageParentBuilder {
     pub fn new() -> Self {
-        TrackAndTracePayloadBuilder::default()
+        LineageParentBuilder::default()
     }
-    pub fn with_action(mut self, value: Action) -> Self {
-        self.action = Some(value);
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
         self
     }
-    pub fn with_timestamp(mut self, value: u64) -> Self {
-        self.timestamp = Some(value);
+    pub fn with_consume(mut self, value: bool) -> Self {
+        self.consume = Some(value);
         self
     }
-    pub fn build(self) -> Result<TrackAndTracePayload, BuilderError> {
-        let action = self
-            .action
-            .ok_or_else(|| BuilderError::MissingField("action".into()))?;
-        let timestamp = self
-            .timestamp
-            .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
-        Ok(TrackAndTracePayload { action, timestamp })
+    pub fn build(self) -> Result<LineageParent, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let consume = self.consume.unwrap_or_default();
+        Ok(LineageParent { record_id, consume })
     }
 }
 
-impl FromProto<track_and_trace_payload::TrackAndTracePayload> for TrackAndTracePayload {
+impl FromProto<track_and_trace_payload::LineageParent> for LineageParent {
     fn from_proto(
-        proto: track_and_trace_payload::TrackAndTracePayload,
+        proto: track_and_trace_payload::LineageParent,
     ) -> Result<Self, ProtoConversionError> {
-        let action = match proto.get_action() {
-            TrackAndTracePayload_Action::CREATE_RECORD => Action::CreateRecord(
-                CreateRecordAction::from_proto(proto.get_create_record().clone())?,
-            ),
-            TrackAndTracePayload_Action::FINALIZE_RECORD => Action::FinalizeRecord(
-                FinalizeRecordAction::from_proto(proto.get_finalize_record().clone())?,
-            ),
-            TrackAndTracePayload_Action::UPDATE_PROPERTIES => Action::UpdateProperties(
-                UpdatePropertiesAction::from_proto(proto.get_update_properties().clone())?,
-            ),
-            TrackAndTracePayload_Action::CREATE_PROPOSAL => Action::CreateProposal(
-                CreateProposalAction::from_proto(proto.get_create_proposal().clone())?,
-            ),
-            TrackAndTracePayload_Action::ANSWER_PROPOSAL => Action::AnswerProposal(
-                AnswerProposalAction::from_proto(proto.get_answer_proposal().clone())?,
-            ),
-            TrackAndTracePayload_Action::REVOKE_REPORTER => Action::RevokeReporter(
-                RevokeReporterAction::from_proto(proto.get_revoke_reporter().clone())?,
-            ),
-            TrackAndTracePayload_Action::UNSET_ACTION => {
-                return Err(ProtoConversionError::InvalidTypeError(
-                    "Cannot convert TrackAndTracePayload_Action with type unset.".to_string(),
-                ));
-            }
-        };
-
-        Ok(TrackAndTracePayload {
-            action,
-            timestamp: proto.get_timestamp(),
+        Ok(LineageParent {
+            record_id: proto.get_record_id().to_string(),
+            consume: proto.get_consume(),
         })
     }
 }
 
-impl FromNative<TrackAndTracePayload> for track_and_trace_payload::TrackAndTracePayload {
-    fn from_native(native: TrackAndTracePayload) -> Result<Self, ProtoConversionError> {
-        let mut proto = track_and_trace_payload::TrackAndTracePayload::new();
-
-        proto.set_timestamp(*native.timestamp());
-
-        match native.action() {
-            Action::CreateRecord(payload) => {
-                proto.set_action(TrackAndTracePayload_Action::CREATE_RECORD);
-                proto.set_create_record(payload.clone().into_proto()?);
-            }
-            Action::FinalizeRecord(payload) => {
-                proto.set_action(TrackAndTracePayload_Action::FINALIZE_RECORD);
-                proto.set_finalize_record(payload.clone().into_proto()?);
-            }
-            Action::UpdateProperties(payload) => {
-                proto.set_action(TrackAndTracePayload_Action::UPDATE_PROPERTIES);
-                proto.set_update_properties(payload.clone().into_proto()?);
-            }
-            Action::CreateProposal(payload) => {
-                proto.set_action(TrackAndTracePayload_Action::CREATE_PROPOSAL);
-                proto.set_create_proposal(payload.clone().into_proto()?);
-            }
-            Action::AnswerProposal(payload) => {
-                proto.set_action(TrackAndTracePayload_Action::ANSWER_PROPOSAL);
-                proto.set_answer_proposal(payload.clone().into_proto()?);
-            }
-            Action::RevokeReporter(payload) => {
-                proto.set_action(TrackAndTracePayload_Action::REVOKE_REPORTER);
-                proto.set_revoke_reporter(payload.clone().into_proto()?);
-            }
-        }
-
+impl FromNative<LineageParent> for track_and_trace_payload::LineageParent {
+    fn from_native(native: LineageParent) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::LineageParent::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_consume(*native.consume());
         Ok(proto)
     }
 }
 
-impl FromBytes<TrackAndTracePayload> for TrackAndTracePayload {
-    fn from_bytes(bytes: &[u8]) -> Result<TrackAndTracePayload, ProtoConversionError> {
-        let proto: track_and_trace_payload::TrackAndTracePayload =
-            protobuf::parse_from_bytes(bytes).map_err(|_| {
+impl FromBytes<LineageParent> for LineageParent {
+    fn from_bytes(bytes: &[u8]) -> Result<LineageParent, ProtoConversionError> {
+        let proto: track_and_trace_payload::LineageParent = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
                 ProtoConversionError::SerializationError(
-                    "Unable to get TrackAndTracePaylaod from bytes".into(),
+                    "Unable to get LineageParent from bytes".into(),
                 )
             })?;
         proto.into_native()
     }
 }
-impl IntoBytes for TrackAndTracePayload {
+impl IntoBytes for LineageParent {
     fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
         let proto = self.into_proto()?;
         let bytes = proto.write_to_bytes().map_err(|_| {
             ProtoConversionError::SerializationError(
-                "Unable to get TrackAndTracePaylaod from bytes".into(),
+                "Unable to get LineageParent from bytes".into(),
             )
         })?;
         Ok(bytes)
     }
 }
-impl IntoProto<track_and_trace_payload::TrackAndTracePayload> for TrackAndTracePayload {}
-impl IntoNative<TrackAndTracePayload> for track_and_trace_payload::TrackAndTracePayload {}
+impl IntoProto<track_and_trace_payload::LineageParent> for LineageParent {}
+impl IntoNative<LineageParent> for track_and_trace_payload::LineageParent {}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::protocol::schema::state::{DataType, PropertyValueBuilder};
-    use std::fmt::Debug;
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitRecordAction {
+    parent: LineageParent,
+    children: Vec<CreateRecordAction>,
+}
 
-    fn test_from_bytes<T: FromBytes<T> + Clone + PartialEq + IntoBytes + Debug, F>(
-        under_test: T,
-        from_bytes: F,
-    ) where
-        F: Fn(&[u8]) -> Result<T, ProtoConversionError>,
-    {
-        let bytes = under_test.clone().into_bytes().unwrap();
-        let created_from_bytes = from_bytes(&bytes).unwrap();
-        assert_eq!(under_test, created_from_bytes);
+impl SplitRecordAction {
+    pub fn parent(&self) -> &LineageParent {
+        &self.parent
     }
-
-    #[test]
-    fn test_create_record_builder() {
-        let property_value = PropertyValueBuilder::new()
-            .with_name("egg".into())
-            .with_data_type(DataType::Number)
-            .with_number_value(42)
-            .build()
-            .unwrap();
-
-        let action = CreateRecordActionBuilder::new()
-            .with_record_id("32".into())
-            .with_schema("schema".into())
-            .with_properties(vec![property_value.clone()])
-            .build()
-            .unwrap();
-
-        assert_eq!(action.record_id(), "32");
-        assert_eq!(action.schema(), "schema");
-        assert!(action.properties().iter().any(|x| *x == property_value));
+    pub fn children(&self) -> &[CreateRecordAction] {
+        &self.children
     }
+}
 
-    #[test]
-    fn test_create_record_bytes() {
-        let property_value = PropertyValueBuilder::new()
-            .with_name("egg".into())
-            .with_data_type(DataType::Number)
-            .with_number_value(42)
-            .build()
-            .unwrap();
+#[derive(Default, Debug)]
+pub struct SplitRecordActionBuilder {
+    parent: Option<LineageParent>,
+    children: Option<Vec<CreateRecordAction>>,
+}
 
-        let action = CreateRecordActionBuilder::new()
-            .with_record_id("32".into())
-            .with_schema("schema".into())
-            .with_properties(vec![property_value.clone()])
-            .build()
-            .unwrap();
+impl SplitRecordActionBuilder {
+    pub fn new() -> Self {
+        SplitRecordActionBuilder::default()
+    }
+    pub fn with_parent(mut self, value: LineageParent) -> Self {
+        self.parent = Some(value);
+        self
+    }
+    pub fn with_children(mut self, value: Vec<CreateRecordAction>) -> Self {
+        self.children = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<SplitRecordAction, BuilderError> {
+        let parent = self
+            .parent
+            .ok_or_else(|| BuilderError::MissingField("parent".into()))?;
+        let children = self
+            .children
+            .ok_or_else(|| BuilderError::MissingField("children".into()))?;
+        Ok(SplitRecordAction { parent, children })
+    }
+}
 
-        test_from_bytes(action, CreateRecordAction::from_bytes);
+impl FromProto<track_and_trace_payload::SplitRecordAction> for SplitRecordAction {
+    fn from_proto(
+        proto: track_and_trace_payload::SplitRecordAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(SplitRecordAction {
+            parent: LineageParent::from_proto(proto.get_parent().clone())?,
+            children: proto
+                .get_children()
+                .to_vec()
+                .into_iter()
+                .map(CreateRecordAction::from_proto)
+                .collect::<Result<Vec<CreateRecordAction>, ProtoConversionError>>()?,
+        })
     }
+}
 
-    #[test]
-    fn test_finalize_record_action_builder() {
-        let action = FinalizeRecordActionBuilder::new()
-            .with_record_id("32".into())
-            .build()
-            .unwrap();
+impl FromNative<SplitRecordAction> for track_and_trace_payload::SplitRecordAction {
+    fn from_native(native: SplitRecordAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::SplitRecordAction::new();
+        proto.set_parent(native.parent().clone().into_proto()?);
+        proto.set_children(RepeatedField::from_vec(
+            native
+                .children()
+                .to_vec()
+                .into_iter()
+                .map(CreateRecordAction::into_proto)
+                .collect::<Result<
+                    Vec<track_and_trace_payload::CreateRecordAction>,
+                    ProtoConversionError,
+                >>()?,
+        ));
+        Ok(proto)
+    }
+}
 
-        assert_eq!(action.record_id(), "32");
+impl FromBytes<SplitRecordAction> for SplitRecordAction {
+    fn from_bytes(bytes: &[u8]) -> Result<SplitRecordAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::SplitRecordAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get SplitRecordAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for SplitRecordAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get SplitRecordAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
     }
+}
+impl IntoProto<track_and_trace_payload::SplitRecordAction> for SplitRecordAction {}
+impl IntoNative<SplitRecordAction> for track_and_trace_payload::SplitRecordAction {}
 
-    #[test]
-    fn test_finalize_record_action_bytes() {
-        let action = FinalizeRecordActionBuilder::new()
-            .with_record_id("32".into())
-            .build()
-            .unwrap();
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeRecordsAction {
+    parents: Vec<LineageParent>,
+    child: CreateRecordAction,
+}
 
-        test_from_bytes(action, FinalizeRecordAction::from_bytes);
+impl MergeRecordsAction {
+    pub fn parents(&self) -> &[LineageParent] {
+        &self.parents
+    }
+    pub fn child(&self) -> &CreateRecordAction {
+        &self.child
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct MergeRecordsActionBuilder {
+    parents: Option<Vec<LineageParent>>,
+    child: Option<CreateRecordAction>,
+}
+
+impl MergeRecordsActionBuilder {
+    pub fn new() -> Self {
+        MergeRecordsActionBuilder::default()
+    }
+    pub fn with_parents(mut self, value: Vec<LineageParent>) -> Self {
+        self.parents = Some(value);
+        self
+    }
+    pub fn with_child(mut self, value: CreateRecordAction) -> Self {
+        self.child = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<MergeRecordsAction, BuilderError> {
+        let parents = self
+            .parents
+            .ok_or_else(|| BuilderError::MissingField("parents".into()))?;
+        let child = self
+            .child
+            .ok_or_else(|| BuilderError::MissingField("child".into()))?;
+        Ok(MergeRecordsAction { parents, child })
+    }
+}
+
+impl FromProto<track_and_trace_payload::MergeRecordsAction> for MergeRecordsAction {
+    fn from_proto(
+        proto: track_and_trace_payload::MergeRecordsAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(MergeRecordsAction {
+            parents: proto
+                .get_parents()
+                .to_vec()
+                .into_iter()
+                .map(LineageParent::from_proto)
+                .collect::<Result<Vec<LineageParent>, ProtoConversionError>>()?,
+            child: CreateRecordAction::from_proto(proto.get_child().clone())?,
+        })
+    }
+}
+
+impl FromNative<MergeRecordsAction> for track_and_trace_payload::MergeRecordsAction {
+    fn from_native(native: MergeRecordsAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::MergeRecordsAction::new();
+        proto.set_parents(RepeatedField::from_vec(
+            native
+                .parents()
+                .to_vec()
+                .into_iter()
+                .map(LineageParent::into_proto)
+                .collect::<Result<
+                    Vec<track_and_trace_payload::LineageParent>,
+                    ProtoConversionError,
+                >>()?,
+        ));
+        proto.set_child(native.child().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<MergeRecordsAction> for MergeRecordsAction {
+    fn from_bytes(bytes: &[u8]) -> Result<MergeRecordsAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::MergeRecordsAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get MergeRecordsAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for MergeRecordsAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get MergeRecordsAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::MergeRecordsAction> for MergeRecordsAction {}
+impl IntoNative<MergeRecordsAction> for track_and_trace_payload::MergeRecordsAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformRecordAction {
+    parent: LineageParent,
+    child: CreateRecordAction,
+}
+
+impl TransformRecordAction {
+    pub fn parent(&self) -> &LineageParent {
+        &self.parent
+    }
+    pub fn child(&self) -> &CreateRecordAction {
+        &self.child
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct TransformRecordActionBuilder {
+    parent: Option<LineageParent>,
+    child: Option<CreateRecordAction>,
+}
+
+impl TransformRecordActionBuilder {
+    pub fn new() -> Self {
+        TransformRecordActionBuilder::default()
+    }
+    pub fn with_parent(mut self, value: LineageParent) -> Self {
+        self.parent = Some(value);
+        self
+    }
+    pub fn with_child(mut self, value: CreateRecordAction) -> Self {
+        self.child = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<TransformRecordAction, BuilderError> {
+        let parent = self
+            .parent
+            .ok_or_else(|| BuilderError::MissingField("parent".into()))?;
+        let child = self
+            .child
+            .ok_or_else(|| BuilderError::MissingField("child".into()))?;
+        Ok(TransformRecordAction { parent, child })
+    }
+}
+
+impl FromProto<track_and_trace_payload::TransformRecordAction> for TransformRecordAction {
+    fn from_proto(
+        proto: track_and_trace_payload::TransformRecordAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(TransformRecordAction {
+            parent: LineageParent::from_proto(proto.get_parent().clone())?,
+            child: CreateRecordAction::from_proto(proto.get_child().clone())?,
+        })
+    }
+}
+
+impl FromNative<TransformRecordAction> for track_and_trace_payload::TransformRecordAction {
+    fn from_native(native: TransformRecordAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::TransformRecordAction::new();
+        proto.set_parent(native.parent().clone().into_proto()?);
+        proto.set_child(native.child().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<TransformRecordAction> for TransformRecordAction {
+    fn from_bytes(bytes: &[u8]) -> Result<TransformRecordAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::TransformRecordAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get TransformRecordAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for TransformRecordAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get TransformRecordAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::TransformRecordAction> for TransformRecordAction {}
+impl IntoNative<TransformRecordAction> for track_and_trace_payload::TransformRecordAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateRecallAction {
+    recall_id: String,
+    product_id: String,
+    record_ids: Vec<String>,
+    reason: String,
+    severity: Severity,
+}
+
+impl CreateRecallAction {
+    pub fn recall_id(&self) -> &str {
+        &self.recall_id
+    }
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+    pub fn record_ids(&self) -> &[String] {
+        &self.record_ids
+    }
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+    pub fn severity(&self) -> &Severity {
+        &self.severity
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct CreateRecallActionBuilder {
+    recall_id: Option<String>,
+    product_id: Option<String>,
+    record_ids: Option<Vec<String>>,
+    reason: Option<String>,
+    severity: Option<Severity>,
+}
+
+impl CreateRecallActionBuilder {
+    pub fn new() -> Self {
+        CreateRecallActionBuilder::default()
+    }
+    pub fn with_recall_id(mut self, value: String) -> Self {
+        self.recall_id = Some(value);
+        self
+    }
+    pub fn with_product_id(mut self, value: String) -> Self {
+        self.product_id = Some(value);
+        self
+    }
+    pub fn with_record_ids(mut self, value: Vec<String>) -> Self {
+        self.record_ids = Some(value);
+        self
+    }
+    pub fn with_reason(mut self, value: String) -> Self {
+        self.reason = Some(value);
+        self
+    }
+    pub fn with_severity(mut self, value: Severity) -> Self {
+        self.severity = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<CreateRecallAction, BuilderError> {
+        let recall_id = self
+            .recall_id
+            .ok_or_else(|| BuilderError::MissingField("recall_id".into()))?;
+        let product_id = self.product_id.unwrap_or_default();
+        let record_ids = self.record_ids.unwrap_or_default();
+        let reason = self
+            .reason
+            .ok_or_else(|| BuilderError::MissingField("reason".into()))?;
+        let severity = self
+            .severity
+            .ok_or_else(|| BuilderError::MissingField("severity".into()))?;
+        Ok(CreateRecallAction {
+            recall_id,
+            product_id,
+            record_ids,
+            reason,
+            severity,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::CreateRecallAction> for CreateRecallAction {
+    fn from_proto(
+        proto: track_and_trace_payload::CreateRecallAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(CreateRecallAction {
+            recall_id: proto.get_recall_id().to_string(),
+            product_id: proto.get_product_id().to_string(),
+            record_ids: proto.get_record_ids().to_vec(),
+            reason: proto.get_reason().to_string(),
+            severity: Severity::from_proto(proto.get_severity())?,
+        })
+    }
+}
+
+impl FromNative<CreateRecallAction> for track_and_trace_payload::CreateRecallAction {
+    fn from_native(native: CreateRecallAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::CreateRecallAction::new();
+        proto.set_recall_id(native.recall_id().to_string());
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_record_ids(RepeatedField::from_vec(native.record_ids().to_vec()));
+        proto.set_reason(native.reason().to_string());
+        proto.set_severity(native.severity().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<CreateRecallAction> for CreateRecallAction {
+    fn from_bytes(bytes: &[u8]) -> Result<CreateRecallAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::CreateRecallAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get CreateRecallAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for CreateRecallAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get CreateRecallAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::CreateRecallAction> for CreateRecallAction {}
+impl IntoNative<CreateRecallAction> for track_and_trace_payload::CreateRecallAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcknowledgeRecallAction {
+    recall_id: String,
+    record_id: String,
+}
+
+impl AcknowledgeRecallAction {
+    pub fn recall_id(&self) -> &str {
+        &self.recall_id
+    }
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AcknowledgeRecallActionBuilder {
+    recall_id: Option<String>,
+    record_id: Option<String>,
+}
+
+impl AcknowledgeRecallActionBuilder {
+    pub fn new() -> Self {
+        AcknowledgeRecallActionBuilder::default()
+    }
+    pub fn with_recall_id(mut self, value: String) -> Self {
+        self.recall_id = Some(value);
+        self
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<AcknowledgeRecallAction, BuilderError> {
+        let recall_id = self
+            .recall_id
+            .ok_or_else(|| BuilderError::MissingField("recall_id".into()))?;
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        Ok(AcknowledgeRecallAction {
+            recall_id,
+            record_id,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::AcknowledgeRecallAction> for AcknowledgeRecallAction {
+    fn from_proto(
+        proto: track_and_trace_payload::AcknowledgeRecallAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(AcknowledgeRecallAction {
+            recall_id: proto.get_recall_id().to_string(),
+            record_id: proto.get_record_id().to_string(),
+        })
+    }
+}
+
+impl FromNative<AcknowledgeRecallAction> for track_and_trace_payload::AcknowledgeRecallAction {
+    fn from_native(native: AcknowledgeRecallAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::AcknowledgeRecallAction::new();
+        proto.set_recall_id(native.recall_id().to_string());
+        proto.set_record_id(native.record_id().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<AcknowledgeRecallAction> for AcknowledgeRecallAction {
+    fn from_bytes(bytes: &[u8]) -> Result<AcknowledgeRecallAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::AcknowledgeRecallAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get AcknowledgeRecallAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for AcknowledgeRecallAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get AcknowledgeRecallAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::AcknowledgeRecallAction> for AcknowledgeRecallAction {}
+impl IntoNative<AcknowledgeRecallAction> for track_and_trace_payload::AcknowledgeRecallAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloseRecallAction {
+    recall_id: String,
+}
+
+impl CloseRecallAction {
+    pub fn recall_id(&self) -> &str {
+        &self.recall_id
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct CloseRecallActionBuilder {
+    recall_id: Option<String>,
+}
+
+impl CloseRecallActionBuilder {
+    pub fn new() -> Self {
+        CloseRecallActionBuilder::default()
+    }
+    pub fn with_recall_id(mut self, value: String) -> Self {
+        self.recall_id = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<CloseRecallAction, BuilderError> {
+        let recall_id = self
+            .recall_id
+            .ok_or_else(|| BuilderError::MissingField("recall_id".into()))?;
+        Ok(CloseRecallAction { recall_id })
+    }
+}
+
+impl FromProto<track_and_trace_payload::CloseRecallAction> for CloseRecallAction {
+    fn from_proto(
+        proto: track_and_trace_payload::CloseRecallAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(CloseRecallAction {
+            recall_id: proto.get_recall_id().to_string(),
+        })
+    }
+}
+
+impl FromNative<CloseRecallAction> for track_and_trace_payload::CloseRecallAction {
+    fn from_native(native: CloseRecallAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::CloseRecallAction::new();
+        proto.set_recall_id(native.recall_id().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<CloseRecallAction> for CloseRecallAction {
+    fn from_bytes(bytes: &[u8]) -> Result<CloseRecallAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::CloseRecallAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get CloseRecallAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for CloseRecallAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get CloseRecallAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::CloseRecallAction> for CloseRecallAction {}
+impl IntoNative<CloseRecallAction> for track_and_trace_payload::CloseRecallAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateBulkProposalAction {
+    proposal_id: String,
+    record_ids: Vec<String>,
+    receiving_agent: String,
+    role: Role,
+    terms: String,
+    expires_at: u64,
+}
+
+impl CreateBulkProposalAction {
+    pub fn proposal_id(&self) -> &str {
+        &self.proposal_id
+    }
+    pub fn record_ids(&self) -> &[String] {
+        &self.record_ids
+    }
+    pub fn receiving_agent(&self) -> &str {
+        &self.receiving_agent
+    }
+    pub fn role(&self) -> &Role {
+        &self.role
+    }
+    pub fn terms(&self) -> &str {
+        &self.terms
+    }
+    pub fn expires_at(&self) -> &u64 {
+        &self.expires_at
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct CreateBulkProposalActionBuilder {
+    proposal_id: Option<String>,
+    record_ids: Option<Vec<String>>,
+    receiving_agent: Option<String>,
+    role: Option<Role>,
+    terms: Option<String>,
+    expires_at: Option<u64>,
+}
+
+impl CreateBulkProposalActionBuilder {
+    pub fn new() -> Self {
+        CreateBulkProposalActionBuilder::default()
+    }
+    pub fn with_proposal_id(mut self, value: String) -> Self {
+        self.proposal_id = Some(value);
+        self
+    }
+    pub fn with_record_ids(mut self, value: Vec<String>) -> Self {
+        self.record_ids = Some(value);
+        self
+    }
+    pub fn with_receiving_agent(mut self, value: String) -> Self {
+        self.receiving_agent = Some(value);
+        self
+    }
+    pub fn with_role(mut self, value: Role) -> Self {
+        self.role = Some(value);
+        self
+    }
+    pub fn with_terms(mut self, value: String) -> Self {
+        self.terms = Some(value);
+        self
+    }
+    pub fn with_expires_at(mut self, value: u64) -> Self {
+        self.expires_at = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<CreateBulkProposalAction, BuilderError> {
+        let proposal_id = self
+            .proposal_id
+            .ok_or_else(|| BuilderError::MissingField("proposal_id".into()))?;
+        let record_ids = self
+            .record_ids
+            .ok_or_else(|| BuilderError::MissingField("record_ids".into()))?;
+        let receiving_agent = self
+            .receiving_agent
+            .ok_or_else(|| BuilderError::MissingField("receiving_agent".into()))?;
+        let role = self
+            .role
+            .ok_or_else(|| BuilderError::MissingField("role".into()))?;
+        let terms = self.terms.unwrap_or_default();
+        let expires_at = self.expires_at.unwrap_or_default();
+        Ok(CreateBulkProposalAction {
+            proposal_id,
+            record_ids,
+            receiving_agent,
+            role,
+            terms,
+            expires_at,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::CreateBulkProposalAction> for CreateBulkProposalAction {
+    fn from_proto(
+        proto: track_and_trace_payload::CreateBulkProposalAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(CreateBulkProposalAction {
+            proposal_id: proto.get_proposal_id().to_string(),
+            record_ids: proto.get_record_ids().to_vec(),
+            receiving_agent: proto.get_receiving_agent().to_string(),
+            role: Role::from_proto(proto.get_role())?,
+            terms: proto.get_terms().to_string(),
+            expires_at: proto.get_expires_at(),
+        })
+    }
+}
+
+impl FromNative<CreateBulkProposalAction> for track_and_trace_payload::CreateBulkProposalAction {
+    fn from_native(native: CreateBulkProposalAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::CreateBulkProposalAction::new();
+        proto.set_proposal_id(native.proposal_id().to_string());
+        proto.set_record_ids(RepeatedField::from_vec(native.record_ids().to_vec()));
+        proto.set_receiving_agent(native.receiving_agent().to_string());
+        proto.set_role(native.role().clone().into_proto()?);
+        proto.set_terms(native.terms().to_string());
+        proto.set_expires_at(*native.expires_at());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<CreateBulkProposalAction> for CreateBulkProposalAction {
+    fn from_bytes(bytes: &[u8]) -> Result<CreateBulkProposalAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::CreateBulkProposalAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get CreateBulkProposalAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for CreateBulkProposalAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get CreateBulkProposalAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::CreateBulkProposalAction> for CreateBulkProposalAction {}
+impl IntoNative<CreateBulkProposalAction> for track_and_trace_payload::CreateBulkProposalAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnswerBulkProposalAction {
+    proposal_id: String,
+    receiving_agent: String,
+    response: Response,
+}
+
+impl AnswerBulkProposalAction {
+    pub fn proposal_id(&self) -> &str {
+        &self.proposal_id
+    }
+    pub fn receiving_agent(&self) -> &str {
+        &self.receiving_agent
+    }
+    pub fn response(&self) -> &Response {
+        &self.response
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AnswerBulkProposalActionBuilder {
+    proposal_id: Option<String>,
+    receiving_agent: Option<String>,
+    response: Option<Response>,
+}
+
+impl AnswerBulkProposalActionBuilder {
+    pub fn new() -> Self {
+        AnswerBulkProposalActionBuilder::default()
+    }
+    pub fn with_proposal_id(mut self, value: String) -> Self {
+        self.proposal_id = Some(value);
+        self
+    }
+    pub fn with_receiving_agent(mut self, value: String) -> Self {
+        self.receiving_agent = Some(value);
+        self
+    }
+    pub fn with_response(mut self, value: Response) -> Self {
+        self.response = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<AnswerBulkProposalAction, BuilderError> {
+        let proposal_id = self
+            .proposal_id
+            .ok_or_else(|| BuilderError::MissingField("proposal_id".into()))?;
+        let receiving_agent = self
+            .receiving_agent
+            .ok_or_else(|| BuilderError::MissingField("receiving_agent".into()))?;
+        let response = self
+            .response
+            .ok_or_else(|| BuilderError::MissingField("response".into()))?;
+        Ok(AnswerBulkProposalAction {
+            proposal_id,
+            receiving_agent,
+            response,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::AnswerBulkProposalAction> for AnswerBulkProposalAction {
+    fn from_proto(
+        proto: track_and_trace_payload::AnswerBulkProposalAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(AnswerBulkProposalAction {
+            proposal_id: proto.get_proposal_id().to_string(),
+            receiving_agent: proto.get_receiving_agent().to_string(),
+            response: Response::from_proto(proto.get_response())?,
+        })
+    }
+}
+
+impl FromNative<AnswerBulkProposalAction> for track_and_trace_payload::AnswerBulkProposalAction {
+    fn from_native(native: AnswerBulkProposalAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::AnswerBulkProposalAction::new();
+        proto.set_proposal_id(native.proposal_id().to_string());
+        proto.set_receiving_agent(native.receiving_agent().to_string());
+        proto.set_response(native.response().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<AnswerBulkProposalAction> for AnswerBulkProposalAction {
+    fn from_bytes(bytes: &[u8]) -> Result<AnswerBulkProposalAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::AnswerBulkProposalAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get AnswerBulkProposalAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for AnswerBulkProposalAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get AnswerBulkProposalAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::AnswerBulkProposalAction> for AnswerBulkProposalAction {}
+impl IntoNative<AnswerBulkProposalAction> for track_and_trace_payload::AnswerBulkProposalAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterSignProposalAction {
+    record_id: String,
+    receiving_agent: String,
+    role: Role,
+}
+
+impl CounterSignProposalAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn receiving_agent(&self) -> &str {
+        &self.receiving_agent
+    }
+    pub fn role(&self) -> &Role {
+        &self.role
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct CounterSignProposalActionBuilder {
+    record_id: Option<String>,
+    receiving_agent: Option<String>,
+    role: Option<Role>,
+}
+
+impl CounterSignProposalActionBuilder {
+    pub fn new() -> Self {
+        CounterSignProposalActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_receiving_agent(mut self, value: String) -> Self {
+        self.receiving_agent = Some(value);
+        self
+    }
+    pub fn with_role(mut self, value: Role) -> Self {
+        self.role = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<CounterSignProposalAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let receiving_agent = self
+            .receiving_agent
+            .ok_or_else(|| BuilderError::MissingField("receiving_agent".into()))?;
+        let role = self
+            .role
+            .ok_or_else(|| BuilderError::MissingField("role".into()))?;
+        Ok(CounterSignProposalAction {
+            record_id,
+            receiving_agent,
+            role,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::CounterSignProposalAction> for CounterSignProposalAction {
+    fn from_proto(
+        proto: track_and_trace_payload::CounterSignProposalAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(CounterSignProposalAction {
+            record_id: proto.get_record_id().to_string(),
+            receiving_agent: proto.get_receiving_agent().to_string(),
+            role: Role::from_proto(proto.get_role())?,
+        })
+    }
+}
+
+impl FromNative<CounterSignProposalAction> for track_and_trace_payload::CounterSignProposalAction {
+    fn from_native(native: CounterSignProposalAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::CounterSignProposalAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_receiving_agent(native.receiving_agent().to_string());
+        proto.set_role(native.role().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<CounterSignProposalAction> for CounterSignProposalAction {
+    fn from_bytes(bytes: &[u8]) -> Result<CounterSignProposalAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::CounterSignProposalAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get CounterSignProposalAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for CounterSignProposalAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get CounterSignProposalAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::CounterSignProposalAction> for CounterSignProposalAction {}
+impl IntoNative<CounterSignProposalAction> for track_and_trace_payload::CounterSignProposalAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorReading {
+    timestamp: u64,
+    value: PropertyValue,
+}
+
+impl SensorReading {
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+    pub fn value(&self) -> &PropertyValue {
+        &self.value
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct SensorReadingBuilder {
+    timestamp: Option<u64>,
+    value: Option<PropertyValue>,
+}
+
+impl SensorReadingBuilder {
+    pub fn new() -> Self {
+        SensorReadingBuilder::default()
+    }
+    pub fn with_timestamp(mut self, value: u64) -> Self {
+        self.timestamp = Some(value);
+        self
+    }
+    pub fn with_value(mut self, value: PropertyValue) -> Self {
+        self.value = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<SensorReading, BuilderError> {
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
+        let value = self
+            .value
+            .ok_or_else(|| BuilderError::MissingField("value".into()))?;
+        Ok(SensorReading { timestamp, value })
+    }
+}
+
+impl FromProto<track_and_trace_payload::SensorReading> for SensorReading {
+    fn from_proto(
+        proto: track_and_trace_payload::SensorReading,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(SensorReading {
+            timestamp: proto.get_timestamp(),
+            value: PropertyValue::from_proto(proto.get_value().clone())?,
+        })
+    }
+}
+
+impl FromNative<SensorReading> for track_and_trace_payload::SensorReading {
+    fn from_native(native: SensorReading) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::SensorReading::new();
+        proto.set_timestamp(*native.timestamp());
+        proto.set_value(native.value().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<SensorReading> for SensorReading {
+    fn from_bytes(bytes: &[u8]) -> Result<SensorReading, ProtoConversionError> {
+        let proto: track_and_trace_payload::SensorReading =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get SensorReading from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for SensorReading {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get SensorReading from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::SensorReading> for SensorReading {}
+impl IntoNative<SensorReading> for track_and_trace_payload::SensorReading {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestReadingsAction {
+    record_id: String,
+    readings: Vec<SensorReading>,
+    data_keys: Vec<DataKey>,
+}
+
+impl IngestReadingsAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn readings(&self) -> &[SensorReading] {
+        &self.readings
+    }
+    /// The rotated data key of the record, wrapped for every agent holding it
+    pub fn data_keys(&self) -> &[DataKey] {
+        &self.data_keys
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct IngestReadingsActionBuilder {
+    record_id: Option<String>,
+    readings: Option<Vec<SensorReading>>,
+    data_keys: Option<Vec<DataKey>>,
+}
+
+impl IngestReadingsActionBuilder {
+    pub fn new() -> Self {
+        IngestReadingsActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_readings(mut self, value: Vec<SensorReading>) -> Self {
+        self.readings = Some(value);
+        self
+    }
+    pub fn with_data_keys(mut self, value: Vec<DataKey>) -> Self {
+        self.data_keys = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<IngestReadingsAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let readings = self
+            .readings
+            .ok_or_else(|| BuilderError::MissingField("readings".into()))?;
+        let data_keys = self.data_keys.unwrap_or_default();
+        Ok(IngestReadingsAction {
+            record_id,
+            readings,
+            data_keys,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::IngestReadingsAction> for IngestReadingsAction {
+    fn from_proto(
+        proto: track_and_trace_payload::IngestReadingsAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(IngestReadingsAction {
+            record_id: proto.get_record_id().to_string(),
+            readings: proto
+                .get_readings()
+                .to_vec()
+                .into_iter()
+                .map(SensorReading::from_proto)
+                .collect::<Result<Vec<SensorReading>, ProtoConversionError>>()?,
+            data_keys: proto
+                .get_data_keys()
+                .to_vec()
+                .into_iter()
+                .map(DataKey::from_proto)
+                .collect::<Result<Vec<DataKey>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<IngestReadingsAction> for track_and_trace_payload::IngestReadingsAction {
+    fn from_native(native: IngestReadingsAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::IngestReadingsAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_readings(RepeatedField::from_vec(
+            native
+                .readings()
+                .to_vec()
+                .into_iter()
+                .map(SensorReading::into_proto)
+                .collect::<Result<Vec<_>, ProtoConversionError>>()?,
+        ));
+        proto.set_data_keys(RepeatedField::from_vec(
+            native
+                .data_keys()
+                .to_vec()
+                .into_iter()
+                .map(DataKey::into_proto)
+                .collect::<Result<Vec<_>, ProtoConversionError>>()?,
+        ));
+        Ok(proto)
+    }
+}
+
+impl FromBytes<IngestReadingsAction> for IngestReadingsAction {
+    fn from_bytes(bytes: &[u8]) -> Result<IngestReadingsAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::IngestReadingsAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get IngestReadingsAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for IngestReadingsAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get IngestReadingsAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::IngestReadingsAction> for IngestReadingsAction {}
+impl IntoNative<IngestReadingsAction> for track_and_trace_payload::IngestReadingsAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    CreateRecord(CreateRecordAction),
+    FinalizeRecord(FinalizeRecordAction),
+    UpdateProperties(UpdatePropertiesAction),
+    CreateProposal(CreateProposalAction),
+    AnswerProposal(AnswerProposalAction),
+    RevokeReporter(RevokeReporterAction),
+    SplitRecord(SplitRecordAction),
+    MergeRecords(MergeRecordsAction),
+    TransformRecord(TransformRecordAction),
+    CreateRecall(CreateRecallAction),
+    AcknowledgeRecall(AcknowledgeRecallAction),
+    CloseRecall(CloseRecallAction),
+    CreateBulkProposal(CreateBulkProposalAction),
+    AnswerBulkProposal(AnswerBulkProposalAction),
+    CounterSignProposal(CounterSignProposalAction),
+    IngestReadings(IngestReadingsAction),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackAndTracePayload {
+    action: Action,
+    timestamp: u64,
+}
+
+impl TrackAndTracePayload {
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct TrackAndTracePayloadBuilder {
+    action: Option<Action>,
+    timestamp: Option<u64>,
+}
+
+impl TrackAndTracePayloadBuilder {
+    pub fn new() -> Self {
+        TrackAndTracePayloadBuilder::default()
+    }
+    pub fn with_action(mut self, value: Action) -> Self {
+        self.action = Some(value);
+        self
+    }
+    pub fn with_timestamp(mut self, value: u64) -> Self {
+        self.timestamp = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<TrackAndTracePayload, BuilderError> {
+        let action = self
+            .action
+            .ok_or_else(|| BuilderError::MissingField("action".into()))?;
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
+        Ok(TrackAndTracePayload { action, timestamp })
+    }
+}
+
+impl FromProto<track_and_trace_payload::TrackAndTracePayload> for TrackAndTracePayload {
+    fn from_proto(
+        proto: track_and_trace_payload::TrackAndTracePayload,
+    ) -> Result<Self, ProtoConversionError> {
+        let action = match proto.get_action() {
+            TrackAndTracePayload_Action::CREATE_RECORD => Action::CreateRecord(
+                CreateRecordAction::from_proto(proto.get_create_record().clone())?,
+            ),
+            TrackAndTracePayload_Action::FINALIZE_RECORD => Action::FinalizeRecord(
+                FinalizeRecordAction::from_proto(proto.get_finalize_record().clone())?,
+            ),
+            TrackAndTracePayload_Action::UPDATE_PROPERTIES => Action::UpdateProperties(
+                UpdatePropertiesAction::from_proto(proto.get_update_properties().clone())?,
+            ),
+            TrackAndTracePayload_Action::CREATE_PROPOSAL => Action::CreateProposal(
+                CreateProposalAction::from_proto(proto.get_create_proposal().clone())?,
+            ),
+            TrackAndTracePayload_Action::ANSWER_PROPOSAL => Action::AnswerProposal(
+                AnswerProposalAction::from_proto(proto.get_answer_proposal().clone())?,
+            ),
+            TrackAndTracePayload_Action::REVOKE_REPORTER => Action::RevokeReporter(
+                RevokeReporterAction::from_proto(proto.get_revoke_reporter().clone())?,
+            ),
+            TrackAndTracePayload_Action::SPLIT_RECORD => Action::SplitRecord(
+                SplitRecordAction::from_proto(proto.get_split_record().clone())?,
+            ),
+            TrackAndTracePayload_Action::MERGE_RECORDS => Action::MergeRecords(
+                MergeRecordsAction::from_proto(proto.get_merge_records().clone())?,
+            ),
+            TrackAndTracePayload_Action::TRANSFORM_RECORD => Action::TransformRecord(
+                TransformRecordAction::from_proto(proto.get_transform_record().clone())?,
+            ),
+            TrackAndTracePayload_Action::CREATE_RECALL => Action::CreateRecall(
+                CreateRecallAction::from_proto(proto.get_create_recall().clone())?,
+            ),
+            TrackAndTracePayload_Action::ACKNOWLEDGE_RECALL => Action::AcknowledgeRecall(
+                AcknowledgeRecallAction::from_proto(proto.get_acknowledge_recall().clone())?,
+            ),
+            TrackAndTracePayload_Action::CLOSE_RECALL => Action::CloseRecall(
+                CloseRecallAction::from_proto(proto.get_close_recall().clone())?,
+            ),
+            TrackAndTracePayload_Action::CREATE_BULK_PROPOSAL => Action::CreateBulkProposal(
+                CreateBulkProposalAction::from_proto(proto.get_create_bulk_proposal().clone())?,
+            ),
+            TrackAndTracePayload_Action::ANSWER_BULK_PROPOSAL => Action::AnswerBulkProposal(
+                AnswerBulkProposalAction::from_proto(proto.get_answer_bulk_proposal().clone())?,
+            ),
+            TrackAndTracePayload_Action::COUNTER_SIGN_PROPOSAL => Action::CounterSignProposal(
+                CounterSignProposalAction::from_proto(proto.get_counter_sign_proposal().clone())?,
+            ),
+            TrackAndTracePayload_Action::INGEST_READINGS => Action::IngestReadings(
+                IngestReadingsAction::from_proto(proto.get_ingest_readings().clone())?,
+            ),
+            TrackAndTracePayload_Action::UNSET_ACTION => {
+                return Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert TrackAndTracePayload_Action with type unset.".to_string(),
+                ));
+            }
+        };
+
+        Ok(TrackAndTracePayload {
+            action,
+            timestamp: proto.get_timestamp(),
+        })
+    }
+}
+
+impl FromNative<TrackAndTracePayload> for track_and_trace_payload::TrackAndTracePayload {
+    fn from_native(native: TrackAndTracePayload) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::TrackAndTracePayload::new();
+
+        proto.set_timestamp(*native.timestamp());
+
+        match native.action() {
+            Action::CreateRecord(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::CREATE_RECORD);
+                proto.set_create_record(payload.clone().into_proto()?);
+            }
+            Action::FinalizeRecord(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::FINALIZE_RECORD);
+                proto.set_finalize_record(payload.clone().into_proto()?);
+            }
+            Action::UpdateProperties(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::UPDATE_PROPERTIES);
+                proto.set_update_properties(payload.clone().into_proto()?);
+            }
+            Action::CreateProposal(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::CREATE_PROPOSAL);
+                proto.set_create_proposal(payload.clone().into_proto()?);
+            }
+            Action::AnswerProposal(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::ANSWER_PROPOSAL);
+                proto.set_answer_proposal(payload.clone().into_proto()?);
+            }
+            Action::RevokeReporter(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::REVOKE_REPORTER);
+                proto.set_revoke_reporter(payload.clone().into_proto()?);
+            }
+            Action::SplitRecord(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::SPLIT_RECORD);
+                proto.set_split_record(payload.clone().into_proto()?);
+            }
+            Action::MergeRecords(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::MERGE_RECORDS);
+                proto.set_merge_records(payload.clone().into_proto()?);
+            }
+            Action::TransformRecord(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::TRANSFORM_RECORD);
+                proto.set_transform_record(payload.clone().into_proto()?);
+            }
+            Action::CreateRecall(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::CREATE_RECALL);
+                proto.set_create_recall(payload.clone().into_proto()?);
+            }
+            Action::AcknowledgeRecall(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::ACKNOWLEDGE_RECALL);
+                proto.set_acknowledge_recall(payload.clone().into_proto()?);
+            }
+            Action::CloseRecall(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::CLOSE_RECALL);
+                proto.set_close_recall(payload.clone().into_proto()?);
+            }
+            Action::CreateBulkProposal(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::CREATE_BULK_PROPOSAL);
+                proto.set_create_bulk_proposal(payload.clone().into_proto()?);
+            }
+            Action::AnswerBulkProposal(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::ANSWER_BULK_PROPOSAL);
+                proto.set_answer_bulk_proposal(payload.clone().into_proto()?);
+            }
+            Action::CounterSignProposal(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::COUNTER_SIGN_PROPOSAL);
+                proto.set_counter_sign_proposal(payload.clone().into_proto()?);
+            }
+            Action::IngestReadings(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::INGEST_READINGS);
+                proto.set_ingest_readings(payload.clone().into_proto()?);
+            }
+        }
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<TrackAndTracePayload> for TrackAndTracePayload {
+    fn from_bytes(bytes: &[u8]) -> Result<TrackAndTracePayload, ProtoConversionError> {
+        let proto: track_and_trace_payload::TrackAndTracePayload =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get TrackAndTracePaylaod from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for TrackAndTracePayload {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get TrackAndTracePaylaod from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::TrackAndTracePayload> for TrackAndTracePayload {}
+impl IntoNative<TrackAndTracePayload> for track_and_trace_payload::TrackAndTracePayload {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::schema::state::{DataType, PropertyValueBuilder};
+    use crate::protocol::track_and_trace::state::DataKeyBuilder;
+    use std::fmt::Debug;
+
+    fn test_from_bytes<T: FromBytes<T> + Clone + PartialEq + IntoBytes + Debug, F>(
+        under_test: T,
+        from_bytes: F,
+    ) where
+        F: Fn(&[u8]) -> Result<T, ProtoConversionError>,
+    {
+        let bytes = under_test.clone().into_bytes().unwrap();
+        let created_from_bytes = from_bytes(&bytes).unwrap();
+        assert_eq!(under_test, created_from_bytes);
+    }
+
+    #[test]
+    fn test_create_record_builder() {
+        let property_value = PropertyValueBuilder::new()
+            .with_name("egg".into())
+            .with_data_type(DataType::Number)
+            .with_number_value(42)
+            .build()
+            .unwrap();
+
+        let action = CreateRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .with_schema("schema".into())
+            .with_properties(vec![property_value.clone()])
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "32");
+        assert_eq!(action.schema(), "schema");
+        assert!(action.properties().iter().any(|x| *x == property_value));
+    }
+
+    #[test]
+    fn test_create_record_bytes() {
+        let property_value = PropertyValueBuilder::new()
+            .with_name("egg".into())
+            .with_data_type(DataType::Number)
+            .with_number_value(42)
+            .build()
+            .unwrap();
+
+        let action = CreateRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .with_schema("schema".into())
+            .with_properties(vec![property_value.clone()])
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, CreateRecordAction::from_bytes);
+    }
+
+    #[test]
+    fn test_finalize_record_action_builder() {
+        let action = FinalizeRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "32");
+    }
+
+    #[test]
+    fn test_finalize_record_action_bytes() {
+        let action = FinalizeRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, FinalizeRecordAction::from_bytes);
     }
 
     #[test]
@@ -1002,9 +2444,17 @@ mod tests {
             .build()
             .unwrap();
 
+        let data_key = DataKeyBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_wrapped_key(vec![1, 2, 3, 4])
+            .with_version(1)
+            .build()
+            .unwrap();
+
         let action = UpdatePropertiesActionBuilder::new()
             .with_record_id("32".into())
             .with_properties(vec![property_value.clone()])
+            .with_data_keys(vec![data_key])
             .build()
             .unwrap();
 
@@ -1098,6 +2548,185 @@ mod tests {
         test_from_bytes(action, RevokeReporterAction::from_bytes);
     }
 
+    #[test]
+    fn test_split_record_action_bytes() {
+        let parent = LineageParentBuilder::new()
+            .with_record_id("32".into())
+            .with_consume(true)
+            .build()
+            .unwrap();
+        let children = vec!["32-a", "32-b"]
+            .into_iter()
+            .map(|record_id| {
+                CreateRecordActionBuilder::new()
+                    .with_record_id(record_id.into())
+                    .with_schema("schema".into())
+                    .with_properties(vec![])
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<CreateRecordAction>>();
+
+        let action = SplitRecordActionBuilder::new()
+            .with_parent(parent.clone())
+            .with_children(children.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(*action.parent(), parent);
+        assert_eq!(action.children(), &children[..]);
+
+        test_from_bytes(action, SplitRecordAction::from_bytes);
+    }
+
+    #[test]
+    fn test_merge_records_action_bytes() {
+        let parents = vec!["32", "33"]
+            .into_iter()
+            .map(|record_id| {
+                LineageParentBuilder::new()
+                    .with_record_id(record_id.into())
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<LineageParent>>();
+        let child = CreateRecordActionBuilder::new()
+            .with_record_id("34".into())
+            .with_schema("schema".into())
+            .with_properties(vec![])
+            .build()
+            .unwrap();
+
+        let action = MergeRecordsActionBuilder::new()
+            .with_parents(parents.clone())
+            .with_child(child.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.parents(), &parents[..]);
+        assert_eq!(*action.parents()[0].consume(), false);
+        assert_eq!(*action.child(), child);
+
+        test_from_bytes(action, MergeRecordsAction::from_bytes);
+    }
+
+    #[test]
+    fn test_transform_record_action_bytes() {
+        let action = TransformRecordActionBuilder::new()
+            .with_parent(
+                LineageParentBuilder::new()
+                    .with_record_id("32".into())
+                    .with_consume(true)
+                    .build()
+                    .unwrap(),
+            )
+            .with_child(
+                CreateRecordActionBuilder::new()
+                    .with_record_id("33".into())
+                    .with_schema("schema".into())
+                    .with_properties(vec![])
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, TransformRecordAction::from_bytes);
+    }
+
+    #[test]
+    fn test_create_recall_action_bytes() {
+        let action = CreateRecallActionBuilder::new()
+            .with_recall_id("recall1".into())
+            .with_record_ids(vec!["32".into(), "33".into()])
+            .with_reason("contaminated".into())
+            .with_severity(Severity::High)
+            .build()
+            .unwrap();
+
+        assert_eq!(action.product_id(), "");
+        assert_eq!(*action.severity(), Severity::High);
+
+        test_from_bytes(action, CreateRecallAction::from_bytes);
+    }
+
+    #[test]
+    fn test_acknowledge_recall_action_bytes() {
+        let action = AcknowledgeRecallActionBuilder::new()
+            .with_recall_id("recall1".into())
+            .with_record_id("32".into())
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, AcknowledgeRecallAction::from_bytes);
+    }
+
+    #[test]
+    fn test_bulk_proposal_actions_bytes() {
+        let action = CreateBulkProposalActionBuilder::new()
+            .with_proposal_id("shipment1".into())
+            .with_record_ids(vec!["32".into(), "33".into()])
+            .with_receiving_agent("jim".into())
+            .with_role(Role::Custodian)
+            .build()
+            .unwrap();
+
+        assert_eq!(action.terms(), "");
+        assert_eq!(*action.expires_at(), 0);
+
+        test_from_bytes(action, CreateBulkProposalAction::from_bytes);
+
+        let action = AnswerBulkProposalActionBuilder::new()
+            .with_proposal_id("shipment1".into())
+            .with_receiving_agent("jim".into())
+            .with_response(Response::Reject)
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, AnswerBulkProposalAction::from_bytes);
+    }
+
+    #[test]
+    fn test_counter_sign_proposal_action_bytes() {
+        let action = CounterSignProposalActionBuilder::new()
+            .with_record_id("32".into())
+            .with_receiving_agent("jim".into())
+            .with_role(Role::Custodian)
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, CounterSignProposalAction::from_bytes);
+    }
+
+    #[test]
+    fn test_ingest_readings_action_bytes() {
+        let readings = vec![10, 12]
+            .into_iter()
+            .enumerate()
+            .map(|(i, number)| {
+                SensorReadingBuilder::new()
+                    .with_timestamp(100 + i as u64)
+                    .with_value(
+                        PropertyValueBuilder::new()
+                            .with_name("temperature".into())
+                            .with_data_type(DataType::Number)
+                            .with_number_value(number)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let action = IngestReadingsActionBuilder::new()
+            .with_record_id("32".into())
+            .with_readings(readings)
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, IngestReadingsAction::from_bytes);
+    }
+
     #[test]
     fn test_payload_builder() {
         let action = RevokeReporterActionBuilder::new()
@@ -21,11 +21,115 @@ use crate::protos::{
 use protobuf::Message;
 use protobuf::RepeatedField;
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReporterAuthorization {
+    granted_at: u64,
+    granted_by: String,
+    revoked_at: u64,
+    revoked_by: String,
+}
+
+impl ReporterAuthorization {
+    pub fn granted_at(&self) -> &u64 {
+        &self.granted_at
+    }
+    pub fn granted_by(&self) -> &str {
+        &self.granted_by
+    }
+    /// When the authorization was revoked, 0 while it is in effect
+    pub fn revoked_at(&self) -> &u64 {
+        &self.revoked_at
+    }
+    pub fn revoked_by(&self) -> &str {
+        &self.revoked_by
+    }
+    /// Whether the authorization was in effect at the time
+    pub fn covers(&self, timestamp: u64) -> bool {
+        self.granted_at <= timestamp && (self.revoked_at == 0 || timestamp < self.revoked_at)
+    }
+    pub fn into_builder(self) -> ReporterAuthorizationBuilder {
+        ReporterAuthorizationBuilder::new()
+            .with_granted_at(self.granted_at)
+            .with_granted_by(self.granted_by)
+            .with_revoked(self.revoked_at, self.revoked_by)
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct ReporterAuthorizationBuilder {
+    granted_at: Option<u64>,
+    granted_by: Option<String>,
+    revoked_at: Option<u64>,
+    revoked_by: Option<String>,
+}
+
+impl ReporterAuthorizationBuilder {
+    pub fn new() -> Self {
+        ReporterAuthorizationBuilder::default()
+    }
+    pub fn with_granted_at(mut self, value: u64) -> Self {
+        self.granted_at = Some(value);
+        self
+    }
+    pub fn with_granted_by(mut self, value: String) -> Self {
+        self.granted_by = Some(value);
+        self
+    }
+    pub fn with_revoked(mut self, revoked_at: u64, revoked_by: String) -> Self {
+        self.revoked_at = Some(revoked_at);
+        self.revoked_by = Some(revoked_by);
+        self
+    }
+    pub fn build(self) -> Result<ReporterAuthorization, BuilderError> {
+        let granted_at = self
+            .granted_at
+            .ok_or_else(|| BuilderError::MissingField("granted_at".into()))?;
+        let granted_by = self
+            .granted_by
+            .ok_or_else(|| BuilderError::MissingField("granted_by".into()))?;
+        Ok(ReporterAuthorization {
+            granted_at,
+            granted_by,
+            revoked_at: self.revoked_at.unwrap_or_default(),
+            revoked_by: self.revoked_by.unwrap_or_default(),
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Property_Reporter_Authorization> for ReporterAuthorization {
+    fn from_proto(
+        proto: track_and_trace_state::Property_Reporter_Authorization,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ReporterAuthorization {
+            granted_at: proto.get_granted_at(),
+            granted_by: proto.get_granted_by().to_string(),
+            revoked_at: proto.get_revoked_at(),
+            revoked_by: proto.get_revoked_by().to_string(),
+        })
+    }
+}
+
+impl FromNative<ReporterAuthorization> for track_and_trace_state::Property_Reporter_Authorization {
+    fn from_native(native: ReporterAuthorization) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Property_Reporter_Authorization::new();
+        proto.set_granted_at(*native.granted_at());
+        proto.set_granted_by(native.granted_by().to_string());
+        proto.set_revoked_at(*native.revoked_at());
+        proto.set_revoked_by(native.revoked_by().to_string());
+
+        Ok(proto)
+    }
+}
+
+impl IntoProto<track_and_trace_state::Property_Reporter_Authorization> for ReporterAuthorization {}
+impl IntoNative<ReporterAuthorization> for track_and_trace_state::Property_Reporter_Authorization {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Reporter {
     public_key: String,
     authorized: bool,
     index: u32,
+    authorizations: Vec<ReporterAuthorization>,
 }
 
 impl Reporter {
@@ -38,11 +142,26 @@ impl Reporter {
     pub fn index(&self) -> &u32 {
         &self.index
     }
+    /// The periods during which the reporter was authorized, oldest first
+    pub fn authorizations(&self) -> &[ReporterAuthorization] {
+        &self.authorizations
+    }
+    /// Whether the reporter was authorized at the time. Reporters authorized before their
+    /// authorizations were recorded only have their current authorization to go by.
+    pub fn authorized_at(&self, timestamp: u64) -> bool {
+        if self.authorizations.is_empty() {
+            return self.authorized;
+        }
+        self.authorizations
+            .iter()
+            .any(|authorization| authorization.covers(timestamp))
+    }
     pub fn into_builder(self) -> ReporterBuilder {
         ReporterBuilder::new()
             .with_public_key(self.public_key)
             .with_authorized(self.authorized)
             .with_index(self.index)
+            .with_authorizations(self.authorizations)
     }
 }
 
@@ -51,6 +170,7 @@ pub struct ReporterBuilder {
     public_key: Option<String>,
     authorized: Option<bool>,
     index: Option<u32>,
+    authorizations: Option<Vec<ReporterAuthorization>>,
 }
 
 impl ReporterBuilder {
@@ -69,6 +189,10 @@ impl ReporterBuilder {
         self.index = Some(value);
         self
     }
+    pub fn with_authorizations(mut self, value: Vec<ReporterAuthorization>) -> Self {
+        self.authorizations = Some(value);
+        self
+    }
     pub fn build(self) -> Result<Reporter, BuilderError> {
         let public_key = self
             .public_key
@@ -83,6 +207,7 @@ impl ReporterBuilder {
             public_key,
             authorized,
             index,
+            authorizations: self.authorizations.unwrap_or_default(),
         })
     }
 }
@@ -95,6 +220,12 @@ impl FromProto<track_and_trace_state::Property_Reporter> for Reporter {
             public_key: proto.get_public_key().to_string(),
             authorized: proto.get_authorized(),
             index: proto.get_index(),
+            authorizations: proto
+                .get_authorizations()
+                .to_vec()
+                .into_iter()
+                .map(ReporterAuthorization::from_proto)
+                .collect::<Result<Vec<ReporterAuthorization>, ProtoConversionError>>()?,
         })
     }
 }
@@ -105,6 +236,14 @@ impl FromNative<Reporter> for track_and_trace_state::Property_Reporter {
         proto.set_public_key(native.public_key().to_string());
         proto.set_authorized(*native.authorized());
         proto.set_index(*native.index());
+        proto.set_authorizations(RepeatedField::from_vec(
+            native
+                .authorizations()
+                .to_vec()
+                .into_iter()
+                .map(ReporterAuthorization::into_proto)
+                .collect::<Result<Vec<_>, ProtoConversionError>>()?,
+        ));
 
         Ok(proto)
     }
@@ -139,6 +278,8 @@ pub struct Property {
     reporters: Vec<Reporter>,
     current_page: u32,
     wrapped: bool,
+    archive_anchor: String,
+    archived_pages: u32,
 }
 
 impl Property {
@@ -160,6 +301,13 @@ impl Property {
     pub fn wrapped(&self) -> &bool {
         &self.wrapped
     }
+    /// The hash chain of the pages archived under the ARCHIVE retention policy
+    pub fn archive_anchor(&self) -> &str {
+        &self.archive_anchor
+    }
+    pub fn archived_pages(&self) -> &u32 {
+        &self.archived_pages
+    }
 
     pub fn into_builder(self) -> PropertyBuilder {
         PropertyBuilder::new()
@@ -169,6 +317,7 @@ impl Property {
             .with_reporters(self.reporters)
             .with_current_page(self.current_page)
             .with_wrapped(self.wrapped)
+            .with_archive(self.archive_anchor, self.archived_pages)
     }
 }
 
@@ -180,6 +329,8 @@ pub struct PropertyBuilder {
     reporters: Option<Vec<Reporter>>,
     current_page: Option<u32>,
     wrapped: Option<bool>,
+    archive_anchor: Option<String>,
+    archived_pages: Option<u32>,
 }
 
 impl PropertyBuilder {
@@ -210,6 +361,11 @@ impl PropertyBuilder {
         self.wrapped = Some(value);
         self
     }
+    pub fn with_archive(mut self, archive_anchor: String, archived_pages: u32) -> Self {
+        self.archive_anchor = Some(archive_anchor);
+        self.archived_pages = Some(archived_pages);
+        self
+    }
     pub fn build(self) -> Result<Property, BuilderError> {
         let name = self
             .name
@@ -236,6 +392,8 @@ impl PropertyBuilder {
             reporters,
             current_page,
             wrapped,
+            archive_anchor: self.archive_anchor.unwrap_or_default(),
+            archived_pages: self.archived_pages.unwrap_or_default(),
         })
     }
 }
@@ -256,6 +414,8 @@ impl FromProto<track_and_trace_state::Property> for Property {
                 .collect::<Result<Vec<Reporter>, ProtoConversionError>>()?,
             current_page: proto.get_current_page(),
             wrapped: proto.get_wrapped(),
+            archive_anchor: proto.get_archive_anchor().to_string(),
+            archived_pages: proto.get_archived_pages(),
         })
     }
 }
@@ -274,6 +434,8 @@ impl FromNative<Property> for track_and_trace_state::Property {
             .collect::<Result<Vec<track_and_trace_state::Property_Reporter>, ProtoConversionError>>()?));
         proto.set_current_page(*native.current_page());
         proto.set_wrapped(*native.wrapped());
+        proto.set_archive_anchor(native.archive_anchor().to_string());
+        proto.set_archived_pages(*native.archived_pages());
 
         Ok(proto)
     }
@@ -395,6 +557,7 @@ pub struct ReportedValue {
     reporter_index: u32,
     timestamp: u64,
     value: PropertyValue,
+    geofence_breaches: Vec<String>,
 }
 
 impl ReportedValue {
@@ -407,11 +570,16 @@ impl ReportedValue {
     pub fn value(&self) -> &PropertyValue {
         &self.value
     }
+    /// The names of the geofences that the value was outside of
+    pub fn geofence_breaches(&self) -> &[String] {
+        &self.geofence_breaches
+    }
     pub fn into_builder(self) -> ReportedValueBuilder {
         ReportedValueBuilder::new()
             .with_reporter_index(self.reporter_index)
             .with_timestamp(self.timestamp)
             .with_value(self.value)
+            .with_geofence_breaches(self.geofence_breaches)
     }
 }
 
@@ -420,6 +588,7 @@ pub struct ReportedValueBuilder {
     reporter_index: Option<u32>,
     timestamp: Option<u64>,
     value: Option<PropertyValue>,
+    geofence_breaches: Vec<String>,
 }
 
 impl ReportedValueBuilder {
@@ -438,6 +607,10 @@ impl ReportedValueBuilder {
         self.value = Some(value);
         self
     }
+    pub fn with_geofence_breaches(mut self, value: Vec<String>) -> Self {
+        self.geofence_breaches = value;
+        self
+    }
     pub fn build(self) -> Result<ReportedValue, BuilderError> {
         let reporter_index = self
             .reporter_index
@@ -452,6 +625,7 @@ impl ReportedValueBuilder {
             reporter_index,
             timestamp,
             value,
+            geofence_breaches: self.geofence_breaches,
         })
     }
 }
@@ -464,6 +638,7 @@ impl FromProto<track_and_trace_state::PropertyPage_ReportedValue> for ReportedVa
             reporter_index: proto.get_reporter_index(),
             timestamp: proto.get_timestamp(),
             value: PropertyValue::from_proto(proto.get_value().clone())?,
+            geofence_breaches: proto.get_geofence_breaches().to_vec(),
         })
     }
 }
@@ -474,6 +649,7 @@ impl FromNative<ReportedValue> for track_and_trace_state::PropertyPage_ReportedV
         proto.set_reporter_index(*native.reporter_index());
         proto.set_timestamp(*native.timestamp());
         proto.set_value(native.value().clone().into_proto()?);
+        proto.set_geofence_breaches(RepeatedField::from_vec(native.geofence_breaches().to_vec()));
 
         Ok(proto)
     }
@@ -732,6 +908,7 @@ pub enum Role {
     Owner,
     Custodian,
     Reporter,
+    Reopen,
 }
 
 impl Default for Role {
@@ -748,6 +925,7 @@ impl FromProto<track_and_trace_state::Proposal_Role> for Role {
             track_and_trace_state::Proposal_Role::OWNER => Ok(Role::Owner),
             track_and_trace_state::Proposal_Role::CUSTODIAN => Ok(Role::Custodian),
             track_and_trace_state::Proposal_Role::REPORTER => Ok(Role::Reporter),
+            track_and_trace_state::Proposal_Role::REOPEN => Ok(Role::Reopen),
         }
     }
 }
@@ -758,6 +936,7 @@ impl FromNative<Role> for track_and_trace_state::Proposal_Role {
             Role::Owner => Ok(track_and_trace_state::Proposal_Role::OWNER),
             Role::Custodian => Ok(track_and_trace_state::Proposal_Role::CUSTODIAN),
             Role::Reporter => Ok(track_and_trace_state::Proposal_Role::REPORTER),
+            Role::Reopen => Ok(track_and_trace_state::Proposal_Role::REOPEN),
         }
     }
 }
@@ -771,6 +950,7 @@ pub enum Status {
     Accepted,
     Rejected,
     Canceled,
+    Expired,
 }
 
 impl Default for Status {
@@ -788,6 +968,7 @@ impl FromProto<track_and_trace_state::Proposal_Status> for Status {
             track_and_trace_state::Proposal_Status::ACCEPTED => Ok(Status::Accepted),
             track_and_trace_state::Proposal_Status::REJECTED => Ok(Status::Rejected),
             track_and_trace_state::Proposal_Status::CANCELED => Ok(Status::Canceled),
+            track_and_trace_state::Proposal_Status::EXPIRED => Ok(Status::Expired),
         }
     }
 }
@@ -799,6 +980,7 @@ impl FromNative<Status> for track_and_trace_state::Proposal_Status {
             Status::Accepted => Ok(track_and_trace_state::Proposal_Status::ACCEPTED),
             Status::Rejected => Ok(track_and_trace_state::Proposal_Status::REJECTED),
             Status::Canceled => Ok(track_and_trace_state::Proposal_Status::CANCELED),
+            Status::Expired => Ok(track_and_trace_state::Proposal_Status::EXPIRED),
         }
     }
 }
@@ -806,6 +988,236 @@ impl FromNative<Status> for track_and_trace_state::Proposal_Status {
 impl IntoProto<track_and_trace_state::Proposal_Status> for Status {}
 impl IntoNative<Status> for track_and_trace_state::Proposal_Status {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionType {
+    PropertyRange,
+    CounterSignature,
+}
+
+impl Default for ConditionType {
+    fn default() -> ConditionType {
+        ConditionType::PropertyRange
+    }
+}
+
+impl FromProto<track_and_trace_state::Proposal_Condition_Type> for ConditionType {
+    fn from_proto(
+        condition_type: track_and_trace_state::Proposal_Condition_Type,
+    ) -> Result<Self, ProtoConversionError> {
+        match condition_type {
+            track_and_trace_state::Proposal_Condition_Type::PROPERTY_RANGE => {
+                Ok(ConditionType::PropertyRange)
+            }
+            track_and_trace_state::Proposal_Condition_Type::COUNTER_SIGNATURE => {
+                Ok(ConditionType::CounterSignature)
+            }
+        }
+    }
+}
+
+impl FromNative<ConditionType> for track_and_trace_state::Proposal_Condition_Type {
+    fn from_native(condition_type: ConditionType) -> Result<Self, ProtoConversionError> {
+        match condition_type {
+            ConditionType::PropertyRange => {
+                Ok(track_and_trace_state::Proposal_Condition_Type::PROPERTY_RANGE)
+            }
+            ConditionType::CounterSignature => {
+                Ok(track_and_trace_state::Proposal_Condition_Type::COUNTER_SIGNATURE)
+            }
+        }
+    }
+}
+
+impl IntoProto<track_and_trace_state::Proposal_Condition_Type> for ConditionType {}
+impl IntoNative<ConditionType> for track_and_trace_state::Proposal_Condition_Type {}
+
+/// A condition checked when a proposal is accepted
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    condition_type: ConditionType,
+    property_name: String,
+    min: i64,
+    max: i64,
+    org_id: String,
+}
+
+impl Condition {
+    pub fn condition_type(&self) -> &ConditionType {
+        &self.condition_type
+    }
+    /// The NUMBER property whose reported values must be within range
+    pub fn property_name(&self) -> &str {
+        &self.property_name
+    }
+    pub fn min(&self) -> &i64 {
+        &self.min
+    }
+    pub fn max(&self) -> &i64 {
+        &self.max
+    }
+    /// The organization whose admin must counter-sign the proposal
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct ConditionBuilder {
+    condition_type: Option<ConditionType>,
+    property_name: Option<String>,
+    min: Option<i64>,
+    max: Option<i64>,
+    org_id: Option<String>,
+}
+
+impl ConditionBuilder {
+    pub fn new() -> Self {
+        ConditionBuilder::default()
+    }
+    pub fn with_condition_type(mut self, value: ConditionType) -> Self {
+        self.condition_type = Some(value);
+        self
+    }
+    pub fn with_property_range(mut self, property_name: String, min: i64, max: i64) -> Self {
+        self.property_name = Some(property_name);
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+    pub fn with_org_id(mut self, value: String) -> Self {
+        self.org_id = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<Condition, BuilderError> {
+        let condition_type = self
+            .condition_type
+            .ok_or_else(|| BuilderError::MissingField("condition_type".into()))?;
+        let property_name = self.property_name.unwrap_or_default();
+        let min = self.min.unwrap_or_default();
+        let max = self.max.unwrap_or_default();
+        let org_id = self.org_id.unwrap_or_default();
+        match condition_type {
+            ConditionType::PropertyRange if property_name.is_empty() => {
+                return Err(BuilderError::MissingField("property_name".into()));
+            }
+            ConditionType::CounterSignature if org_id.is_empty() => {
+                return Err(BuilderError::MissingField("org_id".into()));
+            }
+            _ => (),
+        }
+        Ok(Condition {
+            condition_type,
+            property_name,
+            min,
+            max,
+            org_id,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Proposal_Condition> for Condition {
+    fn from_proto(
+        proto: track_and_trace_state::Proposal_Condition,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(Condition {
+            condition_type: ConditionType::from_proto(proto.get_condition_type())?,
+            property_name: proto.get_property_name().to_string(),
+            min: proto.get_min(),
+            max: proto.get_max(),
+            org_id: proto.get_org_id().to_string(),
+        })
+    }
+}
+
+impl FromNative<Condition> for track_and_trace_state::Proposal_Condition {
+    fn from_native(native: Condition) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Proposal_Condition::new();
+
+        proto.set_condition_type(native.condition_type().clone().into_proto()?);
+        proto.set_property_name(native.property_name().to_string());
+        proto.set_min(*native.min());
+        proto.set_max(*native.max());
+        proto.set_org_id(native.org_id().to_string());
+
+        Ok(proto)
+    }
+}
+
+impl IntoProto<track_and_trace_state::Proposal_Condition> for Condition {}
+impl IntoNative<Condition> for track_and_trace_state::Proposal_Condition {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterSignature {
+    agent_id: String,
+    timestamp: u64,
+}
+
+impl CounterSignature {
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct CounterSignatureBuilder {
+    agent_id: Option<String>,
+    timestamp: Option<u64>,
+}
+
+impl CounterSignatureBuilder {
+    pub fn new() -> Self {
+        CounterSignatureBuilder::default()
+    }
+    pub fn with_agent_id(mut self, value: String) -> Self {
+        self.agent_id = Some(value);
+        self
+    }
+    pub fn with_timestamp(mut self, value: u64) -> Self {
+        self.timestamp = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<CounterSignature, BuilderError> {
+        let agent_id = self
+            .agent_id
+            .ok_or_else(|| BuilderError::MissingField("agent_id".into()))?;
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
+        Ok(CounterSignature {
+            agent_id,
+            timestamp,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Proposal_CounterSignature> for CounterSignature {
+    fn from_proto(
+        proto: track_and_trace_state::Proposal_CounterSignature,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(CounterSignature {
+            agent_id: proto.get_agent_id().to_string(),
+            timestamp: proto.get_timestamp(),
+        })
+    }
+}
+
+impl FromNative<CounterSignature> for track_and_trace_state::Proposal_CounterSignature {
+    fn from_native(native: CounterSignature) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Proposal_CounterSignature::new();
+
+        proto.set_agent_id(native.agent_id().to_string());
+        proto.set_timestamp(*native.timestamp());
+
+        Ok(proto)
+    }
+}
+
+impl IntoProto<track_and_trace_state::Proposal_CounterSignature> for CounterSignature {}
+impl IntoNative<CounterSignature> for track_and_trace_state::Proposal_CounterSignature {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Proposal {
     record_id: String,
@@ -816,6 +1228,11 @@ pub struct Proposal {
     properties: Vec<String>,
     status: Status,
     terms: String,
+    expires_at: u64,
+    conditions: Vec<Condition>,
+    counter_signatures: Vec<CounterSignature>,
+    wrapped_data_key: Vec<u8>,
+    data_key_version: u32,
 }
 
 impl Proposal {
@@ -843,6 +1260,26 @@ impl Proposal {
     pub fn terms(&self) -> &str {
         &self.terms
     }
+    /// The time after which the proposal can no longer be answered, or 0 if it does not expire
+    pub fn expires_at(&self) -> &u64 {
+        &self.expires_at
+    }
+    /// Whether the proposal has expired at the given time
+    pub fn is_expired(&self, timestamp: u64) -> bool {
+        self.expires_at != 0 && timestamp >= self.expires_at
+    }
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+    pub fn counter_signatures(&self) -> &[CounterSignature] {
+        &self.counter_signatures
+    }
+    pub fn wrapped_data_key(&self) -> &[u8] {
+        &self.wrapped_data_key
+    }
+    pub fn data_key_version(&self) -> &u32 {
+        &self.data_key_version
+    }
     pub fn into_builder(self) -> ProposalBuilder {
         ProposalBuilder::new()
             .with_record_id(self.record_id)
@@ -853,6 +1290,11 @@ impl Proposal {
             .with_properties(self.properties)
             .with_status(self.status)
             .with_terms(self.terms)
+            .with_expires_at(self.expires_at)
+            .with_conditions(self.conditions)
+            .with_counter_signatures(self.counter_signatures)
+            .with_wrapped_data_key(self.wrapped_data_key)
+            .with_data_key_version(self.data_key_version)
     }
 }
 
@@ -866,6 +1308,11 @@ pub struct ProposalBuilder {
     properties: Option<Vec<String>>,
     status: Option<Status>,
     terms: Option<String>,
+    expires_at: Option<u64>,
+    conditions: Option<Vec<Condition>>,
+    counter_signatures: Option<Vec<CounterSignature>>,
+    wrapped_data_key: Option<Vec<u8>>,
+    data_key_version: Option<u32>,
 }
 
 impl ProposalBuilder {
@@ -904,6 +1351,26 @@ impl ProposalBuilder {
         self.terms = Some(value);
         self
     }
+    pub fn with_expires_at(mut self, value: u64) -> Self {
+        self.expires_at = Some(value);
+        self
+    }
+    pub fn with_conditions(mut self, value: Vec<Condition>) -> Self {
+        self.conditions = Some(value);
+        self
+    }
+    pub fn with_counter_signatures(mut self, value: Vec<CounterSignature>) -> Self {
+        self.counter_signatures = Some(value);
+        self
+    }
+    pub fn with_wrapped_data_key(mut self, value: Vec<u8>) -> Self {
+        self.wrapped_data_key = Some(value);
+        self
+    }
+    pub fn with_data_key_version(mut self, value: u32) -> Self {
+        self.data_key_version = Some(value);
+        self
+    }
     pub fn build(self) -> Result<Proposal, BuilderError> {
         let record_id = self
             .record_id
@@ -929,6 +1396,11 @@ impl ProposalBuilder {
         let terms = self
             .terms
             .ok_or_else(|| BuilderError::MissingField("terms".into()))?;
+        let expires_at = self.expires_at.unwrap_or_default();
+        let conditions = self.conditions.unwrap_or_default();
+        let counter_signatures = self.counter_signatures.unwrap_or_default();
+        let wrapped_data_key = self.wrapped_data_key.unwrap_or_default();
+        let data_key_version = self.data_key_version.unwrap_or_default();
         Ok(Proposal {
             record_id,
             timestamp,
@@ -938,6 +1410,11 @@ impl ProposalBuilder {
             properties,
             status,
             terms,
+            expires_at,
+            conditions,
+            counter_signatures,
+            wrapped_data_key,
+            data_key_version,
         })
     }
 }
@@ -958,6 +1435,21 @@ impl FromProto<track_and_trace_state::Proposal> for Proposal {
                 .collect(),
             status: Status::from_proto(proto.get_status())?,
             terms: proto.get_terms().to_string(),
+            expires_at: proto.get_expires_at(),
+            conditions: proto
+                .get_conditions()
+                .to_vec()
+                .into_iter()
+                .map(Condition::from_proto)
+                .collect::<Result<Vec<Condition>, ProtoConversionError>>()?,
+            counter_signatures: proto
+                .get_counter_signatures()
+                .to_vec()
+                .into_iter()
+                .map(CounterSignature::from_proto)
+                .collect::<Result<Vec<CounterSignature>, ProtoConversionError>>()?,
+            wrapped_data_key: proto.get_wrapped_data_key().to_vec(),
+            data_key_version: proto.get_data_key_version(),
         })
     }
 }
@@ -974,6 +1466,31 @@ impl FromNative<Proposal> for track_and_trace_state::Proposal {
         proto.set_properties(RepeatedField::from_vec(native.properties().to_vec()));
         proto.set_status(native.status().clone().into_proto()?);
         proto.set_terms(native.terms().to_string());
+        proto.set_expires_at(*native.expires_at());
+        proto.set_conditions(RepeatedField::from_vec(
+            native
+                .conditions()
+                .to_vec()
+                .into_iter()
+                .map(Condition::into_proto)
+                .collect::<Result<
+                    Vec<track_and_trace_state::Proposal_Condition>,
+                    ProtoConversionError,
+                >>()?,
+        ));
+        proto.set_counter_signatures(RepeatedField::from_vec(
+            native
+                .counter_signatures()
+                .to_vec()
+                .into_iter()
+                .map(CounterSignature::into_proto)
+                .collect::<Result<
+                    Vec<track_and_trace_state::Proposal_CounterSignature>,
+                    ProtoConversionError,
+                >>()?,
+        ));
+        proto.set_wrapped_data_key(native.wrapped_data_key().to_vec());
+        proto.set_data_key_version(*native.data_key_version());
 
         Ok(proto)
     }
@@ -1094,54 +1611,414 @@ impl IntoProto<track_and_trace_state::ProposalList> for ProposalList {}
 impl IntoNative<ProposalList> for track_and_trace_state::ProposalList {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct AssociatedAgent {
-    agent_id: String,
+pub struct BulkProposal {
+    proposal_id: String,
     timestamp: u64,
+    issuing_agent: String,
+    receiving_agent: String,
+    role: Role,
+    record_ids: Vec<String>,
+    status: Status,
+    terms: String,
+    expires_at: u64,
 }
 
-impl AssociatedAgent {
-    pub fn agent_id(&self) -> &str {
-        &self.agent_id
+impl BulkProposal {
+    pub fn proposal_id(&self) -> &str {
+        &self.proposal_id
     }
     pub fn timestamp(&self) -> &u64 {
         &self.timestamp
     }
-
-    pub fn into_builder(self) -> AssociatedAgentBuilder {
-        AssociatedAgentBuilder::new()
-            .with_agent_id(self.agent_id)
+    pub fn issuing_agent(&self) -> &str {
+        &self.issuing_agent
+    }
+    pub fn receiving_agent(&self) -> &str {
+        &self.receiving_agent
+    }
+    pub fn role(&self) -> &Role {
+        &self.role
+    }
+    pub fn record_ids(&self) -> &[String] {
+        &self.record_ids
+    }
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+    pub fn terms(&self) -> &str {
+        &self.terms
+    }
+    /// The time after which the proposal can no longer be answered, or 0 if it does not expire
+    pub fn expires_at(&self) -> &u64 {
+        &self.expires_at
+    }
+    /// Whether the proposal has expired at the given time
+    pub fn is_expired(&self, timestamp: u64) -> bool {
+        self.expires_at != 0 && timestamp >= self.expires_at
+    }
+    pub fn into_builder(self) -> BulkProposalBuilder {
+        BulkProposalBuilder::new()
+            .with_proposal_id(self.proposal_id)
             .with_timestamp(self.timestamp)
+            .with_issuing_agent(self.issuing_agent)
+            .with_receiving_agent(self.receiving_agent)
+            .with_role(self.role)
+            .with_record_ids(self.record_ids)
+            .with_status(self.status)
+            .with_terms(self.terms)
+            .with_expires_at(self.expires_at)
     }
 }
 
 #[derive(Default, Debug)]
-pub struct AssociatedAgentBuilder {
-    agent_id: Option<String>,
+pub struct BulkProposalBuilder {
+    proposal_id: Option<String>,
     timestamp: Option<u64>,
+    issuing_agent: Option<String>,
+    receiving_agent: Option<String>,
+    role: Option<Role>,
+    record_ids: Option<Vec<String>>,
+    status: Option<Status>,
+    terms: Option<String>,
+    expires_at: Option<u64>,
 }
 
-impl AssociatedAgentBuilder {
+impl BulkProposalBuilder {
     pub fn new() -> Self {
-        AssociatedAgentBuilder::default()
+        BulkProposalBuilder::default()
     }
-    pub fn with_agent_id(mut self, value: String) -> Self {
-        self.agent_id = Some(value);
+    pub fn with_proposal_id(mut self, value: String) -> Self {
+        self.proposal_id = Some(value);
         self
     }
     pub fn with_timestamp(mut self, value: u64) -> Self {
         self.timestamp = Some(value);
         self
     }
-    pub fn build(self) -> Result<AssociatedAgent, BuilderError> {
-        let agent_id = self
-            .agent_id
-            .ok_or_else(|| BuilderError::MissingField("agent_id".into()))?;
+    pub fn with_issuing_agent(mut self, value: String) -> Self {
+        self.issuing_agent = Some(value);
+        self
+    }
+    pub fn with_receiving_agent(mut self, value: String) -> Self {
+        self.receiving_agent = Some(value);
+        self
+    }
+    pub fn with_role(mut self, value: Role) -> Self {
+        self.role = Some(value);
+        self
+    }
+    pub fn with_record_ids(mut self, value: Vec<String>) -> Self {
+        self.record_ids = Some(value);
+        self
+    }
+    pub fn with_status(mut self, value: Status) -> Self {
+        self.status = Some(value);
+        self
+    }
+    pub fn with_terms(mut self, value: String) -> Self {
+        self.terms = Some(value);
+        self
+    }
+    pub fn with_expires_at(mut self, value: u64) -> Self {
+        self.expires_at = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<BulkProposal, BuilderError> {
+        let proposal_id = self
+            .proposal_id
+            .ok_or_else(|| BuilderError::MissingField("proposal_id".into()))?;
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
+        let issuing_agent = self
+            .issuing_agent
+            .ok_or_else(|| BuilderError::MissingField("issuing_agent".into()))?;
+        let receiving_agent = self
+            .receiving_agent
+            .ok_or_else(|| BuilderError::MissingField("receiving_agent".into()))?;
+        let role = self
+            .role
+            .ok_or_else(|| BuilderError::MissingField("role".into()))?;
+        let record_ids = self
+            .record_ids
+            .ok_or_else(|| BuilderError::MissingField("record_ids".into()))?;
+        let status = self.status.unwrap_or_default();
+        let terms = self.terms.unwrap_or_default();
+        let expires_at = self.expires_at.unwrap_or_default();
+        Ok(BulkProposal {
+            proposal_id,
+            timestamp,
+            issuing_agent,
+            receiving_agent,
+            role,
+            record_ids,
+            status,
+            terms,
+            expires_at,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::BulkProposal> for BulkProposal {
+    fn from_proto(
+        proto: track_and_trace_state::BulkProposal,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(BulkProposal {
+            proposal_id: proto.get_proposal_id().to_string(),
+            timestamp: proto.get_timestamp(),
+            issuing_agent: proto.get_issuing_agent().to_string(),
+            receiving_agent: proto.get_receiving_agent().to_string(),
+            role: Role::from_proto(proto.get_role())?,
+            record_ids: proto.get_record_ids().to_vec(),
+            status: Status::from_proto(proto.get_status())?,
+            terms: proto.get_terms().to_string(),
+            expires_at: proto.get_expires_at(),
+        })
+    }
+}
+
+impl FromNative<BulkProposal> for track_and_trace_state::BulkProposal {
+    fn from_native(native: BulkProposal) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::BulkProposal::new();
+
+        proto.set_proposal_id(native.proposal_id().to_string());
+        proto.set_timestamp(*native.timestamp());
+        proto.set_issuing_agent(native.issuing_agent().to_string());
+        proto.set_receiving_agent(native.receiving_agent().to_string());
+        proto.set_role(native.role().clone().into_proto()?);
+        proto.set_record_ids(RepeatedField::from_vec(native.record_ids().to_vec()));
+        proto.set_status(native.status().clone().into_proto()?);
+        proto.set_terms(native.terms().to_string());
+        proto.set_expires_at(*native.expires_at());
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<BulkProposal> for BulkProposal {
+    fn from_bytes(bytes: &[u8]) -> Result<BulkProposal, ProtoConversionError> {
+        let proto: track_and_trace_state::BulkProposal = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get BulkProposal from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for BulkProposal {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError("Unable to get BulkProposal from bytes".into())
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<track_and_trace_state::BulkProposal> for BulkProposal {}
+impl IntoNative<BulkProposal> for track_and_trace_state::BulkProposal {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkProposalList {
+    proposals: Vec<BulkProposal>,
+}
+
+impl BulkProposalList {
+    pub fn proposals(&self) -> &[BulkProposal] {
+        &self.proposals
+    }
+
+    pub fn into_builder(self) -> BulkProposalListBuilder {
+        BulkProposalListBuilder::new().with_proposals(self.proposals)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct BulkProposalListBuilder {
+    proposals: Option<Vec<BulkProposal>>,
+}
+
+impl BulkProposalListBuilder {
+    pub fn new() -> Self {
+        BulkProposalListBuilder::default()
+    }
+    pub fn with_proposals(mut self, value: Vec<BulkProposal>) -> Self {
+        self.proposals = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<BulkProposalList, BuilderError> {
+        let proposals = self
+            .proposals
+            .ok_or_else(|| BuilderError::MissingField("proposals".into()))?;
+        Ok(BulkProposalList { proposals })
+    }
+}
+
+impl FromProto<track_and_trace_state::BulkProposalList> for BulkProposalList {
+    fn from_proto(
+        proto: track_and_trace_state::BulkProposalList,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(BulkProposalList {
+            proposals: proto
+                .get_entries()
+                .to_vec()
+                .into_iter()
+                .map(BulkProposal::from_proto)
+                .collect::<Result<Vec<BulkProposal>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<BulkProposalList> for track_and_trace_state::BulkProposalList {
+    fn from_native(native: BulkProposalList) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::BulkProposalList::new();
+        proto.set_entries(RepeatedField::from_vec(
+            native
+                .proposals()
+                .to_vec()
+                .into_iter()
+                .map(BulkProposal::into_proto)
+                .collect::<Result<
+                    Vec<track_and_trace_state::BulkProposal>,
+                    ProtoConversionError,
+                >>()?,
+        ));
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<BulkProposalList> for BulkProposalList {
+    fn from_bytes(bytes: &[u8]) -> Result<BulkProposalList, ProtoConversionError> {
+        let proto: track_and_trace_state::BulkProposalList = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get BulkProposalList from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for BulkProposalList {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get BulkProposalList from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<track_and_trace_state::BulkProposalList> for BulkProposalList {}
+impl IntoNative<BulkProposalList> for track_and_trace_state::BulkProposalList {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssociatedAgent {
+    agent_id: String,
+    timestamp: u64,
+    org_id: String,
+    proposal_issuing_agent: String,
+    proposal_timestamp: u64,
+    bulk_proposal_id: String,
+}
+
+impl AssociatedAgent {
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+    /// The organization the agent belonged to when it was associated
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+    /// The issuing agent of the proposal that handed the role over, empty if the agent was
+    /// associated when the record was created
+    pub fn proposal_issuing_agent(&self) -> &str {
+        &self.proposal_issuing_agent
+    }
+    /// The time the proposal that handed the role over was created
+    pub fn proposal_timestamp(&self) -> &u64 {
+        &self.proposal_timestamp
+    }
+    /// The id of the bulk proposal that handed the role over, empty for a single proposal
+    pub fn bulk_proposal_id(&self) -> &str {
+        &self.bulk_proposal_id
+    }
+
+    pub fn into_builder(self) -> AssociatedAgentBuilder {
+        AssociatedAgentBuilder::new()
+            .with_agent_id(self.agent_id)
+            .with_timestamp(self.timestamp)
+            .with_org_id(self.org_id)
+            .with_proposal_issuing_agent(self.proposal_issuing_agent)
+            .with_proposal_timestamp(self.proposal_timestamp)
+            .with_bulk_proposal_id(self.bulk_proposal_id)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AssociatedAgentBuilder {
+    agent_id: Option<String>,
+    timestamp: Option<u64>,
+    org_id: Option<String>,
+    proposal_issuing_agent: Option<String>,
+    proposal_timestamp: Option<u64>,
+    bulk_proposal_id: Option<String>,
+}
+
+impl AssociatedAgentBuilder {
+    pub fn new() -> Self {
+        AssociatedAgentBuilder::default()
+    }
+    pub fn with_agent_id(mut self, value: String) -> Self {
+        self.agent_id = Some(value);
+        self
+    }
+    pub fn with_timestamp(mut self, value: u64) -> Self {
+        self.timestamp = Some(value);
+        self
+    }
+    pub fn with_org_id(mut self, value: String) -> Self {
+        self.org_id = Some(value);
+        self
+    }
+    pub fn with_proposal_issuing_agent(mut self, value: String) -> Self {
+        self.proposal_issuing_agent = Some(value);
+        self
+    }
+    pub fn with_proposal_timestamp(mut self, value: u64) -> Self {
+        self.proposal_timestamp = Some(value);
+        self
+    }
+    pub fn with_bulk_proposal_id(mut self, value: String) -> Self {
+        self.bulk_proposal_id = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<AssociatedAgent, BuilderError> {
+        let agent_id = self
+            .agent_id
+            .ok_or_else(|| BuilderError::MissingField("agent_id".into()))?;
         let timestamp = self
             .timestamp
             .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
+        let org_id = self.org_id.unwrap_or_default();
+        let proposal_issuing_agent = self.proposal_issuing_agent.unwrap_or_default();
+        let proposal_timestamp = self.proposal_timestamp.unwrap_or_default();
+        let bulk_proposal_id = self.bulk_proposal_id.unwrap_or_default();
         Ok(AssociatedAgent {
             agent_id,
             timestamp,
+            org_id,
+            proposal_issuing_agent,
+            proposal_timestamp,
+            bulk_proposal_id,
         })
     }
 }
@@ -1153,6 +2030,10 @@ impl FromProto<track_and_trace_state::Record_AssociatedAgent> for AssociatedAgen
         Ok(AssociatedAgent {
             agent_id: proto.get_agent_id().to_string(),
             timestamp: proto.get_timestamp(),
+            org_id: proto.get_org_id().to_string(),
+            proposal_issuing_agent: proto.get_proposal_issuing_agent().to_string(),
+            proposal_timestamp: proto.get_proposal_timestamp(),
+            bulk_proposal_id: proto.get_bulk_proposal_id().to_string(),
         })
     }
 }
@@ -1163,6 +2044,10 @@ impl FromNative<AssociatedAgent> for track_and_trace_state::Record_AssociatedAge
 
         proto.set_agent_id(native.agent_id().to_string());
         proto.set_timestamp(*native.timestamp());
+        proto.set_org_id(native.org_id().to_string());
+        proto.set_proposal_issuing_agent(native.proposal_issuing_agent().to_string());
+        proto.set_proposal_timestamp(*native.proposal_timestamp());
+        proto.set_bulk_proposal_id(native.bulk_proposal_id().to_string());
 
         Ok(proto)
     }
@@ -1194,59 +2079,457 @@ impl IntoProto<track_and_trace_state::Record_AssociatedAgent> for AssociatedAgen
 impl IntoNative<AssociatedAgent> for track_and_trace_state::Record_AssociatedAgent {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Record {
-    record_id: String,
-    schema: String,
-    owners: Vec<AssociatedAgent>,
-    custodians: Vec<AssociatedAgent>,
-    field_final: bool,
+pub struct Reopening {
+    issuing_agent: String,
+    approving_agent: String,
+    timestamp: u64,
+    reason: String,
 }
 
-impl Record {
-    pub fn record_id(&self) -> &str {
-        &self.record_id
-    }
-    pub fn schema(&self) -> &str {
-        &self.schema
-    }
-    pub fn owners(&self) -> &[AssociatedAgent] {
-        &self.owners
+impl Reopening {
+    pub fn issuing_agent(&self) -> &str {
+        &self.issuing_agent
     }
-    pub fn custodians(&self) -> &[AssociatedAgent] {
-        &self.custodians
+    pub fn approving_agent(&self) -> &str {
+        &self.approving_agent
     }
-    pub fn field_final(&self) -> &bool {
-        &self.field_final
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
     }
-    pub fn into_builder(self) -> RecordBuilder {
-        RecordBuilder::new()
-            .with_record_id(self.record_id)
-            .with_schema(self.schema)
-            .with_owners(self.owners)
-            .with_custodians(self.custodians)
-            .with_field_final(self.field_final)
+    pub fn reason(&self) -> &str {
+        &self.reason
     }
 }
 
 #[derive(Default, Debug)]
-pub struct RecordBuilder {
-    record_id: Option<String>,
-    schema: Option<String>,
-    owners: Option<Vec<AssociatedAgent>>,
-    custodians: Option<Vec<AssociatedAgent>>,
-    field_final: Option<bool>,
+pub struct ReopeningBuilder {
+    issuing_agent: Option<String>,
+    approving_agent: Option<String>,
+    timestamp: Option<u64>,
+    reason: Option<String>,
 }
 
-impl RecordBuilder {
+impl ReopeningBuilder {
     pub fn new() -> Self {
-        RecordBuilder::default()
+        ReopeningBuilder::default()
     }
-    pub fn with_record_id(mut self, value: String) -> Self {
-        self.record_id = Some(value);
+    pub fn with_issuing_agent(mut self, value: String) -> Self {
+        self.issuing_agent = Some(value);
         self
     }
-    pub fn with_schema(mut self, value: String) -> Self {
-        self.schema = Some(value);
+    pub fn with_approving_agent(mut self, value: String) -> Self {
+        self.approving_agent = Some(value);
+        self
+    }
+    pub fn with_timestamp(mut self, value: u64) -> Self {
+        self.timestamp = Some(value);
+        self
+    }
+    pub fn with_reason(mut self, value: String) -> Self {
+        self.reason = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<Reopening, BuilderError> {
+        let issuing_agent = self
+            .issuing_agent
+            .ok_or_else(|| BuilderError::MissingField("issuing_agent".into()))?;
+        let approving_agent = self
+            .approving_agent
+            .ok_or_else(|| BuilderError::MissingField("approving_agent".into()))?;
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
+        let reason = self.reason.unwrap_or_default();
+        Ok(Reopening {
+            issuing_agent,
+            approving_agent,
+            timestamp,
+            reason,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Record_Reopening> for Reopening {
+    fn from_proto(
+        proto: track_and_trace_state::Record_Reopening,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(Reopening {
+            issuing_agent: proto.get_issuing_agent().to_string(),
+            approving_agent: proto.get_approving_agent().to_string(),
+            timestamp: proto.get_timestamp(),
+            reason: proto.get_reason().to_string(),
+        })
+    }
+}
+
+impl FromNative<Reopening> for track_and_trace_state::Record_Reopening {
+    fn from_native(native: Reopening) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Record_Reopening::new();
+
+        proto.set_issuing_agent(native.issuing_agent().to_string());
+        proto.set_approving_agent(native.approving_agent().to_string());
+        proto.set_timestamp(*native.timestamp());
+        proto.set_reason(native.reason().to_string());
+
+        Ok(proto)
+    }
+}
+
+impl IntoProto<track_and_trace_state::Record_Reopening> for Reopening {}
+impl IntoNative<Reopening> for track_and_trace_state::Record_Reopening {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Excursion {
+    property_name: String,
+    rule_name: String,
+    started_at: u64,
+    raised_at: u64,
+    ended_at: u64,
+    last_value: i64,
+    custodian: String,
+}
+
+impl Excursion {
+    pub fn property_name(&self) -> &str {
+        &self.property_name
+    }
+    pub fn rule_name(&self) -> &str {
+        &self.rule_name
+    }
+    pub fn started_at(&self) -> &u64 {
+        &self.started_at
+    }
+    pub fn raised_at(&self) -> &u64 {
+        &self.raised_at
+    }
+    pub fn ended_at(&self) -> &u64 {
+        &self.ended_at
+    }
+    pub fn last_value(&self) -> &i64 {
+        &self.last_value
+    }
+    pub fn custodian(&self) -> &str {
+        &self.custodian
+    }
+    /// Whether the excursion has been raised and has not ended
+    pub fn is_active(&self) -> bool {
+        self.raised_at != 0 && self.ended_at == 0
+    }
+    pub fn into_builder(self) -> ExcursionBuilder {
+        ExcursionBuilder::new()
+            .with_property_name(self.property_name)
+            .with_rule_name(self.rule_name)
+            .with_started_at(self.started_at)
+            .with_raised(self.raised_at, self.custodian)
+            .with_ended_at(self.ended_at)
+            .with_last_value(self.last_value)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct ExcursionBuilder {
+    property_name: Option<String>,
+    rule_name: Option<String>,
+    started_at: Option<u64>,
+    raised_at: Option<u64>,
+    ended_at: Option<u64>,
+    last_value: Option<i64>,
+    custodian: Option<String>,
+}
+
+impl ExcursionBuilder {
+    pub fn new() -> Self {
+        ExcursionBuilder::default()
+    }
+    pub fn with_property_name(mut self, value: String) -> Self {
+        self.property_name = Some(value);
+        self
+    }
+    pub fn with_rule_name(mut self, value: String) -> Self {
+        self.rule_name = Some(value);
+        self
+    }
+    pub fn with_started_at(mut self, value: u64) -> Self {
+        self.started_at = Some(value);
+        self
+    }
+    /// Sets when the excursion was raised and the custodian of the record at the time
+    pub fn with_raised(mut self, raised_at: u64, custodian: String) -> Self {
+        self.raised_at = Some(raised_at);
+        self.custodian = Some(custodian);
+        self
+    }
+    pub fn with_ended_at(mut self, value: u64) -> Self {
+        self.ended_at = Some(value);
+        self
+    }
+    pub fn with_last_value(mut self, value: i64) -> Self {
+        self.last_value = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<Excursion, BuilderError> {
+        let property_name = self
+            .property_name
+            .ok_or_else(|| BuilderError::MissingField("property_name".into()))?;
+        let rule_name = self
+            .rule_name
+            .ok_or_else(|| BuilderError::MissingField("rule_name".into()))?;
+        let started_at = self
+            .started_at
+            .ok_or_else(|| BuilderError::MissingField("started_at".into()))?;
+        Ok(Excursion {
+            property_name,
+            rule_name,
+            started_at,
+            raised_at: self.raised_at.unwrap_or_default(),
+            ended_at: self.ended_at.unwrap_or_default(),
+            last_value: self.last_value.unwrap_or_default(),
+            custodian: self.custodian.unwrap_or_default(),
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Record_Excursion> for Excursion {
+    fn from_proto(
+        proto: track_and_trace_state::Record_Excursion,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(Excursion {
+            property_name: proto.get_property_name().to_string(),
+            rule_name: proto.get_rule_name().to_string(),
+            started_at: proto.get_started_at(),
+            raised_at: proto.get_raised_at(),
+            ended_at: proto.get_ended_at(),
+            last_value: proto.get_last_value(),
+            custodian: proto.get_custodian().to_string(),
+        })
+    }
+}
+
+impl FromNative<Excursion> for track_and_trace_state::Record_Excursion {
+    fn from_native(native: Excursion) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Record_Excursion::new();
+
+        proto.set_property_name(native.property_name().to_string());
+        proto.set_rule_name(native.rule_name().to_string());
+        proto.set_started_at(*native.started_at());
+        proto.set_raised_at(*native.raised_at());
+        proto.set_ended_at(*native.ended_at());
+        proto.set_last_value(*native.last_value());
+        proto.set_custodian(native.custodian().to_string());
+
+        Ok(proto)
+    }
+}
+
+impl IntoProto<track_and_trace_state::Record_Excursion> for Excursion {}
+impl IntoNative<Excursion> for track_and_trace_state::Record_Excursion {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataKey {
+    agent_id: String,
+    wrapped_key: Vec<u8>,
+    version: u32,
+}
+
+impl DataKey {
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+    pub fn wrapped_key(&self) -> &[u8] {
+        &self.wrapped_key
+    }
+    pub fn version(&self) -> &u32 {
+        &self.version
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct DataKeyBuilder {
+    agent_id: Option<String>,
+    wrapped_key: Option<Vec<u8>>,
+    version: Option<u32>,
+}
+
+impl DataKeyBuilder {
+    pub fn new() -> Self {
+        DataKeyBuilder::default()
+    }
+    pub fn with_agent_id(mut self, value: String) -> Self {
+        self.agent_id = Some(value);
+        self
+    }
+    pub fn with_wrapped_key(mut self, value: Vec<u8>) -> Self {
+        self.wrapped_key = Some(value);
+        self
+    }
+    pub fn with_version(mut self, value: u32) -> Self {
+        self.version = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<DataKey, BuilderError> {
+        let agent_id = self
+            .agent_id
+            .ok_or_else(|| BuilderError::MissingField("agent_id".into()))?;
+        let wrapped_key = match self.wrapped_key {
+            Some(wrapped_key) if !wrapped_key.is_empty() => wrapped_key,
+            _ => return Err(BuilderError::MissingField("wrapped_key".into())),
+        };
+        let version = self.version.unwrap_or_default();
+        Ok(DataKey {
+            agent_id,
+            wrapped_key,
+            version,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Record_DataKey> for DataKey {
+    fn from_proto(
+        proto: track_and_trace_state::Record_DataKey,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(DataKey {
+            agent_id: proto.get_agent_id().to_string(),
+            wrapped_key: proto.get_wrapped_key().to_vec(),
+            version: proto.get_version(),
+        })
+    }
+}
+
+impl FromNative<DataKey> for track_and_trace_state::Record_DataKey {
+    fn from_native(native: DataKey) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Record_DataKey::new();
+
+        proto.set_agent_id(native.agent_id().to_string());
+        proto.set_wrapped_key(native.wrapped_key().to_vec());
+        proto.set_version(*native.version());
+
+        Ok(proto)
+    }
+}
+
+impl IntoProto<track_and_trace_state::Record_DataKey> for DataKey {}
+impl IntoNative<DataKey> for track_and_trace_state::Record_DataKey {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    record_id: String,
+    schema: String,
+    owners: Vec<AssociatedAgent>,
+    custodians: Vec<AssociatedAgent>,
+    field_final: bool,
+    schema_version: u32,
+    parents: Vec<String>,
+    children: Vec<String>,
+    reopenings: Vec<Reopening>,
+    excursions: Vec<Excursion>,
+    data_keys: Vec<DataKey>,
+    data_key_version: u32,
+    data_key_rotation_required: bool,
+}
+
+impl Record {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+    pub fn owners(&self) -> &[AssociatedAgent] {
+        &self.owners
+    }
+    pub fn custodians(&self) -> &[AssociatedAgent] {
+        &self.custodians
+    }
+    pub fn field_final(&self) -> &bool {
+        &self.field_final
+    }
+    pub fn schema_version(&self) -> &u32 {
+        &self.schema_version
+    }
+    pub fn parents(&self) -> &[String] {
+        &self.parents
+    }
+    pub fn children(&self) -> &[String] {
+        &self.children
+    }
+    pub fn reopenings(&self) -> &[Reopening] {
+        &self.reopenings
+    }
+    pub fn excursions(&self) -> &[Excursion] {
+        &self.excursions
+    }
+    /// The excursions that have been raised and have not ended
+    pub fn active_excursions(&self) -> Vec<&Excursion> {
+        self.excursions
+            .iter()
+            .filter(|excursion| excursion.is_active())
+            .collect()
+    }
+    pub fn data_keys(&self) -> &[DataKey] {
+        &self.data_keys
+    }
+    /// The current version of the data key wrapped for an agent, if it has been shared with
+    /// the agent
+    pub fn data_key(&self, agent_id: &str) -> Option<&DataKey> {
+        self.versioned_data_key(agent_id, self.data_key_version)
+    }
+    /// A version of the data key wrapped for an agent, if it has been shared with the agent
+    pub fn versioned_data_key(&self, agent_id: &str, version: u32) -> Option<&DataKey> {
+        self.data_keys
+            .iter()
+            .find(|data_key| data_key.agent_id() == agent_id && *data_key.version() == version)
+    }
+    pub fn data_key_version(&self) -> &u32 {
+        &self.data_key_version
+    }
+    pub fn data_key_rotation_required(&self) -> &bool {
+        &self.data_key_rotation_required
+    }
+    pub fn into_builder(self) -> RecordBuilder {
+        RecordBuilder::new()
+            .with_record_id(self.record_id)
+            .with_schema(self.schema)
+            .with_owners(self.owners)
+            .with_custodians(self.custodians)
+            .with_field_final(self.field_final)
+            .with_schema_version(self.schema_version)
+            .with_parents(self.parents)
+            .with_children(self.children)
+            .with_reopenings(self.reopenings)
+            .with_excursions(self.excursions)
+            .with_data_keys(self.data_keys)
+            .with_data_key_version(self.data_key_version)
+            .with_data_key_rotation_required(self.data_key_rotation_required)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct RecordBuilder {
+    record_id: Option<String>,
+    schema: Option<String>,
+    owners: Option<Vec<AssociatedAgent>>,
+    custodians: Option<Vec<AssociatedAgent>>,
+    field_final: Option<bool>,
+    schema_version: Option<u32>,
+    parents: Option<Vec<String>>,
+    children: Option<Vec<String>>,
+    reopenings: Option<Vec<Reopening>>,
+    excursions: Option<Vec<Excursion>>,
+    data_keys: Option<Vec<DataKey>>,
+    data_key_version: Option<u32>,
+    data_key_rotation_required: Option<bool>,
+}
+
+impl RecordBuilder {
+    pub fn new() -> Self {
+        RecordBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_schema(mut self, value: String) -> Self {
+        self.schema = Some(value);
         self
     }
     pub fn with_owners(mut self, value: Vec<AssociatedAgent>) -> Self {
@@ -1261,6 +2544,38 @@ impl RecordBuilder {
         self.field_final = Some(value);
         self
     }
+    pub fn with_schema_version(mut self, value: u32) -> Self {
+        self.schema_version = Some(value);
+        self
+    }
+    pub fn with_parents(mut self, value: Vec<String>) -> Self {
+        self.parents = Some(value);
+        self
+    }
+    pub fn with_children(mut self, value: Vec<String>) -> Self {
+        self.children = Some(value);
+        self
+    }
+    pub fn with_reopenings(mut self, value: Vec<Reopening>) -> Self {
+        self.reopenings = Some(value);
+        self
+    }
+    pub fn with_excursions(mut self, value: Vec<Excursion>) -> Self {
+        self.excursions = Some(value);
+        self
+    }
+    pub fn with_data_keys(mut self, value: Vec<DataKey>) -> Self {
+        self.data_keys = Some(value);
+        self
+    }
+    pub fn with_data_key_version(mut self, value: u32) -> Self {
+        self.data_key_version = Some(value);
+        self
+    }
+    pub fn with_data_key_rotation_required(mut self, value: bool) -> Self {
+        self.data_key_rotation_required = Some(value);
+        self
+    }
     pub fn build(self) -> Result<Record, BuilderError> {
         let record_id = self
             .record_id
@@ -1277,12 +2592,28 @@ impl RecordBuilder {
         let field_final = self
             .field_final
             .ok_or_else(|| BuilderError::MissingField("field_final".into()))?;
+        let schema_version = self.schema_version.unwrap_or_default();
+        let parents = self.parents.unwrap_or_default();
+        let children = self.children.unwrap_or_default();
+        let reopenings = self.reopenings.unwrap_or_default();
+        let excursions = self.excursions.unwrap_or_default();
+        let data_keys = self.data_keys.unwrap_or_default();
+        let data_key_version = self.data_key_version.unwrap_or_default();
+        let data_key_rotation_required = self.data_key_rotation_required.unwrap_or_default();
         Ok(Record {
             record_id,
             schema,
             owners,
             custodians,
             field_final,
+            schema_version,
+            parents,
+            children,
+            reopenings,
+            excursions,
+            data_keys,
+            data_key_version,
+            data_key_rotation_required,
         })
     }
 }
@@ -1305,6 +2636,29 @@ impl FromProto<track_and_trace_state::Record> for Record {
                 .map(AssociatedAgent::from_proto)
                 .collect::<Result<Vec<AssociatedAgent>, ProtoConversionError>>()?,
             field_final: proto.get_field_final(),
+            schema_version: proto.get_schema_version(),
+            parents: proto.get_parents().to_vec(),
+            children: proto.get_children().to_vec(),
+            reopenings: proto
+                .get_reopenings()
+                .to_vec()
+                .into_iter()
+                .map(Reopening::from_proto)
+                .collect::<Result<Vec<Reopening>, ProtoConversionError>>()?,
+            excursions: proto
+                .get_excursions()
+                .to_vec()
+                .into_iter()
+                .map(Excursion::from_proto)
+                .collect::<Result<Vec<Excursion>, ProtoConversionError>>()?,
+            data_keys: proto
+                .get_data_keys()
+                .to_vec()
+                .into_iter()
+                .map(DataKey::from_proto)
+                .collect::<Result<Vec<DataKey>, ProtoConversionError>>()?,
+            data_key_version: proto.get_data_key_version(),
+            data_key_rotation_required: proto.get_data_key_rotation_required(),
         })
     }
 }
@@ -1341,118 +2695,627 @@ impl FromNative<Record> for track_and_trace_state::Record {
             ),
         );
         proto.set_field_final(*native.field_final());
+        proto.set_schema_version(*native.schema_version());
+        proto.set_parents(RepeatedField::from_vec(native.parents().to_vec()));
+        proto.set_children(RepeatedField::from_vec(native.children().to_vec()));
+        proto.set_reopenings(
+            RepeatedField::from_vec(
+                native
+                    .reopenings()
+                    .to_vec()
+                    .into_iter()
+                    .map(Reopening::into_proto)
+                    .collect::<Result<
+                        Vec<track_and_trace_state::Record_Reopening>,
+                        ProtoConversionError,
+                    >>()?,
+            ),
+        );
+        proto.set_excursions(
+            RepeatedField::from_vec(
+                native
+                    .excursions()
+                    .to_vec()
+                    .into_iter()
+                    .map(Excursion::into_proto)
+                    .collect::<Result<
+                        Vec<track_and_trace_state::Record_Excursion>,
+                        ProtoConversionError,
+                    >>()?,
+            ),
+        );
+        proto.set_data_keys(
+            RepeatedField::from_vec(
+                native
+                    .data_keys()
+                    .to_vec()
+                    .into_iter()
+                    .map(DataKey::into_proto)
+                    .collect::<Result<
+                        Vec<track_and_trace_state::Record_DataKey>,
+                        ProtoConversionError,
+                    >>()?,
+            ),
+        );
+        proto.set_data_key_version(*native.data_key_version());
+        proto.set_data_key_rotation_required(*native.data_key_rotation_required());
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<Record> for Record {
+    fn from_bytes(bytes: &[u8]) -> Result<Record, ProtoConversionError> {
+        let proto: track_and_trace_state::Record =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError("Unable to get Record from bytes".into())
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for Record {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError("Unable to get Record from bytes".into())
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_state::Record> for Record {}
+impl IntoNative<Record> for track_and_trace_state::Record {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordList {
+    records: Vec<Record>,
+}
+
+impl RecordList {
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    pub fn into_builder(self) -> RecordListBuilder {
+        RecordListBuilder::new().with_records(self.records)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct RecordListBuilder {
+    records: Option<Vec<Record>>,
+}
+
+impl RecordListBuilder {
+    pub fn new() -> Self {
+        RecordListBuilder::default()
+    }
+    pub fn with_records(mut self, value: Vec<Record>) -> Self {
+        self.records = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<RecordList, BuilderError> {
+        let records = self
+            .records
+            .ok_or_else(|| BuilderError::MissingField("records".into()))?;
+        Ok(RecordList { records })
+    }
+}
+
+impl FromProto<track_and_trace_state::RecordList> for RecordList {
+    fn from_proto(proto: track_and_trace_state::RecordList) -> Result<Self, ProtoConversionError> {
+        Ok(RecordList {
+            records: proto
+                .get_entries()
+                .to_vec()
+                .into_iter()
+                .map(Record::from_proto)
+                .collect::<Result<Vec<Record>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<RecordList> for track_and_trace_state::RecordList {
+    fn from_native(native: RecordList) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::RecordList::new();
+        proto.set_entries(RepeatedField::from_vec(
+            native
+                .records()
+                .to_vec()
+                .into_iter()
+                .map(Record::into_proto)
+                .collect::<Result<Vec<track_and_trace_state::Record>, ProtoConversionError>>()?,
+        ));
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<RecordList> for RecordList {
+    fn from_bytes(bytes: &[u8]) -> Result<RecordList, ProtoConversionError> {
+        let proto: track_and_trace_state::RecordList =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError("Unable to get Record from bytes".into())
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for RecordList {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError("Unable to get Record from bytes".into())
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_state::RecordList> for RecordList {}
+impl IntoNative<RecordList> for track_and_trace_state::RecordList {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Default for Severity {
+    fn default() -> Severity {
+        Severity::Low
+    }
+}
+
+impl FromProto<track_and_trace_state::Recall_Severity> for Severity {
+    fn from_proto(
+        severity: track_and_trace_state::Recall_Severity,
+    ) -> Result<Self, ProtoConversionError> {
+        match severity {
+            track_and_trace_state::Recall_Severity::LOW => Ok(Severity::Low),
+            track_and_trace_state::Recall_Severity::MEDIUM => Ok(Severity::Medium),
+            track_and_trace_state::Recall_Severity::HIGH => Ok(Severity::High),
+        }
+    }
+}
+
+impl FromNative<Severity> for track_and_trace_state::Recall_Severity {
+    fn from_native(severity: Severity) -> Result<Self, ProtoConversionError> {
+        match severity {
+            Severity::Low => Ok(track_and_trace_state::Recall_Severity::LOW),
+            Severity::Medium => Ok(track_and_trace_state::Recall_Severity::MEDIUM),
+            Severity::High => Ok(track_and_trace_state::Recall_Severity::HIGH),
+        }
+    }
+}
+
+impl IntoProto<track_and_trace_state::Recall_Severity> for Severity {}
+impl IntoNative<Severity> for track_and_trace_state::Recall_Severity {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecallStatus {
+    Open,
+    Closed,
+}
+
+impl Default for RecallStatus {
+    fn default() -> RecallStatus {
+        RecallStatus::Open
+    }
+}
+
+impl FromProto<track_and_trace_state::Recall_Status> for RecallStatus {
+    fn from_proto(
+        status: track_and_trace_state::Recall_Status,
+    ) -> Result<Self, ProtoConversionError> {
+        match status {
+            track_and_trace_state::Recall_Status::OPEN => Ok(RecallStatus::Open),
+            track_and_trace_state::Recall_Status::CLOSED => Ok(RecallStatus::Closed),
+        }
+    }
+}
+
+impl FromNative<RecallStatus> for track_and_trace_state::Recall_Status {
+    fn from_native(status: RecallStatus) -> Result<Self, ProtoConversionError> {
+        match status {
+            RecallStatus::Open => Ok(track_and_trace_state::Recall_Status::OPEN),
+            RecallStatus::Closed => Ok(track_and_trace_state::Recall_Status::CLOSED),
+        }
+    }
+}
+
+impl IntoProto<track_and_trace_state::Recall_Status> for RecallStatus {}
+impl IntoNative<RecallStatus> for track_and_trace_state::Recall_Status {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Acknowledgement {
+    record_id: String,
+    agent_id: String,
+    timestamp: u64,
+}
+
+impl Acknowledgement {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AcknowledgementBuilder {
+    record_id: Option<String>,
+    agent_id: Option<String>,
+    timestamp: Option<u64>,
+}
+
+impl AcknowledgementBuilder {
+    pub fn new() -> Self {
+        AcknowledgementBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_agent_id(mut self, value: String) -> Self {
+        self.agent_id = Some(value);
+        self
+    }
+    pub fn with_timestamp(mut self, value: u64) -> Self {
+        self.timestamp = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<Acknowledgement, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let agent_id = self
+            .agent_id
+            .ok_or_else(|| BuilderError::MissingField("agent_id".into()))?;
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
+        Ok(Acknowledgement {
+            record_id,
+            agent_id,
+            timestamp,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Recall_Acknowledgement> for Acknowledgement {
+    fn from_proto(
+        proto: track_and_trace_state::Recall_Acknowledgement,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(Acknowledgement {
+            record_id: proto.get_record_id().to_string(),
+            agent_id: proto.get_agent_id().to_string(),
+            timestamp: proto.get_timestamp(),
+        })
+    }
+}
+
+impl FromNative<Acknowledgement> for track_and_trace_state::Recall_Acknowledgement {
+    fn from_native(native: Acknowledgement) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Recall_Acknowledgement::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_agent_id(native.agent_id().to_string());
+        proto.set_timestamp(*native.timestamp());
+        Ok(proto)
+    }
+}
+
+impl IntoProto<track_and_trace_state::Recall_Acknowledgement> for Acknowledgement {}
+impl IntoNative<Acknowledgement> for track_and_trace_state::Recall_Acknowledgement {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recall {
+    recall_id: String,
+    issuing_agent: String,
+    product_id: String,
+    record_ids: Vec<String>,
+    reason: String,
+    severity: Severity,
+    status: RecallStatus,
+    timestamp: u64,
+    acknowledgements: Vec<Acknowledgement>,
+}
+
+impl Recall {
+    pub fn recall_id(&self) -> &str {
+        &self.recall_id
+    }
+    pub fn issuing_agent(&self) -> &str {
+        &self.issuing_agent
+    }
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+    pub fn record_ids(&self) -> &[String] {
+        &self.record_ids
+    }
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+    pub fn severity(&self) -> &Severity {
+        &self.severity
+    }
+    pub fn status(&self) -> &RecallStatus {
+        &self.status
+    }
+    pub fn timestamp(&self) -> &u64 {
+        &self.timestamp
+    }
+    pub fn acknowledgements(&self) -> &[Acknowledgement] {
+        &self.acknowledgements
+    }
+    pub fn into_builder(self) -> RecallBuilder {
+        RecallBuilder::new()
+            .with_recall_id(self.recall_id)
+            .with_issuing_agent(self.issuing_agent)
+            .with_product_id(self.product_id)
+            .with_record_ids(self.record_ids)
+            .with_reason(self.reason)
+            .with_severity(self.severity)
+            .with_status(self.status)
+            .with_timestamp(self.timestamp)
+            .with_acknowledgements(self.acknowledgements)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct RecallBuilder {
+    recall_id: Option<String>,
+    issuing_agent: Option<String>,
+    product_id: Option<String>,
+    record_ids: Option<Vec<String>>,
+    reason: Option<String>,
+    severity: Option<Severity>,
+    status: Option<RecallStatus>,
+    timestamp: Option<u64>,
+    acknowledgements: Option<Vec<Acknowledgement>>,
+}
+
+impl RecallBuilder {
+    pub fn new() -> Self {
+        RecallBuilder::default()
+    }
+    pub fn with_recall_id(mut self, value: String) -> Self {
+        self.recall_id = Some(value);
+        self
+    }
+    pub fn with_issuing_agent(mut self, value: String) -> Self {
+        self.issuing_agent = Some(value);
+        self
+    }
+    pub fn with_product_id(mut self, value: String) -> Self {
+        self.product_id = Some(value);
+        self
+    }
+    pub fn with_record_ids(mut self, value: Vec<String>) -> Self {
+        self.record_ids = Some(value);
+        self
+    }
+    pub fn with_reason(mut self, value: String) -> Self {
+        self.reason = Some(value);
+        self
+    }
+    pub fn with_severity(mut self, value: Severity) -> Self {
+        self.severity = Some(value);
+        self
+    }
+    pub fn with_status(mut self, value: RecallStatus) -> Self {
+        self.status = Some(value);
+        self
+    }
+    pub fn with_timestamp(mut self, value: u64) -> Self {
+        self.timestamp = Some(value);
+        self
+    }
+    pub fn with_acknowledgements(mut self, value: Vec<Acknowledgement>) -> Self {
+        self.acknowledgements = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<Recall, BuilderError> {
+        let recall_id = self
+            .recall_id
+            .ok_or_else(|| BuilderError::MissingField("recall_id".into()))?;
+        let issuing_agent = self
+            .issuing_agent
+            .ok_or_else(|| BuilderError::MissingField("issuing_agent".into()))?;
+        let product_id = self.product_id.unwrap_or_default();
+        let record_ids = self.record_ids.unwrap_or_default();
+        if product_id.is_empty() && record_ids.is_empty() {
+            return Err(BuilderError::MissingField(
+                "product_id or record_ids".into(),
+            ));
+        }
+        let reason = self
+            .reason
+            .ok_or_else(|| BuilderError::MissingField("reason".into()))?;
+        let severity = self.severity.unwrap_or_default();
+        let status = self.status.unwrap_or_default();
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("timestamp".into()))?;
+        let acknowledgements = self.acknowledgements.unwrap_or_default();
+        Ok(Recall {
+            recall_id,
+            issuing_agent,
+            product_id,
+            record_ids,
+            reason,
+            severity,
+            status,
+            timestamp,
+            acknowledgements,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Recall> for Recall {
+    fn from_proto(proto: track_and_trace_state::Recall) -> Result<Self, ProtoConversionError> {
+        Ok(Recall {
+            recall_id: proto.get_recall_id().to_string(),
+            issuing_agent: proto.get_issuing_agent().to_string(),
+            product_id: proto.get_product_id().to_string(),
+            record_ids: proto.get_record_ids().to_vec(),
+            reason: proto.get_reason().to_string(),
+            severity: Severity::from_proto(proto.get_severity())?,
+            status: RecallStatus::from_proto(proto.get_status())?,
+            timestamp: proto.get_timestamp(),
+            acknowledgements: proto
+                .get_acknowledgements()
+                .to_vec()
+                .into_iter()
+                .map(Acknowledgement::from_proto)
+                .collect::<Result<Vec<Acknowledgement>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<Recall> for track_and_trace_state::Recall {
+    fn from_native(native: Recall) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Recall::new();
+
+        proto.set_recall_id(native.recall_id().to_string());
+        proto.set_issuing_agent(native.issuing_agent().to_string());
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_record_ids(RepeatedField::from_vec(native.record_ids().to_vec()));
+        proto.set_reason(native.reason().to_string());
+        proto.set_severity(native.severity().clone().into_proto()?);
+        proto.set_status(native.status().clone().into_proto()?);
+        proto.set_timestamp(*native.timestamp());
+        proto.set_acknowledgements(RepeatedField::from_vec(
+            native
+                .acknowledgements()
+                .to_vec()
+                .into_iter()
+                .map(Acknowledgement::into_proto)
+                .collect::<Result<
+                    Vec<track_and_trace_state::Recall_Acknowledgement>,
+                    ProtoConversionError,
+                >>()?,
+        ));
 
         Ok(proto)
     }
 }
 
-impl FromBytes<Record> for Record {
-    fn from_bytes(bytes: &[u8]) -> Result<Record, ProtoConversionError> {
-        let proto: track_and_trace_state::Record =
+impl FromBytes<Recall> for Recall {
+    fn from_bytes(bytes: &[u8]) -> Result<Recall, ProtoConversionError> {
+        let proto: track_and_trace_state::Recall =
             protobuf::parse_from_bytes(bytes).map_err(|_| {
-                ProtoConversionError::SerializationError("Unable to get Record from bytes".into())
+                ProtoConversionError::SerializationError("Unable to get Recall from bytes".into())
             })?;
         proto.into_native()
     }
 }
-impl IntoBytes for Record {
+
+impl IntoBytes for Recall {
     fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
         let proto = self.into_proto()?;
         let bytes = proto.write_to_bytes().map_err(|_| {
-            ProtoConversionError::SerializationError("Unable to get Record from bytes".into())
+            ProtoConversionError::SerializationError("Unable to get Recall from bytes".into())
         })?;
         Ok(bytes)
     }
 }
-impl IntoProto<track_and_trace_state::Record> for Record {}
-impl IntoNative<Record> for track_and_trace_state::Record {}
+
+impl IntoProto<track_and_trace_state::Recall> for Recall {}
+impl IntoNative<Recall> for track_and_trace_state::Recall {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct RecordList {
-    records: Vec<Record>,
+pub struct RecallList {
+    recalls: Vec<Recall>,
 }
 
-impl RecordList {
-    pub fn records(&self) -> &[Record] {
-        &self.records
+impl RecallList {
+    pub fn recalls(&self) -> &[Recall] {
+        &self.recalls
     }
 
-    pub fn into_builder(self) -> RecordListBuilder {
-        RecordListBuilder::new().with_records(self.records)
+    pub fn into_builder(self) -> RecallListBuilder {
+        RecallListBuilder::new().with_recalls(self.recalls)
     }
 }
 
 #[derive(Default, Debug)]
-pub struct RecordListBuilder {
-    records: Option<Vec<Record>>,
+pub struct RecallListBuilder {
+    recalls: Option<Vec<Recall>>,
 }
 
-impl RecordListBuilder {
+impl RecallListBuilder {
     pub fn new() -> Self {
-        RecordListBuilder::default()
+        RecallListBuilder::default()
     }
-    pub fn with_records(mut self, value: Vec<Record>) -> Self {
-        self.records = Some(value);
+    pub fn with_recalls(mut self, value: Vec<Recall>) -> Self {
+        self.recalls = Some(value);
         self
     }
-    pub fn build(self) -> Result<RecordList, BuilderError> {
-        let records = self
-            .records
-            .ok_or_else(|| BuilderError::MissingField("records".into()))?;
-        Ok(RecordList { records })
+    pub fn build(self) -> Result<RecallList, BuilderError> {
+        let recalls = self
+            .recalls
+            .ok_or_else(|| BuilderError::MissingField("recalls".into()))?;
+        Ok(RecallList { recalls })
     }
 }
 
-impl FromProto<track_and_trace_state::RecordList> for RecordList {
-    fn from_proto(proto: track_and_trace_state::RecordList) -> Result<Self, ProtoConversionError> {
-        Ok(RecordList {
-            records: proto
+impl FromProto<track_and_trace_state::RecallList> for RecallList {
+    fn from_proto(proto: track_and_trace_state::RecallList) -> Result<Self, ProtoConversionError> {
+        Ok(RecallList {
+            recalls: proto
                 .get_entries()
                 .to_vec()
                 .into_iter()
-                .map(Record::from_proto)
-                .collect::<Result<Vec<Record>, ProtoConversionError>>()?,
+                .map(Recall::from_proto)
+                .collect::<Result<Vec<Recall>, ProtoConversionError>>()?,
         })
     }
 }
 
-impl FromNative<RecordList> for track_and_trace_state::RecordList {
-    fn from_native(native: RecordList) -> Result<Self, ProtoConversionError> {
-        let mut proto = track_and_trace_state::RecordList::new();
+impl FromNative<RecallList> for track_and_trace_state::RecallList {
+    fn from_native(native: RecallList) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::RecallList::new();
         proto.set_entries(RepeatedField::from_vec(
             native
-                .records()
+                .recalls()
                 .to_vec()
                 .into_iter()
-                .map(Record::into_proto)
-                .collect::<Result<Vec<track_and_trace_state::Record>, ProtoConversionError>>()?,
+                .map(Recall::into_proto)
+                .collect::<Result<Vec<track_and_trace_state::Recall>, ProtoConversionError>>()?,
         ));
 
         Ok(proto)
     }
 }
 
-impl FromBytes<RecordList> for RecordList {
-    fn from_bytes(bytes: &[u8]) -> Result<RecordList, ProtoConversionError> {
-        let proto: track_and_trace_state::RecordList =
-            protobuf::parse_from_bytes(bytes).map_err(|_| {
-                ProtoConversionError::SerializationError("Unable to get Record from bytes".into())
+impl FromBytes<RecallList> for RecallList {
+    fn from_bytes(bytes: &[u8]) -> Result<RecallList, ProtoConversionError> {
+        let proto: track_and_trace_state::RecallList = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get RecallList from bytes".into(),
+                )
             })?;
         proto.into_native()
     }
 }
 
-impl IntoBytes for RecordList {
+impl IntoBytes for RecallList {
     fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
         let proto = self.into_proto()?;
         let bytes = proto.write_to_bytes().map_err(|_| {
-            ProtoConversionError::SerializationError("Unable to get Record from bytes".into())
+            ProtoConversionError::SerializationError("Unable to get RecallList from bytes".into())
         })?;
         Ok(bytes)
     }
 }
-impl IntoProto<track_and_trace_state::RecordList> for RecordList {}
-impl IntoNative<RecordList> for track_and_trace_state::RecordList {}
+
+impl IntoProto<track_and_trace_state::RecallList> for RecallList {}
+impl IntoNative<RecallList> for track_and_trace_state::RecallList {}
 
 #[cfg(test)]
 mod tests {
@@ -1517,6 +3380,34 @@ mod tests {
         test_from_bytes(original, Reporter::from_bytes);
     }
 
+    #[test]
+    fn test_reporter_authorizations_bytes() {
+        let revoked = ReporterAuthorizationBuilder::new()
+            .with_granted_at(1)
+            .with_granted_by("owner".to_string())
+            .with_revoked(5, "owner".to_string())
+            .build()
+            .unwrap();
+        let open = ReporterAuthorizationBuilder::new()
+            .with_granted_at(9)
+            .with_granted_by("owner".to_string())
+            .build()
+            .unwrap();
+        let original = ReporterBuilder::new()
+            .with_public_key("1234".to_string())
+            .with_authorized(true)
+            .with_index(0)
+            .with_authorizations(vec![revoked, open])
+            .build()
+            .unwrap();
+
+        assert!(original.authorized_at(3));
+        assert!(!original.authorized_at(7));
+        assert!(original.authorized_at(10));
+
+        test_from_bytes(original, Reporter::from_bytes);
+    }
+
     #[test]
     fn test_property_builder() {
         let property_definition = PropertyDefinitionBuilder::new()
@@ -1935,6 +3826,69 @@ mod tests {
         assert_eq!(proposal.terms(), "a term");
     }
 
+    #[test]
+    fn test_proposal_expiry() {
+        let proposal = ProposalBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_timestamp(214)
+            .with_issuing_agent("james".into())
+            .with_receiving_agent("joe".into())
+            .with_role(Role::Owner)
+            .with_properties(vec![])
+            .with_status(Status::Open)
+            .with_terms("a term".into())
+            .build()
+            .unwrap();
+        assert_eq!(*proposal.expires_at(), 0);
+        assert!(!proposal.is_expired(u64::max_value()));
+
+        let proposal = proposal.into_builder().with_expires_at(300).build().unwrap();
+        assert!(!proposal.is_expired(299));
+        assert!(proposal.is_expired(300));
+
+        test_from_bytes(proposal, Proposal::from_bytes);
+    }
+
+    #[test]
+    fn test_proposal_conditions_bytes() {
+        let range = ConditionBuilder::new()
+            .with_condition_type(ConditionType::PropertyRange)
+            .with_property_range("temperature".into(), 2, 8)
+            .build()
+            .unwrap();
+        let counter_signature = ConditionBuilder::new()
+            .with_condition_type(ConditionType::CounterSignature)
+            .with_org_id("customs".into())
+            .build()
+            .unwrap();
+
+        let proposal = ProposalBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_timestamp(214)
+            .with_issuing_agent("james".into())
+            .with_receiving_agent("joe".into())
+            .with_role(Role::Custodian)
+            .with_properties(vec![])
+            .with_status(Status::Open)
+            .with_terms("".into())
+            .with_conditions(vec![range, counter_signature])
+            .with_counter_signatures(vec![CounterSignatureBuilder::new()
+                .with_agent_id("jane".into())
+                .with_timestamp(220)
+                .build()
+                .unwrap()])
+            .build()
+            .unwrap();
+
+        assert_eq!(proposal.conditions().len(), 2);
+        assert!(ConditionBuilder::new()
+            .with_condition_type(ConditionType::CounterSignature)
+            .build()
+            .is_err());
+
+        test_from_bytes(proposal, Proposal::from_bytes);
+    }
+
     #[test]
     fn test_proposal_into_builder() {
         let proposal = ProposalBuilder::new()
@@ -2116,6 +4070,31 @@ mod tests {
         test_from_bytes(record, Record::from_bytes);
     }
 
+    #[test]
+    fn test_record_lineage_bytes() {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_timestamp(2132)
+            .build()
+            .unwrap();
+
+        let record = RecordBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_schema("egg".into())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent.clone()])
+            .with_field_final(false)
+            .with_parents(vec!["carton1".into(), "carton2".into()])
+            .with_children(vec!["egg1234-a".into()])
+            .build()
+            .unwrap();
+
+        assert_eq!(record.parents(), ["carton1", "carton2"]);
+        assert_eq!(record.children(), ["egg1234-a"]);
+
+        test_from_bytes(record, Record::from_bytes);
+    }
+
     #[test]
     fn test_record_list() {
         let associated_agent = AssociatedAgentBuilder::new()
@@ -2206,4 +4185,179 @@ mod tests {
         assert_eq!(builder.agent_id, Some("agent1234".to_string()));
         assert_eq!(builder.timestamp, Some(2132));
     }
+
+    #[test]
+    fn test_record_reopenings_bytes() {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_timestamp(2132)
+            .build()
+            .unwrap();
+
+        let reopening = ReopeningBuilder::new()
+            .with_issuing_agent("agent1234".into())
+            .with_approving_agent("agent5678".into())
+            .with_timestamp(2140)
+            .with_reason("finalized too early".into())
+            .build()
+            .unwrap();
+
+        let record = RecordBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_schema("egg".into())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent.clone()])
+            .with_field_final(false)
+            .with_reopenings(vec![reopening.clone()])
+            .build()
+            .unwrap();
+
+        assert_eq!(record.reopenings(), &[reopening][..]);
+
+        test_from_bytes(record, Record::from_bytes);
+    }
+
+    #[test]
+    fn test_record_excursions_bytes() {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_timestamp(2132)
+            .build()
+            .unwrap();
+
+        let ended = ExcursionBuilder::new()
+            .with_property_name("temperature".into())
+            .with_rule_name("cold_chain".into())
+            .with_started_at(2140)
+            .with_raised(2150, "agent1234".into())
+            .with_ended_at(2160)
+            .with_last_value(9)
+            .build()
+            .unwrap();
+        let active = ExcursionBuilder::new()
+            .with_property_name("temperature".into())
+            .with_rule_name("cold_chain".into())
+            .with_started_at(2170)
+            .with_raised(2170, "agent1234".into())
+            .with_last_value(12)
+            .build()
+            .unwrap();
+
+        let record = RecordBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_schema("egg".into())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent])
+            .with_field_final(false)
+            .with_excursions(vec![ended, active.clone()])
+            .build()
+            .unwrap();
+
+        assert_eq!(record.active_excursions(), vec![&active]);
+
+        test_from_bytes(record, Record::from_bytes);
+    }
+
+    #[test]
+    // Test that a record with data keys can be converted to bytes and back
+    fn test_record_data_keys_bytes() {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_timestamp(2132)
+            .build()
+            .unwrap();
+
+        let data_key = DataKeyBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_wrapped_key(vec![1, 2, 3, 4])
+            .build()
+            .unwrap();
+        assert!(DataKeyBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_wrapped_key(vec![])
+            .build()
+            .is_err());
+
+        let rotated_data_key = DataKeyBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_wrapped_key(vec![5, 6, 7, 8])
+            .with_version(1)
+            .build()
+            .unwrap();
+
+        let record = RecordBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_schema("egg".into())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent])
+            .with_field_final(false)
+            .with_data_keys(vec![data_key.clone(), rotated_data_key.clone()])
+            .with_data_key_version(1)
+            .with_data_key_rotation_required(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(record.data_key("agent1234"), Some(&rotated_data_key));
+        assert_eq!(record.versioned_data_key("agent1234", 0), Some(&data_key));
+        assert_eq!(record.data_key("agent5678"), None);
+
+        test_from_bytes(record, Record::from_bytes);
+    }
+
+    #[test]
+    fn test_bulk_proposal_bytes() {
+        let proposal = BulkProposalBuilder::new()
+            .with_proposal_id("shipment1234".into())
+            .with_timestamp(214)
+            .with_issuing_agent("james".into())
+            .with_receiving_agent("joe".into())
+            .with_role(Role::Custodian)
+            .with_record_ids(vec!["egg1234".into(), "egg5678".into()])
+            .with_expires_at(300)
+            .build()
+            .unwrap();
+
+        assert_eq!(*proposal.status(), Status::Open);
+        assert_eq!(proposal.terms(), "");
+        assert!(proposal.is_expired(300));
+
+        let proposal_list = BulkProposalListBuilder::new()
+            .with_proposals(vec![proposal.clone()])
+            .build()
+            .unwrap();
+
+        test_from_bytes(proposal, BulkProposal::from_bytes);
+        test_from_bytes(proposal_list, BulkProposalList::from_bytes);
+    }
+
+    #[test]
+    fn test_recall_bytes() {
+        let acknowledgement = AcknowledgementBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_agent_id("agent1234".into())
+            .with_timestamp(2133)
+            .build()
+            .unwrap();
+
+        let recall = RecallBuilder::new()
+            .with_recall_id("recall1".into())
+            .with_issuing_agent("agent1234".into())
+            .with_product_id("688955434684".into())
+            .with_record_ids(vec!["egg1234".into()])
+            .with_reason("salmonella".into())
+            .with_severity(Severity::High)
+            .with_timestamp(2132)
+            .with_acknowledgements(vec![acknowledgement])
+            .build()
+            .unwrap();
+
+        assert_eq!(*recall.status(), RecallStatus::Open);
+
+        let recall_list = RecallListBuilder::new()
+            .with_recalls(vec![recall])
+            .build()
+            .unwrap();
+
+        test_from_bytes(recall_list, RecallList::from_bytes);
+    }
 }
@@ -17,10 +17,19 @@ cfg_if! {
 }
 
 use dgc_config::permissions::PermissionChecker;
+use dgc_config::protocol::pike::state::Agent;
 use dgc_config::protocol::product::payload::{
-    Action, ProductCreateAction, ProductDeleteAction, ProductPayload, ProductUpdateAction,
+    Action, PatchOperation, ProductAnswerProposalAction, ProductCreateAction,
+    ProductCreateProposalAction, ProductDeleteAction, ProductPatchAction, ProductPayload,
+    ProductUpdateAction, PropertyPatch, Response,
+};
+use dgc_config::protocol::product::state::{
+    Product, ProductBuilder, ProductProposalBuilder, ProductProposalListBuilder,
+    ProductRevisionBuilder, ProductType, RevisionChange, Status,
+};
+use dgc_config::protocol::schema::state::{
+    DataType, PropertyDefinition, PropertyValue, ReferenceKind,
 };
-use dgc_config::protocol::product::state::{ProductBuilder, ProductType};
 
 use dgc_config::protos::FromBytes;
 
@@ -28,7 +37,12 @@ use dgc_config::protos::FromBytes;
 use dgc_config::addressing::*;
 use crate::payload::validate_payload;
 use crate::state::ProductState;
-use crate::validation::validate_gtin;
+use crate::validation::{
+    gtin_has_company_prefix, parse_gs1_company_prefixes, validate_gtin,
+    validate_immutable_properties, validate_properties,
+};
+
+const PRODUCT_REVISION_MAX_LENGTH: usize = 32;
 
 #[cfg(target_arch = "wasm32")]
 // Sabre apply must return a bool
@@ -64,7 +78,7 @@ impl ProductTransactionHandler {
         ProductTransactionHandler {
             family_name: PRODUCT_FAMILY_NAME.to_string(),
             family_versions: vec![PRODUCT_FAMILY_VERSION.to_string()],
-            namespaces: vec![get_product_prefix()],
+            namespaces: vec![get_product_prefix(), get_product_proposal_prefix()],
         }
     }
 
@@ -74,11 +88,13 @@ impl ProductTransactionHandler {
         state: &mut ProductState,
         signer: &str,
         perm_checker: &PermissionChecker,
+        timestamp: u64,
     ) -> Result<(), ApplyError> {
         let product_id = payload.product_id();
         let owner = payload.owner();
         let product_type = payload.product_type();
         let properties = payload.properties();
+        let schema_name = payload.schema();
 
         // Check that the agent submitting the transactions exists in state
         let agent = match state.get_agent(signer)? {
@@ -133,6 +149,15 @@ impl ProductTransactionHandler {
             }
         };
 
+        // Check that the agent is creating the product for its own organization
+        if owner != agent.org_id() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "The signing Agent does not belong to the product owner organization: {} != {}",
+                agent.org_id(),
+                owner,
+            )));
+        }
+
         /* Check if the agents organization contain GS1 Company Prefix key in its metadata
         (gs1_company_prefixes), and the prefix must match the company prefix in the product_id */
         let gs1_company_prefix_vec = org.metadata().to_vec();
@@ -148,26 +173,32 @@ impl ProductTransactionHandler {
                 )));
             }
         };
-        // If the 'gs1_company_prefixes' key is found
-        if gs1_company_prefix_kv.key().is_empty() {
-            // If the gtin identifer does not contain the organizations gs1 prefix
-            if !product_id.contains(gs1_company_prefix_kv.value()) {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "The agents organization does not own the GS1 company prefix in the GTIN product_id: {:?}",
-                    org.metadata()
-                )));
-            }
+        // The 'gs1_company_prefixes' value is a comma separated list of prefixes
+        let gs1_company_prefixes = parse_gs1_company_prefixes(gs1_company_prefix_kv.value());
+        if !gs1_company_prefixes
+            .iter()
+            .any(|prefix| gtin_has_company_prefix(product_id, prefix))
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "The agents organization does not own the GS1 company prefix in the GTIN product_id: {} not in {:?}",
+                product_id, gs1_company_prefixes
+            )));
         }
 
+        // Check that the properties match the Schema declared by the product
+        validate_product_schema(state, schema_name, properties, None)?;
+
         let new_product = ProductBuilder::new()
             .with_product_id(product_id.to_string())
             .with_owner(owner.to_string())
             .with_product_type(product_type.clone())
             .with_properties(properties.to_vec())
+            .with_schema(schema_name.to_string())
             .build()
             .map_err(|err| {
                 ApplyError::InvalidTransaction(format!("Cannot build product: {}", err))
             })?;
+        let new_product = add_revision(new_product, RevisionChange::Create, signer, timestamp, "")?;
 
         state.set_product(product_id, new_product)?;
 
@@ -180,6 +211,7 @@ impl ProductTransactionHandler {
         state: &mut ProductState,
         signer: &str,
         perm_checker: &PermissionChecker,
+        timestamp: u64,
     ) -> Result<(), ApplyError> {
         let product_id = payload.product_id();
         let product_type = payload.product_type();
@@ -224,6 +256,9 @@ impl ProductTransactionHandler {
             Err(err) => Err(err),
         }?;
 
+        // Check that the product has not been deleted
+        check_not_deleted(&product)?;
+
         // Check if the agent updating the product is part of the organization associated with the product
         if product.owner() != agent.org_id() {
             return Err(ApplyError::InvalidTransaction(
@@ -236,16 +271,114 @@ impl ProductTransactionHandler {
             return Err(ApplyError::InvalidTransaction(e.to_string()));
         }
 
+        // Check that the new properties match the Schema declared by the product
+        validate_product_schema(
+            state,
+            product.schema(),
+            properties,
+            Some(product.properties()),
+        )?;
+
         // Handle updating the product
-        let updated_product = ProductBuilder::new()
-            .with_product_id(product_id.to_string())
-            .with_owner(product.owner().to_string())
+        let updated_product = product
+            .into_builder()
             .with_product_type(product_type.clone())
             .with_properties(properties.to_vec())
             .build()
             .map_err(|err| {
                 ApplyError::InvalidTransaction(format!("Cannot build product: {}", err))
             })?;
+        let updated_product =
+            add_revision(updated_product, RevisionChange::Update, signer, timestamp, "")?;
+
+        state.set_product(product_id, updated_product)?;
+
+        Ok(())
+    }
+
+    fn patch_product(
+        &self,
+        payload: &ProductPatchAction,
+        state: &mut ProductState,
+        signer: &str,
+        perm_checker: &PermissionChecker,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let product_id = payload.product_id();
+        let product_type = payload.product_type();
+
+        // Check that the agent submitting the transactions exists in state
+        let agent = match state.get_agent(signer)? {
+            Some(agent) => agent,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "The signing Agent does not exist: {}",
+                    signer
+                )));
+            }
+        };
+
+        // Check signing agent's permission
+        check_permission(perm_checker, signer, "can_update_product")?;
+
+        // Check that the agent has an organization associated with it
+        if agent.org_id().is_empty() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "The signing Agent does not have an associated organization: {}",
+                signer
+            )));
+        }
+
+        // Check if the product type is a GS1 product
+        if product_type != &ProductType::GS1 {
+            return Err(ApplyError::InvalidTransaction(
+                "Invalid product type enum for product".to_string(),
+            ));
+        }
+
+        // Check if product exists
+        let product = match state.get_product(product_id) {
+            Ok(Some(product)) => Ok(product),
+            Ok(None) => Err(ApplyError::InvalidTransaction(format!(
+                "No product exists: {}",
+                product_id
+            ))),
+            Err(err) => Err(err),
+        }?;
+
+        // Check that the product has not been deleted
+        check_not_deleted(&product)?;
+
+        // Check if the agent updating the product is part of the organization associated with the product
+        if product.owner() != agent.org_id() {
+            return Err(ApplyError::InvalidTransaction(
+                "Invalid organization for the agent submitting this transaction".to_string(),
+            ));
+        }
+
+        // Apply the patches in order to the current properties
+        let mut properties = product.properties().to_vec();
+        for patch in payload.patches() {
+            apply_property_patch(&mut properties, patch)?;
+        }
+
+        // Check that the patched properties still match the Schema declared by the product
+        validate_product_schema(
+            state,
+            product.schema(),
+            &properties,
+            Some(product.properties()),
+        )?;
+
+        let updated_product = product
+            .into_builder()
+            .with_properties(properties)
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!("Cannot build product: {}", err))
+            })?;
+        let updated_product =
+            add_revision(updated_product, RevisionChange::Patch, signer, timestamp, "")?;
 
         state.set_product(product_id, updated_product)?;
 
@@ -258,6 +391,7 @@ impl ProductTransactionHandler {
         state: &mut ProductState,
         signer: &str,
         perm_checker: &PermissionChecker,
+        timestamp: u64,
     ) -> Result<(), ApplyError> {
         let product_id = payload.product_id();
         let product_type = payload.product_type();
@@ -293,6 +427,9 @@ impl ProductTransactionHandler {
             Err(err) => Err(err),
         }?;
 
+        // Check that the product has not already been deleted
+        check_not_deleted(&product)?;
+
         // Check if product product_id is a valid gtin
         if let Err(e) = validate_gtin(product_id) {
             return Err(ApplyError::InvalidTransaction(e.to_string()));
@@ -305,8 +442,253 @@ impl ProductTransactionHandler {
             ));
         }
 
-        // Delete the product
-        state.remove_product(product_id)?;
+        // Mark the product as deleted, keeping it in state along with its history
+        let reason = payload.reason();
+        let deleted_product = product
+            .into_builder()
+            .with_deleted(true)
+            .with_deleted_reason(reason.to_string())
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!("Cannot build product: {}", err))
+            })?;
+        let deleted_product =
+            add_revision(deleted_product, RevisionChange::Delete, signer, timestamp, reason)?;
+
+        state.set_product(product_id, deleted_product)?;
+        Ok(())
+    }
+
+    fn create_proposal(
+        &self,
+        payload: &ProductCreateProposalAction,
+        state: &mut ProductState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let product_id = payload.product_id();
+        let product_type = payload.product_type();
+        let receiving_org = payload.receiving_org();
+
+        // Check if the product type is a GS1 product
+        if product_type != &ProductType::GS1 {
+            return Err(ApplyError::InvalidTransaction(
+                "Invalid product type enum for product".to_string(),
+            ));
+        }
+
+        // Check if product exists
+        let product = match state.get_product(product_id)? {
+            Some(product) => product,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "No product exists: {}",
+                    product_id
+                )));
+            }
+        };
+
+        // Check that the product has not been deleted
+        check_not_deleted(&product)?;
+
+        // Only an admin of the organization that owns the product can propose a transfer
+        check_org_admin(state, signer, product.owner())?;
+
+        if receiving_org == product.owner() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Product is already owned by the receiving organization: {}",
+                receiving_org
+            )));
+        }
+
+        if state.get_organization(receiving_org)?.is_none() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Receiving organization does not exist: {}",
+                receiving_org
+            )));
+        }
+
+        let mut proposals = match state.get_proposal_list(product_id, receiving_org)? {
+            Some(proposals) => proposals.proposals().to_vec(),
+            None => vec![],
+        };
+
+        if proposals.iter().any(|proposal| {
+            proposal.status() == &Status::Open
+                && proposal.product_id() == product_id
+                && proposal.receiving_org() == receiving_org
+        }) {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Proposal already exists",
+            )));
+        }
+
+        let new_proposal = ProductProposalBuilder::new()
+            .with_product_id(product_id.to_string())
+            .with_timestamp(timestamp)
+            .with_issuing_agent(signer.to_string())
+            .with_issuing_org(product.owner().to_string())
+            .with_receiving_org(receiving_org.to_string())
+            .with_status(Status::Open)
+            .with_terms(payload.terms().to_string())
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!("Cannot build product proposal: {}", err))
+            })?;
+
+        proposals.push(new_proposal);
+        proposals.sort_by_key(|p| {
+            (
+                p.product_id().to_string(),
+                p.receiving_org().to_string(),
+                *p.timestamp(),
+            )
+        });
+        let proposal_list = ProductProposalListBuilder::new()
+            .with_proposals(proposals)
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!(
+                    "Cannot build product proposal list: {}",
+                    err
+                ))
+            })?;
+
+        state.set_proposal_list(product_id, receiving_org, proposal_list)?;
+
+        Ok(())
+    }
+
+    fn answer_proposal(
+        &self,
+        payload: &ProductAnswerProposalAction,
+        state: &mut ProductState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let product_id = payload.product_id();
+        let receiving_org = payload.receiving_org();
+
+        let mut proposals = match state.get_proposal_list(product_id, receiving_org)? {
+            Some(proposal_list) => proposal_list.proposals().to_vec(),
+            None => {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Proposal does not exist",
+                )));
+            }
+        };
+
+        // find proposal to answer or return error.
+        let (proposal_index, current_proposal) = proposals
+            .iter()
+            .enumerate()
+            .find_map(|(i, prop)| {
+                if prop.product_id() == product_id
+                    && prop.receiving_org() == receiving_org
+                    && prop.status() == &Status::Open
+                {
+                    Some((i, prop.clone()))
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| {
+                ApplyError::InvalidTransaction(format!(
+                    "No open proposals found for product {} for {}",
+                    product_id, receiving_org
+                ))
+            })?;
+
+        let mut updated_proposal_builder = current_proposal.clone().into_builder();
+
+        match payload.response() {
+            Response::Cancel => {
+                check_org_admin(state, signer, current_proposal.issuing_org())?;
+                updated_proposal_builder = updated_proposal_builder.with_status(Status::Canceled);
+            }
+
+            Response::Reject => {
+                check_org_admin(state, signer, receiving_org)?;
+                updated_proposal_builder = updated_proposal_builder.with_status(Status::Rejected);
+            }
+
+            Response::Accept => {
+                check_org_admin(state, signer, receiving_org)?;
+
+                let product = match state.get_product(product_id)? {
+                    Some(product) => product,
+                    None => {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Product in proposal does not exist: {}",
+                            product_id
+                        )));
+                    }
+                };
+
+                if *product.deleted() {
+                    info!("Product in proposal has been deleted");
+
+                    updated_proposal_builder =
+                        updated_proposal_builder.with_status(Status::Canceled);
+                } else if product.owner() != current_proposal.issuing_org() {
+                    info!("Product owner does not match the issuing organization of the proposal");
+
+                    updated_proposal_builder =
+                        updated_proposal_builder.with_status(Status::Canceled);
+                } else {
+                    let updated_product = product
+                        .into_builder()
+                        .with_owner(receiving_org.to_string())
+                        .build()
+                        .map_err(|err| {
+                            ApplyError::InvalidTransaction(format!(
+                                "Cannot build product: {}",
+                                err
+                            ))
+                        })?;
+                    let updated_product = add_revision(
+                        updated_product,
+                        RevisionChange::Transfer,
+                        signer,
+                        timestamp,
+                        current_proposal.terms(),
+                    )?;
+
+                    state.set_product(product_id, updated_product)?;
+
+                    updated_proposal_builder =
+                        updated_proposal_builder.with_status(Status::Accepted);
+                }
+            }
+        }
+
+        let updated_proposal = updated_proposal_builder.build().map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build product proposal: {}", err))
+        })?;
+
+        // remove outdated proposal
+        proposals.remove(proposal_index);
+        proposals.push(updated_proposal);
+        proposals.sort_by_key(|p| {
+            (
+                p.product_id().to_string(),
+                p.receiving_org().to_string(),
+                *p.timestamp(),
+            )
+        });
+
+        let proposal_list = ProductProposalListBuilder::new()
+            .with_proposals(proposals)
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!(
+                    "Cannot build product proposal list: {}",
+                    err
+                ))
+            })?;
+
+        state.set_proposal_list(product_id, receiving_org, proposal_list)?;
+
         Ok(())
     }
 }
@@ -346,15 +728,46 @@ impl TransactionHandler for ProductTransactionHandler {
         let perm_checker = PermissionChecker::new(context);
 
         match payload.action() {
-            Action::ProductCreate(create_product_payload) => {
-                self.create_product(create_product_payload, &mut state, signer, &perm_checker)?
-            }
-            Action::ProductUpdate(update_product_payload) => {
-                self.update_product(update_product_payload, &mut state, signer, &perm_checker)?
-            }
-            Action::ProductDelete(delete_product_payload) => {
-                self.delete_product(delete_product_payload, &mut state, signer, &perm_checker)?
-            }
+            Action::ProductCreate(create_product_payload) => self.create_product(
+                create_product_payload,
+                &mut state,
+                signer,
+                &perm_checker,
+                *payload.timestamp(),
+            )?,
+            Action::ProductUpdate(update_product_payload) => self.update_product(
+                update_product_payload,
+                &mut state,
+                signer,
+                &perm_checker,
+                *payload.timestamp(),
+            )?,
+            Action::ProductDelete(delete_product_payload) => self.delete_product(
+                delete_product_payload,
+                &mut state,
+                signer,
+                &perm_checker,
+                *payload.timestamp(),
+            )?,
+            Action::ProductCreateProposal(create_proposal_payload) => self.create_proposal(
+                create_proposal_payload,
+                &mut state,
+                signer,
+                *payload.timestamp(),
+            )?,
+            Action::ProductAnswerProposal(answer_proposal_payload) => self.answer_proposal(
+                answer_proposal_payload,
+                &mut state,
+                signer,
+                *payload.timestamp(),
+            )?,
+            Action::ProductPatch(patch_product_payload) => self.patch_product(
+                patch_product_payload,
+                &mut state,
+                signer,
+                &perm_checker,
+                *payload.timestamp(),
+            )?,
         }
         Ok(())
     }
@@ -375,73 +788,340 @@ fn check_permission(
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    use std::cell::RefCell;
-    use std::collections::HashMap;
-
-    use dgc_config::protocol::pike::state::{
-        AgentBuilder, AgentListBuilder, KeyValueEntryBuilder, OrganizationBuilder,
-        OrganizationListBuilder,
-    };
-    use dgc_config::protocol::product::payload::{
-        ProductCreateAction, ProductCreateActionBuilder, ProductDeleteAction,
-        ProductDeleteActionBuilder, ProductUpdateAction, ProductUpdateActionBuilder,
-    };
-    use dgc_config::protocol::product::state::{
-        Product, ProductBuilder, ProductListBuilder, ProductType,
+/// Checks that the signer is an active admin agent of the given organization
+fn check_org_admin(state: &ProductState, signer: &str, org_id: &str) -> Result<Agent, ApplyError> {
+    let agent = match state.get_agent(signer)? {
+        Some(agent) => agent,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "The signing Agent does not exist: {}",
+                signer
+            )));
+        }
     };
-    use dgc_config::protocol::schema::state::{DataType, PropertyValue, PropertyValueBuilder};
-    use dgc_config::protos::IntoBytes;
 
-    use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
+    if agent.org_id() != org_id {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "The signing Agent is not associated with the organization: {} != {}",
+            agent.org_id(),
+            org_id
+        )));
+    }
 
-    const AGENT_ORG_ID: &str = "test_org";
-    const PUBLIC_KEY: &str = "test_public_key";
-    const PRODUCT_ID: &str = "688955434684";
-    const PRODUCT_2_ID: &str = "9781981855728";
+    if !agent.roles().iter().any(|role| role == "admin") {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "The signing Agent is not an admin of the organization: {}",
+            signer
+        )));
+    }
 
-    #[derive(Default, Debug)]
-    /// A MockTransactionContext that can be used to test ProductState
-    struct MockTransactionContext {
-        state: RefCell<HashMap<String, Vec<u8>>>,
+    if !*agent.active() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "The signing Agent is not active: {}",
+            signer
+        )));
     }
 
-    impl TransactionContext for MockTransactionContext {
-        fn get_state_entries(
-            &self,
-            addresses: &[String],
-        ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
-            let mut results = Vec::new();
-            for addr in addresses {
-                let data = match self.state.borrow().get(addr) {
-                    Some(data) => data.clone(),
-                    None => Vec::new(),
-                };
-                results.push((addr.to_string(), data));
-            }
-            Ok(results)
-        }
+    Ok(agent)
+}
 
-        fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
-            for (addr, data) in entries {
-                self.state.borrow_mut().insert(addr, data);
-            }
-            Ok(())
-        }
+/// Applies a single patch to the given properties. If the patch carries an expected value,
+/// the current value must match it, otherwise the product was modified concurrently.
+fn apply_property_patch(
+    properties: &mut Vec<PropertyValue>,
+    patch: &PropertyPatch,
+) -> Result<(), ApplyError> {
+    let position = properties
+        .iter()
+        .position(|property| property.name() == patch.name());
 
-        /// this is not needed for these tests
-        fn delete_state_entries(&self, _addresses: &[String]) -> Result<Vec<String>, ContextError> {
-            unimplemented!()
+    if let Some(expected_value) = patch.expected_value() {
+        let current_value = position.map(|index| &properties[index]);
+        if current_value != Some(expected_value) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Property {} was modified concurrently: expected value does not match current value",
+                patch.name()
+            )));
         }
+    }
 
-        /// this is not needed for these tests
-        fn add_receipt_data(&self, _data: &[u8]) -> Result<(), ContextError> {
-            unimplemented!()
+    match (patch.operation(), position) {
+        (PatchOperation::Set, Some(index)) => {
+            properties[index] = patch_value(patch)?;
         }
-
+        (PatchOperation::Add, None) => {
+            properties.push(patch_value(patch)?);
+        }
+        (PatchOperation::Remove, Some(index)) => {
+            properties.remove(index);
+        }
+        (PatchOperation::Add, Some(_)) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Cannot add property, it already exists: {}",
+                patch.name()
+            )));
+        }
+        (_, None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Cannot patch property, it does not exist: {}",
+                patch.name()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn patch_value(patch: &PropertyPatch) -> Result<PropertyValue, ApplyError> {
+    match patch.value() {
+        Some(value) if value.name() == patch.name() => Ok(value.clone()),
+        Some(value) => Err(ApplyError::InvalidTransaction(format!(
+            "Patch value name does not match patched property: {} != {}",
+            value.name(),
+            patch.name()
+        ))),
+        None => Err(ApplyError::InvalidTransaction(format!(
+            "No value provided for property: {}",
+            patch.name()
+        ))),
+    }
+}
+
+fn check_not_deleted(product: &Product) -> Result<(), ApplyError> {
+    if *product.deleted() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Product has been deleted: {}",
+            product.product_id()
+        )));
+    }
+    Ok(())
+}
+
+/// Bumps the revision of the product and appends the change to its history. Only the most
+/// recent PRODUCT_REVISION_MAX_LENGTH revisions are kept.
+fn add_revision(
+    product: Product,
+    change: RevisionChange,
+    signer: &str,
+    timestamp: u64,
+    reason: &str,
+) -> Result<Product, ApplyError> {
+    let revision_number = product.revision() + 1;
+
+    let revision = ProductRevisionBuilder::new()
+        .with_revision(revision_number)
+        .with_timestamp(timestamp)
+        .with_agent(signer.to_string())
+        .with_change(change)
+        .with_owner(product.owner().to_string())
+        .with_properties(product.properties().to_vec())
+        .with_reason(reason.to_string())
+        .build()
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build product revision: {}", err))
+        })?;
+
+    let mut revisions = product.revisions().to_vec();
+    revisions.push(revision);
+    if revisions.len() > PRODUCT_REVISION_MAX_LENGTH {
+        let overflow = revisions.len() - PRODUCT_REVISION_MAX_LENGTH;
+        revisions.drain(0..overflow);
+    }
+
+    product
+        .into_builder()
+        .with_revision(revision_number)
+        .with_revisions(revisions)
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build product: {}", err)))
+}
+
+/// Validates the properties against the named schema. When `previous` holds the
+/// properties of an existing product, immutable properties must be left unchanged,
+/// otherwise a new product is being created and the schema must not be deprecated.
+fn validate_product_schema(
+    state: &ProductState,
+    schema_name: &str,
+    properties: &[PropertyValue],
+    previous: Option<&[PropertyValue]>,
+) -> Result<(), ApplyError> {
+    let schema = match state.get_schema(schema_name)? {
+        Some(schema) => schema,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Schema does not exist: {}",
+                schema_name
+            )));
+        }
+    };
+    validate_properties(&schema, properties)?;
+    check_references(state, schema.properties(), properties, "")?;
+    match previous {
+        Some(previous) => validate_immutable_properties(&schema, previous, properties)?,
+        None if *schema.deprecated() => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Schema has been deprecated: {}",
+                schema_name
+            )));
+        }
+        None => (),
+    }
+    Ok(())
+}
+
+/// Checks that every entity referenced by a REFERENCE value, including REFERENCE values
+/// nested in a STRUCT or LIST, exists in state. The values must already have been
+/// validated against the definitions.
+fn check_references(
+    state: &ProductState,
+    definitions: &[PropertyDefinition],
+    values: &[PropertyValue],
+    prefix: &str,
+) -> Result<(), ApplyError> {
+    for value in values {
+        if let Some(definition) = definitions.iter().find(|d| d.name() == value.name()) {
+            check_reference_value(
+                state,
+                definition,
+                value,
+                &format!("{}{}", prefix, value.name()),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn check_reference_value(
+    state: &ProductState,
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+    path: &str,
+) -> Result<(), ApplyError> {
+    match definition.data_type() {
+        DataType::Reference => {
+            let reference = value.reference_value();
+            let exists = match definition.reference_kind() {
+                Some(ReferenceKind::Record) => match state.get_record(reference)? {
+                    Some(record) => {
+                        definition.reference_schema().is_empty()
+                            || record.schema() == definition.reference_schema()
+                    }
+                    None => false,
+                },
+                Some(ReferenceKind::Product) => match state.get_product(reference)? {
+                    Some(product) => !product.deleted(),
+                    None => false,
+                },
+                Some(ReferenceKind::Organization) => state.get_organization(reference)?.is_some(),
+                Some(ReferenceKind::Agent) => state.get_agent(reference)?.is_some(),
+                None => false,
+            };
+            if !exists {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Entity referenced by {} does not exist: {}",
+                    path, reference
+                )));
+            }
+        }
+        DataType::Struct => check_references(
+            state,
+            definition.struct_properties(),
+            value.struct_values(),
+            &format!("{}.", path),
+        )?,
+        DataType::List => {
+            if let Some(element) = definition.list_element() {
+                for (index, list_value) in value.list_values().iter().enumerate() {
+                    check_reference_value(
+                        state,
+                        element,
+                        list_value,
+                        &format!("{}[{}]", path, index),
+                    )?;
+                }
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use dgc_config::protocol::pike::state::{
+        AgentBuilder, AgentListBuilder, KeyValueEntryBuilder, OrganizationBuilder,
+        OrganizationListBuilder,
+    };
+    use dgc_config::protocol::product::payload::{
+        ProductAnswerProposalActionBuilder, ProductCreateAction, ProductCreateActionBuilder,
+        ProductCreateProposalActionBuilder, ProductDeleteAction, ProductDeleteActionBuilder,
+        ProductPatchActionBuilder, ProductUpdateAction, ProductUpdateActionBuilder,
+        PropertyPatchBuilder,
+    };
+    use dgc_config::protocol::product::state::{
+        Product, ProductBuilder, ProductListBuilder, ProductType,
+    };
+    use dgc_config::protocol::schema::state::{
+        DataType, PropertyConstraintsBuilder, PropertyDefinitionBuilder, PropertyValue,
+        PropertyValueBuilder, SchemaBuilder, SchemaListBuilder,
+    };
+    use dgc_config::protos::IntoBytes;
+
+    use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
+
+    const AGENT_ORG_ID: &str = "test_org";
+    const PUBLIC_KEY: &str = "test_public_key";
+    const PRODUCT_ID: &str = "688955434684";
+    const PRODUCT_2_ID: &str = "9781981855728";
+    const SCHEMA_NAME: &str = "gs1_product";
+    const ADMIN_PUBLIC_KEY: &str = "admin_public_key";
+    const RECEIVING_ORG_ID: &str = "receiving_org";
+    const RECEIVING_ADMIN_PUBLIC_KEY: &str = "receiving_admin_public_key";
+
+    #[derive(Default, Debug)]
+    /// A MockTransactionContext that can be used to test ProductState
+    struct MockTransactionContext {
+        state: RefCell<HashMap<String, Vec<u8>>>,
+    }
+
+    impl TransactionContext for MockTransactionContext {
+        fn get_state_entries(
+            &self,
+            addresses: &[String],
+        ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+            let mut results = Vec::new();
+            for addr in addresses {
+                let data = match self.state.borrow().get(addr) {
+                    Some(data) => data.clone(),
+                    None => Vec::new(),
+                };
+                results.push((addr.to_string(), data));
+            }
+            Ok(results)
+        }
+
+        fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+            for (addr, data) in entries {
+                self.state.borrow_mut().insert(addr, data);
+            }
+            Ok(())
+        }
+
+        /// this is not needed for these tests
+        fn delete_state_entries(&self, _addresses: &[String]) -> Result<Vec<String>, ContextError> {
+            unimplemented!()
+        }
+
+        /// this is not needed for these tests
+        fn add_receipt_data(&self, _data: &[u8]) -> Result<(), ContextError> {
+            unimplemented!()
+        }
+
         /// this is not needed for these tests
         fn add_event(
             &self,
@@ -494,13 +1174,31 @@ mod tests {
             self.set_state_entry(agent_address, agent_bytes).unwrap();
         }
 
+        fn add_admin(&self, public_key: &str, org_id: &str) {
+            let agent = AgentBuilder::new()
+                .with_org_id(org_id.to_string())
+                .with_public_key(public_key.to_string())
+                .with_active(true)
+                .with_roles(vec!["admin".to_string()])
+                .build()
+                .unwrap();
+
+            let agent_list = AgentListBuilder::new()
+                .with_agents(vec![agent])
+                .build()
+                .unwrap();
+            let agent_bytes = agent_list.into_bytes().unwrap();
+            let agent_address = make_agent_address(public_key);
+            self.set_state_entry(agent_address, agent_bytes).unwrap();
+        }
+
         fn add_org(&self, org_id: &str) {
             // Products can only be created when there is a gs1 prefix
             // within the product organization's metadata
             let builder = KeyValueEntryBuilder::new();
             let key_value = builder
                 .with_key("gs1_company_prefixes".to_string())
-                .with_value("test_value".to_string())
+                .with_value("0614141,688955".to_string())
                 .build()
                 .unwrap();
 
@@ -542,6 +1240,60 @@ mod tests {
             self.set_state_entry(org_address, org_bytes).unwrap();
         }
 
+        fn add_schema(&self) {
+            let description = PropertyDefinitionBuilder::new()
+                .with_name("description".into())
+                .with_data_type(DataType::String)
+                .with_required(true)
+                .build()
+                .unwrap();
+            let price = PropertyDefinitionBuilder::new()
+                .with_name("price".into())
+                .with_data_type(DataType::Number)
+                .with_number_exponent(0)
+                .with_constraints(
+                    PropertyConstraintsBuilder::new()
+                        .with_number_min(0)
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap();
+            let color = PropertyDefinitionBuilder::new()
+                .with_name("color".into())
+                .with_data_type(DataType::Enum)
+                .with_enum_options(vec!["red".into(), "blue".into()])
+                .build()
+                .unwrap();
+            let origin = PropertyDefinitionBuilder::new()
+                .with_name("origin".into())
+                .with_data_type(DataType::String)
+                .with_constraints(
+                    PropertyConstraintsBuilder::new()
+                        .with_max_length(32)
+                        .with_immutable(true)
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap();
+
+            let schema = SchemaBuilder::new()
+                .with_name(SCHEMA_NAME.to_string())
+                .with_owner(AGENT_ORG_ID.to_string())
+                .with_properties(vec![description, price, color, origin])
+                .build()
+                .unwrap();
+
+            let schema_list = SchemaListBuilder::new()
+                .with_schemas(vec![schema])
+                .build()
+                .unwrap();
+            let schema_bytes = schema_list.into_bytes().unwrap();
+            let schema_address = make_schema_address(SCHEMA_NAME);
+            self.set_state_entry(schema_address, schema_bytes).unwrap();
+        }
+
         fn add_product(&self, prod_id: &str) {
             let product_list = ProductListBuilder::new()
                 .with_products(vec![make_product()])
@@ -576,6 +1328,7 @@ mod tests {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
@@ -587,7 +1340,8 @@ mod tests {
                 &product_create_action,
                 &mut state,
                 PUBLIC_KEY,
-                &perm_checker
+                &perm_checker,
+                0,
             )
             .is_ok());
 
@@ -596,7 +1350,9 @@ mod tests {
             .expect("Failed to fetch product")
             .expect("No product found");
 
-        assert_eq!(product, make_product());
+        assert_eq!(product.properties(), make_product().properties());
+        assert_eq!(*product.revision(), 1);
+        assert_eq!(*product.revisions()[0].change(), RevisionChange::Create);
     }
 
     #[test]
@@ -614,6 +1370,7 @@ mod tests {
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
             Ok(()) => panic!("Agent should not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
@@ -639,6 +1396,7 @@ mod tests {
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
             Ok(()) => panic!(
                 "Agent should not have can_create_product role, InvalidTransaction should be returned"
@@ -669,6 +1427,7 @@ mod tests {
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
             Ok(()) => panic!(
                 "Agent's organization should not exist, InvalidTransaction should be returned"
@@ -699,7 +1458,8 @@ mod tests {
             &product_create_action,
             &mut state,
             PUBLIC_KEY,
-            &perm_checker
+            &perm_checker,
+            0,
         ) {
             Ok(()) => panic!("Agent's organization should not have a gs1 prefix key, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
@@ -709,6 +1469,44 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Test that ProductCreationAction is invalid if the GTIN does not begin with one of the
+    /// gs1 company prefixes owned by the agent's org.
+    fn test_create_product_gtin_not_in_gs1_prefixes() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = ProductCreateActionBuilder::new()
+            .with_product_id(PRODUCT_2_ID.to_string())
+            .with_owner(AGENT_ORG_ID.to_string())
+            .with_schema(SCHEMA_NAME.into())
+            .with_product_type(ProductType::GS1)
+            .with_properties(make_properties())
+            .build()
+            .expect("Failed to build ProductCreateAction");
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("GTIN should not match the org's gs1 prefixes, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "The agents organization does not own the GS1 company prefix in the GTIN product_id: {}",
+                    PRODUCT_2_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
     #[test]
     /// Test that ProductCreationAction is invalid if the a product with the same id
     /// already exists.
@@ -728,6 +1526,7 @@ mod tests {
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
             Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
@@ -738,144 +1537,508 @@ mod tests {
     }
 
     #[test]
-    /// Test that if ProductUpdateAction is valid an OK is returned and a Product is updated in state
-    fn test_update_product_handler_valid() {
+    /// Test that ProductCreationAction is invalid if the product's schema does not exist.
+    fn test_create_product_schema_does_not_exist() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_product(PRODUCT_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_update_action = make_product_update_action();
-
-        assert!(transaction_handler
-            .update_product(
-                &product_update_action,
-                &mut state,
-                PUBLIC_KEY,
-                &perm_checker,
-            )
-            .is_ok());
-
-        let product = state
-            .get_product(PRODUCT_ID)
-            .expect("Failed to fetch product")
-            .expect("No product found");
+        let product_create_action = make_product_create_action();
 
-        assert_eq!(product, make_updated_product());
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("Schema should not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Schema does not exist: {}", SCHEMA_NAME)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
     }
 
     #[test]
-    /// Test that ProductUpdateAction is invalid if the signer is not an Agent.
-    fn test_update_product_agent_does_not_exist() {
+    /// Test that ProductCreationAction is invalid if a required schema property is missing.
+    fn test_create_product_missing_required_property() {
         let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_update_action = make_product_update_action();
+        let product_create_action = make_product_create_action_with_properties(vec![
+            make_properties()[1].clone(),
+        ]);
 
-        match transaction_handler.update_product(
-            &product_update_action,
+        match transaction_handler.create_product(
+            &product_create_action,
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
-            Ok(()) => panic!("Agent should not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Required property is missing, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("The signing Agent does not exist: {}", PUBLIC_KEY)));
+                assert!(err.contains("Required property description not provided"));
             }
             Err(err) => panic!("Should have gotten invalid error but go {}", err),
         }
     }
 
     #[test]
-    /// Test that ProductUpdateAction is invalid if the agent does not have can_update_product role
-    fn test_update_product_agent_without_roles() {
+    /// Test that ProductCreationAction is invalid if a property has the wrong data type.
+    fn test_create_product_invalid_property_type() {
         let transaction_context = MockTransactionContext::default();
-        transaction_context.add_agent_without_roles(PUBLIC_KEY);
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_update_action = make_product_update_action();
+        let price = PropertyValueBuilder::new()
+            .with_name("price".into())
+            .with_data_type(DataType::String)
+            .with_string_value("3".into())
+            .build()
+            .unwrap();
+        let product_create_action = make_product_create_action_with_properties(vec![
+            make_properties()[0].clone(),
+            price,
+        ]);
 
-        match transaction_handler.update_product(
-            &product_update_action,
+        match transaction_handler.create_product(
+            &product_create_action,
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
-            Ok(()) => panic!(
-                "Agent should not have can_update_product role, InvalidTransaction should be returned"
-            ),
+            Ok(()) => panic!("Property has the wrong type, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!(
-                    "The signer does not have the can_update_product permission: {}",
-                    PUBLIC_KEY
-                )));
+                assert!(err.contains("Value provided for price is the wrong type"));
             }
             Err(err) => panic!("Should have gotten invalid error but go {}", err),
         }
     }
 
     #[test]
-    /// Test that ProductUpdateAction is invalid if there is no product to update
-    fn test_update_product_that_does_not_exist() {
+    /// Test that ProductCreationAction is invalid if an enum value is out of range.
+    fn test_create_product_enum_out_of_range() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_update_action = make_product_update_action();
+        let color = PropertyValueBuilder::new()
+            .with_name("color".into())
+            .with_data_type(DataType::Enum)
+            .with_enum_value(2)
+            .build()
+            .unwrap();
+        let mut properties = make_properties();
+        properties.push(color);
+        let product_create_action = make_product_create_action_with_properties(properties);
 
-        match transaction_handler.update_product(
-            &product_update_action,
+        match transaction_handler.create_product(
+            &product_create_action,
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
-            Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Enum value is out of range, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("No product exists: {}", PRODUCT_ID)));
+                assert!(err.contains("Enum value provided for color is out of range"));
             }
             Err(err) => panic!("Should have gotten invalid error but go {}", err),
         }
     }
 
     #[test]
-    /// Test that if ProductDeleteAction is valid an OK is returned and a Product is deleted from state
-    fn test_delete_product_handler_valid() {
+    /// Test that if ProductUpdateAction is valid an OK is returned and a Product is updated in state
+    fn test_update_product_handler_valid() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        transaction_context.add_schema();
+        transaction_context.add_product(PRODUCT_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_delete_action = make_product_delete_action(PRODUCT_ID);
+        let product_update_action = make_product_update_action();
+
+        assert!(transaction_handler
+            .update_product(
+                &product_update_action,
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker,
+                0,
+            )
+            .is_ok());
+
+        let product = state
+            .get_product(PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+
+        assert_eq!(product.properties(), make_updated_product().properties());
+        assert_eq!(*product.revision(), 1);
+        assert_eq!(product.revisions()[0].properties(), &make_updated_properties()[..]);
+    }
+
+    #[test]
+    /// Test that ProductUpdateAction is invalid if the signer is not an Agent.
+    fn test_update_product_agent_does_not_exist() {
+        let transaction_context = MockTransactionContext::default();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_update_action = make_product_update_action();
+
+        match transaction_handler.update_product(
+            &product_update_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("Agent should not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("The signing Agent does not exist: {}", PUBLIC_KEY)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductUpdateAction is invalid if the agent does not have can_update_product role
+    fn test_update_product_agent_without_roles() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent_without_roles(PUBLIC_KEY);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_update_action = make_product_update_action();
+
+        match transaction_handler.update_product(
+            &product_update_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!(
+                "Agent should not have can_update_product role, InvalidTransaction should be returned"
+            ),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "The signer does not have the can_update_product permission: {}",
+                    PUBLIC_KEY
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductUpdateAction is invalid if there is no product to update
+    fn test_update_product_that_does_not_exist() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_update_action = make_product_update_action();
+
+        match transaction_handler.update_product(
+            &product_update_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("No product exists: {}", PRODUCT_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if ProductPatchAction is valid, the named properties are set and added while
+    /// the remaining properties are left untouched
+    fn test_patch_product_handler_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
+        transaction_context.add_product(PRODUCT_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_patch_action = make_product_patch_action(vec![
+            PropertyPatchBuilder::new()
+                .with_operation(PatchOperation::Set)
+                .with_name("price".into())
+                .with_value(make_number_property("price", 4))
+                .with_expected_value(make_number_property("price", 3))
+                .build()
+                .unwrap(),
+            PropertyPatchBuilder::new()
+                .with_operation(PatchOperation::Add)
+                .with_name("color".into())
+                .with_value(make_color_property())
+                .build()
+                .unwrap(),
+        ]);
+
+        assert!(transaction_handler
+            .patch_product(&product_patch_action, &mut state, PUBLIC_KEY, &perm_checker, 0)
+            .is_ok());
+
+        let product = state
+            .get_product(PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+
+        let mut expected_properties = make_properties();
+        expected_properties[1] = make_number_property("price", 4);
+        expected_properties.push(make_color_property());
+        assert_eq!(product.properties(), &expected_properties[..]);
+    }
+
+    #[test]
+    /// Test that a patch setting a value outside of the schema constraints is rejected
+    fn test_patch_product_constraint_violation() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
+        transaction_context.add_product(PRODUCT_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_patch_action = make_product_patch_action(vec![PropertyPatchBuilder::new()
+            .with_operation(PatchOperation::Set)
+            .with_name("price".into())
+            .with_value(make_number_property("price", -1))
+            .build()
+            .unwrap()]);
+
+        match transaction_handler.patch_product(
+            &product_patch_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("Price below the minimum should be invalid"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Value provided for price violates the schema constraints"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that an immutable property cannot be added after the product was created
+    fn test_patch_product_immutable_property() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
+        transaction_context.add_product(PRODUCT_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let origin = PropertyValueBuilder::new()
+            .with_name("origin".into())
+            .with_data_type(DataType::String)
+            .with_string_value("US".into())
+            .build()
+            .unwrap();
+        let product_patch_action = make_product_patch_action(vec![PropertyPatchBuilder::new()
+            .with_operation(PatchOperation::Add)
+            .with_name("origin".into())
+            .with_value(origin)
+            .build()
+            .unwrap()]);
+
+        match transaction_handler.patch_product(
+            &product_patch_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("Changing an immutable property should be invalid"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Property origin is immutable and cannot be changed"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the expected value of a patch does not match the current value, the
+    /// patch is rejected as a concurrent modification
+    fn test_patch_product_concurrent_modification() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
+        transaction_context.add_product(PRODUCT_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_patch_action = make_product_patch_action(vec![PropertyPatchBuilder::new()
+            .with_operation(PatchOperation::Set)
+            .with_name("price".into())
+            .with_value(make_number_property("price", 5))
+            .with_expected_value(make_number_property("price", 4))
+            .build()
+            .unwrap()]);
+
+        match transaction_handler.patch_product(
+            &product_patch_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("Patch with stale expected value should be invalid"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Property price was modified concurrently"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that removing a required property is rejected by the product's schema
+    fn test_patch_product_remove_required_property() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
+        transaction_context.add_product(PRODUCT_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_patch_action = make_product_patch_action(vec![PropertyPatchBuilder::new()
+            .with_operation(PatchOperation::Remove)
+            .with_name("description".into())
+            .build()
+            .unwrap()]);
+
+        match transaction_handler.patch_product(
+            &product_patch_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("Removing a required property should be invalid"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Required property description not provided"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that adding a property that already exists is rejected
+    fn test_patch_product_add_existing_property() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
+        transaction_context.add_product(PRODUCT_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_patch_action = make_product_patch_action(vec![PropertyPatchBuilder::new()
+            .with_operation(PatchOperation::Add)
+            .with_name("price".into())
+            .with_value(make_number_property("price", 4))
+            .build()
+            .unwrap()]);
+
+        match transaction_handler.patch_product(
+            &product_patch_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            0,
+        ) {
+            Ok(()) => panic!("Adding an existing property should be invalid"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Cannot add property, it already exists: price"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if ProductDeleteAction is valid an OK is returned and a Product is marked as deleted in state
+    fn test_delete_product_handler_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_delete_action = make_product_delete_action(PRODUCT_ID);
 
         assert!(transaction_handler
             .delete_product(
                 &product_delete_action,
                 &mut state,
                 PUBLIC_KEY,
-                &perm_checker
+                &perm_checker,
+                0,
             )
             .is_ok());
 
-        let product = state.get_product(PRODUCT_ID).expect("No product found");
+        let product = state
+            .get_product(PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
 
-        assert_eq!(product, None);
+        assert!(*product.deleted());
+        assert_eq!(product.deleted_reason(), "discontinued");
+        assert_eq!(*product.revisions()[0].change(), RevisionChange::Delete);
     }
 
     #[test]
     /// Test that if ProductDeleteAction is valid an OK is returned and a
-    /// second product is deleted from state
+    /// second product is marked as deleted in state
     fn test_delete_second_product_handler_valid() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
@@ -893,12 +2056,55 @@ mod tests {
                 &mut state,
                 PUBLIC_KEY,
                 &perm_checker,
+                0,
             )
             .is_ok());
 
-        let product = state.get_product(PRODUCT_2_ID).expect("No product found");
+        let product = state
+            .get_product(PRODUCT_2_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+
+        assert!(*product.deleted());
+        assert_eq!(product.deleted_reason(), "discontinued");
+        assert_eq!(*product.revisions()[0].change(), RevisionChange::Delete);
+    }
+
+    #[test]
+    /// Test that a deleted product can no longer be updated
+    fn test_update_deleted_product() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_schema();
+        transaction_context.add_product(PRODUCT_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        transaction_handler
+            .delete_product(
+                &make_product_delete_action(PRODUCT_ID),
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker,
+                1,
+            )
+            .expect("Failed to delete product");
 
-        assert_eq!(product, None);
+        match transaction_handler.update_product(
+            &make_product_update_action(),
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+            2,
+        ) {
+            Ok(()) => panic!("Updating a deleted product should be invalid"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Product has been deleted"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
     }
 
     #[test]
@@ -919,6 +2125,7 @@ mod tests {
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
             Ok(()) => panic!(
                 "Agent should not have can_delete_product role, InvalidTransaction should be returned"
@@ -951,6 +2158,7 @@ mod tests {
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
+            0,
         ) {
             Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
@@ -960,10 +2168,152 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Test that if ProductCreateProposalAction is valid an OK is returned and an open
+    /// proposal is added to state
+    fn test_create_proposal_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_admin(ADMIN_PUBLIC_KEY, AGENT_ORG_ID);
+        transaction_context.add_org(RECEIVING_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+
+        assert!(transaction_handler
+            .create_proposal(
+                &make_product_create_proposal_action(),
+                &mut state,
+                ADMIN_PUBLIC_KEY,
+                1,
+            )
+            .is_ok());
+
+        let proposals = state
+            .get_proposal_list(PRODUCT_ID, RECEIVING_ORG_ID)
+            .expect("Failed to fetch proposals")
+            .expect("No proposals found");
+
+        assert_eq!(proposals.proposals().len(), 1);
+        let proposal = &proposals.proposals()[0];
+        assert_eq!(proposal.issuing_agent(), ADMIN_PUBLIC_KEY);
+        assert_eq!(proposal.issuing_org(), AGENT_ORG_ID);
+        assert_eq!(proposal.receiving_org(), RECEIVING_ORG_ID);
+        assert_eq!(*proposal.status(), Status::Open);
+    }
+
+    #[test]
+    /// Test that ProductCreateProposalAction is invalid if the signer is not an admin of the
+    /// organization that owns the product
+    fn test_create_proposal_signer_not_admin() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(RECEIVING_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+
+        match transaction_handler.create_proposal(
+            &make_product_create_proposal_action(),
+            &mut state,
+            PUBLIC_KEY,
+            1,
+        ) {
+            Ok(()) => panic!("Signer is not an admin, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "The signing Agent is not an admin of the organization: {}",
+                    PUBLIC_KEY
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the receiving org's admin accepts the proposal, the product owner is changed
+    fn test_answer_proposal_accept_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_admin(ADMIN_PUBLIC_KEY, AGENT_ORG_ID);
+        transaction_context.add_admin(RECEIVING_ADMIN_PUBLIC_KEY, RECEIVING_ORG_ID);
+        transaction_context.add_org(RECEIVING_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        transaction_handler
+            .create_proposal(
+                &make_product_create_proposal_action(),
+                &mut state,
+                ADMIN_PUBLIC_KEY,
+                1,
+            )
+            .expect("Failed to create proposal");
+
+        assert!(transaction_handler
+            .answer_proposal(
+                &make_product_answer_proposal_action(Response::Accept),
+                &mut state,
+                RECEIVING_ADMIN_PUBLIC_KEY,
+                0,
+            )
+            .is_ok());
+
+        let product = state
+            .get_product(PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+        assert_eq!(product.owner(), RECEIVING_ORG_ID);
+
+        let proposals = state
+            .get_proposal_list(PRODUCT_ID, RECEIVING_ORG_ID)
+            .expect("Failed to fetch proposals")
+            .expect("No proposals found");
+        assert_eq!(*proposals.proposals()[0].status(), Status::Accepted);
+    }
+
+    #[test]
+    /// Test that only an admin of the receiving org can accept the proposal
+    fn test_answer_proposal_accept_signer_not_receiving_admin() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_admin(ADMIN_PUBLIC_KEY, AGENT_ORG_ID);
+        transaction_context.add_org(RECEIVING_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        transaction_handler
+            .create_proposal(
+                &make_product_create_proposal_action(),
+                &mut state,
+                ADMIN_PUBLIC_KEY,
+                1,
+            )
+            .expect("Failed to create proposal");
+
+        match transaction_handler.answer_proposal(
+            &make_product_answer_proposal_action(Response::Accept),
+            &mut state,
+            ADMIN_PUBLIC_KEY,
+            0,
+        ) {
+            Ok(()) => panic!("Signer is not a receiving org admin, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "The signing Agent is not associated with the organization: {} != {}",
+                    AGENT_ORG_ID, RECEIVING_ORG_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
     fn make_product() -> Product {
         ProductBuilder::new()
             .with_product_id(PRODUCT_ID.to_string())
             .with_owner(AGENT_ORG_ID.to_string())
+            .with_schema(SCHEMA_NAME.into())
             .with_product_type(ProductType::GS1)
             .with_properties(make_properties())
             .build()
@@ -975,6 +2325,7 @@ mod tests {
             ProductBuilder::new()
                 .with_product_id(product_ids[0].to_string())
                 .with_owner(AGENT_ORG_ID.to_string())
+                .with_schema(SCHEMA_NAME.into())
                 .with_product_type(ProductType::GS1)
                 .with_properties(make_properties())
                 .build()
@@ -982,6 +2333,7 @@ mod tests {
             ProductBuilder::new()
                 .with_product_id(product_ids[1].to_string())
                 .with_owner(AGENT_ORG_ID.to_string())
+                .with_schema(SCHEMA_NAME.into())
                 .with_product_type(ProductType::GS1)
                 .with_properties(make_properties())
                 .build()
@@ -993,6 +2345,7 @@ mod tests {
         ProductBuilder::new()
             .with_product_id(PRODUCT_ID.to_string())
             .with_owner(AGENT_ORG_ID.to_string())
+            .with_schema(SCHEMA_NAME.into())
             .with_product_type(ProductType::GS1)
             .with_properties(make_updated_properties())
             .build()
@@ -1040,11 +2393,18 @@ mod tests {
     }
 
     fn make_product_create_action() -> ProductCreateAction {
+        make_product_create_action_with_properties(make_properties())
+    }
+
+    fn make_product_create_action_with_properties(
+        properties: Vec<PropertyValue>,
+    ) -> ProductCreateAction {
         ProductCreateActionBuilder::new()
             .with_product_id(PRODUCT_ID.to_string())
             .with_owner(AGENT_ORG_ID.to_string())
+            .with_schema(SCHEMA_NAME.into())
             .with_product_type(ProductType::GS1)
-            .with_properties(make_properties())
+            .with_properties(properties)
             .build()
             .expect("Failed to build ProductCreateAction")
     }
@@ -1058,11 +2418,59 @@ mod tests {
             .expect("Failed to build ProductUpdateAction")
     }
 
+    fn make_product_patch_action(patches: Vec<PropertyPatch>) -> ProductPatchAction {
+        ProductPatchActionBuilder::new()
+            .with_product_id(PRODUCT_ID.to_string())
+            .with_product_type(ProductType::GS1)
+            .with_patches(patches)
+            .build()
+            .expect("Failed to build ProductPatchAction")
+    }
+
+    fn make_number_property(name: &str, value: i64) -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(name.into())
+            .with_data_type(DataType::Number)
+            .with_number_value(value)
+            .build()
+            .unwrap()
+    }
+
+    fn make_color_property() -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name("color".into())
+            .with_data_type(DataType::Enum)
+            .with_enum_value(1)
+            .build()
+            .unwrap()
+    }
+
     fn make_product_delete_action(product_id: &str) -> ProductDeleteAction {
         ProductDeleteActionBuilder::new()
             .with_product_id(product_id.to_string())
             .with_product_type(ProductType::GS1)
+            .with_reason("discontinued".to_string())
             .build()
             .expect("Failed to build ProductDeleteAction")
     }
+
+    fn make_product_create_proposal_action() -> ProductCreateProposalAction {
+        ProductCreateProposalActionBuilder::new()
+            .with_product_id(PRODUCT_ID.to_string())
+            .with_product_type(ProductType::GS1)
+            .with_receiving_org(RECEIVING_ORG_ID.to_string())
+            .with_terms("brand sale".to_string())
+            .build()
+            .expect("Failed to build ProductCreateProposalAction")
+    }
+
+    fn make_product_answer_proposal_action(response: Response) -> ProductAnswerProposalAction {
+        ProductAnswerProposalActionBuilder::new()
+            .with_product_id(PRODUCT_ID.to_string())
+            .with_product_type(ProductType::GS1)
+            .with_receiving_org(RECEIVING_ORG_ID.to_string())
+            .with_response(response)
+            .build()
+            .expect("Failed to build ProductAnswerProposalAction")
+    }
 }
@@ -20,12 +20,17 @@ cfg_if! {
     }
 }
 
-use dgc_config::protocol::product::payload::{Action, ProductCreateAction, ProductPayload};
+use dgc_config::protocol::product::payload::{
+    Action, ProductCreateAction, ProductCreateProposalAction, ProductPayload,
+};
 
 pub fn validate_payload(payload: &ProductPayload) -> Result<(), ApplyError> {
     validate_timestamp(*payload.timestamp())?;
     match payload.action() {
         Action::ProductCreate(action_payload) => validate_product_create_action(action_payload),
+        Action::ProductCreateProposal(action_payload) => {
+            validate_product_create_proposal_action(action_payload)
+        }
         _ => Ok(()),
     }
 }
@@ -46,6 +51,22 @@ fn validate_product_create_action(
     Ok(())
 }
 
+fn validate_product_create_proposal_action(
+    product_create_proposal_action: &ProductCreateProposalAction,
+) -> Result<(), ApplyError> {
+    if product_create_proposal_action.product_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "product_id cannot be empty string",
+        )));
+    }
+    if product_create_proposal_action.receiving_org() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Receiving organization cannot be empty string",
+        )));
+    }
+    Ok(())
+}
+
 fn validate_timestamp(timestamp: u64) -> Result<(), ApplyError> {
     match timestamp {
         0 => Err(ApplyError::InvalidTransaction(String::from(
@@ -13,7 +13,11 @@ cfg_if! {
 
 use dgc_config::protocol::pike::state::{Agent, AgentList};
 use dgc_config::protocol::pike::state::{Organization, OrganizationList};
-use dgc_config::protocol::product::state::{Product, ProductList, ProductListBuilder};
+use dgc_config::protocol::product::state::{
+    Product, ProductList, ProductListBuilder, ProductProposalList,
+};
+use dgc_config::protocol::schema::state::{Schema, SchemaList};
+use dgc_config::protocol::track_and_trace::state::{Record, RecordList};
 use dgc_config::protos::{FromBytes, IntoBytes};
 
 //use crate::addressing::*;
@@ -104,59 +108,74 @@ impl<'a> ProductState<'a> {
         Ok(())
     }
 
-    // Currently product_id = gtin
-    pub fn remove_product(&self, product_id: &str) -> Result<(), ApplyError> {
-        let address = make_product_address(product_id);
+    pub fn get_proposal_list(
+        &self,
+        product_id: &str,
+        receiving_org: &str,
+    ) -> Result<Option<ProductProposalList>, ApplyError> {
+        let address = make_product_proposal_address(product_id, receiving_org);
         let d = self.context.get_state_entry(&address)?;
-        let products = match d {
-            Some(packed) => match ProductList::from_bytes(packed.as_slice()) {
-                Ok(product_list) => product_list.products().to_vec(),
-                Err(err) => {
-                    return Err(ApplyError::InternalError(format!(
-                        "Cannot deserialize product list: {:?}",
-                        err
-                    )));
-                }
+        match d {
+            Some(packed) => match ProductProposalList::from_bytes(packed.as_slice()) {
+                Ok(proposals) => Ok(Some(proposals)),
+                Err(err) => Err(ApplyError::InternalError(format!(
+                    "Cannot deserialize product proposal list: {:?}",
+                    err,
+                ))),
             },
-            None => vec![],
-        };
-
-        // Filter out the product we are deleting
-        let filtered_products = products
-            .into_iter()
-            .filter(|p| p.product_id() != product_id)
-            .collect::<Vec<_>>();
-
-        // If the only product at the address was the one we are removing, we can delete the entire state entry
-        // Else, we can set the the filtered product list at the address
-        if filtered_products.is_empty() {
-            self.context
-                .delete_state_entries(&[address])
-                .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
-        } else {
-            let product_list = ProductListBuilder::new()
-                .with_products(filtered_products)
-                .build()
-                .map_err(|err| {
-                    ApplyError::InvalidTransaction(format!("Cannot build product list: {:?}", err))
-                })?;
-
-            let serialized = match product_list.into_bytes() {
-                Ok(serialized) => serialized,
-                Err(_) => {
-                    return Err(ApplyError::InternalError(String::from(
-                        "Cannot serialize product list",
-                    )));
-                }
-            };
-            self.context
-                .set_state_entry(address, serialized)
-                .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+            None => Ok(None),
         }
+    }
 
+    pub fn set_proposal_list(
+        &self,
+        product_id: &str,
+        receiving_org: &str,
+        proposals: ProductProposalList,
+    ) -> Result<(), ApplyError> {
+        let address = make_product_proposal_address(product_id, receiving_org);
+        let serialized = match proposals.into_bytes() {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                return Err(ApplyError::InternalError(format!(
+                    "Cannot serialize product proposal list: {:?}",
+                    err
+                )));
+            }
+        };
+        self.context
+            .set_state_entry(address, serialized)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
         Ok(())
     }
 
+    /// Gets a Schema. Handles retrieving the correct schema from a SchemaList.
+    pub fn get_schema(&self, schema_name: &str) -> Result<Option<Schema>, ApplyError> {
+        let address = make_schema_address(schema_name);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let schemas = match SchemaList::from_bytes(packed.as_slice()) {
+                    Ok(schemas) => schemas,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize schema list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the schema with the correct name
+                Ok(schemas
+                    .schemas()
+                    .iter()
+                    .find(|s| s.name() == schema_name)
+                    .cloned())
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Gets a Pike Agent. Handles retrieving the correct agent from an AgentList.
     pub fn get_agent(&self, public_key: &str) -> Result<Option<Agent>, ApplyError> {
         //let address = compute_agent_address(public_key);
@@ -211,6 +230,31 @@ impl<'a> ProductState<'a> {
             None => Ok(None),
         }
     }
+
+    pub fn get_record(&self, record_id: &str) -> Result<Option<Record>, ApplyError> {
+        let address = make_record_address(record_id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let records = match RecordList::from_bytes(packed.as_slice()) {
+                    Ok(records) => records,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize record list: {:?}",
+                            err,
+                        )))
+                    }
+                };
+
+                Ok(records
+                    .records()
+                    .iter()
+                    .find(|r| r.record_id() == record_id)
+                    .cloned())
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -345,6 +389,7 @@ mod tests {
         ProductBuilder::new()
             .with_product_id(PRODUCT_ID.to_string())
             .with_owner("some_owner".to_string())
+            .with_schema("gs1_product".into())
             .with_product_type(ProductType::GS1)
             .with_properties(make_properties())
             .build()
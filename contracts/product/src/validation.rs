@@ -20,6 +20,9 @@ cfg_if! {
     }
 }
 
+use dgc_config::protocol::schema::state::{PropertyValue, Schema};
+use dgc_config::protocol::schema::validation::validate_property_values;
+
 /* The purpose of this file is to programmatically express the equation used to validate a GTIN
 It validates gtin format to avoid mistype errors similar to a credit card validation
 Check digit validation: (https://www.gs1.org/services/how-calculate-check-digit-manually) */
@@ -54,6 +57,65 @@ pub fn validate_gtin(gtin: &str) -> Result<(), ApplyError> {
     }
 }
 
+/// Validates the product properties against the Schema the product declares. Every
+/// required property must be provided and every provided property must be defined by the
+/// schema and valid for its definition, including values nested in structs and lists.
+pub fn validate_properties(
+    schema: &Schema,
+    properties: &[PropertyValue],
+) -> Result<(), ApplyError> {
+    validate_property_values(schema.properties(), properties)
+        .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))
+}
+
+/// Checks that properties marked immutable by the schema keep the value they had when
+/// the product was created.
+pub fn validate_immutable_properties(
+    schema: &Schema,
+    previous: &[PropertyValue],
+    properties: &[PropertyValue],
+) -> Result<(), ApplyError> {
+    for definition in schema.properties() {
+        if !*definition.constraints().immutable() {
+            continue;
+        }
+        let old_value = previous.iter().find(|v| v.name() == definition.name());
+        let new_value = properties.iter().find(|v| v.name() == definition.name());
+        if old_value != new_value {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Property {} is immutable and cannot be changed",
+                definition.name()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Splits the comma separated `gs1_company_prefixes` organization metadata value into
+/// its individual prefixes
+pub fn parse_gs1_company_prefixes(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|prefix| prefix.trim().to_string())
+        .filter(|prefix| !prefix.is_empty())
+        .collect()
+}
+
+/// Checks that the GS1 company prefix is at the start of the GTIN. GTIN-14 identifiers carry
+/// an indicator digit in front of the company prefix, which is stripped first so that only
+/// the rest of the GTIN is matched.
+pub fn gtin_has_company_prefix(gtin: &str, prefix: &str) -> bool {
+    if prefix.is_empty() {
+        return false;
+    }
+    let gtin = if gtin.chars().count() == 14 {
+        gtin.get(1..).unwrap_or_default()
+    } else {
+        gtin
+    };
+    gtin.starts_with(prefix)
+}
+
 fn check_digit_validation(gtin: &str) -> Result<(), ApplyError> {
     let mut gtin_vec: Vec<char> = gtin.chars().collect();
     // Remove the check digit from the gtin_vec and store it for later
@@ -208,4 +270,26 @@ mod tests {
             "InvalidTransaction: Invalid GTIN, GTIN-8 is not supported at this time: 40170725"
         );
     }
+
+    #[test]
+    // This tests that the gs1_company_prefixes metadata value is split and trimmed
+    fn parse_company_prefixes() {
+        assert_eq!(
+            parse_gs1_company_prefixes("0614141, 688955,,"),
+            vec!["0614141".to_string(), "688955".to_string()]
+        );
+        assert!(parse_gs1_company_prefixes("").is_empty());
+    }
+
+    #[test]
+    // This tests that a GTIN only matches a company prefix found at its start
+    fn company_prefix_match() {
+        assert!(gtin_has_company_prefix("688955434684", "688955"));
+        assert!(gtin_has_company_prefix("10012345678902", "0012345"));
+        // The indicator digit of a GTIN-14 is not part of the company prefix
+        assert!(!gtin_has_company_prefix("10012345678902", "1001234"));
+        assert!(!gtin_has_company_prefix("9781981855728", "688955"));
+        assert!(!gtin_has_company_prefix("688955434684", "434684"));
+        assert!(!gtin_has_company_prefix("688955434684", ""));
+    }
 }
@@ -18,10 +18,14 @@ cfg_if! {
 
 use dgc_config::addressing::*;
 use dgc_config::permissions::PermissionChecker;
+use dgc_config::protocol::pike::state::Agent;
 use dgc_config::protocol::schema::payload::{
-    Action, SchemaCreateAction, SchemaPayload, SchemaUpdateAction,
+    Action, SchemaCreateAction, SchemaDeprecateAction, SchemaPayload, SchemaTransferAction,
+    SchemaUpdateAction,
+};
+use dgc_config::protocol::schema::state::{
+    DataType, PropertyDefinition, PropertyDefinitionBuilder, Schema, SchemaBuilder,
 };
-use dgc_config::protocol::schema::state::SchemaBuilder;
 use dgc_config::protos::FromBytes;
 
 use crate::payload::validate_payload;
@@ -103,6 +107,12 @@ impl TransactionHandler for GridSchemaTransactionHandler {
             Action::SchemaUpdate(schema_update_payload) => {
                 schema_update(schema_update_payload, signer, &state, &perm_checker)
             }
+            Action::SchemaDeprecate(schema_deprecate_payload) => {
+                schema_deprecate(schema_deprecate_payload, signer, &state, &perm_checker)
+            }
+            Action::SchemaTransfer(schema_transfer_payload) => {
+                schema_transfer(schema_transfer_payload, signer, &state, &perm_checker)
+            }
         }
     }
 }
@@ -161,7 +171,6 @@ fn schema_update(
     perm_checker: &PermissionChecker,
 ) -> Result<(), ApplyError> {
     let schema_name = payload.schema_name();
-    let mut new_properties = payload.properties().to_vec();
 
     let schema = match state.get_schema(schema_name)? {
         Some(schema) => schema,
@@ -174,7 +183,105 @@ fn schema_update(
     };
 
     check_permission(perm_checker, signer, "can_update_schema")?;
+    check_owner_agent(state, signer, &schema)?;
+    check_not_deprecated(&schema)?;
+
+    let properties = apply_compatible_changes(schema.properties(), payload)?;
+    let version = schema.version() + 1;
+
+    let schema = schema
+        .into_builder()
+        .with_version(version)
+        .with_properties(properties)
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build schema: {}", err)))?;
+
+    state.set_schema(schema_name, schema)
+}
+
+fn schema_deprecate(
+    payload: &SchemaDeprecateAction,
+    signer: &str,
+    state: &GridSchemaState,
+    perm_checker: &PermissionChecker,
+) -> Result<(), ApplyError> {
+    let schema_name = payload.schema_name();
+
+    let schema = match state.get_schema(schema_name)? {
+        Some(schema) => schema,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Schema with name {} does not exist",
+                schema_name
+            )));
+        }
+    };
+
+    check_permission(perm_checker, signer, "can_deprecate_schema")?;
+    check_owner_agent(state, signer, &schema)?;
+    check_not_deprecated(&schema)?;
+
+    let schema = schema
+        .into_builder()
+        .with_deprecated(true)
+        .with_deprecated_reason(payload.reason().to_string())
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build schema: {}", err)))?;
+
+    state.set_schema(schema_name, schema)
+}
+
+fn schema_transfer(
+    payload: &SchemaTransferAction,
+    signer: &str,
+    state: &GridSchemaState,
+    perm_checker: &PermissionChecker,
+) -> Result<(), ApplyError> {
+    let schema_name = payload.schema_name();
+    let new_owner = payload.new_owner();
+
+    let schema = match state.get_schema(schema_name)? {
+        Some(schema) => schema,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Schema with name {} does not exist",
+                schema_name
+            )));
+        }
+    };
+
+    check_permission(perm_checker, signer, "can_transfer_schema")?;
+    check_owner_agent(state, signer, &schema)?;
 
+    if schema.owner() == new_owner {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Schema {} is already owned by {}",
+            schema_name, new_owner
+        )));
+    }
+
+    if state.get_organization(new_owner)?.is_none() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Organization does not exist: {}",
+            new_owner
+        )));
+    }
+
+    let schema = schema
+        .into_builder()
+        .with_owner(new_owner.to_string())
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build schema: {}", err)))?;
+
+    state.set_schema(schema_name, schema)
+}
+
+/// Checks that the signer is an active Agent of the organization that owns the schema
+fn check_owner_agent(
+    state: &GridSchemaState,
+    signer: &str,
+    schema: &Schema,
+) -> Result<Agent, ApplyError> {
     let agent = match state.get_agent(signer)? {
         Some(agent) => agent,
         None => {
@@ -200,31 +307,128 @@ fn schema_update(
         )));
     }
 
-    let mut properties = schema.properties().to_vec();
-    properties.sort_by_key(|p| p.name().to_string());
+    Ok(agent)
+}
+
+fn check_not_deprecated(schema: &Schema) -> Result<(), ApplyError> {
+    if *schema.deprecated() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Schema {} has been deprecated",
+            schema.name()
+        )));
+    }
+    Ok(())
+}
+
+/// Applies the changes in a SchemaUpdateAction to the existing property definitions. Only
+/// changes that keep records created under an earlier version of the schema valid are allowed:
+///
+/// * new properties may be added, but they may not be required
+/// * options may be appended to ENUM properties, existing options keep their position
+/// * properties may be deprecated, which also makes them optional
+/// * required properties may be made optional
+fn apply_compatible_changes(
+    existing: &[PropertyDefinition],
+    payload: &SchemaUpdateAction,
+) -> Result<Vec<PropertyDefinition>, ApplyError> {
+    let mut properties = existing.to_vec();
 
-    for property in new_properties.iter() {
-        if properties
-            .binary_search_by_key(&property.name().to_string(), |p| p.name().to_string())
-            .is_ok()
-        {
+    for property in payload.properties() {
+        if properties.iter().any(|p| p.name() == property.name()) {
             return Err(ApplyError::InvalidTransaction(format!(
                 "Schema already has PropertyDefination with name {}",
                 property.name()
             )));
         }
+        if *property.required() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Cannot add required property {} to an existing schema",
+                property.name()
+            )));
+        }
+        // Existing records have no data key to encrypt the values of a new property with
+        if *property.confidential() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Cannot add confidential property {} to an existing schema",
+                property.name()
+            )));
+        }
+        properties.push(property.clone());
     }
-    properties.append(&mut new_properties);
 
-    let schema = SchemaBuilder::new()
-        .with_name(schema.name().into())
-        .with_description(schema.description().into())
-        .with_owner(schema.owner().into())
-        .with_properties(properties)
-        .build()
-        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build schema: {}", err)))?;
+    for addition in payload.enum_options_additions() {
+        let index = find_property(&properties, addition.property_name())?;
+        let definition = &properties[index];
+        if definition.data_type() != &DataType::Enum {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Cannot add enum options to property {}, it is not an ENUM",
+                addition.property_name()
+            )));
+        }
+        let mut enum_options = definition.enum_options().to_vec();
+        for option in addition.enum_options() {
+            if enum_options.contains(option) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Property {} already has enum option {}",
+                    addition.property_name(),
+                    option
+                )));
+            }
+            enum_options.push(option.to_string());
+        }
+        properties[index] = rebuild_property(
+            definition.clone().into_builder().with_enum_options(enum_options),
+        )?;
+    }
 
-    state.set_schema(schema_name, schema)
+    for name in payload.deprecated_properties() {
+        let index = find_property(&properties, name)?;
+        if *properties[index].deprecated() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Property {} is already deprecated",
+                name
+            )));
+        }
+        properties[index] = rebuild_property(
+            properties[index]
+                .clone()
+                .into_builder()
+                .with_deprecated(true)
+                .with_required(false),
+        )?;
+    }
+
+    for name in payload.optional_properties() {
+        let index = find_property(&properties, name)?;
+        if !*properties[index].required() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Property {} is not required",
+                name
+            )));
+        }
+        properties[index] =
+            rebuild_property(properties[index].clone().into_builder().with_required(false))?;
+    }
+
+    Ok(properties)
+}
+
+fn find_property(properties: &[PropertyDefinition], name: &str) -> Result<usize, ApplyError> {
+    properties
+        .iter()
+        .position(|p| p.name() == name)
+        .ok_or_else(|| {
+            ApplyError::InvalidTransaction(format!(
+                "Schema does not have PropertyDefination with name {}",
+                name
+            ))
+        })
+}
+
+fn rebuild_property(builder: PropertyDefinitionBuilder) -> Result<PropertyDefinition, ApplyError> {
+    builder.build().map_err(|err| {
+        ApplyError::InvalidTransaction(format!("Cannot build property definition: {}", err))
+    })
 }
 
 fn check_permission(
@@ -250,8 +454,13 @@ mod tests {
     use std::collections::HashMap;
 
     use dgc_config::addressing::*;
-    use dgc_config::protocol::pike::state::{AgentBuilder, AgentListBuilder};
-    use dgc_config::protocol::schema::payload::{SchemaCreateBuilder, SchemaUpdateBuilder};
+    use dgc_config::protocol::pike::state::{
+        AgentBuilder, AgentListBuilder, OrganizationBuilder, OrganizationListBuilder,
+    };
+    use dgc_config::protocol::schema::payload::{
+        EnumOptionsAdditionBuilder, SchemaCreateBuilder, SchemaDeprecateBuilder,
+        SchemaTransferBuilder, SchemaUpdateBuilder,
+    };
     use dgc_config::protocol::schema::state::{
         DataType, PropertyDefinitionBuilder, SchemaBuilder, SchemaListBuilder,
     };
@@ -319,6 +528,8 @@ mod tests {
                 .with_roles(vec![
                     "can_create_schema".to_string(),
                     "can_update_schema".to_string(),
+                    "can_deprecate_schema".to_string(),
+                    "can_transfer_schema".to_string(),
                 ])
                 .build()
                 .unwrap();
@@ -388,6 +599,23 @@ mod tests {
             self.set_state_entry(agent_address, agent_bytes).unwrap();
         }
 
+        fn add_org(&self, org_id: &str) {
+            let org = OrganizationBuilder::new()
+                .with_org_id(org_id.to_string())
+                .with_name("test_org_name".to_string())
+                .with_address("test_org_address".to_string())
+                .build()
+                .unwrap();
+
+            let org_list = OrganizationListBuilder::new()
+                .with_organizations(vec![org])
+                .build()
+                .unwrap();
+            let org_bytes = org_list.into_bytes().unwrap();
+            let org_address = make_org_address(org_id);
+            self.set_state_entry(org_address, org_bytes).unwrap();
+        }
+
         fn add_schema(&self) {
             let builder = PropertyDefinitionBuilder::new();
             let property_definition = builder
@@ -822,4 +1050,268 @@ mod tests {
 
         assert!(schema_update(&action, signer, &state, &perm_checker).is_ok());
     }
+
+    #[test]
+    // Test that compatible evolution changes are applied and the schema version is incremented
+    fn test_update_schema_handler_evolution() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let addition = EnumOptionsAdditionBuilder::new()
+            .with_property_name("TEST".to_string())
+            .with_enum_options(vec!["Four".to_string()])
+            .build()
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_enum_options_additions(vec![addition])
+            .with_deprecated_properties(vec!["TEST".to_string()])
+            .build()
+            .unwrap();
+
+        assert!(schema_update(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state
+            .get_schema("TestSchema")
+            .expect("Failed to fetch schema")
+            .expect("No schema found");
+        assert_eq!(*schema.version(), 2);
+        assert_eq!(
+            schema.properties()[0].enum_options(),
+            &[
+                "One".to_string(),
+                "Two".to_string(),
+                "Three".to_string(),
+                "Four".to_string()
+            ]
+        );
+        assert!(*schema.properties()[0].deprecated());
+    }
+
+    #[test]
+    // Test that deprecating a required property also makes it optional
+    fn test_update_schema_handler_deprecate_required_property() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let schema = state
+            .get_schema("TestSchema")
+            .expect("Failed to fetch schema")
+            .expect("No schema found");
+        let properties = schema
+            .properties()
+            .iter()
+            .map(|property| property.clone().into_builder().with_required(true).build().unwrap())
+            .collect::<Vec<_>>();
+        state
+            .set_schema(
+                "TestSchema",
+                schema.into_builder().with_properties(properties).build().unwrap(),
+            )
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_deprecated_properties(vec!["TEST".to_string()])
+            .build()
+            .unwrap();
+
+        assert!(schema_update(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state
+            .get_schema("TestSchema")
+            .expect("Failed to fetch schema")
+            .expect("No schema found");
+        assert!(*schema.properties()[0].deprecated());
+        assert!(!*schema.properties()[0].required());
+    }
+
+    #[test]
+    // Test that adding a required property to an existing schema is rejected
+    fn test_update_schema_handler_required_property() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let property_definition = PropertyDefinitionBuilder::new()
+            .with_name("NEW".to_string())
+            .with_data_type(DataType::String)
+            .with_required(true)
+            .build()
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_properties(vec![property_definition])
+            .build()
+            .unwrap();
+
+        match schema_update(&action, signer, &state, &perm_checker) {
+            Ok(()) => panic!("Required property added, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Cannot add required property NEW to an existing schema"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that adding a confidential property to an existing schema is rejected
+    fn test_update_schema_handler_confidential_property() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let property_definition = PropertyDefinitionBuilder::new()
+            .with_name("PRICE".to_string())
+            .with_data_type(DataType::Number)
+            .with_confidential(true)
+            .build()
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_properties(vec![property_definition])
+            .build()
+            .unwrap();
+
+        match schema_update(&action, signer, &state, &perm_checker) {
+            Ok(()) => panic!("Confidential property added, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Cannot add confidential property PRICE"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that adding an enum option that already exists is rejected
+    fn test_update_schema_handler_duplicate_enum_option() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let addition = EnumOptionsAdditionBuilder::new()
+            .with_property_name("TEST".to_string())
+            .with_enum_options(vec!["Two".to_string()])
+            .build()
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_enum_options_additions(vec![addition])
+            .build()
+            .unwrap();
+
+        match schema_update(&action, signer, &state, &perm_checker) {
+            Ok(()) => panic!("Duplicate enum option, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Property TEST already has enum option Two"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that a deprecated schema is marked as such and can no longer be updated
+    fn test_deprecate_schema_handler_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let action = SchemaDeprecateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_reason("superseded".to_string())
+            .build()
+            .unwrap();
+
+        assert!(schema_deprecate(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state.get_schema("TestSchema").unwrap().unwrap();
+        assert!(schema.deprecated());
+        assert_eq!(schema.deprecated_reason(), "superseded");
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_deprecated_properties(vec!["TEST".to_string()])
+            .build()
+            .unwrap();
+
+        match schema_update(&action, signer, &state, &perm_checker) {
+            Ok(()) => panic!("Schema is deprecated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Schema TestSchema has been deprecated"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that the ownership of a schema can be transferred to an existing organization
+    fn test_transfer_schema_handler_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        transaction_context.add_org("new_org");
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let action = SchemaTransferBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_new_owner("new_org".to_string())
+            .build()
+            .unwrap();
+
+        assert!(schema_transfer(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state.get_schema("TestSchema").unwrap().unwrap();
+        assert_eq!(schema.owner(), "new_org");
+    }
+
+    #[test]
+    // Test that if the new owner of a schema does not exist an InvalidTransaction is returned
+    fn test_transfer_schema_handler_org_does_not_exist() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let action = SchemaTransferBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_new_owner("new_org".to_string())
+            .build()
+            .unwrap();
+
+        match schema_transfer(&action, signer, &state, &perm_checker) {
+            Ok(()) => panic!("Organization does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Organization does not exist: new_org"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
 }
@@ -21,13 +21,19 @@ cfg_if! {
 }
 
 use dgc_config::protocol::schema::payload::{
-    Action, SchemaCreateAction, SchemaPayload, SchemaUpdateAction,
+    Action, SchemaCreateAction, SchemaDeprecateAction, SchemaPayload, SchemaTransferAction,
+    SchemaUpdateAction,
+};
+use dgc_config::protocol::schema::state::{
+    DataType, LatLong, LatLongBuilder, PropertyDefinition, MAX_NUMBER_EXPONENT, MAX_PAGES,
 };
 
 pub fn validate_payload(payload: &SchemaPayload) -> Result<(), ApplyError> {
     match payload.action() {
         Action::SchemaCreate(payload) => validate_schema_create_action(payload),
         Action::SchemaUpdate(payload) => validate_schema_update_action(payload),
+        Action::SchemaDeprecate(payload) => validate_schema_deprecate_action(payload),
+        Action::SchemaTransfer(payload) => validate_schema_transfer_action(payload),
     }
 }
 
@@ -43,7 +49,11 @@ fn validate_schema_create_action(create_action: &SchemaCreateAction) -> Result<(
             "Properties must not be empty",
         )));
     }
-    Ok(())
+    validate_number_exponents(create_action.properties())?;
+    validate_paging(create_action.properties())?;
+    validate_alert_rules(create_action.properties())?;
+    validate_geofences(create_action.properties())?;
+    validate_property_definitions(create_action.properties())
 }
 
 fn validate_schema_update_action(update_action: &SchemaUpdateAction) -> Result<(), ApplyError> {
@@ -53,9 +63,207 @@ fn validate_schema_update_action(update_action: &SchemaUpdateAction) -> Result<(
         )));
     }
 
-    if update_action.properties().is_empty() {
+    if update_action.is_empty() {
         return Err(ApplyError::InvalidTransaction(String::from(
-            "Properties must not be empty",
+            "Schema update must contain at least one change",
+        )));
+    }
+    validate_number_exponents(update_action.properties())?;
+    validate_paging(update_action.properties())?;
+    validate_alert_rules(update_action.properties())?;
+    validate_geofences(update_action.properties())?;
+    validate_property_definitions(update_action.properties())
+}
+
+/// Checks that NUMBER exponents, including those of STRUCT fields and LIST elements, are
+/// within the range of exponents that can be applied to an i64 number value.
+fn validate_number_exponents(properties: &[PropertyDefinition]) -> Result<(), ApplyError> {
+    for property in properties {
+        if property.number_exponent().abs() > MAX_NUMBER_EXPONENT {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Number exponent of property {} must be between -{} and {}",
+                property.name(),
+                MAX_NUMBER_EXPONENT,
+                MAX_NUMBER_EXPONENT
+            )));
+        }
+        validate_number_exponents(property.struct_properties())?;
+        if let Some(list_element) = property.list_element() {
+            validate_number_exponents(std::slice::from_ref(list_element))?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the history of each property can be paged: pages hold at least one value, and
+/// page numbers fit in the last four hex characters of a page's address.
+fn validate_paging(properties: &[PropertyDefinition]) -> Result<(), ApplyError> {
+    for property in properties {
+        if *property.page_size() == 0 {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Page size of property {} must be greater than 0",
+                property.name()
+            )));
+        }
+        if *property.max_pages() > MAX_PAGES {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Max pages of property {} must not be greater than {}",
+                property.name(),
+                MAX_PAGES
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that alert rules are only set on NUMBER properties that are not confidential, as
+/// the values of other properties can not be compared to their bounds.
+fn validate_alert_rules(properties: &[PropertyDefinition]) -> Result<(), ApplyError> {
+    for property in properties {
+        if property.alert_rules().is_empty() {
+            continue;
+        }
+        if property.data_type() != &DataType::Number {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Alert rules can only be set on NUMBER properties, {} is a {:?}",
+                property.name(),
+                property.data_type()
+            )));
+        }
+        if *property.confidential() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Alert rules can not be set on confidential property {}",
+                property.name()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that geofences are only set on LAT_LONG properties that are not confidential, as
+/// the values of other properties can not be located.
+fn validate_geofences(properties: &[PropertyDefinition]) -> Result<(), ApplyError> {
+    for property in properties {
+        if property.geofences().is_empty() {
+            continue;
+        }
+        if property.data_type() != &DataType::LatLong {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Geofences can only be set on LAT_LONG properties, {} is a {:?}",
+                property.name(),
+                property.data_type()
+            )));
+        }
+        if *property.confidential() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Geofences can not be set on confidential property {}",
+                property.name()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that property definitions, including those of STRUCT fields and LIST elements, can
+/// be built with the builders. Definitions that come in through the proto skip the checks of
+/// the builders, such as those of the patterns, of the number and LAT_LONG bounds, and of the
+/// geofences and alert rules.
+fn validate_property_definitions(properties: &[PropertyDefinition]) -> Result<(), ApplyError> {
+    for property in properties {
+        validate_property_definitions(property.struct_properties())?;
+        if let Some(list_element) = property.list_element() {
+            validate_property_definitions(std::slice::from_ref(list_element))?;
+        }
+
+        let invalid_property = |err: String| {
+            ApplyError::InvalidTransaction(format!(
+                "Property {} is not valid: {}",
+                property.name(),
+                err
+            ))
+        };
+        let mut constraints = property.constraints().clone().into_builder();
+        constraints.lat_long_min = constraints
+            .lat_long_min
+            .map(|lat_long| rebuild_lat_long(&lat_long))
+            .transpose()
+            .map_err(invalid_property)?;
+        constraints.lat_long_max = constraints
+            .lat_long_max
+            .map(|lat_long| rebuild_lat_long(&lat_long))
+            .transpose()
+            .map_err(invalid_property)?;
+        let constraints = constraints
+            .build()
+            .map_err(|err| invalid_property(err.to_string()))?;
+
+        let mut geofences = Vec::new();
+        for geofence in property.geofences() {
+            let vertices = geofence
+                .vertices()
+                .iter()
+                .map(rebuild_lat_long)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(invalid_property)?;
+            geofences.push(
+                geofence
+                    .clone()
+                    .into_builder()
+                    .with_vertices(vertices)
+                    .build()
+                    .map_err(|err| invalid_property(err.to_string()))?,
+            );
+        }
+
+        let alert_rules = property
+            .alert_rules()
+            .iter()
+            .map(|rule| rule.clone().into_builder().build())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| invalid_property(err.to_string()))?;
+
+        property
+            .clone()
+            .into_builder()
+            .with_constraints(constraints)
+            .with_geofences(geofences)
+            .with_alert_rules(alert_rules)
+            .build()
+            .map_err(|err| invalid_property(err.to_string()))?;
+    }
+    Ok(())
+}
+
+fn rebuild_lat_long(lat_long: &LatLong) -> Result<LatLong, String> {
+    LatLongBuilder::new()
+        .with_lat_long(*lat_long.latitude(), *lat_long.longitude())
+        .build()
+        .map_err(|err| err.to_string())
+}
+
+fn validate_schema_deprecate_action(
+    deprecate_action: &SchemaDeprecateAction,
+) -> Result<(), ApplyError> {
+    if deprecate_action.schema_name().is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Schema name must be set",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_schema_transfer_action(
+    transfer_action: &SchemaTransferAction,
+) -> Result<(), ApplyError> {
+    if transfer_action.schema_name().is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Schema name must be set",
+        )));
+    }
+
+    if transfer_action.new_owner().is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "New owner must be set",
         )));
     }
     Ok(())
@@ -139,6 +347,206 @@ mod tests {
         );
     }
 
+    #[test]
+    // Test that a schema create action with a NUMBER exponent that can not be applied to an
+    // i64 is not valid. The proto is used directly as the PropertyDefinitionBuilder rejects
+    // such an exponent.
+    fn test_validate_schema_create_action_number_exponent() {
+        let mut property_proto = protos::schema_state::PropertyDefinition::new();
+        property_proto.set_name("weight".into());
+        property_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::NUMBER);
+        property_proto.set_number_exponent(30);
+
+        let mut action = protos::schema_payload::SchemaCreateAction::new();
+        action.set_schema_name("test_schema".into());
+        action.mut_properties().push(property_proto);
+
+        let mut payload_proto = protos::schema_payload::SchemaPayload::new();
+        payload_proto.set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_CREATE);
+        payload_proto.set_schema_create(action);
+        let payload = payload_proto.into_native().unwrap();
+
+        match validate_payload(&payload) {
+            Ok(()) => panic!("Number exponent is out of range, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Number exponent of property weight must be between -18 and 18"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that a schema create action with more pages than a page address can number is not
+    // valid. The proto is used directly as the PropertyDefinitionBuilder rejects such paging.
+    fn test_validate_schema_create_action_max_pages() {
+        let mut property_proto = protos::schema_state::PropertyDefinition::new();
+        property_proto.set_name("temperature".into());
+        property_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::NUMBER);
+        property_proto.set_max_pages(0x10000);
+
+        let mut action = protos::schema_payload::SchemaCreateAction::new();
+        action.set_schema_name("test_schema".into());
+        action.mut_properties().push(property_proto);
+
+        let mut payload_proto = protos::schema_payload::SchemaPayload::new();
+        payload_proto.set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_CREATE);
+        payload_proto.set_schema_create(action);
+        let payload = payload_proto.into_native().unwrap();
+
+        match validate_payload(&payload) {
+            Ok(()) => panic!("Max pages is out of range, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Max pages of property temperature must not be greater"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that a schema create action with alert rules on a confidential property, or on a
+    // property that is not a NUMBER, is not valid. The proto is used directly as the
+    // PropertyDefinitionBuilder rejects such alert rules.
+    fn test_validate_schema_create_action_alert_rules() {
+        let create_payload = |property_proto: protos::schema_state::PropertyDefinition| {
+            let mut action = protos::schema_payload::SchemaCreateAction::new();
+            action.set_schema_name("test_schema".into());
+            action.mut_properties().push(property_proto);
+
+            let mut payload_proto = protos::schema_payload::SchemaPayload::new();
+            payload_proto.set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_CREATE);
+            payload_proto.set_schema_create(action);
+            payload_proto.into_native().unwrap()
+        };
+        let mut rule_proto = protos::schema_state::AlertRule::new();
+        rule_proto.set_name("too_warm".into());
+        rule_proto.set_has_max(true);
+        rule_proto.set_max(8);
+
+        let mut property_proto = protos::schema_state::PropertyDefinition::new();
+        property_proto.set_name("temperature".into());
+        property_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::NUMBER);
+        property_proto.set_confidential(true);
+        property_proto.mut_alert_rules().push(rule_proto.clone());
+        match validate_payload(&create_payload(property_proto)) {
+            Ok(()) => panic!("Property is confidential, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("can not be set on confidential property temperature"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let mut property_proto = protos::schema_state::PropertyDefinition::new();
+        property_proto.set_name("label".into());
+        property_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::STRING);
+        property_proto.mut_alert_rules().push(rule_proto);
+        match validate_payload(&create_payload(property_proto)) {
+            Ok(()) => panic!("Property is not a NUMBER, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Alert rules can only be set on NUMBER properties"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that a schema create action with a geofence on a confidential property, or on a
+    // property that is not a LAT_LONG, is not valid. The proto is used directly as the
+    // PropertyDefinitionBuilder rejects such geofences.
+    fn test_validate_schema_create_action_geofences() {
+        let create_payload = |property_proto: protos::schema_state::PropertyDefinition| {
+            let mut action = protos::schema_payload::SchemaCreateAction::new();
+            action.set_schema_name("test_schema".into());
+            action.mut_properties().push(property_proto);
+
+            let mut payload_proto = protos::schema_payload::SchemaPayload::new();
+            payload_proto.set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_CREATE);
+            payload_proto.set_schema_create(action);
+            payload_proto.into_native().unwrap()
+        };
+        let mut geofence_proto = protos::schema_state::Geofence::new();
+        geofence_proto.set_name("depot".into());
+        for (latitude, longitude) in &[(0, 0), (0, 1_000_000), (1_000_000, 0)] {
+            let mut vertex = protos::schema_state::LatLong::new();
+            vertex.set_latitude(*latitude);
+            vertex.set_longitude(*longitude);
+            geofence_proto.mut_vertices().push(vertex);
+        }
+
+        let mut property_proto = protos::schema_state::PropertyDefinition::new();
+        property_proto.set_name("location".into());
+        property_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::LAT_LONG);
+        property_proto.set_confidential(true);
+        property_proto.mut_geofences().push(geofence_proto.clone());
+        match validate_payload(&create_payload(property_proto)) {
+            Ok(()) => panic!("Property is confidential, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("can not be set on confidential property location"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let mut property_proto = protos::schema_state::PropertyDefinition::new();
+        property_proto.set_name("weight".into());
+        property_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::NUMBER);
+        property_proto.mut_geofences().push(geofence_proto);
+        match validate_payload(&create_payload(property_proto)) {
+            Ok(()) => panic!("Property is not a LAT_LONG, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Geofences can only be set on LAT_LONG properties"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that a schema create action with definitions the builders reject, here an invalid
+    // pattern and a STRUCT field with inverted number bounds, is not valid. The proto is used
+    // directly as the builders reject such definitions.
+    fn test_validate_schema_create_action_invalid_definitions() {
+        let create_payload = |property_proto: protos::schema_state::PropertyDefinition| {
+            let mut action = protos::schema_payload::SchemaCreateAction::new();
+            action.set_schema_name("test_schema".into());
+            action.mut_properties().push(property_proto);
+
+            let mut payload_proto = protos::schema_payload::SchemaPayload::new();
+            payload_proto.set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_CREATE);
+            payload_proto.set_schema_create(action);
+            payload_proto.into_native().unwrap()
+        };
+
+        let mut property_proto = protos::schema_state::PropertyDefinition::new();
+        property_proto.set_name("code".into());
+        property_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::STRING);
+        property_proto.mut_constraints().set_pattern("[A-Z".into());
+        match validate_payload(&create_payload(property_proto)) {
+            Ok(()) => panic!("Pattern is not valid, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Property code is not valid"));
+                assert!(err.contains("invalid pattern"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let mut field_proto = protos::schema_state::PropertyDefinition::new();
+        field_proto.set_name("weight".into());
+        field_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::NUMBER);
+        field_proto.mut_constraints().set_has_number_min(true);
+        field_proto.mut_constraints().set_number_min(10);
+        field_proto.mut_constraints().set_has_number_max(true);
+        field_proto.mut_constraints().set_number_max(1);
+        let mut property_proto = protos::schema_state::PropertyDefinition::new();
+        property_proto.set_name("package".into());
+        property_proto.set_data_type(protos::schema_state::PropertyDefinition_DataType::STRUCT);
+        property_proto.mut_struct_properties().push(field_proto);
+        match validate_payload(&create_payload(property_proto)) {
+            Ok(()) => panic!("Number bounds are inverted, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Property weight is not valid"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
     #[test]
     // Test a payload with a schema update action is properly validated. This test needs to use
     // the proto directly originally to be able to mimic the scenarios possbile from creating
@@ -169,13 +577,13 @@ mod tests {
             "Schema name must be set"
         );
 
-        // create payload with SchemaCreateAction without any properites,
+        // create payload with SchemaUpdateAction without any changes,
         action.set_schema_name("test_schema".into());
         payload_proto.set_schema_update(action.clone());
         let payload = payload_proto.clone().into_native().unwrap();
         assert!(
             validate_payload(&payload).is_err(),
-            "Properties must not be empty"
+            "Schema update must contain at least one change"
         );
 
         // create payload with full payload
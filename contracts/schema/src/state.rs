@@ -3,7 +3,7 @@
 
 use crypto::digest::Digest;
 use crypto::sha2::Sha512;
-use dgc_config::protocol::pike::state::{Agent, AgentList};
+use dgc_config::protocol::pike::state::{Agent, AgentList, Organization, OrganizationList};
 use dgc_config::protocol::schema::state::{Schema, SchemaList, SchemaListBuilder};
 use dgc_config::protos::{FromBytes, IntoBytes};
 use dgc_config::addressing::*;
@@ -56,6 +56,35 @@ impl<'a> GridSchemaState<'a> {
         }
     }
 
+    /// Gets a Pike Organization. Handles retrieving the correct organization from an
+    /// OrganizationList.
+    pub fn get_organization(&self, id: &str) -> Result<Option<Organization>, ApplyError> {
+        let address = make_org_address(id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let orgs = match OrganizationList::from_bytes(packed.as_slice()) {
+                    Ok(orgs) => orgs,
+                    Err(err) => {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Cannot deserialize organization list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the organization with the correct id
+                for org in orgs.organizations() {
+                    if org.org_id() == id {
+                        return Ok(Some(org.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Gets a Grid Schema. Handles retrieving the correct Schema from a SchemaList
     pub fn get_schema(&self, name: &str) -> Result<Option<Schema>, ApplyError> {
         let address = make_schema_address(name);
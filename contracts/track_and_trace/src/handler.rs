@@ -1,7 +1,7 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 cfg_if! {
     if #[cfg(target_arch = "wasm32")] {
@@ -19,24 +19,36 @@ cfg_if! {
 }
 
 use dgc_config::protocol::errors::BuilderError;
-use dgc_config::protocol::schema::state::{PropertyDefinition, PropertyValue};
+use dgc_config::protocol::pike::state::Agent;
+use dgc_config::protocol::schema::state::{
+    DataType, PropertyDefinition, PropertyValue, ReferenceKind, RetentionPolicy,
+};
+use dgc_config::protocol::schema::validation::validate_property_value;
 use dgc_config::protocol::track_and_trace::payload::{
-    Action, AnswerProposalAction, CreateProposalAction, CreateRecordAction, FinalizeRecordAction,
-    Response, RevokeReporterAction, TrackAndTracePayload, UpdatePropertiesAction,
+    AcknowledgeRecallAction, Action, AnswerBulkProposalAction, AnswerProposalAction,
+    CloseRecallAction, CounterSignProposalAction, CreateBulkProposalAction, CreateProposalAction,
+    CreateRecallAction, CreateRecordAction, FinalizeRecordAction, IngestReadingsAction,
+    LineageParent, MergeRecordsAction, Response, RevokeReporterAction, SensorReading,
+    SplitRecordAction, TrackAndTracePayload, TransformRecordAction, UpdatePropertiesAction,
 };
 use dgc_config::protocol::track_and_trace::state::{
-    AssociatedAgentBuilder, PropertyBuilder, PropertyPageBuilder, ProposalBuilder,
-    ProposalListBuilder, RecordBuilder, ReportedValueBuilder, ReporterBuilder, Role, Status,
+    AcknowledgementBuilder, AssociatedAgent, AssociatedAgentBuilder, BulkProposalBuilder,
+    Condition, ConditionType, CounterSignatureBuilder, DataKey, DataKeyBuilder, ExcursionBuilder,
+    Property, PropertyBuilder, PropertyPage, PropertyPageBuilder, Proposal, ProposalBuilder,
+    ProposalListBuilder, RecallBuilder, RecallStatus, Record, RecordBuilder, ReopeningBuilder,
+    ReportedValue, ReportedValueBuilder, Reporter, ReporterAuthorizationBuilder, ReporterBuilder,
+    Role, Status,
 };
 
-use dgc_config::protos::FromBytes;
+use dgc_config::protos::{FromBytes, IntoBytes};
 
 //use crate::addressing::*;
 use dgc_config::addressing::*;
 use crate::payload::validate_payload;
 use crate::state::TrackAndTraceState;
 
-const PROPERTY_PAGE_MAX_LENGTH: usize = 256;
+/// The type of the events emitted for reported LAT_LONG values that are outside of a geofence
+const GEOFENCE_BREACH_EVENT: &str = "grid_track_and_trace/geofence_breach";
 
 pub struct TrackAndTraceTransactionHandler {
     family_name: String,
@@ -99,12 +111,47 @@ impl TrackAndTraceTransactionHandler {
             }
         };
 
+        if *schema.deprecated() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Schema has been deprecated: {}",
+                schema_name
+            )));
+        }
+
+        // The values of confidential properties are encrypted with a data key of the record,
+        // which the creator wraps for itself
+        let confidential = schema
+            .properties()
+            .iter()
+            .any(|property| *property.confidential());
+        let data_keys = match (confidential, payload.wrapped_data_key()) {
+            (true, wrapped_key) if wrapped_key.is_empty() => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Schema {} has confidential properties, a data key must be provided",
+                    schema_name
+                )));
+            }
+            (true, wrapped_key) => vec![DataKeyBuilder::new()
+                .with_agent_id(signer.to_string())
+                .with_wrapped_key(wrapped_key.to_vec())
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "DataKey"))?],
+            (false, wrapped_key) if !wrapped_key.is_empty() => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Schema {} has no confidential properties to encrypt with a data key",
+                    schema_name
+                )));
+            }
+            (false, _) => vec![],
+        };
+
         let mut type_schemata: HashMap<&str, PropertyDefinition> = HashMap::new();
         let mut required_properties: HashMap<&str, PropertyDefinition> = HashMap::new();
         let mut provided_properties: HashMap<&str, PropertyValue> = HashMap::new();
         for property in schema.properties() {
             type_schemata.insert(property.name(), property.clone());
-            if *property.required() {
+            // Deprecated properties are optional, even in schemas deprecated while required
+            if *property.required() && !*property.deprecated() {
                 required_properties.insert(property.name(), property.clone());
             }
         }
@@ -123,8 +170,14 @@ impl TrackAndTraceTransactionHandler {
         }
 
         for (provided_name, provided_properties) in provided_properties.clone() {
-            let required_type = match type_schemata.get(provided_name) {
-                Some(required_type) => required_type.data_type(),
+            let definition = match type_schemata.get(provided_name) {
+                Some(definition) if *definition.deprecated() => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Provided property {} is deprecated",
+                        provided_name
+                    )));
+                }
+                Some(definition) => definition,
                 None => {
                     return Err(ApplyError::InvalidTransaction(format!(
                         "Provided property {} is not in schema",
@@ -132,45 +185,41 @@ impl TrackAndTraceTransactionHandler {
                     )));
                 }
             };
-            let provided_type = provided_properties.data_type();
-            if provided_type != required_type {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Value provided for {} is the wrong type",
-                    provided_name
-                )));
-            };
+            validate_property_value(definition, &provided_properties)
+                .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
+            check_references(
+                state,
+                &type_schemata[provided_name],
+                &provided_properties,
+                provided_name,
+            )?;
         }
 
-        let owner = AssociatedAgentBuilder::new()
-            .with_agent_id(signer.to_string())
-            .with_timestamp(timestamp)
+        let owner = associated_agent_builder(state, signer, timestamp)?
             .build()
             .map_err(|err| map_builder_error_to_apply_error(err, "AssociatedAgent"))?;
 
         let new_record = RecordBuilder::new()
             .with_record_id(record_id.to_string())
             .with_schema(schema_name.to_string())
+            .with_schema_version(*schema.version())
             .with_field_final(false)
             .with_owners(vec![owner.clone()])
             .with_custodians(vec![owner])
+            .with_data_keys(data_keys)
             .build()
             .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
 
         state.set_record(record_id, new_record)?;
 
-        let reporter = ReporterBuilder::new()
-            .with_public_key(signer.to_string())
-            .with_authorized(true)
-            .with_index(0)
-            .build()
-            .map_err(|err| map_builder_error_to_apply_error(err, "Reporter"))?;
+        let reporters = authorize_reporter(&[], signer, signer, timestamp)?;
 
         for (property_name, property) in type_schemata {
             let new_property = PropertyBuilder::new()
                 .with_name(property_name.to_string())
                 .with_record_id(record_id.to_string())
                 .with_property_definition(property.clone())
-                .with_reporters(vec![reporter.clone()])
+                .with_reporters(reporters.clone())
                 .with_current_page(1)
                 .with_wrapped(false)
                 .build()
@@ -190,6 +239,7 @@ impl TrackAndTraceTransactionHandler {
                     .with_value(provided_property)
                     .build()
                     .map_err(|err| map_builder_error_to_apply_error(err, "ReportedValue"))?;
+                let reported_value = flag_geofence_breaches(state, &new_property, reported_value)?;
 
                 new_property_page = new_property_page.with_reported_values(vec![reported_value]);
                 state.set_property_page(
@@ -288,12 +338,16 @@ impl TrackAndTraceTransactionHandler {
             )));
         }
 
-        let updates = payload.properties();
+        rotate_data_key(
+            state,
+            update_record,
+            payload.data_keys(),
+            payload.properties().iter(),
+            signer,
+        )?;
 
-        for update in updates {
+        for update in payload.properties() {
             let name = update.name();
-            let data_type = update.data_type();
-
             let prop = match state.get_property(record_id, name)? {
                 Some(prop) => prop,
                 None => {
@@ -303,41 +357,7 @@ impl TrackAndTraceTransactionHandler {
                     )));
                 }
             };
-
-            let mut allowed = false;
-            let mut reporter_index = 0;
-            for reporter in prop.reporters() {
-                if reporter.public_key() == signer && *reporter.authorized() {
-                    allowed = true;
-                    reporter_index = *reporter.index();
-                    break;
-                }
-            }
-            if !allowed {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Reporter is not authorized: {}",
-                    signer
-                )));
-            }
-
-            if data_type != prop.property_definition().data_type() {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Update has wrong type: {:?} != {:?}",
-                    data_type,
-                    prop.property_definition().data_type()
-                )));
-            }
-
-            let page_number = prop.current_page();
-            let page = match state.get_property_page(record_id, name, *page_number)? {
-                Some(page) => page,
-                None => PropertyPageBuilder::new()
-                    .with_name(prop.name().to_string())
-                    .with_record_id(record_id.to_string())
-                    .with_reported_values(vec![])
-                    .build()
-                    .map_err(|err| map_builder_error_to_apply_error(err, "PropertyPage"))?,
-            };
+            let reporter_index = check_property_update(state, &prop, update, signer)?;
 
             let reported_value = ReportedValueBuilder::new()
                 .with_reporter_index(reporter_index)
@@ -346,58 +366,82 @@ impl TrackAndTraceTransactionHandler {
                 .build()
                 .map_err(|err| map_builder_error_to_apply_error(err, "ReportedValue"))?;
 
-            let mut updated_reported_values = page.reported_values().to_vec();
-            updated_reported_values.push(reported_value);
-            updated_reported_values.sort_by_key(|rv| (*rv.timestamp(), *rv.reporter_index()));
-
-            let updated_property_page = page
-                .clone()
-                .into_builder()
-                .with_reported_values(updated_reported_values)
-                .build()
-                .map_err(|err| map_builder_error_to_apply_error(err, "PropertyPage"))?;
+            evaluate_alert_rules(state, &prop, &[reported_value.clone()])?;
+            append_reported_values(state, prop, vec![reported_value])?;
+        }
 
-            state.set_property_page(
-                record_id,
-                name,
-                *page_number,
-                updated_property_page.clone(),
-            )?;
+        Ok(())
+    }
 
-            if updated_property_page.reported_values().len() >= PROPERTY_PAGE_MAX_LENGTH {
-                let new_page_number = if *page_number > PROPERTY_PAGE_MAX_LENGTH as u32 {
-                    1
-                } else {
-                    page_number + 1
-                };
+    /// Reports many readings at once, each with the time it was read. The readings of each
+    /// property are sorted by time and every page they touch is written once.
+    fn _ingest_readings(
+        &self,
+        payload: &IngestReadingsAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let record = match state.get_record(record_id)? {
+            Some(record) => record,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not exist: {}",
+                    record_id
+                )));
+            }
+        };
 
-                let new_page = match state.get_property_page(record_id, name, new_page_number)? {
-                    Some(new_page) => new_page
-                        .into_builder()
-                        .with_reported_values(vec![])
-                        .build()
-                        .map_err(|err| map_builder_error_to_apply_error(err, "PropertyPage"))?,
-                    None => PropertyPageBuilder::new()
-                        .with_name(name.to_string())
-                        .with_record_id(record_id.to_string())
-                        .with_reported_values(vec![])
-                        .build()
-                        .map_err(|err| map_builder_error_to_apply_error(err, "PropertyPage"))?,
-                };
+        if *record.field_final() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record is final: {}",
+                record_id
+            )));
+        }
 
-                state.set_property_page(record_id, name, new_page_number, new_page)?;
+        rotate_data_key(
+            state,
+            record,
+            payload.data_keys(),
+            payload.readings().iter().map(SensorReading::value),
+            signer,
+        )?;
+
+        let mut readings_by_property = BTreeMap::<&str, Vec<&SensorReading>>::new();
+        for reading in payload.readings() {
+            readings_by_property
+                .entry(reading.value().name())
+                .or_insert_with(Vec::new)
+                .push(reading);
+        }
 
-                let wrapped = new_page_number == 1 && !prop.wrapped();
-                let new_property = prop
-                    .clone()
-                    .into_builder()
-                    .with_current_page(new_page_number)
-                    .with_wrapped(wrapped)
-                    .build()
-                    .map_err(|err| map_builder_error_to_apply_error(err, "Property"))?;
+        for (name, mut readings) in readings_by_property {
+            let prop = match state.get_property(record_id, name)? {
+                Some(prop) => prop,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record does not have provided property: {}",
+                        name
+                    )));
+                }
+            };
 
-                state.set_property(record_id, name, new_property)?;
+            readings.sort_by_key(|reading| *reading.timestamp());
+            let mut reported_values = Vec::with_capacity(readings.len());
+            for reading in readings {
+                let reporter_index = check_property_update(state, &prop, reading.value(), signer)?;
+                reported_values.push(
+                    ReportedValueBuilder::new()
+                        .with_reporter_index(reporter_index)
+                        .with_timestamp(*reading.timestamp())
+                        .with_value(reading.value().clone())
+                        .build()
+                        .map_err(|err| map_builder_error_to_apply_error(err, "ReportedValue"))?,
+                );
             }
+
+            evaluate_alert_rules(state, &prop, &reported_values)?;
+            append_reported_values(state, prop, reported_values)?;
         }
 
         Ok(())
@@ -415,6 +459,14 @@ impl TrackAndTraceTransactionHandler {
         let role = payload.role();
         let properties = payload.properties();
         let terms = payload.terms();
+        let expires_at = payload.expires_at();
+
+        if *expires_at != 0 && *expires_at <= timestamp {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Proposal must expire after it is created: {}",
+                expires_at
+            )));
+        }
 
         match state.get_agent(signer)? {
             Some(agent) => agent,
@@ -436,8 +488,14 @@ impl TrackAndTraceTransactionHandler {
             }
         };
 
+        // Open proposals that have expired are closed, so they no longer block new proposals
         let mut proposals = match state.get_proposal_list(&record_id, &receiving_agent)? {
-            Some(proposals) => proposals.proposals().to_vec(),
+            Some(proposals) => proposals
+                .proposals()
+                .iter()
+                .cloned()
+                .map(|proposal| expire_proposal(proposal, timestamp))
+                .collect::<Result<Vec<_>, ApplyError>>()?,
             None => vec![],
         };
 
@@ -467,7 +525,7 @@ impl TrackAndTraceTransactionHandler {
             }
         };
 
-        if *proposal_record.field_final() {
+        if *proposal_record.field_final() && role != &Role::Reopen {
             return Err(ApplyError::InvalidTransaction(format!(
                 "Record is final: {}",
                 record_id
@@ -528,6 +586,108 @@ impl TrackAndTraceTransactionHandler {
             }
         }
 
+        for condition in payload.conditions() {
+            match condition.condition_type() {
+                ConditionType::PropertyRange => {
+                    let property = state.get_property(record_id, condition.property_name())?;
+                    let definition = property.as_ref().map(|p| p.property_definition());
+                    if definition.map(|d| d.data_type()) != Some(&DataType::Number) {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Condition property {} is not a NUMBER property of record {}",
+                            condition.property_name(),
+                            record_id
+                        )));
+                    }
+                    if definition.map_or(false, |d| *d.confidential()) {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Condition property {} of record {} is confidential",
+                            condition.property_name(),
+                            record_id
+                        )));
+                    }
+                }
+                ConditionType::CounterSignature => {
+                    if state.get_organization(condition.org_id())?.is_none() {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Organization does not exist: {}",
+                            condition.org_id()
+                        )));
+                    }
+                }
+            }
+        }
+
+        // Reopening a finalized record needs the approval of both its current owner and its
+        // current custodian, so one of them proposes it to the other
+        if role == &Role::Reopen {
+            if !*proposal_record.field_final() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record is not final: {}",
+                    record_id
+                )));
+            }
+            let owner = match proposal_record.owners().last() {
+                Some(owner) => owner,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Owner not found",
+                    )));
+                }
+            };
+            let custodian = match proposal_record.custodians().last() {
+                Some(custodian) => custodian,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Custodian not found",
+                    )));
+                }
+            };
+            let approving_agent = if owner.agent_id() == signer {
+                custodian.agent_id()
+            } else if custodian.agent_id() == signer {
+                owner.agent_id()
+            } else {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Only the owner or custodian can create a proposal to reopen a record",
+                )));
+            };
+            if approving_agent != receiving_agent {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "A proposal to reopen a record must be sent to {}",
+                    approving_agent
+                )));
+            }
+        }
+
+        // An agent that is to read or report confidential properties needs the data key of the
+        // record, which only an agent that already holds it can wrap
+        let needs_data_key = !proposal_record.data_keys().is_empty()
+            && proposal_record.data_key(receiving_agent).is_none()
+            && match role {
+                Role::Owner | Role::Custodian => true,
+                Role::Reporter => {
+                    let mut confidential = false;
+                    for property_name in properties {
+                        if let Some(property) = state.get_property(record_id, property_name)? {
+                            confidential |= *property.property_definition().confidential();
+                        }
+                    }
+                    confidential
+                }
+                Role::Reopen => false,
+            };
+        let wrapped_data_key = if needs_data_key {
+            if payload.wrapped_data_key().is_empty() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} has confidential properties, its data key must be wrapped for {}",
+                    record_id, receiving_agent
+                )));
+            }
+            payload.wrapped_data_key().to_vec()
+        } else {
+            vec![]
+        };
+
         let new_proposal = ProposalBuilder::new()
             .with_record_id(record_id.to_string())
             .with_timestamp(timestamp)
@@ -537,6 +697,10 @@ impl TrackAndTraceTransactionHandler {
             .with_properties(properties.to_vec())
             .with_status(Status::Open)
             .with_terms(terms.to_string())
+            .with_expires_at(*expires_at)
+            .with_conditions(payload.conditions().to_vec())
+            .with_wrapped_data_key(wrapped_data_key)
+            .with_data_key_version(*proposal_record.data_key_version())
             .build()
             .map_err(|err| map_builder_error_to_apply_error(err, "Proposal"))?;
 
@@ -604,6 +768,12 @@ impl TrackAndTraceTransactionHandler {
         let mut updated_proposal_builder = current_proposal.clone().into_builder();
 
         match response {
+            _ if current_proposal.is_expired(timestamp) => {
+                info!("Proposal expired before it was answered");
+
+                updated_proposal_builder = updated_proposal_builder.with_status(Status::Expired);
+            }
+
             Response::Cancel => {
                 if current_proposal.issuing_agent() != signer {
                     return Err(ApplyError::InvalidTransaction(String::from(
@@ -629,6 +799,8 @@ impl TrackAndTraceTransactionHandler {
                     )));
                 };
 
+                check_proposal_conditions(state, &current_proposal)?;
+
                 let proposal_record = match state.get_record(record_id)? {
                     Some(record) => record,
                     None => {
@@ -665,105 +837,10 @@ impl TrackAndTraceTransactionHandler {
                             updated_proposal_builder =
                                 updated_proposal_builder.with_status(Status::Canceled);
                         } else {
-                            let new_owner = AssociatedAgentBuilder::new()
-                                .with_agent_id(receiving_agent.to_string())
-                                .with_timestamp(timestamp)
-                                .build()
-                                .map_err(|err| {
-                                    map_builder_error_to_apply_error(err, "AssociatedAgent")
-                                })?;
-
-                            let mut record_owners = proposal_record.owners().to_vec();
-                            record_owners.push(new_owner);
-                            let updated_record = proposal_record
-                                .clone()
-                                .into_builder()
-                                .with_owners(record_owners)
-                                .build()
-                                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
-
-                            state.set_record(record_id, updated_record)?;
-
-                            let schema = match state.get_schema(proposal_record.schema())? {
-                                Some(record_type) => record_type,
-                                None => {
-                                    return Err(ApplyError::InvalidTransaction(format!(
-                                        "Schema does not exist: {}",
-                                        proposal_record.schema()
-                                    )));
-                                }
-                            };
-
-                            for prop_schema in schema.properties() {
-                                let prop =
-                                    match state.get_property(record_id, prop_schema.name())? {
-                                        Some(prop) => prop,
-                                        None => {
-                                            return Err(ApplyError::InvalidTransaction(format!(
-                                                "Property does not exist: {}",
-                                                prop_schema.name()
-                                            )));
-                                        }
-                                    };
-
-                                let mut authorized = false;
-                                let mut new_reporters = prop
-                                    .reporters()
-                                    .to_vec()
-                                    .iter()
-                                    .map(|reporter| {
-                                        if reporter.public_key() == owner.agent_id() {
-                                            reporter
-                                                .clone()
-                                                .into_builder()
-                                                .with_authorized(false)
-                                                .build()
-                                                .map_err(|err| {
-                                                    map_builder_error_to_apply_error(
-                                                        err, "Reporter",
-                                                    )
-                                                })
-                                        } else if reporter.public_key() == receiving_agent {
-                                            authorized = true;
-                                            reporter
-                                                .clone()
-                                                .into_builder()
-                                                .with_authorized(true)
-                                                .build()
-                                                .map_err(|err| {
-                                                    map_builder_error_to_apply_error(
-                                                        err, "Reporter",
-                                                    )
-                                                })
-                                        } else {
-                                            Ok(reporter.clone())
-                                        }
-                                    })
-                                    .collect::<Result<Vec<_>, ApplyError>>()?;
-
-                                if !authorized {
-                                    let reporter = ReporterBuilder::new()
-                                        .with_public_key(receiving_agent.to_string())
-                                        .with_authorized(true)
-                                        .with_index(prop.reporters().len() as u32)
-                                        .build()
-                                        .map_err(|err| {
-                                            map_builder_error_to_apply_error(err, "Reporter")
-                                        })?;
-                                    new_reporters.push(reporter);
-                                }
-
-                                let updated_property = prop
-                                    .clone()
-                                    .into_builder()
-                                    .with_reporters(new_reporters.clone())
-                                    .build()
-                                    .map_err(|err| {
-                                        map_builder_error_to_apply_error(err, "Property")
-                                    })?;
+                            let new_owner =
+                                proposal_associated_agent(state, &current_proposal, timestamp)?;
+                            self._transfer_ownership(proposal_record, new_owner, state, timestamp)?;
 
-                                state.set_property(record_id, prop.name(), updated_property)?;
-                            }
                             updated_proposal_builder =
                                 updated_proposal_builder.with_status(Status::Accepted);
                         }
@@ -776,23 +853,9 @@ impl TrackAndTraceTransactionHandler {
                             updated_proposal_builder =
                                 updated_proposal_builder.with_status(Status::Canceled);
                         } else {
-                            let new_custodian = AssociatedAgentBuilder::new()
-                                .with_agent_id(receiving_agent.to_string())
-                                .with_timestamp(timestamp)
-                                .build()
-                                .map_err(|err| {
-                                    map_builder_error_to_apply_error(err, "AssociatedAgent")
-                                })?;
-
-                            let mut record_custodians = proposal_record.custodians().to_vec();
-                            record_custodians.push(new_custodian);
-                            let updated_record = proposal_record
-                                .into_builder()
-                                .with_custodians(record_custodians)
-                                .build()
-                                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
-
-                            state.set_record(record_id, updated_record)?;
+                            let new_custodian =
+                                proposal_associated_agent(state, &current_proposal, timestamp)?;
+                            self._transfer_custody(proposal_record, new_custodian, state)?;
 
                             updated_proposal_builder =
                                 updated_proposal_builder.with_status(Status::Accepted);
@@ -805,10 +868,6 @@ impl TrackAndTraceTransactionHandler {
                             updated_proposal_builder =
                                 updated_proposal_builder.with_status(Status::Canceled);
                         } else {
-                            let reporter_builder = ReporterBuilder::new()
-                                .with_public_key(receiving_agent.to_string())
-                                .with_authorized(true);
-
                             for prop_name in current_proposal.properties() {
                                 let prop = match state.get_property(record_id, prop_name)? {
                                     Some(prop) => prop,
@@ -819,16 +878,12 @@ impl TrackAndTraceTransactionHandler {
                                         )));
                                     }
                                 };
-                                let reporter = reporter_builder
-                                    .clone()
-                                    .with_index(prop.reporters().len() as u32)
-                                    .build()
-                                    .map_err(|err| {
-                                        map_builder_error_to_apply_error(err, "Reporter")
-                                    })?;
-
-                                let mut updated_reporter_list = prop.reporters().to_vec();
-                                updated_reporter_list.push(reporter);
+                                let updated_reporter_list = authorize_reporter(
+                                    prop.reporters(),
+                                    receiving_agent,
+                                    current_proposal.issuing_agent(),
+                                    timestamp,
+                                )?;
                                 let updated_property = prop
                                     .clone()
                                     .into_builder()
@@ -843,6 +898,48 @@ impl TrackAndTraceTransactionHandler {
                                 updated_proposal_builder.with_status(Status::Accepted);
                         }
                     }
+                    Role::Reopen => {
+                        let approvals = [current_proposal.issuing_agent(), receiving_agent];
+                        if !*proposal_record.field_final() {
+                            info!("Record in the proposal to reopen is no longer final");
+
+                            updated_proposal_builder =
+                                updated_proposal_builder.with_status(Status::Canceled);
+                        } else if !approvals.contains(&owner.agent_id())
+                            || !approvals.contains(&custodian.agent_id())
+                        {
+                            info!(
+                                "Record owner or custodian changed since the proposal was made"
+                            );
+
+                            updated_proposal_builder =
+                                updated_proposal_builder.with_status(Status::Canceled);
+                        } else {
+                            let reopening = ReopeningBuilder::new()
+                                .with_issuing_agent(current_proposal.issuing_agent().to_string())
+                                .with_approving_agent(receiving_agent.to_string())
+                                .with_timestamp(timestamp)
+                                .with_reason(current_proposal.terms().to_string())
+                                .build()
+                                .map_err(|err| {
+                                    map_builder_error_to_apply_error(err, "Reopening")
+                                })?;
+
+                            let mut reopenings = proposal_record.reopenings().to_vec();
+                            reopenings.push(reopening);
+                            let updated_record = proposal_record
+                                .into_builder()
+                                .with_field_final(false)
+                                .with_reopenings(reopenings)
+                                .build()
+                                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+
+                            state.set_record(record_id, updated_record)?;
+
+                            updated_proposal_builder =
+                                updated_proposal_builder.with_status(Status::Accepted);
+                        }
+                    }
                 }
             }
         }
@@ -850,6 +947,18 @@ impl TrackAndTraceTransactionHandler {
             .build()
             .map_err(|err| map_builder_error_to_apply_error(err, "Proposal"))?;
 
+        if updated_proposal.status() == &Status::Accepted
+            && !updated_proposal.wrapped_data_key().is_empty()
+        {
+            share_data_key(
+                state,
+                record_id,
+                receiving_agent,
+                updated_proposal.wrapped_data_key(),
+                *updated_proposal.data_key_version(),
+            )?;
+        }
+
         // remove outdated proposal
         proposals.remove(proposal_index);
         proposals.push(updated_proposal);
@@ -871,1064 +980,4732 @@ impl TrackAndTraceTransactionHandler {
         Ok(())
     }
 
-    fn _revoke_reporter(
+    /// Counter-signs an open proposal. The signer must be an admin of an organization whose
+    /// counter-signature one of the conditions of the proposal requires.
+    fn _counter_sign_proposal(
         &self,
-        payload: &RevokeReporterAction,
+        payload: &CounterSignProposalAction,
         state: &mut TrackAndTraceState,
         signer: &str,
+        timestamp: u64,
     ) -> Result<(), ApplyError> {
         let record_id = payload.record_id();
-        let reporter_id = payload.reporter_id();
-        let properties = payload.properties();
-
-        let revoke_record = match state.get_record(record_id)? {
-            Some(record) => record,
-            None => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Record does not exists: {}",
-                    record_id
-                )));
-            }
-        };
+        let receiving_agent = payload.receiving_agent();
 
-        let owner = match revoke_record.owners().last() {
-            Some(x) => x,
+        let mut proposals = match state.get_proposal_list(record_id, receiving_agent)? {
+            Some(proposal_list) => proposal_list.proposals().to_vec(),
             None => {
                 return Err(ApplyError::InvalidTransaction(String::from(
-                    "Owner was not found",
+                    "Proposal does not exist",
                 )));
             }
         };
 
-        if owner.agent_id() != signer {
-            return Err(ApplyError::InvalidTransaction(
-                "Must be owner to revoke reporters".to_string(),
-            ));
-        }
+        let (proposal_index, proposal) = proposals
+            .iter()
+            .enumerate()
+            .find(|(_, prop)| prop.role() == payload.role() && prop.status() == &Status::Open)
+            .map(|(i, prop)| (i, prop.clone()))
+            .ok_or_else(|| {
+                ApplyError::InvalidTransaction(format!(
+                    "No open proposals found for record {} for {}",
+                    record_id, receiving_agent
+                ))
+            })?;
 
-        if *revoke_record.field_final() {
-            return Err(ApplyError::InvalidTransaction(format!(
-                "Record is final: {}",
-                record_id
-            )));
-        }
+        let updated_proposal = if proposal.is_expired(timestamp) {
+            info!("Proposal expired before it was counter-signed");
 
-        for prop_name in properties {
-            let prop = match state.get_property(record_id, prop_name)? {
-                Some(prop) => prop,
+            expire_proposal(proposal, timestamp)?
+        } else {
+            let agent = match state.get_agent(signer)? {
+                Some(agent) => agent,
                 None => {
                     return Err(ApplyError::InvalidTransaction(format!(
-                        "Property does not exist: {}",
-                        prop_name
+                        "Agent is not registered: {}",
+                        signer
                     )));
                 }
             };
-
-            let mut revoked = false;
-            let new_reporters = prop
-                .reporters()
-                .to_vec()
+            if !proposal
+                .conditions()
                 .iter()
-                .map(|reporter| {
-                    if reporter.public_key() == reporter_id {
-                        if !*reporter.authorized() {
-                            return Err(ApplyError::InvalidTransaction(
-                                "Reporter is already unauthorized.".to_string(),
-                            ));
-                        }
-                        revoked = true;
-                        reporter
-                            .clone()
-                            .into_builder()
-                            .with_authorized(false)
-                            .build()
-                            .map_err(|err| map_builder_error_to_apply_error(err, "Reporter"))
-                    } else {
-                        Ok(reporter.clone())
-                    }
-                })
-                .collect::<Result<Vec<_>, ApplyError>>()?;
-
-            if !revoked {
+                .any(|condition| is_counter_signer(condition, &agent))
+            {
                 return Err(ApplyError::InvalidTransaction(format!(
-                    "{} not a reporter for property {}",
-                    reporter_id, prop_name
+                    "Agent {} is not an admin of an organization that must counter-sign the \
+                     proposal",
+                    signer
                 )));
             }
-            let updated_property = prop
-                .clone()
+            if proposal
+                .counter_signatures()
+                .iter()
+                .any(|counter_signature| counter_signature.agent_id() == signer)
+            {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Proposal is already counter-signed by {}",
+                    signer
+                )));
+            }
+
+            let counter_signature = CounterSignatureBuilder::new()
+                .with_agent_id(signer.to_string())
+                .with_timestamp(timestamp)
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "CounterSignature"))?;
+            let mut counter_signatures = proposal.counter_signatures().to_vec();
+            counter_signatures.push(counter_signature);
+            proposal
                 .into_builder()
-                .with_reporters(new_reporters)
+                .with_counter_signatures(counter_signatures)
                 .build()
-                .map_err(|err| map_builder_error_to_apply_error(err, "Property"))?;
-
-            state.set_property(record_id, prop_name, updated_property)?;
-        }
-
-        Ok(())
-    }
-}
-
-fn map_builder_error_to_apply_error(err: BuilderError, protocol_name: &str) -> ApplyError {
-    ApplyError::InvalidTransaction(format!(
-        "Failed to build {}. {}",
-        protocol_name,
-        err.to_string()
-    ))
-}
-
-impl TransactionHandler for TrackAndTraceTransactionHandler {
-    fn family_name(&self) -> String {
-        self.family_name.clone()
-    }
+                .map_err(|err| map_builder_error_to_apply_error(err, "Proposal"))?
+        };
 
-    fn family_versions(&self) -> Vec<String> {
-        self.family_versions.clone()
-    }
+        proposals[proposal_index] = updated_proposal;
+        let proposal_list = ProposalListBuilder::new()
+            .with_proposals(proposals)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "ProposalList"))?;
 
-    fn namespaces(&self) -> Vec<String> {
-        self.namespaces.clone()
+        state.set_proposal_list(record_id, receiving_agent, proposal_list)
     }
 
-    fn apply(
+    fn _create_bulk_proposal(
         &self,
-        request: &TpProcessRequest,
-        context: &mut dyn TransactionContext,
+        payload: &CreateBulkProposalAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
     ) -> Result<(), ApplyError> {
-        let payload = TrackAndTracePayload::from_bytes(request.get_payload()).map_err(|err| {
-            ApplyError::InvalidTransaction(format!("Cannot build track and trace payload: {}", err))
-        })?;
+        let proposal_id = payload.proposal_id();
+        let receiving_agent = payload.receiving_agent();
+        let role = payload.role();
+        let expires_at = payload.expires_at();
 
-        validate_payload(&payload)?;
+        if role != &Role::Owner && role != &Role::Custodian {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Only ownership and custodianship can be transferred in bulk",
+            )));
+        }
 
-        let signer = request.get_header().get_signer_public_key();
-        let mut state = TrackAndTraceState::new(context);
+        if *expires_at != 0 && *expires_at <= timestamp {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Proposal must expire after it is created: {}",
+                expires_at
+            )));
+        }
 
-        info!(
-            "Track and Trace payload: {:?} {}",
-            payload.action(),
-            payload.timestamp(),
-        );
+        if state.get_agent(signer)?.is_none() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Issuing agent does not exist: {}",
+                signer
+            )));
+        }
 
-        match payload.action() {
-            Action::CreateRecord(action_payload) => {
-                self._create_record(action_payload, &mut state, signer, *payload.timestamp())?
-            }
-            Action::FinalizeRecord(action_payload) => {
-                self._finalize_record(action_payload, &mut state, signer)?
-            }
-            Action::UpdateProperties(action_payload) => {
-                self._update_properties(action_payload, &mut state, signer, *payload.timestamp())?
+        if state.get_agent(receiving_agent)?.is_none() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Receiving agent does not exist: {}",
+                receiving_agent
+            )));
+        }
+
+        if state.get_bulk_proposal(proposal_id, receiving_agent)?.is_some() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Bulk proposal already exists: {}",
+                proposal_id
+            )));
+        }
+
+        for (i, record_id) in payload.record_ids().iter().enumerate() {
+            if payload.record_ids()[..i].contains(record_id) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} is listed more than once",
+                    record_id
+                )));
             }
-            Action::CreateProposal(action_payload) => {
-                self._create_proposal(action_payload, &mut state, signer, *payload.timestamp())?
+            let record = match state.get_record(record_id)? {
+                Some(record) => record,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record does not exist: {}",
+                        record_id
+                    )));
+                }
+            };
+            if *record.field_final() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record is final: {}",
+                    record_id
+                )));
             }
-            Action::AnswerProposal(action_payload) => {
-                self._answer_proposal(action_payload, &mut state, signer, *payload.timestamp())?
+            if !holds_role(&record, role, signer) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Must be {:?} of record {} to propose its transfer",
+                    role, record_id
+                )));
             }
-            Action::RevokeReporter(action_payload) => {
-                self._revoke_reporter(action_payload, &mut state, signer)?
+            // A bulk proposal can not carry a data key for each of its records
+            if !record.data_keys().is_empty() && record.data_key(receiving_agent).is_none() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} has confidential properties and must be transferred on its own",
+                    record_id
+                )));
             }
         }
-        Ok(())
-    }
-}
 
-#[cfg(target_arch = "wasm32")]
-// Sabre apply must return a bool
-fn apply(
-    request: &TpProcessRequest,
-    context: &mut dyn TransactionContext,
-) -> Result<bool, ApplyError> {
-    let handler = TrackAndTraceTransactionHandler::new();
-    match handler.apply(request, context) {
-        Ok(_) => Ok(true),
-        Err(err) => {
-            info!("{} received {}", handler.family_name(), err);
-            Err(err)
-        }
+        let proposal = BulkProposalBuilder::new()
+            .with_proposal_id(proposal_id.to_string())
+            .with_timestamp(timestamp)
+            .with_issuing_agent(signer.to_string())
+            .with_receiving_agent(receiving_agent.to_string())
+            .with_role(role.clone())
+            .with_record_ids(payload.record_ids().to_vec())
+            .with_status(Status::Open)
+            .with_terms(payload.terms().to_string())
+            .with_expires_at(*expires_at)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "BulkProposal"))?;
+
+        state.set_bulk_proposal(proposal_id, receiving_agent, proposal)
     }
-}
 
-#[cfg(target_arch = "wasm32")]
-#[no_mangle]
-pub unsafe fn entrypoint(payload: WasmPtr, signer: WasmPtr, signature: WasmPtr) -> i32 {
-    execute_entrypoint(payload, signer, signature, apply)
-}
+    /// Answers a bulk proposal. On acceptance every record is transferred, or none of them
+    /// are if any record can no longer be transferred by the issuing agent, in which case the
+    /// proposal is canceled.
+    fn _answer_bulk_proposal(
+        &self,
+        payload: &AnswerBulkProposalAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let proposal_id = payload.proposal_id();
+        let receiving_agent = payload.receiving_agent();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let proposal = match state.get_bulk_proposal(proposal_id, receiving_agent)? {
+            Some(proposal) => proposal,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Bulk proposal does not exist: {}",
+                    proposal_id
+                )));
+            }
+        };
 
-    use std::cell::RefCell;
-    use std::collections::HashMap;
+        if proposal.status() != &Status::Open {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Bulk proposal is not open: {}",
+                proposal_id
+            )));
+        }
 
-    use dgc_config::protocol::pike::state::{AgentBuilder, AgentListBuilder};
-    use dgc_config::protocol::schema::state::{
-        DataType, PropertyDefinitionBuilder, PropertyValueBuilder, SchemaBuilder, SchemaListBuilder,
-    };
-    use dgc_config::protocol::track_and_trace::payload::{
-        AnswerProposalActionBuilder, CreateProposalActionBuilder, CreateRecordActionBuilder,
-        FinalizeRecordActionBuilder, RevokeReporterActionBuilder, UpdatePropertiesAction,
-        UpdatePropertiesActionBuilder,
-    };
-    use dgc_config::protocol::track_and_trace::state::{
-        Property, PropertyListBuilder, PropertyPage, PropertyPageListBuilder, Proposal, Record,
-        RecordListBuilder, Role, Status,
-    };
-    use dgc_config::protos::IntoBytes;
-    use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
+        let mut updated_proposal_builder = proposal.clone().into_builder();
 
-    const TIMESTAMP: u64 = 1;
-    const RECORD_ID: &str = "test_record_action";
-    const PUBLIC_KEY: &str = "agent_public_key";
-    const OPTIONAL_PROPERTY_NAME: &str = "test_optional";
-    const REQUIRED_PROPERTY_NAME: &str = "test_required";
-    const SCHEMA_NAME: &str = "test_schema";
+        match payload.response() {
+            _ if proposal.is_expired(timestamp) => {
+                info!("Bulk proposal expired before it was answered");
 
-    #[derive(Default, Debug)]
-    /// A MockTransactionContext that can be used to test TrackAndTraceState
-    struct MockTransactionContext {
-        state: RefCell<HashMap<String, Vec<u8>>>,
-    }
+                updated_proposal_builder = updated_proposal_builder.with_status(Status::Expired);
+            }
 
-    impl TransactionContext for MockTransactionContext {
-        fn get_state_entries(
-            &self,
-            addresses: &[String],
-        ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
-            let mut results = Vec::new();
-            for addr in addresses {
-                let data = match self.state.borrow().get(addr) {
-                    Some(data) => data.clone(),
-                    None => Vec::new(),
-                };
-                results.push((addr.to_string(), data));
+            Response::Cancel => {
+                if proposal.issuing_agent() != signer {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Only the issuing agent can cancel a proposal",
+                    )));
+                }
+                updated_proposal_builder = updated_proposal_builder.with_status(Status::Canceled);
             }
-            Ok(results)
-        }
 
-        fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
-            for (addr, data) in entries {
-                self.state.borrow_mut().insert(addr, data);
+            Response::Reject => {
+                if proposal.receiving_agent() != signer {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Only the receiving agent can reject a proposal",
+                    )));
+                }
+                updated_proposal_builder = updated_proposal_builder.with_status(Status::Rejected);
             }
-            Ok(())
-        }
 
-        /// this is not needed for these tests
-        fn delete_state_entries(&self, _addresses: &[String]) -> Result<Vec<String>, ContextError> {
-            unimplemented!()
-        }
+            Response::Accept => {
+                if proposal.receiving_agent() != signer {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Only the receiving agent can accept a proposal",
+                    )));
+                }
+
+                // Check every record before transferring any of them
+                let mut records = Vec::new();
+                for record_id in proposal.record_ids() {
+                    let record = match state.get_record(record_id)? {
+                        Some(record) => record,
+                        None => {
+                            return Err(ApplyError::InvalidTransaction(format!(
+                                "Record in proposal does not exist: {}",
+                                record_id
+                            )));
+                        }
+                    };
+                    // The receiving agent may have lost the data key since the proposal
+                    // was created
+                    let lost_data_key = !record.data_keys().is_empty()
+                        && record
+                            .data_keys()
+                            .iter()
+                            .all(|data_key| data_key.agent_id() != receiving_agent);
+                    if *record.field_final()
+                        || lost_data_key
+                        || !holds_role(&record, proposal.role(), proposal.issuing_agent())
+                    {
+                        info!(
+                            "Record {} can no longer be transferred by the issuing agent",
+                            record_id
+                        );
+                        break;
+                    }
+                    records.push(record);
+                }
+
+                if records.len() == proposal.record_ids().len() {
+                    let new_holder = associated_agent_builder(state, receiving_agent, timestamp)?
+                        .with_proposal_issuing_agent(proposal.issuing_agent().to_string())
+                        .with_proposal_timestamp(*proposal.timestamp())
+                        .with_bulk_proposal_id(proposal_id.to_string())
+                        .build()
+                        .map_err(|err| map_builder_error_to_apply_error(err, "AssociatedAgent"))?;
+                    for record in records {
+                        let new_holder = new_holder.clone();
+                        match proposal.role() {
+                            Role::Owner => {
+                                self._transfer_ownership(record, new_holder, state, timestamp)?
+                            }
+                            _ => self._transfer_custody(record, new_holder, state)?,
+                        }
+                    }
+                    updated_proposal_builder =
+                        updated_proposal_builder.with_status(Status::Accepted);
+                } else {
+                    updated_proposal_builder =
+                        updated_proposal_builder.with_status(Status::Canceled);
+                }
+            }
+        }
+
+        let updated_proposal = updated_proposal_builder
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "BulkProposal"))?;
+
+        state.set_bulk_proposal(proposal_id, receiving_agent, updated_proposal)
+    }
+
+    /// Adds the receiving agent as the new owner of a record. The previous owner loses its
+    /// authorization to report on the properties of the record and the new owner gains it,
+    /// and the previous owner loses the data key unless it still holds another role.
+    fn _transfer_ownership(
+        &self,
+        record: Record,
+        new_owner: AssociatedAgent,
+        state: &mut TrackAndTraceState,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let record_id = record.record_id().to_string();
+        let receiving_agent = new_owner.agent_id().to_string();
+        let owner = match record.owners().last() {
+            Some(owner) => owner.clone(),
+            None => {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Owner not found",
+                )));
+            }
+        };
+
+        let mut record_owners = record.owners().to_vec();
+        record_owners.push(new_owner);
+        let updated_record = record
+            .clone()
+            .into_builder()
+            .with_owners(record_owners)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+
+        state.set_record(&record_id, updated_record)?;
+
+        let schema = match state.get_schema(record.schema())? {
+            Some(record_type) => record_type,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Schema does not exist: {}",
+                    record.schema()
+                )));
+            }
+        };
+
+        for prop_schema in schema.properties() {
+            let prop = match state.get_property(&record_id, prop_schema.name())? {
+                Some(prop) => prop,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Property does not exist: {}",
+                        prop_schema.name()
+                    )));
+                }
+            };
+
+            let new_reporters = prop
+                .reporters()
+                .iter()
+                .map(|reporter| {
+                    if reporter.public_key() == owner.agent_id() && *reporter.authorized() {
+                        unauthorize_reporter(reporter, owner.agent_id(), timestamp)
+                    } else {
+                        Ok(reporter.clone())
+                    }
+                })
+                .collect::<Result<Vec<_>, ApplyError>>()?;
+            let new_reporters = authorize_reporter(
+                &new_reporters,
+                &receiving_agent,
+                owner.agent_id(),
+                timestamp,
+            )?;
+
+            let updated_property = prop
+                .clone()
+                .into_builder()
+                .with_reporters(new_reporters.clone())
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Property"))?;
+
+            state.set_property(&record_id, prop.name(), updated_property)?;
+        }
+
+        revoke_data_keys(state, &record_id)
+    }
+
+    /// Adds the receiving agent as the new custodian of a record. The previous custodian loses
+    /// the data key unless it still holds another role.
+    fn _transfer_custody(
+        &self,
+        record: Record,
+        new_custodian: AssociatedAgent,
+        state: &mut TrackAndTraceState,
+    ) -> Result<(), ApplyError> {
+        let record_id = record.record_id().to_string();
+        let mut record_custodians = record.custodians().to_vec();
+        record_custodians.push(new_custodian);
+        let updated_record = record
+            .into_builder()
+            .with_custodians(record_custodians)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+
+        state.set_record(&record_id, updated_record)?;
+
+        revoke_data_keys(state, &record_id)
+    }
+
+    fn _revoke_reporter(
+        &self,
+        payload: &RevokeReporterAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let reporter_id = payload.reporter_id();
+        let properties = payload.properties();
+
+        let revoke_record = match state.get_record(record_id)? {
+            Some(record) => record,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not exists: {}",
+                    record_id
+                )));
+            }
+        };
+
+        let owner = match revoke_record.owners().last() {
+            Some(x) => x,
+            None => {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Owner was not found",
+                )));
+            }
+        };
+
+        if owner.agent_id() != signer {
+            return Err(ApplyError::InvalidTransaction(
+                "Must be owner to revoke reporters".to_string(),
+            ));
+        }
+
+        if *revoke_record.field_final() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record is final: {}",
+                record_id
+            )));
+        }
+
+        for prop_name in properties {
+            let prop = match state.get_property(record_id, prop_name)? {
+                Some(prop) => prop,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Property does not exist: {}",
+                        prop_name
+                    )));
+                }
+            };
+
+            let mut revoked = false;
+            let new_reporters = prop
+                .reporters()
+                .to_vec()
+                .iter()
+                .map(|reporter| {
+                    if reporter.public_key() == reporter_id {
+                        if !*reporter.authorized() {
+                            return Err(ApplyError::InvalidTransaction(
+                                "Reporter is already unauthorized.".to_string(),
+                            ));
+                        }
+                        revoked = true;
+                        unauthorize_reporter(reporter, signer, timestamp)
+                    } else {
+                        Ok(reporter.clone())
+                    }
+                })
+                .collect::<Result<Vec<_>, ApplyError>>()?;
+
+            if !revoked {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "{} not a reporter for property {}",
+                    reporter_id, prop_name
+                )));
+            }
+            let updated_property = prop
+                .clone()
+                .into_builder()
+                .with_reporters(new_reporters)
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Property"))?;
+
+            state.set_property(record_id, prop_name, updated_property)?;
+        }
+
+        revoke_data_keys(state, record_id)
+    }
+
+    fn _split_record(
+        &self,
+        payload: &SplitRecordAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        self._create_child_records(
+            &[payload.parent().clone()],
+            payload.children(),
+            state,
+            signer,
+            timestamp,
+        )
+    }
+
+    fn _merge_records(
+        &self,
+        payload: &MergeRecordsAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        self._create_child_records(
+            payload.parents(),
+            &[payload.child().clone()],
+            state,
+            signer,
+            timestamp,
+        )
+    }
+
+    fn _transform_record(
+        &self,
+        payload: &TransformRecordAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        self._create_child_records(
+            &[payload.parent().clone()],
+            &[payload.child().clone()],
+            state,
+            signer,
+            timestamp,
+        )
+    }
+
+    /// Creates the child records of a split, merge or transform and links them to their
+    /// parents. The signer must be the owner and custodian of every parent. Consumed parents
+    /// are finalized, the others stay open so the rest of them can be used later.
+    fn _create_child_records(
+        &self,
+        parents: &[LineageParent],
+        children: &[CreateRecordAction],
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let mut parent_records: Vec<(&LineageParent, Record)> = Vec::new();
+        for parent in parents {
+            let record_id = parent.record_id();
+            if parent_records
+                .iter()
+                .any(|(_, record)| record.record_id() == record_id)
+            {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Parent record {} is listed more than once",
+                    record_id
+                )));
+            }
+            let record = match state.get_record(record_id)? {
+                Some(record) => record,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record does not exist: {}",
+                        record_id
+                    )));
+                }
+            };
+            if *record.field_final() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record is final: {}",
+                    record_id
+                )));
+            }
+            let is_owner = record
+                .owners()
+                .last()
+                .map(|owner| owner.agent_id() == signer)
+                .unwrap_or(false);
+            let is_custodian = record
+                .custodians()
+                .last()
+                .map(|custodian| custodian.agent_id() == signer)
+                .unwrap_or(false);
+            if !is_owner || !is_custodian {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Must be owner and custodian of parent record {}",
+                    record_id
+                )));
+            }
+            parent_records.push((parent, record));
+        }
+
+        let mut child_ids: Vec<String> = Vec::new();
+        for child in children {
+            let record_id = child.record_id().to_string();
+            if child_ids.contains(&record_id) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Child record {} is listed more than once",
+                    record_id
+                )));
+            }
+            child_ids.push(record_id);
+        }
+
+        let parent_ids = parent_records
+            .iter()
+            .map(|(_, record)| record.record_id().to_string())
+            .collect::<Vec<String>>();
+
+        for child in children {
+            self._create_record(child, state, signer, timestamp)?;
+            let record = match state.get_record(child.record_id())? {
+                Some(record) => record,
+                None => {
+                    return Err(ApplyError::InternalError(format!(
+                        "Child record was not created: {}",
+                        child.record_id()
+                    )));
+                }
+            };
+            let updated_record = record
+                .into_builder()
+                .with_parents(parent_ids.clone())
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+            state.set_record(child.record_id(), updated_record)?;
+        }
+
+        for (parent, record) in parent_records {
+            let mut children = record.children().to_vec();
+            children.extend(child_ids.iter().cloned());
+            let updated_record = record
+                .clone()
+                .into_builder()
+                .with_children(children)
+                .with_field_final(*parent.consume())
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+            state.set_record(parent.record_id(), updated_record)?;
+        }
+
+        Ok(())
+    }
+
+    fn _create_recall(
+        &self,
+        payload: &CreateRecallAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let agent = match state.get_agent(signer)? {
+            Some(agent) => agent,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Agent is not registered: {}",
+                    signer
+                )));
+            }
+        };
+        let recall_id = payload.recall_id();
+        if state.get_recall(recall_id)?.is_some() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Recall already exists: {}",
+                recall_id
+            )));
+        }
+
+        let product_id = payload.product_id();
+        if !product_id.is_empty() {
+            let product = match state.get_product(product_id)? {
+                Some(product) if !product.deleted() => product,
+                _ => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Product does not exist: {}",
+                        product_id
+                    )));
+                }
+            };
+            if product.owner() != agent.org_id() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "The signing Agent does not belong to the product owner organization: {} != {}",
+                    agent.org_id(),
+                    product.owner()
+                )));
+            }
+        }
+
+        let mut record_ids: Vec<String> = Vec::new();
+        for record_id in payload.record_ids() {
+            if record_ids.contains(record_id) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} is listed more than once",
+                    record_id
+                )));
+            }
+            let record = match state.get_record(record_id)? {
+                Some(record) => record,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record does not exist: {}",
+                        record_id
+                    )));
+                }
+            };
+            // Owning the product does not extend to records owned by other agents
+            if record.owners().last().map(|owner| owner.agent_id()) != Some(signer) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Must be owner of record {} to recall it",
+                    record_id
+                )));
+            }
+            record_ids.push(record_id.to_string());
+        }
+
+        let recall = RecallBuilder::new()
+            .with_recall_id(recall_id.to_string())
+            .with_issuing_agent(signer.to_string())
+            .with_product_id(product_id.to_string())
+            .with_record_ids(record_ids)
+            .with_reason(payload.reason().to_string())
+            .with_severity(payload.severity().clone())
+            .with_status(RecallStatus::Open)
+            .with_timestamp(timestamp)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Recall"))?;
+
+        state.set_recall(recall_id, recall)?;
+
+        Ok(())
+    }
+
+    fn _acknowledge_recall(
+        &self,
+        payload: &AcknowledgeRecallAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let recall_id = payload.recall_id();
+        let recall = match state.get_recall(recall_id)? {
+            Some(recall) => recall,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Recall does not exist: {}",
+                    recall_id
+                )));
+            }
+        };
+        if *recall.status() != RecallStatus::Open {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Recall is closed: {}",
+                recall_id
+            )));
+        }
+
+        let record_id = payload.record_id();
+        let record = match state.get_record(record_id)? {
+            Some(record) => record,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not exist: {}",
+                    record_id
+                )));
+            }
+        };
+        // A product recall also covers the records that reference the product
+        let recalled = recall.record_ids().iter().any(|id| id == record_id)
+            || (!recall.product_id().is_empty()
+                && references_product(state, &record, recall.product_id())?);
+        if !recalled {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record {} is not part of recall {}",
+                record_id, recall_id
+            )));
+        }
+        if record.custodians().last().map(|custodian| custodian.agent_id()) != Some(signer) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Must be custodian of record {} to acknowledge recall",
+                record_id
+            )));
+        }
+        if recall
+            .acknowledgements()
+            .iter()
+            .any(|ack| ack.record_id() == record_id && ack.agent_id() == signer)
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Recall of record {} already acknowledged",
+                record_id
+            )));
+        }
+
+        let acknowledgement = AcknowledgementBuilder::new()
+            .with_record_id(record_id.to_string())
+            .with_agent_id(signer.to_string())
+            .with_timestamp(timestamp)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Acknowledgement"))?;
+        let mut acknowledgements = recall.acknowledgements().to_vec();
+        acknowledgements.push(acknowledgement);
+
+        let updated_recall = recall
+            .into_builder()
+            .with_acknowledgements(acknowledgements)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Recall"))?;
+
+        state.set_recall(recall_id, updated_recall)?;
+
+        Ok(())
+    }
+
+    fn _close_recall(
+        &self,
+        payload: &CloseRecallAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let recall_id = payload.recall_id();
+        let recall = match state.get_recall(recall_id)? {
+            Some(recall) => recall,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Recall does not exist: {}",
+                    recall_id
+                )));
+            }
+        };
+        if recall.issuing_agent() != signer {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Only the issuing agent can close recall {}",
+                recall_id
+            )));
+        }
+        if *recall.status() != RecallStatus::Open {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Recall is closed: {}",
+                recall_id
+            )));
+        }
+
+        let updated_recall = recall
+            .into_builder()
+            .with_status(RecallStatus::Closed)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Recall"))?;
+
+        state.set_recall(recall_id, updated_recall)?;
+
+        Ok(())
+    }
+}
+
+/// Checks that every entity referenced by a REFERENCE value, including REFERENCE values
+/// nested in a STRUCT or LIST, exists in state.
+fn check_references(
+    state: &TrackAndTraceState,
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+    path: &str,
+) -> Result<(), ApplyError> {
+    // The references in an encrypted value can not be read
+    if *definition.confidential() {
+        return Ok(());
+    }
+    match definition.data_type() {
+        DataType::Reference => check_reference(state, definition, value.reference_value(), path),
+        DataType::Struct => {
+            for struct_value in value.struct_values() {
+                if let Some(struct_definition) = definition
+                    .struct_properties()
+                    .iter()
+                    .find(|d| d.name() == struct_value.name())
+                {
+                    check_references(
+                        state,
+                        struct_definition,
+                        struct_value,
+                        &format!("{}.{}", path, struct_value.name()),
+                    )?;
+                }
+            }
+            Ok(())
+        }
+        DataType::List => {
+            if let Some(element) = definition.list_element() {
+                for (index, list_value) in value.list_values().iter().enumerate() {
+                    check_references(
+                        state,
+                        element,
+                        list_value,
+                        &format!("{}[{}]", path, index),
+                    )?;
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_reference(
+    state: &TrackAndTraceState,
+    definition: &PropertyDefinition,
+    reference: &str,
+    path: &str,
+) -> Result<(), ApplyError> {
+    let exists = match definition.reference_kind() {
+        Some(ReferenceKind::Record) => match state.get_record(reference)? {
+            Some(record) => {
+                if !definition.reference_schema().is_empty()
+                    && record.schema() != definition.reference_schema()
+                {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record referenced by {} does not have schema {}: {}",
+                        path,
+                        definition.reference_schema(),
+                        reference
+                    )));
+                }
+                true
+            }
+            None => false,
+        },
+        Some(ReferenceKind::Product) => match state.get_product(reference)? {
+            Some(product) => !product.deleted(),
+            None => false,
+        },
+        Some(ReferenceKind::Organization) => state.get_organization(reference)?.is_some(),
+        Some(ReferenceKind::Agent) => state.get_agent(reference)?.is_some(),
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Property {} does not define a reference kind",
+                path
+            )));
+        }
+    };
+
+    if !exists {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Entity referenced by {} does not exist: {}",
+            path, reference
+        )));
+    }
+    Ok(())
+}
+
+/// Whether the latest value of a non-confidential property of the record references the
+/// product, including REFERENCE values nested in a STRUCT or LIST
+fn references_product(
+    state: &TrackAndTraceState,
+    record: &Record,
+    product_id: &str,
+) -> Result<bool, ApplyError> {
+    let schema = match state.get_schema(record.schema())? {
+        Some(schema) => schema,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Schema does not exist: {}",
+                record.schema()
+            )));
+        }
+    };
+    for definition in schema.properties() {
+        if *definition.confidential() {
+            continue;
+        }
+        let prop = match state.get_property(record.record_id(), definition.name())? {
+            Some(prop) => prop,
+            None => continue,
+        };
+        if let Some(latest) = latest_reported_value(state, &prop)? {
+            if value_references_product(definition, latest.value(), product_id) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn value_references_product(
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+    product_id: &str,
+) -> bool {
+    match definition.data_type() {
+        DataType::Reference => {
+            definition.reference_kind() == Some(&ReferenceKind::Product)
+                && value.reference_value() == product_id
+        }
+        DataType::Struct => value.struct_values().iter().any(|struct_value| {
+            definition
+                .struct_properties()
+                .iter()
+                .find(|d| d.name() == struct_value.name())
+                .map_or(false, |struct_definition| {
+                    value_references_product(struct_definition, struct_value, product_id)
+                })
+        }),
+        DataType::List => definition.list_element().map_or(false, |element| {
+            value
+                .list_values()
+                .iter()
+                .any(|list_value| value_references_product(element, list_value, product_id))
+        }),
+        _ => false,
+    }
+}
+
+/// Whether the agent is an active admin of the organization that must counter-sign a proposal
+/// under the condition
+fn is_counter_signer(condition: &Condition, agent: &Agent) -> bool {
+    condition.condition_type() == &ConditionType::CounterSignature
+        && condition.org_id() == agent.org_id()
+        && *agent.active()
+        && agent.roles().iter().any(|role| role == "admin")
+}
+
+/// Checks the conditions of a proposal being accepted, returning the first one that is not
+/// met as an error
+fn check_proposal_conditions(
+    state: &TrackAndTraceState,
+    proposal: &Proposal,
+) -> Result<(), ApplyError> {
+    for condition in proposal.conditions() {
+        match condition.condition_type() {
+            ConditionType::PropertyRange => {
+                let property = match state
+                    .get_property(proposal.record_id(), condition.property_name())?
+                {
+                    Some(property) => property,
+                    None => {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Proposal condition not met: property {} does not exist",
+                            condition.property_name()
+                        )));
+                    }
+                };
+                for reported_value in reported_values(state, &property)? {
+                    let value = *reported_value.value().number_value();
+                    if value < *condition.min() || value > *condition.max() {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Proposal condition not met: {} was {} at {}, outside of {} to {}",
+                            condition.property_name(),
+                            value,
+                            reported_value.timestamp(),
+                            condition.min(),
+                            condition.max()
+                        )));
+                    }
+                }
+            }
+            ConditionType::CounterSignature => {
+                let mut counter_signed = false;
+                for counter_signature in proposal.counter_signatures() {
+                    if let Some(agent) = state.get_agent(counter_signature.agent_id())? {
+                        counter_signed |= is_counter_signer(condition, &agent);
+                    }
+                }
+                if !counter_signed {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Proposal condition not met: no counter-signature from an admin of {}",
+                        condition.org_id()
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Collects every value reported for a property that is still in state, oldest page first
+fn reported_values(
+    state: &TrackAndTraceState,
+    property: &Property,
+) -> Result<Vec<ReportedValue>, ApplyError> {
+    let current_page = *property.current_page();
+    let mut page_numbers = (1..=current_page).collect::<Vec<_>>();
+    if *property.wrapped() {
+        let max_pages = *property.property_definition().max_pages();
+        page_numbers = (current_page + 1..=max_pages).chain(page_numbers).collect();
+    }
+    let mut reported_values = Vec::new();
+    for page_number in page_numbers {
+        if let Some(page) =
+            state.get_property_page(property.record_id(), property.name(), page_number)?
+        {
+            reported_values.extend(page.reported_values().iter().cloned());
+        }
+    }
+    Ok(reported_values)
+}
+
+/// Checks that the signer may report the update for the property and that the update is a
+/// valid value of it, returning the reporter index of the signer
+fn check_property_update(
+    state: &TrackAndTraceState,
+    prop: &Property,
+    update: &PropertyValue,
+    signer: &str,
+) -> Result<u32, ApplyError> {
+    let reporter_index = match prop
+        .reporters()
+        .iter()
+        .find(|reporter| reporter.public_key() == signer && *reporter.authorized())
+    {
+        Some(reporter) => *reporter.index(),
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Reporter is not authorized: {}",
+                signer
+            )));
+        }
+    };
+
+    if update.data_type() != prop.property_definition().data_type() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Update has wrong type: {:?} != {:?}",
+            update.data_type(),
+            prop.property_definition().data_type()
+        )));
+    }
+
+    let constraints = prop.property_definition().constraints();
+    if *constraints.immutable() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Property {} is immutable and cannot be updated",
+            prop.name()
+        )));
+    }
+    validate_property_value(prop.property_definition(), update)
+        .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
+    check_references(state, prop.property_definition(), update, prop.name())?;
+
+    Ok(reporter_index)
+}
+
+/// Appends reported values to the history of a property, starting new pages as the current
+/// one fills up according to the paging of the property definition. Every page that is
+/// touched is written once, as is the property when its current page changes.
+fn append_reported_values(
+    state: &mut TrackAndTraceState,
+    prop: Property,
+    reported_values: Vec<ReportedValue>,
+) -> Result<(), ApplyError> {
+    let record_id = prop.record_id().to_string();
+    let name = prop.name().to_string();
+    let page_size = *prop.property_definition().page_size() as usize;
+    let max_pages = *prop.property_definition().max_pages();
+    let retention = prop.property_definition().retention().clone();
+
+    let mut page_number = *prop.current_page();
+    let mut wrapped = *prop.wrapped();
+    let mut archive_anchor = prop.archive_anchor().to_string();
+    let mut archived_pages = *prop.archived_pages();
+
+    let empty_page = || {
+        PropertyPageBuilder::new()
+            .with_name(name.to_string())
+            .with_record_id(record_id.to_string())
+            .with_reported_values(vec![])
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "PropertyPage"))
+    };
+    let mut page = match state.get_property_page(&record_id, &name, page_number)? {
+        Some(page) => page,
+        None => empty_page()?,
+    };
+    let mut values = page.reported_values().to_vec();
+    let mut advanced = false;
+    // The filled pages, in the order they were filled, waiting to be written
+    let mut filled_pages: Vec<(u32, PropertyPage, Vec<ReportedValue>)> = Vec::new();
+
+    for reported_value in reported_values {
+        // Only the last page of a property that keeps all of its history is left full
+        if values.len() >= page_size {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Property {} has filled all {} pages of its history",
+                name, max_pages
+            )));
+        }
+        values.push(flag_geofence_breaches(state, &prop, reported_value)?);
+
+        let last_page = page_number >= max_pages;
+        if values.len() >= page_size && !(last_page && retention == RetentionPolicy::KeepAll) {
+            filled_pages.push((page_number, page, values));
+            advanced = true;
+
+            page_number = if last_page { 1 } else { page_number + 1 };
+            wrapped = wrapped || page_number == 1;
+            // A page filled earlier in this batch is overwritten in place
+            let filled_index = filled_pages
+                .iter()
+                .position(|(number, _, _)| *number == page_number);
+            let old_page = match filled_index {
+                Some(index) => {
+                    let (_, old_page, old_values) = filled_pages.remove(index);
+                    Some(
+                        old_page
+                            .into_builder()
+                            .with_reported_values(old_values)
+                            .build()
+                            .map_err(|err| map_builder_error_to_apply_error(err, "PropertyPage"))?,
+                    )
+                }
+                None => state.get_property_page(&record_id, &name, page_number)?,
+            };
+            page = match old_page {
+                Some(old_page) => {
+                    if retention == RetentionPolicy::Archive
+                        && !old_page.reported_values().is_empty()
+                    {
+                        archive_anchor = archive_page(&archive_anchor, &old_page)?;
+                        archived_pages += 1;
+                    }
+                    old_page
+                }
+                None => empty_page()?,
+            };
+            values = vec![];
+        }
+    }
+    filled_pages.push((page_number, page, values));
+
+    for (number, page, mut values) in filled_pages {
+        values.sort_by_key(|rv| (*rv.timestamp(), *rv.reporter_index()));
+        let page = page
+            .into_builder()
+            .with_reported_values(values)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "PropertyPage"))?;
+        state.set_property_page(&record_id, &name, number, page)?;
+    }
+
+    if advanced {
+        let new_property = prop
+            .into_builder()
+            .with_current_page(page_number)
+            .with_wrapped(wrapped)
+            .with_archive(archive_anchor, archived_pages)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Property"))?;
+        state.set_property(&record_id, &name, new_property)?;
+    }
+
+    Ok(())
+}
+
+/// The most recently reported value of a property, from its current page or, when that page
+/// was just started, from the page before it
+fn latest_reported_value(
+    state: &TrackAndTraceState,
+    prop: &Property,
+) -> Result<Option<ReportedValue>, ApplyError> {
+    let current_page = *prop.current_page();
+    let previous_page = match current_page {
+        1 if *prop.wrapped() => Some(*prop.property_definition().max_pages()),
+        1 => None,
+        page => Some(page - 1),
+    };
+
+    for page_number in std::iter::once(current_page).chain(previous_page) {
+        if let Some(page) = state.get_property_page(prop.record_id(), prop.name(), page_number)? {
+            if let Some(latest) = page
+                .reported_values()
+                .iter()
+                .max_by_key(|rv| (*rv.timestamp(), *rv.reporter_index()))
+            {
+                return Ok(Some(latest.clone()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Evaluates the alert rules of a NUMBER property against new values, oldest first. A value
+/// that breaks a rule starts an excursion, which is raised once the rule has stayed broken
+/// for its sustained duration; the first value that no longer breaks the rule ends it, or
+/// drops it if it was never raised. The record is written when its excursions changed.
+fn evaluate_alert_rules(
+    state: &mut TrackAndTraceState,
+    prop: &Property,
+    reported_values: &[ReportedValue],
+) -> Result<(), ApplyError> {
+    let rules = prop.property_definition().alert_rules();
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let record = match state.get_record(prop.record_id())? {
+        Some(record) => record,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record does not exist: {}",
+                prop.record_id()
+            )));
+        }
+    };
+    let custodian = record
+        .custodians()
+        .last()
+        .map(|custodian| custodian.agent_id().to_string())
+        .unwrap_or_default();
+    let latest = latest_reported_value(state, prop)?
+        .map(|rv| (*rv.timestamp(), *rv.value().number_value()));
+
+    let mut excursions = record.excursions().to_vec();
+    for rule in rules {
+        let mut open = excursions.iter().position(|excursion| {
+            excursion.property_name() == prop.name()
+                && excursion.rule_name() == rule.name()
+                && *excursion.ended_at() == 0
+        });
+        let mut previous = latest;
+
+        for reported_value in reported_values {
+            let timestamp = *reported_value.timestamp();
+            let value = *reported_value.value().number_value();
+            let broken = rule.is_out_of_range(value)
+                || previous.map_or(false, |(previous_timestamp, previous_value)| {
+                    rule.exceeds_rate_of_change(
+                        previous_value,
+                        previous_timestamp,
+                        value,
+                        timestamp,
+                    )
+                });
+            previous = Some((timestamp, value));
+
+            if broken {
+                let index = match open {
+                    Some(index) => index,
+                    None => {
+                        excursions.push(
+                            ExcursionBuilder::new()
+                                .with_property_name(prop.name().to_string())
+                                .with_rule_name(rule.name().to_string())
+                                .with_started_at(timestamp)
+                                .build()
+                                .map_err(|err| map_builder_error_to_apply_error(err, "Excursion"))?,
+                        );
+                        excursions.len() - 1
+                    }
+                };
+                let excursion = excursions[index].clone();
+                let sustained =
+                    timestamp.saturating_sub(*excursion.started_at()) >= *rule.sustained_duration();
+                let raise = *excursion.raised_at() == 0 && sustained;
+                let mut builder = excursion.into_builder().with_last_value(value);
+                if raise {
+                    builder = builder.with_raised(timestamp, custodian.to_string());
+                }
+                excursions[index] = builder
+                    .build()
+                    .map_err(|err| map_builder_error_to_apply_error(err, "Excursion"))?;
+                open = Some(index);
+            } else if let Some(index) = open.take() {
+                if *excursions[index].raised_at() == 0 {
+                    excursions.remove(index);
+                } else {
+                    excursions[index] = excursions[index]
+                        .clone()
+                        .into_builder()
+                        .with_ended_at(timestamp)
+                        .build()
+                        .map_err(|err| map_builder_error_to_apply_error(err, "Excursion"))?;
+                }
+            }
+        }
+    }
+
+    if excursions.as_slice() != record.excursions() {
+        let updated_record = record
+            .into_builder()
+            .with_excursions(excursions)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+        state.set_record(prop.record_id(), updated_record)?;
+    }
+
+    Ok(())
+}
+
+/// Flags a reported LAT_LONG value with the geofences of its property that it is outside of,
+/// emitting an event for each of them
+fn flag_geofence_breaches(
+    state: &mut TrackAndTraceState,
+    prop: &Property,
+    reported_value: ReportedValue,
+) -> Result<ReportedValue, ApplyError> {
+    let breaches = prop
+        .property_definition()
+        .geofence_breaches(reported_value.value());
+    if breaches.is_empty() {
+        return Ok(reported_value);
+    }
+
+    let reporter = prop
+        .reporters()
+        .iter()
+        .find(|reporter| reporter.index() == reported_value.reporter_index())
+        .map(|reporter| reporter.public_key().to_string())
+        .unwrap_or_default();
+    let location = reported_value.value().lat_long_value();
+    for geofence in &breaches {
+        let attributes = vec![
+            ("record_id".to_string(), prop.record_id().to_string()),
+            ("property_name".to_string(), prop.name().to_string()),
+            ("geofence".to_string(), geofence.to_string()),
+            ("reporter".to_string(), reporter.to_string()),
+            ("timestamp".to_string(), reported_value.timestamp().to_string()),
+            ("latitude".to_string(), location.latitude().to_string()),
+            ("longitude".to_string(), location.longitude().to_string()),
+        ];
+        state.add_event(GEOFENCE_BREACH_EVENT, attributes, &[])?;
+    }
+
+    reported_value
+        .into_builder()
+        .with_geofence_breaches(breaches)
+        .build()
+        .map_err(|err| map_builder_error_to_apply_error(err, "ReportedValue"))
+}
+
+/// Adds a version of the data key of a record wrapped for an agent to the record, unless the
+/// agent already holds that version. An agent that receives an outdated version is only given
+/// the current one on the next rotation, which is therefore required.
+fn share_data_key(
+    state: &mut TrackAndTraceState,
+    record_id: &str,
+    agent_id: &str,
+    wrapped_key: &[u8],
+    version: u32,
+) -> Result<(), ApplyError> {
+    let record = match state.get_record(record_id)? {
+        Some(record) => record,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record does not exist: {}",
+                record_id
+            )));
+        }
+    };
+    if record.versioned_data_key(agent_id, version).is_some() {
+        return Ok(());
+    }
+
+    let rotation_required =
+        *record.data_key_rotation_required() || version != *record.data_key_version();
+    let mut data_keys = record.data_keys().to_vec();
+    data_keys.push(
+        DataKeyBuilder::new()
+            .with_agent_id(agent_id.to_string())
+            .with_wrapped_key(wrapped_key.to_vec())
+            .with_version(version)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "DataKey"))?,
+    );
+    let updated_record = record
+        .into_builder()
+        .with_data_keys(data_keys)
+        .with_data_key_rotation_required(rotation_required)
+        .build()
+        .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+    state.set_record(record_id, updated_record)
+}
+
+/// The agents that may hold the data key of a record: its current owner and custodian, and
+/// the authorized reporters of its confidential properties
+fn data_key_holders(
+    state: &TrackAndTraceState,
+    record: &Record,
+) -> Result<HashSet<String>, ApplyError> {
+    let mut holders = HashSet::new();
+    if let Some(owner) = record.owners().last() {
+        holders.insert(owner.agent_id().to_string());
+    }
+    if let Some(custodian) = record.custodians().last() {
+        holders.insert(custodian.agent_id().to_string());
+    }
+
+    let schema = match state.get_schema(record.schema())? {
+        Some(schema) => schema,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Schema does not exist: {}",
+                record.schema()
+            )));
+        }
+    };
+    for definition in schema.properties() {
+        if !*definition.confidential() {
+            continue;
+        }
+        if let Some(prop) = state.get_property(record.record_id(), definition.name())? {
+            for reporter in prop.reporters() {
+                if *reporter.authorized() {
+                    holders.insert(reporter.public_key().to_string());
+                }
+            }
+        }
+    }
+
+    Ok(holders)
+}
+
+/// Removes the wrapped data keys of the agents that no longer hold a role on a record. The
+/// data key must then be rotated before the next confidential value is written, as the
+/// removed agents may have kept the data key.
+fn revoke_data_keys(state: &mut TrackAndTraceState, record_id: &str) -> Result<(), ApplyError> {
+    let record = match state.get_record(record_id)? {
+        Some(record) => record,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record does not exist: {}",
+                record_id
+            )));
+        }
+    };
+    if record.data_keys().is_empty() {
+        return Ok(());
+    }
+
+    let holders = data_key_holders(state, &record)?;
+    let data_keys = record
+        .data_keys()
+        .iter()
+        .filter(|data_key| holders.contains(data_key.agent_id()))
+        .cloned()
+        .collect::<Vec<_>>();
+    if data_keys.len() == record.data_keys().len() {
+        return Ok(());
+    }
+
+    let updated_record = record
+        .into_builder()
+        .with_data_keys(data_keys)
+        .with_data_key_rotation_required(true)
+        .build()
+        .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+    state.set_record(record_id, updated_record)
+}
+
+/// Rotates the data key of a record when a write carries a new version of it, which must be
+/// wrapped for exactly the agents that hold the data key. Checks that the confidential values
+/// of the write are encrypted with the current version, and that a data key that must be
+/// rotated is not used for new values.
+fn rotate_data_key<'a>(
+    state: &mut TrackAndTraceState,
+    record: Record,
+    data_keys: &[DataKey],
+    values: impl Iterator<Item = &'a PropertyValue>,
+    signer: &str,
+) -> Result<(), ApplyError> {
+    let record_id = record.record_id().to_string();
+    let encrypted_values = values
+        .filter(|value| !value.encrypted_value().is_empty())
+        .collect::<Vec<_>>();
+
+    let version = if !data_keys.is_empty() {
+        if record.data_keys().is_empty() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record {} has no data key to rotate",
+                record_id
+            )));
+        }
+        let holders = record
+            .data_keys()
+            .iter()
+            .map(|data_key| data_key.agent_id())
+            .collect::<HashSet<_>>();
+        if !holders.contains(signer) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Only an agent holding the data key of record {} can rotate it",
+                record_id
+            )));
+        }
+        let mut wrapped_for = HashSet::new();
+        for data_key in data_keys {
+            if !wrapped_for.insert(data_key.agent_id()) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "The data key of record {} is wrapped more than once for {}",
+                    record_id,
+                    data_key.agent_id()
+                )));
+            }
+        }
+        if wrapped_for != holders {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "The data key of record {} must be wrapped for every agent holding it",
+                record_id
+            )));
+        }
+
+        let version = record.data_key_version() + 1;
+        let mut updated_data_keys = record.data_keys().to_vec();
+        for data_key in data_keys {
+            updated_data_keys.push(
+                DataKeyBuilder::new()
+                    .with_agent_id(data_key.agent_id().to_string())
+                    .with_wrapped_key(data_key.wrapped_key().to_vec())
+                    .with_version(version)
+                    .build()
+                    .map_err(|err| map_builder_error_to_apply_error(err, "DataKey"))?,
+            );
+        }
+        let updated_record = record
+            .into_builder()
+            .with_data_keys(updated_data_keys)
+            .with_data_key_version(version)
+            .with_data_key_rotation_required(false)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+        state.set_record(&record_id, updated_record)?;
+        version
+    } else if *record.data_key_rotation_required() && !encrypted_values.is_empty() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "The data key of record {} was revoked, it must be rotated before confidential \
+             values are written",
+            record_id
+        )));
+    } else {
+        *record.data_key_version()
+    };
+
+    for value in encrypted_values {
+        if *value.key_version() != version {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Value of {} is encrypted with version {} of the data key, not {}",
+                value.name(),
+                value.key_version(),
+                version
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Folds a page that is about to be overwritten into the archive anchor of its property
+fn archive_page(archive_anchor: &str, page: &PropertyPage) -> Result<String, ApplyError> {
+    let page_bytes = page.clone().into_bytes().map_err(|err| {
+        ApplyError::InternalError(format!("Cannot serialize property page: {:?}", err))
+    })?;
+    Ok(hash(&format!("{}{}", archive_anchor, hex::encode(page_bytes)), 128))
+}
+
+/// Moves an open proposal that has expired at the given time to the Expired status
+fn expire_proposal(proposal: Proposal, timestamp: u64) -> Result<Proposal, ApplyError> {
+    if proposal.status() == &Status::Open && proposal.is_expired(timestamp) {
+        proposal
+            .into_builder()
+            .with_status(Status::Expired)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Proposal"))
+    } else {
+        Ok(proposal)
+    }
+}
+
+/// Whether the agent is the current owner, or the current custodian, of the record
+/// Starts the entry of an agent associated with a record, recording the organization the
+/// agent belongs to at the time
+fn associated_agent_builder(
+    state: &TrackAndTraceState,
+    agent_id: &str,
+    timestamp: u64,
+) -> Result<AssociatedAgentBuilder, ApplyError> {
+    let org_id = state
+        .get_agent(agent_id)?
+        .map(|agent| agent.org_id().to_string())
+        .unwrap_or_default();
+    Ok(AssociatedAgentBuilder::new()
+        .with_agent_id(agent_id.to_string())
+        .with_timestamp(timestamp)
+        .with_org_id(org_id))
+}
+
+/// The entry of the receiving agent of a proposal accepted at `timestamp`
+fn proposal_associated_agent(
+    state: &TrackAndTraceState,
+    proposal: &Proposal,
+    timestamp: u64,
+) -> Result<AssociatedAgent, ApplyError> {
+    associated_agent_builder(state, proposal.receiving_agent(), timestamp)?
+        .with_proposal_issuing_agent(proposal.issuing_agent().to_string())
+        .with_proposal_timestamp(*proposal.timestamp())
+        .build()
+        .map_err(|err| map_builder_error_to_apply_error(err, "AssociatedAgent"))
+}
+
+fn holds_role(record: &Record, role: &Role, agent_id: &str) -> bool {
+    let holders = match role {
+        Role::Owner => record.owners(),
+        _ => record.custodians(),
+    };
+    holders.last().map(|holder| holder.agent_id()) == Some(agent_id)
+}
+
+/// Authorizes an agent to report a property, opening a new authorization interval.
+///
+/// An agent that reported the property before keeps its reporter index, so values it
+/// reported under earlier authorizations still resolve to it.
+fn authorize_reporter(
+    reporters: &[Reporter],
+    public_key: &str,
+    granted_by: &str,
+    timestamp: u64,
+) -> Result<Vec<Reporter>, ApplyError> {
+    let authorization = ReporterAuthorizationBuilder::new()
+        .with_granted_at(timestamp)
+        .with_granted_by(granted_by.to_string())
+        .build()
+        .map_err(|err| map_builder_error_to_apply_error(err, "ReporterAuthorization"))?;
+
+    let mut updated_reporters = reporters.to_vec();
+    match updated_reporters
+        .iter_mut()
+        .find(|reporter| reporter.public_key() == public_key)
+    {
+        Some(reporter) if *reporter.authorized() => (),
+        Some(reporter) => {
+            let mut authorizations = reporter.authorizations().to_vec();
+            authorizations.push(authorization);
+            *reporter = reporter
+                .clone()
+                .into_builder()
+                .with_authorized(true)
+                .with_authorizations(authorizations)
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Reporter"))?;
+        }
+        None => {
+            let reporter = ReporterBuilder::new()
+                .with_public_key(public_key.to_string())
+                .with_authorized(true)
+                .with_index(reporters.len() as u32)
+                .with_authorizations(vec![authorization])
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Reporter"))?;
+            updated_reporters.push(reporter);
+        }
+    }
+
+    Ok(updated_reporters)
+}
+
+/// Unauthorizes a reporter, closing its open authorization interval
+fn unauthorize_reporter(
+    reporter: &Reporter,
+    revoked_by: &str,
+    timestamp: u64,
+) -> Result<Reporter, ApplyError> {
+    let authorizations = reporter
+        .authorizations()
+        .iter()
+        .map(|authorization| {
+            if *authorization.revoked_at() == 0 {
+                authorization
+                    .clone()
+                    .into_builder()
+                    .with_revoked(timestamp, revoked_by.to_string())
+                    .build()
+                    .map_err(|err| map_builder_error_to_apply_error(err, "ReporterAuthorization"))
+            } else {
+                Ok(authorization.clone())
+            }
+        })
+        .collect::<Result<Vec<_>, ApplyError>>()?;
+
+    reporter
+        .clone()
+        .into_builder()
+        .with_authorized(false)
+        .with_authorizations(authorizations)
+        .build()
+        .map_err(|err| map_builder_error_to_apply_error(err, "Reporter"))
+}
+
+fn map_builder_error_to_apply_error(err: BuilderError, protocol_name: &str) -> ApplyError {
+    ApplyError::InvalidTransaction(format!(
+        "Failed to build {}. {}",
+        protocol_name,
+        err.to_string()
+    ))
+}
+
+impl TransactionHandler for TrackAndTraceTransactionHandler {
+    fn family_name(&self) -> String {
+        self.family_name.clone()
+    }
+
+    fn family_versions(&self) -> Vec<String> {
+        self.family_versions.clone()
+    }
+
+    fn namespaces(&self) -> Vec<String> {
+        self.namespaces.clone()
+    }
+
+    fn apply(
+        &self,
+        request: &TpProcessRequest,
+        context: &mut dyn TransactionContext,
+    ) -> Result<(), ApplyError> {
+        let payload = TrackAndTracePayload::from_bytes(request.get_payload()).map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build track and trace payload: {}", err))
+        })?;
+
+        validate_payload(&payload)?;
+
+        let signer = request.get_header().get_signer_public_key();
+        let mut state = TrackAndTraceState::new(context);
+
+        info!(
+            "Track and Trace payload: {:?} {}",
+            payload.action(),
+            payload.timestamp(),
+        );
+
+        match payload.action() {
+            Action::CreateRecord(action_payload) => {
+                self._create_record(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::FinalizeRecord(action_payload) => {
+                self._finalize_record(action_payload, &mut state, signer)?
+            }
+            Action::UpdateProperties(action_payload) => {
+                self._update_properties(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::CreateProposal(action_payload) => {
+                self._create_proposal(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::AnswerProposal(action_payload) => {
+                self._answer_proposal(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::RevokeReporter(action_payload) => self._revoke_reporter(
+                action_payload,
+                &mut state,
+                signer,
+                *payload.timestamp(),
+            )?,
+            Action::SplitRecord(action_payload) => {
+                self._split_record(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::MergeRecords(action_payload) => {
+                self._merge_records(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::TransformRecord(action_payload) => {
+                self._transform_record(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::CreateRecall(action_payload) => {
+                self._create_recall(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::AcknowledgeRecall(action_payload) => {
+                self._acknowledge_recall(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::CloseRecall(action_payload) => {
+                self._close_recall(action_payload, &mut state, signer)?
+            }
+            Action::IngestReadings(action_payload) => {
+                self._ingest_readings(action_payload, &mut state, signer)?
+            }
+            Action::CounterSignProposal(action_payload) => self._counter_sign_proposal(
+                action_payload,
+                &mut state,
+                signer,
+                *payload.timestamp(),
+            )?,
+            Action::CreateBulkProposal(action_payload) => self._create_bulk_proposal(
+                action_payload,
+                &mut state,
+                signer,
+                *payload.timestamp(),
+            )?,
+            Action::AnswerBulkProposal(action_payload) => self._answer_bulk_proposal(
+                action_payload,
+                &mut state,
+                signer,
+                *payload.timestamp(),
+            )?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+// Sabre apply must return a bool
+fn apply(
+    request: &TpProcessRequest,
+    context: &mut dyn TransactionContext,
+) -> Result<bool, ApplyError> {
+    let handler = TrackAndTraceTransactionHandler::new();
+    match handler.apply(request, context) {
+        Ok(_) => Ok(true),
+        Err(err) => {
+            info!("{} received {}", handler.family_name(), err);
+            Err(err)
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[no_mangle]
+pub unsafe fn entrypoint(payload: WasmPtr, signer: WasmPtr, signature: WasmPtr) -> i32 {
+    execute_entrypoint(payload, signer, signature, apply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use dgc_config::protocol::pike::state::{AgentBuilder, AgentListBuilder};
+    use dgc_config::protocol::product::state::{ProductBuilder, ProductListBuilder, ProductType};
+    use dgc_config::protocol::schema::state::{
+        AlertRuleBuilder, DataType, GeofenceBuilder, LatLongBuilder, PropertyConstraintsBuilder,
+        PropertyDefinitionBuilder, PropertyValueBuilder, SchemaBuilder, SchemaListBuilder,
+        DEFAULT_PAGE_SIZE,
+    };
+    use dgc_config::protocol::track_and_trace::payload::{
+        AcknowledgeRecallActionBuilder, AnswerBulkProposalActionBuilder,
+        AnswerProposalActionBuilder, CloseRecallActionBuilder, CounterSignProposalActionBuilder,
+        CreateBulkProposalActionBuilder, CreateProposalActionBuilder, CreateRecallActionBuilder,
+        CreateRecordActionBuilder, FinalizeRecordActionBuilder, IngestReadingsActionBuilder,
+        LineageParentBuilder, MergeRecordsActionBuilder, RevokeReporterActionBuilder,
+        SensorReadingBuilder, SplitRecordActionBuilder, TransformRecordActionBuilder,
+        UpdatePropertiesAction, UpdatePropertiesActionBuilder,
+    };
+    use dgc_config::protocol::track_and_trace::state::{
+        ConditionBuilder, Property, PropertyListBuilder, PropertyPage, PropertyPageListBuilder,
+        Proposal, Record, RecordListBuilder, ReporterAuthorization, Role, Severity, Status,
+    };
+    use dgc_config::protos::IntoBytes;
+    use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
+
+    const TIMESTAMP: u64 = 1;
+    const RECORD_ID: &str = "test_record_action";
+    const PUBLIC_KEY: &str = "agent_public_key";
+    const OPTIONAL_PROPERTY_NAME: &str = "test_optional";
+    const REQUIRED_PROPERTY_NAME: &str = "test_required";
+    const SCHEMA_NAME: &str = "test_schema";
+    const RECALL_ID: &str = "test_recall";
+    const BULK_PROPOSAL_ID: &str = "test_bulk_proposal";
+
+    #[derive(Default, Debug)]
+    /// A MockTransactionContext that can be used to test TrackAndTraceState
+    struct MockTransactionContext {
+        state: RefCell<HashMap<String, Vec<u8>>>,
+        events: RefCell<Vec<(String, Vec<(String, String)>)>>,
+    }
+
+    impl TransactionContext for MockTransactionContext {
+        fn get_state_entries(
+            &self,
+            addresses: &[String],
+        ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+            let mut results = Vec::new();
+            for addr in addresses {
+                let data = match self.state.borrow().get(addr) {
+                    Some(data) => data.clone(),
+                    None => Vec::new(),
+                };
+                results.push((addr.to_string(), data));
+            }
+            Ok(results)
+        }
+
+        fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+            for (addr, data) in entries {
+                self.state.borrow_mut().insert(addr, data);
+            }
+            Ok(())
+        }
+
+        /// this is not needed for these tests
+        fn delete_state_entries(&self, _addresses: &[String]) -> Result<Vec<String>, ContextError> {
+            unimplemented!()
+        }
+
+        /// this is not needed for these tests
+        fn add_receipt_data(&self, _data: &[u8]) -> Result<(), ContextError> {
+            unimplemented!()
+        }
+
+        fn add_event(
+            &self,
+            event_type: String,
+            attributes: Vec<(String, String)>,
+            _data: &[u8],
+        ) -> Result<(), ContextError> {
+            self.events.borrow_mut().push((event_type, attributes));
+            Ok(())
+        }
+    }
+
+    impl MockTransactionContext {
+        fn add_agent(&self, public_key: &str) {
+            self.add_agent_with_roles(public_key, vec![]);
+        }
+
+        fn add_agent_with_roles(&self, public_key: &str, roles: Vec<String>) {
+            let builder = AgentBuilder::new();
+            let agent = builder
+                .with_org_id("test_org".to_string())
+                .with_public_key(public_key.to_string())
+                .with_active(true)
+                .with_roles(roles)
+                .build()
+                .unwrap();
+
+            let builder = AgentListBuilder::new();
+            let agent_list = builder.with_agents(vec![agent.clone()]).build().unwrap();
+            let agent_bytes = agent_list.into_bytes().unwrap();
+            let agent_address = make_agent_address(public_key);
+            //let agent_address = compute_agent_address(public_key);
+            self.set_state_entry(agent_address, agent_bytes).unwrap();
+        }
+
+        fn add_product(&self, product_id: &str) {
+            let product = ProductBuilder::new()
+                .with_product_id(product_id.to_string())
+                .with_owner("test_org".to_string())
+                .with_schema(SCHEMA_NAME.to_string())
+                .with_product_type(ProductType::GS1)
+                .with_properties(vec![])
+                .build()
+                .unwrap();
+
+            let product_list = ProductListBuilder::new()
+                .with_products(vec![product])
+                .build()
+                .unwrap();
+            let product_bytes = product_list.into_bytes().unwrap();
+            let product_address = make_product_address(product_id);
+            self.set_state_entry(product_address, product_bytes).unwrap();
+        }
+
+        fn add_schema(&self) {
+            let builder = SchemaBuilder::new();
+            let schema = builder
+                .with_name(SCHEMA_NAME.to_string())
+                .with_description("Test Schema".to_string())
+                .with_owner("test_org".to_string())
+                .with_properties(vec![
+                    optional_property_definition(),
+                    required_property_definition(),
+                ])
+                .build()
+                .unwrap();
+
+            let builder = SchemaListBuilder::new();
+            let schema_list = builder.with_schemas(vec![schema]).build().unwrap();
+            let schema_bytes = schema_list.into_bytes().unwrap();
+            let schema_address = make_schema_address(SCHEMA_NAME);
+            self.set_state_entry(schema_address, schema_bytes).unwrap();
+        }
+
+        fn add_schema_with_property(&self, property_definition: PropertyDefinition) {
+            let schema = SchemaBuilder::new()
+                .with_name(SCHEMA_NAME.to_string())
+                .with_description("Test Schema".to_string())
+                .with_owner("test_org".to_string())
+                .with_properties(vec![
+                    optional_property_definition(),
+                    required_property_definition(),
+                    property_definition,
+                ])
+                .build()
+                .unwrap();
+
+            let schema_list = SchemaListBuilder::new().with_schemas(vec![schema]).build().unwrap();
+            let schema_bytes = schema_list.into_bytes().unwrap();
+            let schema_address = make_schema_address(SCHEMA_NAME);
+            self.set_state_entry(schema_address, schema_bytes).unwrap();
+        }
+
+        fn add_schema_with_deprecated_property(&self) {
+            let deprecated_property_definition = optional_property_definition()
+                .into_builder()
+                .with_deprecated(true)
+                .build()
+                .unwrap();
+            let schema = SchemaBuilder::new()
+                .with_name(SCHEMA_NAME.to_string())
+                .with_description("Test Schema".to_string())
+                .with_owner("test_org".to_string())
+                .with_version(2)
+                .with_properties(vec![
+                    deprecated_property_definition,
+                    required_property_definition(),
+                ])
+                .build()
+                .unwrap();
+
+            let schema_list = SchemaListBuilder::new()
+                .with_schemas(vec![schema])
+                .build()
+                .unwrap();
+            let schema_bytes = schema_list.into_bytes().unwrap();
+            let schema_address = make_schema_address(SCHEMA_NAME);
+            self.set_state_entry(schema_address, schema_bytes).unwrap();
+        }
+
+        fn add_schema_with_deprecated_required_property(&self) {
+            let deprecated_property_definition = required_property_definition()
+                .into_builder()
+                .with_deprecated(true)
+                .build()
+                .unwrap();
+            let schema = SchemaBuilder::new()
+                .with_name(SCHEMA_NAME.to_string())
+                .with_description("Test Schema".to_string())
+                .with_owner("test_org".to_string())
+                .with_version(2)
+                .with_properties(vec![
+                    optional_property_definition(),
+                    deprecated_property_definition,
+                ])
+                .build()
+                .unwrap();
+
+            let schema_list = SchemaListBuilder::new()
+                .with_schemas(vec![schema])
+                .build()
+                .unwrap();
+            let schema_bytes = schema_list.into_bytes().unwrap();
+            let schema_address = make_schema_address(SCHEMA_NAME);
+            self.set_state_entry(schema_address, schema_bytes).unwrap();
+        }
+
+        fn add_record(&self) {
+            let record_list = RecordListBuilder::new()
+                .with_records(vec![make_record()])
+                .build()
+                .unwrap();
+            let record_bytes = record_list.into_bytes().unwrap();
+            let record_address = make_record_address(RECORD_ID);
+            self.set_state_entry(record_address, record_bytes).unwrap();
+        }
+
+        fn add_property(&self, property_name: &str, property_definition: PropertyDefinition) {
+            let property_list = PropertyListBuilder::new()
+                .with_properties(vec![make_property(property_name, property_definition)])
+                .build()
+                .unwrap();
+
+            let property_list_bytes = property_list.into_bytes().unwrap();
+            let property_list_address = make_property_address(RECORD_ID, property_name, 0);
+            self.set_state_entry(property_list_address, property_list_bytes)
+                .unwrap();
+        }
+
+        fn add_property_page(&self, property_name: &str, property_value: PropertyValue) {
+            let property_page_list = PropertyPageListBuilder::new()
+                .with_property_pages(vec![make_property_page(property_name, property_value)])
+                .build()
+                .expect("Failed to build property page list");
+
+            let property_page_list_bytes = property_page_list
+                .into_bytes()
+                .expect("Failed to write page list to bytes");
+            let address = make_property_address(RECORD_ID, property_name, 1);
+            self.set_state_entry(address, property_page_list_bytes)
+                .expect("Failed to set state");
+        }
+
+        fn add_finalized_record(&self) {
+            let associated_agent = AssociatedAgentBuilder::new()
+                .with_agent_id(PUBLIC_KEY.to_string())
+                .with_timestamp(TIMESTAMP)
+                .build()
+                .expect("Failed to build associated agent");
+
+            let record = RecordBuilder::new()
+                .with_record_id(RECORD_ID.to_string())
+                .with_schema(SCHEMA_NAME.to_string())
+                .with_owners(vec![associated_agent.clone()])
+                .with_custodians(vec![associated_agent.clone()])
+                .with_field_final(true)
+                .build()
+                .expect("Failed to build new_record");
+
+            let record_list = RecordListBuilder::new()
+                .with_records(vec![record])
+                .build()
+                .unwrap();
+            let record_bytes = record_list.into_bytes().unwrap();
+            let record_address = make_record_address(RECORD_ID);
+            self.set_state_entry(record_address, record_bytes).unwrap();
+        }
+
+        fn add_expiring_proposal(&self, receiving_agent_key: &str, role: Role, expires_at: u64) {
+            let proposal = make_proposal(PUBLIC_KEY, receiving_agent_key, role, Status::Open)
+                .into_builder()
+                .with_expires_at(expires_at)
+                .build()
+                .expect("Failed to build proposal");
+            let proposal_list = ProposalListBuilder::new()
+                .with_proposals(vec![proposal])
+                .build()
+                .expect("Failed to build proposal list");
+            let proposal_list_bytes = proposal_list
+                .into_bytes()
+                .expect("Failed to write proposal list to bytes");
+            let proposal_list_address = make_proposal_address(RECORD_ID, receiving_agent_key);
+            self.set_state_entry(proposal_list_address, proposal_list_bytes)
+                .expect("Failed to set state");
+        }
+
+        fn add_proposal(
+            &self,
+            issuing_agent: &str,
+            receiving_agent_key: &str,
+            role: Role,
+            status: Status,
+        ) {
+            let proposal_list = ProposalListBuilder::new()
+                .with_proposals(vec![make_proposal(
+                    issuing_agent,
+                    receiving_agent_key,
+                    role,
+                    status,
+                )])
+                .build()
+                .unwrap();
+            let proposal_list_bytes = proposal_list.into_bytes().unwrap();
+            let proposal_list_address = make_proposal_address(RECORD_ID, receiving_agent_key);
+            self.set_state_entry(proposal_list_address, proposal_list_bytes)
+                .unwrap();
+        }
+
+        fn add_property_with_reporter(
+            &self,
+            property_name: &str,
+            reporter_key: &str,
+            authorized: bool,
+            property_definition: PropertyDefinition,
+        ) {
+            let property = make_property_with_reporter(
+                property_name,
+                reporter_key,
+                authorized,
+                property_definition,
+            );
+            let property_list = PropertyListBuilder::new()
+                .with_properties(vec![property])
+                .build()
+                .unwrap();
+
+            let property_list_bytes = property_list.into_bytes().unwrap();
+            let property_list_address = make_property_address(RECORD_ID, property_name, 0);
+            self.set_state_entry(property_list_address, property_list_bytes)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    /// Test that if the CreateRecordAction is valid an OK is returned and that a new Record,
+    /// Property and PropertyPage are added to state
+    fn test_create_record_handler_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = create_record_action_with_properties(vec![
+            optional_property_value(),
+            required_property_value(),
+        ]);
+
+        assert!(transaction_handler
+            ._create_record(&create_record_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+
+        assert_eq!(record, make_record());
+
+        let optional_property = state
+            .get_property(RECORD_ID, OPTIONAL_PROPERTY_NAME)
+            .expect("Failed to fetch optional property")
+            .expect("Optional property not found");
+
+        assert_eq!(
+            optional_property,
+            make_property(OPTIONAL_PROPERTY_NAME, optional_property_definition())
+        );
+
+        let required_property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch required property")
+            .expect("Required property not found");
+
+        assert_eq!(
+            required_property,
+            make_property(REQUIRED_PROPERTY_NAME, required_property_definition())
+        );
+
+        let property_page_optional = state
+            .get_property_page(RECORD_ID, OPTIONAL_PROPERTY_NAME, 1)
+            .expect("Failed to fetch property page for optional property")
+            .expect("Property page for optional property not found");
+
+        assert_eq!(
+            property_page_optional,
+            make_property_page(OPTIONAL_PROPERTY_NAME, optional_property_value())
+        );
+
+        let property_page_required = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
+            .expect("Failed to fetch property page for required property")
+            .expect("Property page for required property not found");
+
+        assert_eq!(
+            property_page_required,
+            make_property_page(REQUIRED_PROPERTY_NAME, required_property_value())
+        );
+    }
+
+    #[test]
+    /// Test that a record whose schema has a confidential property can only be created with
+    /// an encrypted value and a data key, which is stored on the record for its creator
+    fn test_create_record_confidential_property() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        let price_definition = PropertyDefinitionBuilder::new()
+            .with_name("price".to_string())
+            .with_data_type(DataType::Number)
+            .with_confidential(true)
+            .build()
+            .expect("Failed to build property definition");
+        let schema = SchemaBuilder::new()
+            .with_name(SCHEMA_NAME.to_string())
+            .with_description("Test Schema".to_string())
+            .with_owner("test_org".to_string())
+            .with_properties(vec![required_property_definition(), price_definition])
+            .build()
+            .expect("Failed to build schema");
+        let schema_list = SchemaListBuilder::new()
+            .with_schemas(vec![schema])
+            .build()
+            .expect("Failed to build schema list");
+        transaction_context
+            .set_state_entry(
+                make_schema_address(SCHEMA_NAME),
+                schema_list.into_bytes().unwrap(),
+            )
+            .unwrap();
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let price = |builder: PropertyValueBuilder| {
+            builder
+                .with_name("price".to_string())
+                .with_data_type(DataType::Number)
+                .build()
+                .expect("Failed to build property value")
+        };
+        let create_record_action = |price: PropertyValue, wrapped_data_key: Vec<u8>| {
+            CreateRecordActionBuilder::new()
+                .with_record_id(RECORD_ID.to_string())
+                .with_schema(SCHEMA_NAME.to_string())
+                .with_properties(vec![required_property_value(), price])
+                .with_wrapped_data_key(wrapped_data_key)
+                .build()
+                .expect("Failed to build CreateRecordAction")
+        };
+
+        let encrypted_price = price(PropertyValueBuilder::new().with_encrypted_value(vec![1, 2]));
+        match transaction_handler._create_record(
+            &create_record_action(encrypted_price.clone(), vec![]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("No data key was provided, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("has confidential properties, a data key must be provided"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let plain_price = price(PropertyValueBuilder::new().with_number_value(1250));
+        assert!(transaction_handler
+            ._create_record(
+                &create_record_action(plain_price, vec![9, 9, 9]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .is_err());
+
+        assert!(transaction_handler
+            ._create_record(
+                &create_record_action(encrypted_price, vec![9, 9, 9]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(
+            record.data_key(PUBLIC_KEY).map(|data_key| data_key.wrapped_key()),
+            Some(&[9, 9, 9][..])
+        );
+    }
+
+    #[test]
+    /// Test that if the CreateRecordAction is invalid if the signer is not an Agent.
+    fn test_create_record_agent_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = create_record_action_with_properties(vec![
+            optional_property_value(),
+            required_property_value(),
+        ]);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Agent does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Agent is not registered: {}", PUBLIC_KEY)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the CreateRecordAction is invalid if the schema does not exist.
+    fn test_create_record_schema_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let create_record_action = create_record_action_with_properties(vec![
+            optional_property_value(),
+            required_property_value(),
+        ]);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Schema does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Schema does not exist {}", SCHEMA_NAME)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the CreateRecordAction is invalid if the a record with the same id
+    /// already exists.
+    fn test_create_record_already_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_schema();
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let create_record_action = create_record_action_with_properties(vec![
+            optional_property_value(),
+            required_property_value(),
+        ]);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record id is duplicated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record already exists: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the CreateRecordAction is invalid if the the payload is missing a required
+    /// property as defined in the schema.
+    fn test_create_record_missing_required_property() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_schema();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let create_record_action =
+            create_record_action_with_properties(vec![optional_property_value()]);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Required property is missing, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Required property {} not provided",
+                    REQUIRED_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the CreateRecordAction is invalid if the the payload has an extra property
+    /// that is not defined in the schema.
+    fn test_create_record_extra_property() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_schema();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let property_value_extra = PropertyValueBuilder::new()
+            .with_name("invalid_property".to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("Property that does not exist in schema".to_string())
+            .build()
+            .expect("Failed to build property value");
+
+        let create_record_action = create_record_action_with_properties(vec![
+            optional_property_value(),
+            required_property_value(),
+            property_value_extra.clone(),
+        ]);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!(
+                "There is an invalid property in the payload,
+                InvalidTransaction should be returned"
+            ),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Provided property invalid_property is not in schema"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a record pins the schema version it was created against and that values
+    /// for deprecated properties are rejected.
+    fn test_create_record_deprecated_property() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_schema_with_deprecated_property();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let create_record_action = create_record_action_with_properties(vec![
+            optional_property_value(),
+            required_property_value(),
+        ]);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Deprecated property provided, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Provided property {} is deprecated",
+                    OPTIONAL_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let create_record_action =
+            create_record_action_with_properties(vec![required_property_value()]);
+
+        assert!(transaction_handler
+            ._create_record(&create_record_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(*record.schema_version(), 2);
+    }
+
+    #[test]
+    /// Test that a record can be created without a required property that has been deprecated
+    fn test_create_record_deprecated_required_property() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_schema_with_deprecated_required_property();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let create_record_action =
+            create_record_action_with_properties(vec![optional_property_value()]);
+
+        assert!(transaction_handler
+            ._create_record(&create_record_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(*record.schema_version(), 2);
+    }
+
+    #[test]
+    /// Test that if the CreateRecordAction is invalid if a property value has a type that is not
+    /// the same as the type in the property definition.
+    fn test_create_record_invalid_property_value_type() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let property_value_wrong = PropertyValueBuilder::new()
+            .with_name(REQUIRED_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::Number)
+            .with_number_value(123)
+            .build()
+            .expect("Failed to build property value");
+
+        let create_record_action = create_record_action_with_properties(vec![
+            optional_property_value(),
+            property_value_wrong.clone(),
+        ]);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!(
+                "There is an invalid property value in the payload,
+                InvalidTransaction should be returned"
+            ),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Value provided for {} is the wrong type",
+                    REQUIRED_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the FinalizeRecordAction is valid an OK is returned and that the record is
+    /// marked as finalized
+    fn test_finalize_record_handler_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        assert!(transaction_handler
+            ._finalize_record(&create_finalize_record(), &mut state, PUBLIC_KEY)
+            .is_ok());
+
+        let finalized_record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+
+        assert!(finalized_record.field_final());
+    }
+
+    #[test]
+    /// Test that if the FinalizeRecordAction fails if a record with the provided id does
+    /// not exist.
+    fn test_finalize_record_handler_record_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._finalize_record(
+            &create_finalize_record(),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Record does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record does not exist: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the FinalizeRecordAction fails if the signer is not record owner nor
+    // custodian
+    fn test_finalize_record_handler_signer_not_owner_nor_custodian() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let signer = "agent_public_key_not_owner";
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._finalize_record(&create_finalize_record(), &mut state, signer) {
+            Ok(()) => panic!(
+                "Signer is not record owner nor custodian, InvalidTransaction should be returned"
+            ),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Must be owner and custodian to finalize record"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the FinalizeRecordAction fails if the record is alreadt final.
+    fn test_finalize_record_handler_record_already_final() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_finalized_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._finalize_record(
+            &create_finalize_record(),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Record is already final, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record is already final: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction is valid an OK is returned and new value is added
+    /// to the record's PropertyPage
+    fn test_update_properties_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        assert!(transaction_handler
+            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, TIMESTAMP,)
+            .is_ok());
+
+        let page = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
+            .expect("Failed to get property page from state")
+            .expect("Property page is none, it should be some");
+
+        assert_eq!(page.reported_values().len(), 2);
+        assert_eq!(page.reported_values()[1].value(), &updated_property_value());
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the record does not exist
+    fn test_update_properties_record_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_finalized_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the record is set to final
+    fn test_update_properties_record_is_final() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_finalized_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is not part of
+    /// the record
+    fn test_update_properties_record_missing_property() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record does not have provided property: {}",
+                    REQUIRED_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the signer is not an authorized reporter
+    fn test_update_properties_signer_not_authorized() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
 
-        /// this is not needed for these tests
-        fn add_receipt_data(&self, _data: &[u8]) -> Result<(), ContextError> {
-            unimplemented!()
+        let signer = "not_authorized";
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            signer.clone(),
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Reporter is not authorized: {}", signer)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is set to
+    /// a data type that does not match the property definition.
+    fn test_update_properties_property_value_wrong_type() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let property_value_wrong = PropertyValueBuilder::new()
+            .with_name(REQUIRED_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::Number)
+            .with_number_value(123)
+            .build()
+            .expect("Failed to build property value");
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![property_value_wrong]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Update has wrong type: {:?} != {:?}",
+                    DataType::Number,
+                    DataType::String
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is
+    /// marked immutable by its property definition.
+    fn test_update_properties_immutable_property() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let immutable_property_definition = required_property_definition()
+            .into_builder()
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_immutable(true)
+                    .build()
+                    .expect("Failed to build property constraints"),
+            )
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, immutable_property_definition);
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![required_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Property is immutable, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Property {} is immutable and cannot be updated",
+                    REQUIRED_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a REFERENCE value is only accepted if the referenced record exists and
+    /// has the schema named by the property definition.
+    fn test_update_properties_reference() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let reference_property_definition = PropertyDefinitionBuilder::new()
+            .with_name("contains".to_string())
+            .with_data_type(DataType::Reference)
+            .with_reference_kind(ReferenceKind::Record)
+            .with_reference_schema(SCHEMA_NAME.to_string())
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property("contains", reference_property_definition);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let missing_reference = PropertyValueBuilder::new()
+            .with_name("contains".to_string())
+            .with_data_type(DataType::Reference)
+            .with_reference_value("missing_record".to_string())
+            .build()
+            .expect("Failed to build property value");
+
+        match transaction_handler._update_properties(
+            &update_property_action(vec![missing_reference]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Referenced record does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Entity referenced by contains does not exist: missing_record"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let reference = PropertyValueBuilder::new()
+            .with_name("contains".to_string())
+            .with_data_type(DataType::Reference)
+            .with_reference_value(RECORD_ID.to_string())
+            .build()
+            .expect("Failed to build property value");
+
+        assert!(transaction_handler
+            ._update_properties(
+                &update_property_action(vec![reference]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    /// Test that an UpdatePropertiesAction for a LIST property is rejected if an element
+    /// has the wrong type or violates the element constraints, and accepted otherwise.
+    fn test_update_properties_list() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let element_definition = PropertyDefinitionBuilder::new()
+            .with_name("reading".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_number_min(0)
+                    .build()
+                    .expect("Failed to build property constraints"),
+            )
+            .build()
+            .expect("Failed to build property definition");
+        let list_property_definition = PropertyDefinitionBuilder::new()
+            .with_name("readings".to_string())
+            .with_data_type(DataType::List)
+            .with_list_element(element_definition)
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property("readings", list_property_definition);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let number_element = |number: i64| {
+            PropertyValueBuilder::new()
+                .with_name("reading".to_string())
+                .with_data_type(DataType::Number)
+                .with_number_value(number)
+                .build()
+                .expect("Failed to build property value")
+        };
+        let string_element = PropertyValueBuilder::new()
+            .with_name("reading".to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("ten".to_string())
+            .build()
+            .expect("Failed to build property value");
+        let list_value = |elements: Vec<PropertyValue>| {
+            PropertyValueBuilder::new()
+                .with_name("readings".to_string())
+                .with_data_type(DataType::List)
+                .with_list_values(elements)
+                .build()
+                .expect("Failed to build property value")
+        };
+
+        match transaction_handler._update_properties(
+            &update_property_action(vec![list_value(vec![number_element(1), string_element])]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("List element has the wrong type, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Value provided for readings[1] is the wrong type"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        match transaction_handler._update_properties(
+            &update_property_action(vec![list_value(vec![number_element(-1)])]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("List element is below minimum, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Value provided for readings[0] violates the schema constraints"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        assert!(transaction_handler
+            ._update_properties(
+                &update_property_action(vec![list_value(vec![
+                    number_element(1),
+                    number_element(2)
+                ])]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    /// Test that an UpdatePropertiesAction with an invalid value nested in a STRUCT is
+    /// rejected with the path of the invalid value.
+    fn test_update_properties_invalid_struct_value() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let width_definition = PropertyDefinitionBuilder::new()
+            .with_name("width".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_number_min(0)
+                    .build()
+                    .expect("Failed to build property constraints"),
+            )
+            .build()
+            .expect("Failed to build property definition");
+        let dimensions_definition = PropertyDefinitionBuilder::new()
+            .with_name("dimensions".to_string())
+            .with_data_type(DataType::Struct)
+            .with_struct_properties(vec![width_definition])
+            .build()
+            .expect("Failed to build property definition");
+        let shipment_definition = PropertyDefinitionBuilder::new()
+            .with_name("shipment".to_string())
+            .with_data_type(DataType::Struct)
+            .with_struct_properties(vec![dimensions_definition])
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property("shipment", shipment_definition);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let width = PropertyValueBuilder::new()
+            .with_name("width".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_value(-1)
+            .build()
+            .expect("Failed to build property value");
+        let dimensions = PropertyValueBuilder::new()
+            .with_name("dimensions".to_string())
+            .with_data_type(DataType::Struct)
+            .with_struct_values(vec![width])
+            .build()
+            .expect("Failed to build property value");
+        let shipment = PropertyValueBuilder::new()
+            .with_name("shipment".to_string())
+            .with_data_type(DataType::Struct)
+            .with_struct_values(vec![dimensions])
+            .build()
+            .expect("Failed to build property value");
+
+        match transaction_handler._update_properties(
+            &update_property_action(vec![shipment]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Nested value is invalid, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(
+                    "Value provided for shipment.dimensions.width violates the schema constraints"
+                ));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction start new PropertyPage when needed.
+    fn test_update_properties_new_page() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let property_value = updated_property_value();
+
+        // Generate enough value updates that would required the start of a new PropertyPage.
+        let updates = std::iter::repeat(property_value)
+            .take(DEFAULT_PAGE_SIZE as usize)
+            .collect::<Vec<_>>();
+
+        let update_property_action = update_property_action(updates);
+
+        assert!(transaction_handler
+            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, TIMESTAMP,)
+            .is_ok());
+
+        let new_page = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 2)
+            .expect("Failed to get property page from state");
+
+        assert!(new_page.is_some());
+    }
+
+    #[test]
+    /// Test that ingested readings keep their own timestamps and are spread over new pages in
+    /// the order they were read
+    fn test_ingest_readings_ok() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let temperature_definition = PropertyDefinitionBuilder::new()
+            .with_name("temperature".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_paging(2, 0, RetentionPolicy::Wrap)
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property("temperature", temperature_definition);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let action = ingest_readings_action(vec![(105, 5), (101, 1), (104, 4), (102, 2), (103, 3)]);
+        assert!(transaction_handler
+            ._ingest_readings(&action, &mut state, PUBLIC_KEY)
+            .is_ok());
 
-        /// this is not needed for these tests
-        fn add_event(
-            &self,
-            _event_type: String,
-            _attributes: Vec<(String, String)>,
-            _data: &[u8],
-        ) -> Result<(), ContextError> {
-            unimplemented!()
+        let property = state
+            .get_property(RECORD_ID, "temperature")
+            .expect("Failed to get property from state")
+            .expect("Property not found");
+        assert_eq!(property.current_page(), &3);
+
+        let mut readings = Vec::new();
+        for page_number in 1..=3 {
+            let page = state
+                .get_property_page(RECORD_ID, "temperature", page_number)
+                .expect("Failed to get property page from state")
+                .expect("Property page not found");
+            readings.extend(
+                page.reported_values()
+                    .iter()
+                    .map(|rv| (*rv.timestamp(), *rv.value().number_value())),
+            );
         }
+        assert_eq!(readings, vec![(101, 1), (102, 2), (103, 3), (104, 4), (105, 5)]);
     }
 
-    impl MockTransactionContext {
-        fn add_agent(&self, public_key: &str) {
-            let builder = AgentBuilder::new();
-            let agent = builder
-                .with_org_id("test_org".to_string())
-                .with_public_key(public_key.to_string())
-                .with_active(true)
-                .with_roles(vec![])
-                .build()
-                .unwrap();
+    #[test]
+    /// Test that no reading is ingested when one of them is not reported by an authorized
+    /// reporter
+    fn test_ingest_readings_reporter_not_authorized() {
+        let mut transaction_context = MockTransactionContext::default();
+        let other_agent_key = "other_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(other_agent_key);
+        transaction_context.add_record();
+        let temperature_definition = PropertyDefinitionBuilder::new()
+            .with_name("temperature".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property("temperature", temperature_definition);
 
-            let builder = AgentListBuilder::new();
-            let agent_list = builder.with_agents(vec![agent.clone()]).build().unwrap();
-            let agent_bytes = agent_list.into_bytes().unwrap();
-            let agent_address = make_agent_address(public_key);
-            //let agent_address = compute_agent_address(public_key);
-            self.set_state_entry(agent_address, agent_bytes).unwrap();
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let action = ingest_readings_action(vec![(101, 1), (102, 2)]);
+        match transaction_handler._ingest_readings(&action, &mut state, other_agent_key) {
+            Ok(()) => panic!("Signer is not a reporter, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Reporter is not authorized: {}", other_agent_key)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
 
-        fn add_schema(&self) {
-            let builder = SchemaBuilder::new();
-            let schema = builder
-                .with_name(SCHEMA_NAME.to_string())
-                .with_description("Test Schema".to_string())
-                .with_owner("test_org".to_string())
-                .with_properties(vec![
-                    optional_property_definition(),
-                    required_property_definition(),
-                ])
+        assert!(state
+            .get_property_page(RECORD_ID, "temperature", 1)
+            .expect("Failed to get property page from state")
+            .is_none());
+    }
+
+    #[test]
+    /// Test that a LAT_LONG value reported outside of a geofence of its property is accepted,
+    /// flagged with the geofence and emitted as an event, while a value inside is not
+    fn test_update_properties_geofence_breach() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let lat_long = |latitude, longitude| {
+            LatLongBuilder::new()
+                .with_lat_long(latitude, longitude)
                 .build()
-                .unwrap();
+                .expect("Failed to build LatLong")
+        };
+        let depot = GeofenceBuilder::new()
+            .with_name("depot".to_string())
+            .with_vertices(vec![
+                lat_long(0, 0),
+                lat_long(0, 1_000_000),
+                lat_long(1_000_000, 1_000_000),
+                lat_long(1_000_000, 0),
+            ])
+            .build()
+            .expect("Failed to build Geofence");
+        let location_definition = PropertyDefinitionBuilder::new()
+            .with_name("location".to_string())
+            .with_data_type(DataType::LatLong)
+            .with_geofences(vec![depot])
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property("location", location_definition);
 
-            let builder = SchemaListBuilder::new();
-            let schema_list = builder.with_schemas(vec![schema]).build().unwrap();
-            let schema_bytes = schema_list.into_bytes().unwrap();
-            let schema_address = make_schema_address(SCHEMA_NAME);
-            self.set_state_entry(schema_address, schema_bytes).unwrap();
-        }
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        fn add_record(&self) {
-            let record_list = RecordListBuilder::new()
-                .with_records(vec![make_record()])
+        let location_value = |latitude, longitude| {
+            PropertyValueBuilder::new()
+                .with_name("location".to_string())
+                .with_data_type(DataType::LatLong)
+                .with_lat_long_value(lat_long(latitude, longitude))
                 .build()
-                .unwrap();
-            let record_bytes = record_list.into_bytes().unwrap();
-            let record_address = make_record_address(RECORD_ID);
-            self.set_state_entry(record_address, record_bytes).unwrap();
-        }
+                .expect("Failed to build property value")
+        };
+        let inside = update_property_action(vec![location_value(500_000, 500_000)]);
+        let outside = update_property_action(vec![location_value(1_500_000, 500_000)]);
+        transaction_handler
+            ._update_properties(&inside, &mut state, PUBLIC_KEY, 1)
+            .expect("Failed to update property inside of the geofence");
+        transaction_handler
+            ._update_properties(&outside, &mut state, PUBLIC_KEY, 2)
+            .expect("Failed to update property outside of the geofence");
 
-        fn add_property(&self, property_name: &str, property_definition: PropertyDefinition) {
-            let property_list = PropertyListBuilder::new()
-                .with_properties(vec![make_property(property_name, property_definition)])
-                .build()
-                .unwrap();
+        let page = state
+            .get_property_page(RECORD_ID, "location", 1)
+            .expect("Failed to get property page from state")
+            .expect("Property page not found");
+        let breaches = page
+            .reported_values()
+            .iter()
+            .map(|rv| rv.geofence_breaches().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(breaches, vec![vec![], vec!["depot".to_string()]]);
+
+        let events = transaction_context.events.borrow();
+        assert_eq!(events.len(), 1);
+        let (event_type, attributes) = &events[0];
+        assert_eq!(event_type, GEOFENCE_BREACH_EVENT);
+        assert!(attributes.contains(&("geofence".to_string(), "depot".to_string())));
+        assert!(attributes.contains(&("timestamp".to_string(), "2".to_string())));
+    }
 
-            let property_list_bytes = property_list.into_bytes().unwrap();
-            let property_list_address = make_property_address(RECORD_ID, property_name, 0);
-            self.set_state_entry(property_list_address, property_list_bytes)
-                .unwrap();
-        }
+    #[test]
+    /// Test that NUMBER values that break an alert rule raise an excursion on the record once
+    /// the rule stays broken for its sustained duration, that the excursion ends with the first
+    /// value inside of the rule, and that an excursion that was never raised is dropped
+    fn test_update_properties_alert_excursion() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let cold_chain = AlertRuleBuilder::new()
+            .with_name("cold_chain".to_string())
+            .with_min(2)
+            .with_max(8)
+            .with_sustained_duration(10)
+            .build()
+            .expect("Failed to build AlertRule");
+        let temperature_definition = PropertyDefinitionBuilder::new()
+            .with_name("temperature".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_alert_rules(vec![cold_chain])
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property("temperature", temperature_definition);
 
-        fn add_property_page(&self, property_name: &str, property_value: PropertyValue) {
-            let property_page_list = PropertyPageListBuilder::new()
-                .with_property_pages(vec![make_property_page(property_name, property_value)])
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let mut update = |value, timestamp| {
+            let temperature = PropertyValueBuilder::new()
+                .with_name("temperature".to_string())
+                .with_data_type(DataType::Number)
+                .with_number_value(value)
                 .build()
-                .expect("Failed to build property page list");
+                .expect("Failed to build property value");
+            transaction_handler
+                ._update_properties(
+                    &update_property_action(vec![temperature]),
+                    &mut state,
+                    PUBLIC_KEY,
+                    timestamp,
+                )
+                .expect("Failed to update temperature");
+            state
+                .get_record(RECORD_ID)
+                .expect("Failed to fetch record")
+                .expect("No record found")
+                .excursions()
+                .to_vec()
+        };
 
-            let property_page_list_bytes = property_page_list
-                .into_bytes()
-                .expect("Failed to write page list to bytes");
-            let address = make_property_address(RECORD_ID, property_name, 1);
-            self.set_state_entry(address, property_page_list_bytes)
-                .expect("Failed to set state");
-        }
+        assert!(update(5, 100).is_empty());
 
-        fn add_finalized_record(&self) {
-            let associated_agent = AssociatedAgentBuilder::new()
-                .with_agent_id(PUBLIC_KEY.to_string())
-                .with_timestamp(TIMESTAMP)
-                .build()
-                .expect("Failed to build associated agent");
+        let excursions = update(9, 105);
+        assert_eq!(excursions.len(), 1);
+        assert_eq!(*excursions[0].started_at(), 105);
+        assert!(!excursions[0].is_active());
 
-            let record = RecordBuilder::new()
-                .with_record_id(RECORD_ID.to_string())
-                .with_schema(SCHEMA_NAME.to_string())
-                .with_owners(vec![associated_agent.clone()])
-                .with_custodians(vec![associated_agent.clone()])
-                .with_field_final(true)
-                .build()
-                .expect("Failed to build new_record");
+        let excursions = update(10, 120);
+        assert_eq!(excursions.len(), 1);
+        assert!(excursions[0].is_active());
+        assert_eq!(*excursions[0].raised_at(), 120);
+        assert_eq!(*excursions[0].last_value(), 10);
+        assert_eq!(excursions[0].custodian(), PUBLIC_KEY);
 
-            let record_list = RecordListBuilder::new()
-                .with_records(vec![record])
-                .build()
-                .unwrap();
-            let record_bytes = record_list.into_bytes().unwrap();
-            let record_address = make_record_address(RECORD_ID);
-            self.set_state_entry(record_address, record_bytes).unwrap();
+        let excursions = update(6, 130);
+        assert_eq!(*excursions[0].ended_at(), 130);
+        assert!(!excursions[0].is_active());
+
+        assert_eq!(update(9, 140).len(), 2);
+        let excursions = update(5, 145);
+        assert_eq!(excursions.len(), 1);
+        assert_eq!(*excursions[0].started_at(), 105);
+    }
+
+    #[test]
+    /// Test that a property with the ARCHIVE retention policy folds the oldest page into its
+    /// archive anchor before overwriting it
+    fn test_update_properties_archive_page() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let property_definition = required_property_definition()
+            .into_builder()
+            .with_paging(2, 2, RetentionPolicy::Archive)
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, property_definition);
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        // Fill the first page, then the second one, then start over on the first one
+        let update = update_property_action(vec![updated_property_value()]);
+        assert!(transaction_handler
+            ._update_properties(&update, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+        let first_page = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
+            .expect("Failed to get property page from state")
+            .expect("Property page not found");
+        for _ in 0..2 {
+            assert!(transaction_handler
+                ._update_properties(&update, &mut state, PUBLIC_KEY, TIMESTAMP)
+                .is_ok());
         }
 
-        fn add_proposal(
-            &self,
-            issuing_agent: &str,
-            receiving_agent_key: &str,
-            role: Role,
-            status: Status,
+        let property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to get property from state")
+            .expect("Property not found");
+        assert_eq!(property.current_page(), &1);
+        assert!(*property.wrapped());
+        assert_eq!(property.archived_pages(), &1);
+        assert_eq!(property.archive_anchor(), archive_page("", &first_page).unwrap());
+
+        let page = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
+            .expect("Failed to get property page from state")
+            .expect("Property page not found");
+        assert!(page.reported_values().is_empty());
+    }
+
+    #[test]
+    /// Test that a property with the KEEP_ALL retention policy rejects updates once all of its
+    /// pages are full instead of overwriting its history
+    fn test_update_properties_keep_all_full() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let property_definition = required_property_definition()
+            .into_builder()
+            .with_paging(2, 2, RetentionPolicy::KeepAll)
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, property_definition);
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let updates = std::iter::repeat(updated_property_value())
+            .take(3)
+            .collect::<Vec<_>>();
+        assert!(transaction_handler
+            ._update_properties(&update_property_action(updates), &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        match transaction_handler._update_properties(
+            &update_property_action(vec![updated_property_value()]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
         ) {
-            let proposal_list = ProposalListBuilder::new()
-                .with_proposals(vec![make_proposal(
-                    issuing_agent,
-                    receiving_agent_key,
-                    role,
-                    status,
-                )])
-                .build()
-                .unwrap();
-            let proposal_list_bytes = proposal_list.into_bytes().unwrap();
-            let proposal_list_address = make_proposal_address(RECORD_ID, receiving_agent_key);
-            self.set_state_entry(proposal_list_address, proposal_list_bytes)
-                .unwrap();
+            Ok(()) => panic!("History is full, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Property {} has filled all 2 pages of its history",
+                    REQUIRED_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
 
-        fn add_property_with_reporter(
-            &self,
-            property_name: &str,
-            reporter_key: &str,
-            authorized: bool,
-            property_definition: PropertyDefinition,
-        ) {
-            let property = make_property_with_reporter(
-                property_name,
-                reporter_key,
-                authorized,
-                property_definition,
-            );
-            let property_list = PropertyListBuilder::new()
-                .with_properties(vec![property])
-                .build()
-                .unwrap();
+        let property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to get property from state")
+            .expect("Property not found");
+        assert_eq!(property.current_page(), &2);
+        assert!(!*property.wrapped());
+    }
 
-            let property_list_bytes = property_list.into_bytes().unwrap();
-            let property_list_address = make_property_address(RECORD_ID, property_name, 0);
-            self.set_state_entry(property_list_address, property_list_bytes)
-                .unwrap();
-        }
+    #[test]
+    /// Test that if the CreateProposalAction, with role set to Owner, is valid an OK is returned
+    /// and new proposal is added state
+    fn test_create_proposal_valid_owner() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        assert!(transaction_handler
+            ._create_proposal(
+                &create_proposal_action(Role::Owner, receiving_agent_key),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .is_ok());
+
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(
+            proposal_list.proposals()[0],
+            make_proposal(PUBLIC_KEY, receiving_agent_key, Role::Owner, Status::Open)
+        );
     }
 
     #[test]
-    /// Test that if the CreateRecordAction is valid an OK is returned and that a new Record,
-    /// Property and PropertyPage are added to state
-    fn test_create_record_handler_valid() {
+    /// Test that if the CreateProposalAction, with role set to Custodian, is valid an OK is returned
+    /// and new proposal is added state
+    fn test_create_proposal_valid_custodian() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let create_record_action = create_record_action_with_properties(vec![
-            optional_property_value(),
-            required_property_value(),
-        ]);
 
         assert!(transaction_handler
-            ._create_record(&create_record_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            ._create_proposal(
+                &create_proposal_action(Role::Custodian, receiving_agent_key),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
             .is_ok());
 
-        let record = state
-            .get_record(RECORD_ID)
-            .expect("Failed to fetch record")
-            .expect("No record found");
-
-        assert_eq!(record, make_record());
-
-        let optional_property = state
-            .get_property(RECORD_ID, OPTIONAL_PROPERTY_NAME)
-            .expect("Failed to fetch optional property")
-            .expect("Optional property not found");
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
 
+        assert_eq!(proposal_list.proposals().len(), 1);
         assert_eq!(
-            optional_property,
-            make_property(OPTIONAL_PROPERTY_NAME, optional_property_definition())
+            proposal_list.proposals()[0],
+            make_proposal(
+                PUBLIC_KEY,
+                receiving_agent_key,
+                Role::Custodian,
+                Status::Open
+            )
         );
+    }
 
-        let required_property = state
-            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
-            .expect("Failed to fetch required property")
-            .expect("Required property not found");
+    #[test]
+    /// Test that if the CreateProposalAction fails when the signer is not an agent
+    fn test_create_proposal_agent_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(receiving_agent_key);
 
-        assert_eq!(
-            required_property,
-            make_property(REQUIRED_PROPERTY_NAME, required_property_definition())
-        );
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
 
-        let property_page_optional = state
-            .get_property_page(RECORD_ID, OPTIONAL_PROPERTY_NAME, 1)
-            .expect("Failed to fetch property page for optional property")
-            .expect("Property page for optional property not found");
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        assert_eq!(
-            property_page_optional,
-            make_property_page(OPTIONAL_PROPERTY_NAME, optional_property_value())
-        );
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Owner, receiving_agent_key),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer is not an Agent, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Issuing agent does not exist: {}", PUBLIC_KEY,)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
 
-        let property_page_required = state
-            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
-            .expect("Failed to fetch property page for required property")
-            .expect("Property page for required property not found");
+    #[test]
+    /// Test that if the CreateProposalAction fails when the receiving agent key is not a valid agent
+    fn test_create_proposal_receiving_agent_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
 
-        assert_eq!(
-            property_page_required,
-            make_property_page(REQUIRED_PROPERTY_NAME, required_property_value())
-        );
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Owner, receiving_agent_key),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!(
+                "Receiving agent key is not a valid Agent, InvalidTransaction should be returned"
+            ),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Receiving agent does not exist: {}",
+                    receiving_agent_key,
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
     }
 
     #[test]
-    /// Test that if the CreateRecordAction is invalid if the signer is not an Agent.
-    fn test_create_record_agent_does_not_exist() {
+    /// Test that if the CreateProposalAction fails when the a similar proposal already exists
+    fn test_create_proposal_already_exists() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Owner,
+            Status::Open,
+        );
+
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let create_record_action = create_record_action_with_properties(vec![
-            optional_property_value(),
-            required_property_value(),
-        ]);
 
-        match transaction_handler._create_record(
-            &create_record_action,
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Owner, receiving_agent_key),
             &mut state,
             PUBLIC_KEY,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Agent does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Proposal already exists, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Agent is not registered: {}", PUBLIC_KEY)));
+                assert!(err.contains("Proposal already exists"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the CreateRecordAction is invalid if the schema does not exist.
-    fn test_create_record_schema_does_not_exist() {
+    /// Test that if the CreateProposalAction fails when the record does not exist
+    fn test_create_proposal_record_does_not_exist() {
         let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let create_record_action = create_record_action_with_properties(vec![
-            optional_property_value(),
-            required_property_value(),
-        ]);
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Owner, receiving_agent_key),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record does not exist: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
 
-        match transaction_handler._create_record(
-            &create_record_action,
+    #[test]
+    /// Test that if the CreateProposalAction fails when the record is already final
+    fn test_create_proposal_record_is_final() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_finalized_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Owner, receiving_agent_key),
             &mut state,
             PUBLIC_KEY,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Schema does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Record is final, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Schema does not exist {}", SCHEMA_NAME)));
+                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the CreateRecordAction is invalid if the a record with the same id
-    /// already exists.
-    fn test_create_record_already_exist() {
+    /// Test that if the CreateProposalAction fails when the signer is not owner and tries to
+    /// transfer owenership
+    fn test_create_proposal_signer_not_owner() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        let signer_public_key = "not_owner_agent_key";
+        transaction_context.add_agent(signer_public_key);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Owner, receiving_agent_key),
+            &mut state,
+            signer_public_key,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer is not owner, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Only the owner can create a proposal to change ownership"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the CreateProposalAction fails when the signer is not custodian and tries to
+    /// transfer custodianship
+    fn test_create_proposal_signer_not_custodian() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        let signer_public_key = "not_custodian_agent_key";
+        transaction_context.add_agent(signer_public_key);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Custodian, receiving_agent_key),
+            &mut state,
+            signer_public_key,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer is not custodian, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err
+                    .contains("Only the custodian can create a proposal to change custodianship"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the CreateProposalAction fails when a reporter authorization is proposed but no
+    /// properties are specified
+    fn test_create_proposal_reporter_with_no_properties() {
         let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_schema();
+        transaction_context.add_agent(receiving_agent_key);
         transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let create_record_action = create_record_action_with_properties(vec![
-            optional_property_value(),
-            required_property_value(),
-        ]);
-
-        match transaction_handler._create_record(
-            &create_record_action,
+        match transaction_handler._create_proposal(
+            &create_proposal_no_props_action(Role::Reporter, receiving_agent_key),
             &mut state,
             PUBLIC_KEY,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Record id is duplicated, InvalidTransaction should be returned"),
+            Ok(()) => panic!("No properties specified, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record already exists: {}", RECORD_ID)));
+                assert!(err.contains("No properties were specified for authorization"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the CreateRecordAction is invalid if the the payload is missing a required
-    /// property as defined in the schema.
-    fn test_create_record_missing_required_property() {
+    /// Test that if the AnswerProposalAction, with Reponse set to Cancel, is valid an OK is returned
+    /// and the proposal is updated to have status Canceled
+    fn test_answer_proposal_cancel_ok() {
         let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_schema();
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Owner,
+            Status::Open,
+        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let create_record_action =
-            create_record_action_with_properties(vec![optional_property_value()]);
+        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Cancel);
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, PUBLIC_KEY, TIMESTAMP,)
+            .is_ok());
 
-        match transaction_handler._create_record(
-            &create_record_action,
-            &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Required property is missing, InvalidTransaction should be returned"),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!(
-                    "Required property {} not provided",
-                    REQUIRED_PROPERTY_NAME
-                )));
-            }
-            Err(err) => panic!("Should have gotten invalid error but got {}", err),
-        }
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(
+            proposal_list.proposals()[0],
+            make_proposal(
+                PUBLIC_KEY,
+                receiving_agent_key,
+                Role::Owner,
+                Status::Canceled
+            )
+        );
     }
 
     #[test]
-    /// Test that if the CreateRecordAction is invalid if the the payload has an extra property
-    /// that is not defined in the schema.
-    fn test_create_record_extra_property() {
+    /// Test that if the AnswerProposalAction, with Reponse set to Reject, is valid an OK is returned
+    /// and the proposal is updated to have status Rejected
+    fn test_answer_proposal_reject_ok() {
         let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_schema();
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Owner,
+            Status::Open,
+        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let property_value_extra = PropertyValueBuilder::new()
-            .with_name("invalid_property".to_string())
-            .with_data_type(DataType::String)
-            .with_string_value("Property that does not exist in schema".to_string())
-            .build()
-            .expect("Failed to build property value");
+        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Reject);
 
-        let create_record_action = create_record_action_with_properties(vec![
-            optional_property_value(),
-            required_property_value(),
-            property_value_extra.clone(),
-        ]);
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
+            .is_ok());
 
-        match transaction_handler._create_record(
-            &create_record_action,
-            &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!(
-                "There is an invalid property in the payload,
-                InvalidTransaction should be returned"
-            ),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Provided property invalid_property is not in schema"));
-            }
-            Err(err) => panic!("Should have gotten invalid error but got {}", err),
-        }
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(
+            proposal_list.proposals()[0],
+            make_proposal(
+                PUBLIC_KEY,
+                receiving_agent_key,
+                Role::Owner,
+                Status::Rejected
+            )
+        );
     }
 
     #[test]
-    /// Test that if the CreateRecordAction is invalid if a property value has a type that is not
-    /// the same as the type in the property definition.
-    fn test_create_record_invalid_property_value_type() {
+    /// Test that when the AnswerProposalAction, with Reponse set to Accept and Role set to Owner,
+    /// is valid an OK is returned and the proposal is updated to have status Accepted, the owner
+    /// in the record has been updated and the reporters for the properties have been updated.
+    fn test_answer_proposal_accept_owner_ok() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+        transaction_context.add_schema();
+        transaction_context.add_property(OPTIONAL_PROPERTY_NAME, optional_property_definition());
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Owner,
+            Status::Open,
+        );
+
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let property_value_wrong = PropertyValueBuilder::new()
-            .with_name(REQUIRED_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::Number)
-            .with_number_value(123)
+        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
+
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
+            .is_ok());
+
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(
+            proposal_list.proposals()[0],
+            make_proposal(
+                PUBLIC_KEY,
+                receiving_agent_key,
+                Role::Owner,
+                Status::Accepted
+            )
+        );
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+
+        let old_owner = AssociatedAgentBuilder::new()
+            .with_agent_id(PUBLIC_KEY.to_string())
+            .with_timestamp(TIMESTAMP)
+            .with_org_id("test_org".to_string())
             .build()
-            .expect("Failed to build property value");
+            .expect("Failed to build AssociatedAgent");
 
-        let create_record_action = create_record_action_with_properties(vec![
-            optional_property_value(),
-            property_value_wrong.clone(),
-        ]);
+        let new_owner = AssociatedAgentBuilder::new()
+            .with_agent_id(receiving_agent_key.to_string())
+            .with_timestamp(TIMESTAMP)
+            .with_org_id("test_org".to_string())
+            .with_proposal_issuing_agent(PUBLIC_KEY.to_string())
+            .with_proposal_timestamp(TIMESTAMP)
+            .build()
+            .expect("Failed to build AssociatedAgent");
 
-        match transaction_handler._create_record(
-            &create_record_action,
-            &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!(
-                "There is an invalid property value in the payload,
-                InvalidTransaction should be returned"
-            ),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!(
-                    "Value provided for {} is the wrong type",
-                    REQUIRED_PROPERTY_NAME
-                )));
-            }
-            Err(err) => panic!("Should have gotten invalid error but got {}", err),
-        }
+        // Create record that has the receiving agent as a owner.
+        let expected_record = make_record()
+            .into_builder()
+            .with_owners(vec![old_owner, new_owner])
+            .build()
+            .expect("Failed to build record");
+
+        // Compare record found in state, whith what would be expected if the transaction was
+        // executed correctly
+        assert_eq!(record, expected_record);
+
+        let required_property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch required property")
+            .expect("Required property not found");
+
+        let revoked_authorization = make_reporter_authorization(PUBLIC_KEY)
+            .into_builder()
+            .with_revoked(TIMESTAMP, PUBLIC_KEY.to_string())
+            .build()
+            .expect("Failed to build ReporterAuthorization");
+
+        let old_reporter = ReporterBuilder::new()
+            .with_public_key(PUBLIC_KEY.to_string())
+            .with_authorized(false)
+            .with_index(0)
+            .with_authorizations(vec![revoked_authorization])
+            .build()
+            .expect("Failed to build Reporter");
+
+        let new_reporter = ReporterBuilder::new()
+            .with_public_key(receiving_agent_key.to_string())
+            .with_authorized(true)
+            .with_index(1)
+            .with_authorizations(vec![make_reporter_authorization(PUBLIC_KEY)])
+            .build()
+            .expect("Failed to build Reporter");
+
+        // Create record that has the receiving agent as a reporter.
+        let expected_property =
+            make_property(REQUIRED_PROPERTY_NAME, required_property_definition())
+                .into_builder()
+                .with_reporters(vec![old_reporter, new_reporter])
+                .build()
+                .expect("Failed to build property");
+
+        // Compare property found in state, whith what would be expected if the transaction was
+        // executed correctly
+        assert_eq!(required_property, expected_property);
     }
 
     #[test]
-    /// Test that if the FinalizeRecordAction is valid an OK is returned and that the record is
-    /// marked as finalized
-    fn test_finalize_record_handler_valid() {
+    /// Test that when the AnswerProposalAction, with Reponse set to Accept and Role set to Custodian,
+    /// is valid an OK is returned and the proposal is updated to have status Accepted, the custodians
+    /// in the record have been updated.
+    fn test_answer_proposal_accept_custodian_ok() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
         transaction_context.add_record();
+        transaction_context.add_schema();
+        transaction_context.add_property(OPTIONAL_PROPERTY_NAME, optional_property_definition());
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Custodian,
+            Status::Open,
+        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
+        let payload =
+            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
+
         assert!(transaction_handler
-            ._finalize_record(&create_finalize_record(), &mut state, PUBLIC_KEY)
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
             .is_ok());
 
-        let finalized_record = state
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(
+            proposal_list.proposals()[0],
+            make_proposal(
+                PUBLIC_KEY,
+                receiving_agent_key,
+                Role::Custodian,
+                Status::Accepted
+            )
+        );
+
+        let record = state
             .get_record(RECORD_ID)
             .expect("Failed to fetch record")
-            .expect("Record not found");
+            .expect("No record found");
 
-        assert!(finalized_record.field_final());
+        let old_custodian = AssociatedAgentBuilder::new()
+            .with_agent_id(PUBLIC_KEY.to_string())
+            .with_timestamp(TIMESTAMP)
+            .with_org_id("test_org".to_string())
+            .build()
+            .expect("Failed to build AssociatedAgent");
+
+        let new_custodian = AssociatedAgentBuilder::new()
+            .with_agent_id(receiving_agent_key.to_string())
+            .with_timestamp(TIMESTAMP)
+            .with_org_id("test_org".to_string())
+            .with_proposal_issuing_agent(PUBLIC_KEY.to_string())
+            .with_proposal_timestamp(TIMESTAMP)
+            .build()
+            .expect("Failed to build AssociatedAgent");
+
+        // Create record that has receiving agent as a custodian
+        let expected_record = make_record()
+            .into_builder()
+            .with_custodians(vec![old_custodian, new_custodian])
+            .build()
+            .expect("Failed to build record");
+
+        // Compare record found in state, whith what would be expected if the transaction was
+        // executed correctly
+        assert_eq!(record, expected_record);
     }
 
     #[test]
-    /// Test that if the FinalizeRecordAction fails if a record with the provided id does
-    /// not exist.
-    fn test_finalize_record_handler_record_does_not_exist() {
+    /// Test that a proposal on a record with confidential properties must carry the data key
+    /// of the record wrapped for the receiving agent, and that the key is shared with the
+    /// receiving agent when the proposal is accepted
+    fn test_answer_proposal_accept_shares_data_key() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_schema();
+        transaction_context.add_property(OPTIONAL_PROPERTY_NAME, optional_property_definition());
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let data_key = DataKeyBuilder::new()
+            .with_agent_id(PUBLIC_KEY.to_string())
+            .with_wrapped_key(vec![1, 1, 1])
+            .build()
+            .expect("Failed to build DataKey");
+        state
+            .set_record(
+                RECORD_ID,
+                make_record()
+                    .into_builder()
+                    .with_data_keys(vec![data_key])
+                    .build()
+                    .expect("Failed to build record"),
+            )
+            .expect("Failed to set record");
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        match transaction_handler._finalize_record(
-            &create_finalize_record(),
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Custodian, receiving_agent_key),
             &mut state,
             PUBLIC_KEY,
+            TIMESTAMP,
         ) {
-            Ok(()) => panic!("Record does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("No data key was wrapped, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record does not exist: {}", RECORD_ID)));
+                assert!(err.contains(&format!(
+                    "its data key must be wrapped for {}",
+                    receiving_agent_key
+                )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
-    }
-
-    #[test]
-    /// Test that if the FinalizeRecordAction fails if the signer is not record owner nor
-    // custodian
-    fn test_finalize_record_handler_signer_not_owner_nor_custodian() {
-        let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_record();
 
-        let mut state = TrackAndTraceState::new(&mut transaction_context);
-        let signer = "agent_public_key_not_owner";
+        let create_proposal = create_proposal_action(Role::Custodian, receiving_agent_key);
+        let create_proposal = CreateProposalActionBuilder::new()
+            .with_record_id(create_proposal.record_id().to_string())
+            .with_properties(create_proposal.properties().to_vec())
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(Role::Custodian)
+            .with_terms("".to_string())
+            .with_wrapped_data_key(vec![2, 2, 2])
+            .build()
+            .expect("Failed to build CreateProposalAction");
+        transaction_handler
+            ._create_proposal(&create_proposal, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to create proposal");
 
-        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let payload =
+            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
+        transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP)
+            .expect("Failed to accept proposal");
 
-        match transaction_handler._finalize_record(&create_finalize_record(), &mut state, signer) {
-            Ok(()) => panic!(
-                "Signer is not record owner nor custodian, InvalidTransaction should be returned"
-            ),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Must be owner and custodian to finalize record"));
-            }
-            Err(err) => panic!("Should have gotten invalid error but got {}", err),
-        }
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(record.data_keys().len(), 2);
+        assert!(!*record.data_key_rotation_required());
+        assert_eq!(
+            record
+                .data_key(receiving_agent_key)
+                .map(|data_key| data_key.wrapped_key()),
+            Some(&[2, 2, 2][..])
+        );
+        assert_eq!(
+            record.custodians().last().map(|custodian| custodian.agent_id()),
+            Some(receiving_agent_key)
+        );
     }
 
     #[test]
-    /// Test that if the FinalizeRecordAction fails if the record is alreadt final.
-    fn test_finalize_record_handler_record_already_final() {
+    /// Test that an agent that loses its last role on a record loses the data key, and that
+    /// the data key must then be rotated, for the remaining holders only, before confidential
+    /// values are written again
+    fn test_transfer_custody_revokes_data_key() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
+        let custodian_key = "custodian_key";
         transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_finalized_record();
+        transaction_context.add_agent(custodian_key);
+        let price_definition = PropertyDefinitionBuilder::new()
+            .with_name("price".to_string())
+            .with_data_type(DataType::Number)
+            .with_confidential(true)
+            .build()
+            .expect("Failed to build property definition");
+        let schema = SchemaBuilder::new()
+            .with_name(SCHEMA_NAME.to_string())
+            .with_description("Test Schema".to_string())
+            .with_owner("test_org".to_string())
+            .with_properties(vec![price_definition.clone()])
+            .build()
+            .expect("Failed to build schema");
+        let schema_list = SchemaListBuilder::new()
+            .with_schemas(vec![schema])
+            .build()
+            .expect("Failed to build schema list");
+        transaction_context
+            .set_state_entry(
+                make_schema_address(SCHEMA_NAME),
+                schema_list.into_bytes().unwrap(),
+            )
+            .unwrap();
+        transaction_context.add_property("price", price_definition);
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let data_key = |agent_id: &str, wrapped_key: Vec<u8>| {
+            DataKeyBuilder::new()
+                .with_agent_id(agent_id.to_string())
+                .with_wrapped_key(wrapped_key)
+                .build()
+                .expect("Failed to build DataKey")
+        };
+        let custodian = AssociatedAgentBuilder::new()
+            .with_agent_id(custodian_key.to_string())
+            .with_timestamp(TIMESTAMP)
+            .build()
+            .expect("Failed to build AssociatedAgent");
+        let record = make_record()
+            .into_builder()
+            .with_custodians(vec![custodian])
+            .with_data_keys(vec![
+                data_key(PUBLIC_KEY, vec![1, 1, 1]),
+                data_key(custodian_key, vec![2, 2, 2]),
+            ])
+            .build()
+            .expect("Failed to build record");
+        state
+            .set_record(RECORD_ID, record.clone())
+            .expect("Failed to set record");
 
+        let new_custodian = AssociatedAgentBuilder::new()
+            .with_agent_id(PUBLIC_KEY.to_string())
+            .with_timestamp(TIMESTAMP)
+            .build()
+            .expect("Failed to build AssociatedAgent");
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        transaction_handler
+            ._transfer_custody(record, new_custodian, &mut state)
+            .expect("Failed to transfer custody");
 
-        match transaction_handler._finalize_record(
-            &create_finalize_record(),
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(
+            record.data_keys().to_vec(),
+            vec![data_key(PUBLIC_KEY, vec![1, 1, 1])]
+        );
+        assert!(*record.data_key_rotation_required());
+
+        let price = |key_version: u32| {
+            PropertyValueBuilder::new()
+                .with_name("price".to_string())
+                .with_data_type(DataType::Number)
+                .with_encrypted_value(vec![3, 3])
+                .with_key_version(key_version)
+                .build()
+                .expect("Failed to build property value")
+        };
+        let update = |key_version: u32, data_keys: Vec<DataKey>| {
+            UpdatePropertiesActionBuilder::new()
+                .with_record_id(RECORD_ID.to_string())
+                .with_properties(vec![price(key_version)])
+                .with_data_keys(data_keys)
+                .build()
+                .expect("Failed to build UpdatePropertiesAction")
+        };
+
+        match transaction_handler._update_properties(
+            &update(0, vec![]),
             &mut state,
             PUBLIC_KEY,
+            TIMESTAMP,
         ) {
-            Ok(()) => panic!("Record is already final, InvalidTransaction should be returned"),
+            Ok(()) => panic!("The data key was not rotated, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record is already final: {}", RECORD_ID)));
+                assert!(err.contains("it must be rotated before confidential values are written"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
+
+        let rotated_keys = vec![
+            data_key(PUBLIC_KEY, vec![4, 4, 4]),
+            data_key(custodian_key, vec![5, 5, 5]),
+        ];
+        assert!(transaction_handler
+            ._update_properties(&update(1, rotated_keys), &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_err());
+        let rotated_keys = vec![data_key(PUBLIC_KEY, vec![4, 4, 4])];
+        assert!(transaction_handler
+            ._update_properties(
+                &update(0, rotated_keys.clone()),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP
+            )
+            .is_err());
+        transaction_handler
+            ._update_properties(&update(1, rotated_keys), &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to update properties");
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(*record.data_key_version(), 1);
+        assert!(!*record.data_key_rotation_required());
+        assert_eq!(record.data_keys().len(), 2);
+        assert_eq!(
+            record
+                .data_key(PUBLIC_KEY)
+                .map(|data_key| data_key.wrapped_key()),
+            Some(&[4, 4, 4][..])
+        );
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction is valid an OK is returned and new value is added
-    /// to the record's PropertyPage
-    fn test_update_properties_valid() {
+    /// Test that when the AnswerProposalAction, with Reponse set to Accept and Role set to Reporter,
+    /// is valid an OK is returned and the proposal is updated to have status Accepted,
+    /// and the reporters for the properties have been updated.
+    fn test_answer_proposal_accept_reporter_ok() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
+        let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
         transaction_context.add_record();
+        transaction_context.add_schema();
+        transaction_context.add_property(OPTIONAL_PROPERTY_NAME, optional_property_definition());
         transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Reporter,
+            Status::Open,
+        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
 
         assert!(transaction_handler
-            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, TIMESTAMP,)
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
             .is_ok());
 
-        let page = state
-            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
-            .expect("Failed to get property page from state")
-            .expect("Property page is none, it should be some");
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(
+            proposal_list.proposals()[0],
+            make_proposal(
+                PUBLIC_KEY,
+                receiving_agent_key,
+                Role::Reporter,
+                Status::Accepted
+            )
+        );
+
+        let required_property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch required property")
+            .expect("Required property not found");
+
+        let optional_property = state
+            .get_property(RECORD_ID, OPTIONAL_PROPERTY_NAME)
+            .expect("Failed to fetch optional property")
+            .expect("Optional property not found");
+
+        let old_reporter = ReporterBuilder::new()
+            .with_public_key(PUBLIC_KEY.to_string())
+            .with_authorized(true)
+            .with_index(0)
+            .with_authorizations(vec![make_reporter_authorization(PUBLIC_KEY)])
+            .build()
+            .expect("Failed to build Reporter");
+
+        let new_reporter = ReporterBuilder::new()
+            .with_public_key(receiving_agent_key.to_string())
+            .with_authorized(true)
+            .with_index(1)
+            .with_authorizations(vec![make_reporter_authorization(PUBLIC_KEY)])
+            .build()
+            .expect("Failed to build Reporter");
+
+        // Create required property that has receiving agent as an reporter
+        let expected_required_property =
+            make_property(REQUIRED_PROPERTY_NAME, required_property_definition())
+                .into_builder()
+                .with_reporters(vec![old_reporter.clone(), new_reporter.clone()])
+                .build()
+                .expect("Failed to build property");
+
+        // Create optional property that has receiving agent as an reporter
+        let expected_optional_property =
+            make_property(OPTIONAL_PROPERTY_NAME, optional_property_definition())
+                .into_builder()
+                .with_reporters(vec![old_reporter.clone(), new_reporter.clone()])
+                .build()
+                .expect("Failed to build property");
 
-        assert_eq!(page.reported_values().len(), 2);
-        assert_eq!(page.reported_values()[1].value(), &updated_property_value());
+        // Compare properties found in state, whith what would be expected if the transaction was
+        // executed correctly
+        assert_eq!(required_property, expected_required_property);
+        assert_eq!(optional_property, expected_optional_property);
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the record does not exist
-    fn test_update_properties_record_does_not_exist() {
+    /// Test that when the AnswerProposalAction fails if the proposal does not exists.
+    fn test_answer_proposal_does_not_exist() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_finalized_record();
-
+        let receiving_agent_key = "receiving_agent_key";
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
+        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
+
+        match transaction_handler._answer_proposal(
+            &payload,
             &mut state,
-            PUBLIC_KEY,
+            receiving_agent_key,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Proposal does not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+                assert!(err.contains(&format!(
+                    "No open proposals found for record {} for {}",
+                    RECORD_ID, receiving_agent_key
+                )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the record is set to final
-    fn test_update_properties_record_is_final() {
+    /// Test that when the AnswerProposalAction fails if the response is set to cancel and signer
+    /// is not the proposal's issuing agent
+    fn test_answer_proposal_signer_not_issuing_agent() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_finalized_record();
+        let receiving_agent_key = "receiving_agent_key";
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Reporter,
+            Status::Open,
+        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
+        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Cancel);
+
+        match transaction_handler._answer_proposal(
+            &payload,
             &mut state,
-            PUBLIC_KEY,
+            receiving_agent_key,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Signer not issuing agent, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+                assert!(err.contains("Only the issuing agent can cancel a proposal"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is not part of
-    /// the record
-    fn test_update_properties_record_missing_property() {
+    /// Test that when the AnswerProposalAction fails if the response is set to reject and signer
+    /// is not the proposal's receiving agent
+    fn test_answer_proposal_signer_not_receiving_agent_reject() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_record();
+        let receiving_agent_key = "receiving_agent_key";
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Reporter,
+            Status::Open,
+        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
-            &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Reject);
+
+        match transaction_handler._answer_proposal(&payload, &mut state, PUBLIC_KEY, TIMESTAMP) {
+            Ok(()) => panic!("Signer not issuing agent, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!(
-                    "Record does not have provided property: {}",
-                    REQUIRED_PROPERTY_NAME
-                )));
+                assert!(err.contains("Only the receiving agent can reject a proposal"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the signer is not an authorized reporter
-    fn test_update_properties_signer_not_authorized() {
+    /// Test that when the AnswerProposalAction fails if the response is set to accept and signer
+    /// is not the proposal's receiving agent
+    fn test_answer_proposal_signer_not_receiving_agent_accept() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_record();
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+        let receiving_agent_key = "receiving_agent_key";
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Reporter,
+            Status::Open,
+        );
 
-        let signer = "not_authorized";
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
-            &mut state,
-            signer.clone(),
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
+
+        match transaction_handler._answer_proposal(&payload, &mut state, PUBLIC_KEY, TIMESTAMP) {
+            Ok(()) => panic!("Signer not issuing agent, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Reporter is not authorized: {}", signer)));
+                assert!(err.contains("Only the receiving agent can accept a proposal"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is set to
-    /// a data type that does not match the property definition.
-    fn test_update_properties_property_value_wrong_type() {
+    /// Test that when the AnswerProposalAction fails if the response is set to accept and the
+    /// record does not exist
+    fn test_answer_proposal_record_does_not_exist() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_record();
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+        let receiving_agent_key = "receiving_agent_key";
 
-        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Reporter,
+            Status::Open,
+        );
 
-        let property_value_wrong = PropertyValueBuilder::new()
-            .with_name(REQUIRED_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::Number)
-            .with_number_value(123)
-            .build()
-            .expect("Failed to build property value");
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![property_value_wrong]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
+        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
+
+        match transaction_handler._answer_proposal(
+            &payload,
             &mut state,
-            PUBLIC_KEY,
+            receiving_agent_key,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Signer not issuing agent, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!(
-                    "Update has wrong type: {:?} != {:?}",
-                    DataType::Number,
-                    DataType::String
-                )));
+                assert!(err.contains(&format!("Record in proposal does not exist: {}", RECORD_ID)));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction start new PropertyPage when needed.
-    fn test_update_properties_new_page() {
-        let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_record();
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
-
-        let mut state = TrackAndTraceState::new(&mut transaction_context);
-
-        let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let property_value = updated_property_value();
-
-        // Generate enough value updates that would required the start of a new PropertyPage.
-        let updates = std::iter::repeat(property_value)
-            .take(PROPERTY_PAGE_MAX_LENGTH)
-            .collect::<Vec<_>>();
-
-        let update_property_action = update_property_action(updates);
-
-        assert!(transaction_handler
-            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, TIMESTAMP,)
-            .is_ok());
-
-        let new_page = state
-            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 2)
-            .expect("Failed to get property page from state");
-
-        assert!(new_page.is_some());
-    }
-
-    #[test]
-    /// Test that if the CreateProposalAction, with role set to Owner, is valid an OK is returned
-    /// and new proposal is added state
-    fn test_create_proposal_valid_owner() {
-        let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_agent(receiving_agent_key);
-        transaction_context.add_record();
-
-        let mut state = TrackAndTraceState::new(&mut transaction_context);
-
-        let transaction_handler = TrackAndTraceTransactionHandler::new();
-
-        assert!(transaction_handler
-            ._create_proposal(
-                &create_proposal_action(Role::Owner, receiving_agent_key),
-                &mut state,
-                PUBLIC_KEY,
-                TIMESTAMP,
-            )
-            .is_ok());
-
-        let proposal_list = state
-            .get_proposal_list(RECORD_ID, receiving_agent_key)
-            .expect("Failed to get ProposalList from state")
-            .expect("ProposalList not found");
-
-        assert_eq!(proposal_list.proposals().len(), 1);
-        assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(PUBLIC_KEY, receiving_agent_key, Role::Owner, Status::Open)
-        );
-    }
-
-    #[test]
-    /// Test that if the CreateProposalAction, with role set to Custodian, is valid an OK is returned
-    /// and new proposal is added state
-    fn test_create_proposal_valid_custodian() {
+    /// Test that when the AnswerProposalAction is Ok if the response is set to accept and the
+    /// role is set to owner, but the proposal's issuing agent is not the owner of the record.
+    /// The status of the proposal should be set to Canceled.
+    fn test_answer_proposal_issuing_agent_not_owner() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_agent(receiving_agent_key);
+        let issuing_agent_key = "issuing_agent_key";
+
+        transaction_context.add_proposal(
+            issuing_agent_key,
+            receiving_agent_key,
+            Role::Owner,
+            Status::Open,
+        );
         transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
+        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
+
         assert!(transaction_handler
-            ._create_proposal(
-                &create_proposal_action(Role::Custodian, receiving_agent_key),
-                &mut state,
-                PUBLIC_KEY,
-                TIMESTAMP,
-            )
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
             .is_ok());
 
         let proposal_list = state
@@ -1940,63 +5717,83 @@ mod tests {
         assert_eq!(
             proposal_list.proposals()[0],
             make_proposal(
-                PUBLIC_KEY,
+                issuing_agent_key,
                 receiving_agent_key,
-                Role::Custodian,
-                Status::Open
+                Role::Owner,
+                Status::Canceled
             )
         );
     }
 
     #[test]
-    /// Test that if the CreateProposalAction fails when the signer is not an agent
-    fn test_create_proposal_agent_does_not_exist() {
+    /// Test that when the AnswerProposalAction fails if the response is set to accept and the
+    /// role is set to owner, but the record's schema does not exist.
+    fn test_answer_proposal_schema_does_not_exist() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
-        transaction_context.add_agent(receiving_agent_key);
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Owner,
+            Status::Open,
+        );
+        transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        match transaction_handler._create_proposal(
-            &create_proposal_action(Role::Owner, receiving_agent_key),
+        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
+
+        match transaction_handler._answer_proposal(
+            &payload,
             &mut state,
-            PUBLIC_KEY,
+            receiving_agent_key,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Signer is not an Agent, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Schema does not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Issuing agent does not exist: {}", PUBLIC_KEY,)));
+                assert!(err.contains(&format!("Schema does not exist: {}", SCHEMA_NAME)));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the CreateProposalAction fails when the receiving agent key is not a valid agent
-    fn test_create_proposal_receiving_agent_does_not_exist() {
+    /// Test that when the AnswerProposalAction fails if the response is set to accept and the
+    /// role is set to owner, schema's properties does not exist.
+    fn test_answer_proposal_schema_property_does_not_exist() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
-        transaction_context.add_agent(PUBLIC_KEY);
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Owner,
+            Status::Open,
+        );
+        transaction_context.add_record();
+        transaction_context.add_schema();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        match transaction_handler._create_proposal(
-            &create_proposal_action(Role::Owner, receiving_agent_key),
+        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
+
+        match transaction_handler._answer_proposal(
+            &payload,
             &mut state,
-            PUBLIC_KEY,
+            receiving_agent_key,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!(
-                "Receiving agent key is not a valid Agent, InvalidTransaction should be returned"
-            ),
+            Ok(()) => panic!("Property does not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "Receiving agent does not exist: {}",
-                    receiving_agent_key,
+                    "Property does not exist: {}",
+                    OPTIONAL_PROPERTY_NAME
                 )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
@@ -2004,128 +5801,140 @@ mod tests {
     }
 
     #[test]
-    /// Test that if the CreateProposalAction fails when the a similar proposal already exists
-    fn test_create_proposal_already_exists() {
+    /// Test that when the AnswerProposalAction is Ok if the response is set to accept and the
+    /// role is set to custodian, but the proposal's issuing agent is not the custodian of the
+    /// record. The status of the proposal should be set to Canceled.
+    fn test_answer_proposal_issuing_agent_not_custodian() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_agent(receiving_agent_key);
+        let issuing_agent_key = "issuing_agent_key";
+
         transaction_context.add_proposal(
-            PUBLIC_KEY,
+            issuing_agent_key,
             receiving_agent_key,
-            Role::Owner,
+            Role::Custodian,
             Status::Open,
         );
+        transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        match transaction_handler._create_proposal(
-            &create_proposal_action(Role::Owner, receiving_agent_key),
-            &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Proposal already exists, InvalidTransaction should be returned"),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Proposal already exists"));
-            }
-            Err(err) => panic!("Should have gotten invalid error but got {}", err),
-        }
-    }
-
-    #[test]
-    /// Test that if the CreateProposalAction fails when the record does not exist
-    fn test_create_proposal_record_does_not_exist() {
-        let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_agent(receiving_agent_key);
+        let payload =
+            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
 
-        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
+            .is_ok());
 
-        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
 
-        match transaction_handler._create_proposal(
-            &create_proposal_action(Role::Owner, receiving_agent_key),
-            &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Record does not exist, InvalidTransaction should be returned"),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record does not exist: {}", RECORD_ID)));
-            }
-            Err(err) => panic!("Should have gotten invalid error but got {}", err),
-        }
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(
+            proposal_list.proposals()[0],
+            make_proposal(
+                issuing_agent_key,
+                receiving_agent_key,
+                Role::Custodian,
+                Status::Canceled
+            )
+        );
     }
 
     #[test]
-    /// Test that if the CreateProposalAction fails when the record is already final
-    fn test_create_proposal_record_is_final() {
+    /// Test that when the AnswerProposalAction is Ok if the response is set to accept and the
+    /// role is set to reporter, but the proposal's issuing agent is not the owner of the record.
+    /// The status of the proposal should be set to Canceled.
+    fn test_answer_proposal_issuing_agent_not_owner_reporter() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_agent(receiving_agent_key);
-        transaction_context.add_finalized_record();
+        let issuing_agent_key = "issuing_agent_key";
+
+        transaction_context.add_proposal(
+            issuing_agent_key,
+            receiving_agent_key,
+            Role::Reporter,
+            Status::Open,
+        );
+        transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        match transaction_handler._create_proposal(
-            &create_proposal_action(Role::Owner, receiving_agent_key),
-            &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Record is final, InvalidTransaction should be returned"),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
-            }
-            Err(err) => panic!("Should have gotten invalid error but got {}", err),
-        }
+        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
+
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
+            .is_ok());
+
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(
+            proposal_list.proposals()[0],
+            make_proposal(
+                issuing_agent_key,
+                receiving_agent_key,
+                Role::Reporter,
+                Status::Canceled
+            )
+        );
     }
 
     #[test]
-    /// Test that if the CreateProposalAction fails when the signer is not owner and tries to
-    /// transfer owenership
-    fn test_create_proposal_signer_not_owner() {
+    /// Test that when the AnswerProposalAction fails if the response is set to accept and the
+    /// role is set to reporter, but one of the proposal's properties does not exist.
+    fn test_answer_proposal_property_does_not_exist() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
-        let signer_public_key = "not_owner_agent_key";
-        transaction_context.add_agent(signer_public_key);
-        transaction_context.add_agent(receiving_agent_key);
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Reporter,
+            Status::Open,
+        );
         transaction_context.add_record();
+        transaction_context.add_schema();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        match transaction_handler._create_proposal(
-            &create_proposal_action(Role::Owner, receiving_agent_key),
+        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
+
+        match transaction_handler._answer_proposal(
+            &payload,
             &mut state,
-            signer_public_key,
+            receiving_agent_key,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Signer is not owner, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Schema does not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Only the owner can create a proposal to change ownership"));
+                assert!(err.contains(&format!(
+                    "Property does not exist: {}",
+                    OPTIONAL_PROPERTY_NAME
+                )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the CreateProposalAction fails when the signer is not custodian and tries to
-    /// transfer custodianship
-    fn test_create_proposal_signer_not_custodian() {
+    /// Test that the CreateProposalAction fails if a record that is not final is proposed to be
+    /// reopened
+    fn test_create_proposal_reopen_record_not_final() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-        let signer_public_key = "not_custodian_agent_key";
-        transaction_context.add_agent(signer_public_key);
-        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
@@ -2133,114 +5942,173 @@ mod tests {
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
         match transaction_handler._create_proposal(
-            &create_proposal_action(Role::Custodian, receiving_agent_key),
+            &create_proposal_action(Role::Reopen, PUBLIC_KEY),
             &mut state,
-            signer_public_key,
+            PUBLIC_KEY,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Signer is not custodian, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Record is not final, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err
-                    .contains("Only the custodian can create a proposal to change custodianship"));
+                assert!(err.contains(&format!("Record is not final: {}", RECORD_ID)));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
-    #[test]
-    /// Test that the CreateProposalAction fails when a reporter authorization is proposed but no
-    /// properties are specified
-    fn test_create_proposal_reporter_with_no_properties() {
+    #[test]
+    /// Test that the CreateProposalAction fails if a proposal to reopen a record is not sent to
+    /// the other holder of the owner and custodian roles
+    fn test_create_proposal_reopen_wrong_receiving_agent() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_agent(receiving_agent_key);
-        transaction_context.add_record();
+        transaction_context.add_finalized_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
         match transaction_handler._create_proposal(
-            &create_proposal_no_props_action(Role::Reporter, receiving_agent_key),
+            &create_proposal_action(Role::Reopen, receiving_agent_key),
             &mut state,
             PUBLIC_KEY,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("No properties specified, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Wrong receiving agent, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("No properties were specified for authorization"));
+                assert!(err.contains(&format!(
+                    "A proposal to reopen a record must be sent to {}",
+                    PUBLIC_KEY
+                )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the AnswerProposalAction, with Reponse set to Cancel, is valid an OK is returned
-    /// and the proposal is updated to have status Canceled
-    fn test_answer_proposal_cancel_ok() {
+    /// Test that a finalized record is reopened once the custodian accepts the proposal of the
+    /// owner to reopen it, and that the reopening is recorded on the record.
+    fn test_answer_proposal_accept_reopen_ok() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
+        let custodian_key = "custodian_key";
         transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_agent(receiving_agent_key);
-        transaction_context.add_record();
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Owner,
-            Status::Open,
-        );
+        transaction_context.add_agent(custodian_key);
+
+        let custodian = AssociatedAgentBuilder::new()
+            .with_agent_id(custodian_key.to_string())
+            .with_timestamp(TIMESTAMP)
+            .build()
+            .expect("Failed to build AssociatedAgent");
+        let finalized_record = make_record()
+            .into_builder()
+            .with_custodians(vec![custodian])
+            .with_field_final(true)
+            .build()
+            .expect("Failed to build record");
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
+        state
+            .set_record(RECORD_ID, finalized_record.clone())
+            .expect("Failed to set record");
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Cancel);
         assert!(transaction_handler
-            ._answer_proposal(&payload, &mut state, PUBLIC_KEY, TIMESTAMP,)
+            ._create_proposal(
+                &create_proposal_action(Role::Reopen, custodian_key),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .is_ok());
+
+        let payload = answer_proposal_action(Role::Reopen, custodian_key, Response::Accept);
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, custodian_key, TIMESTAMP + 1)
             .is_ok());
 
         let proposal_list = state
-            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .get_proposal_list(RECORD_ID, custodian_key)
             .expect("Failed to get ProposalList from state")
             .expect("ProposalList not found");
+        assert_eq!(proposal_list.proposals()[0].status(), &Status::Accepted);
 
-        assert_eq!(proposal_list.proposals().len(), 1);
-        assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(
-                PUBLIC_KEY,
-                receiving_agent_key,
-                Role::Owner,
-                Status::Canceled
-            )
-        );
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+
+        let reopening = ReopeningBuilder::new()
+            .with_issuing_agent(PUBLIC_KEY.to_string())
+            .with_approving_agent(custodian_key.to_string())
+            .with_timestamp(TIMESTAMP + 1)
+            .build()
+            .expect("Failed to build Reopening");
+        let expected_record = finalized_record
+            .into_builder()
+            .with_field_final(false)
+            .with_reopenings(vec![reopening])
+            .build()
+            .expect("Failed to build record");
+
+        assert_eq!(record, expected_record);
     }
 
     #[test]
-    /// Test that if the AnswerProposalAction, with Reponse set to Reject, is valid an OK is returned
-    /// and the proposal is updated to have status Rejected
-    fn test_answer_proposal_reject_ok() {
+    /// Test that the CreateProposalAction fails if the proposal would expire before it is created
+    fn test_create_proposal_expires_before_created() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_agent(receiving_agent_key);
         transaction_context.add_record();
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Owner,
-            Status::Open,
-        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Reject);
+        let payload = CreateProposalActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_properties(vec![])
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(Role::Owner)
+            .with_terms("".to_string())
+            .with_expires_at(TIMESTAMP)
+            .build()
+            .expect("Failed to build CreateProposalAction");
+
+        match transaction_handler._create_proposal(&payload, &mut state, PUBLIC_KEY, TIMESTAMP) {
+            Ok(()) => panic!("Proposal expires too early, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Proposal must expire after it is created"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that an expired open proposal does not block a new proposal for the same role, and
+    /// that it is moved to the Expired status when the new proposal is created.
+    fn test_create_proposal_replaces_expired_proposal() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+        transaction_context.add_expiring_proposal(receiving_agent_key, Role::Owner, TIMESTAMP + 1);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
 
         assert!(transaction_handler
-            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
+            ._create_proposal(
+                &create_proposal_action(Role::Owner, receiving_agent_key),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP + 1,
+            )
             .is_ok());
 
         let proposal_list = state
@@ -2248,38 +6116,21 @@ mod tests {
             .expect("Failed to get ProposalList from state")
             .expect("ProposalList not found");
 
-        assert_eq!(proposal_list.proposals().len(), 1);
-        assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(
-                PUBLIC_KEY,
-                receiving_agent_key,
-                Role::Owner,
-                Status::Rejected
-            )
-        );
+        assert_eq!(proposal_list.proposals().len(), 2);
+        assert_eq!(proposal_list.proposals()[0].status(), &Status::Expired);
+        assert_eq!(proposal_list.proposals()[1].status(), &Status::Open);
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction, with Reponse set to Accept and Role set to Owner,
-    /// is valid an OK is returned and the proposal is updated to have status Accepted, the owner
-    /// in the record has been updated and the reporters for the properties have been updated.
-    fn test_answer_proposal_accept_owner_ok() {
+    /// Test that answering an expired proposal moves it to the Expired status without applying
+    /// the response
+    fn test_answer_proposal_expired() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_agent(receiving_agent_key);
         transaction_context.add_record();
-        transaction_context.add_schema();
-        transaction_context.add_property(OPTIONAL_PROPERTY_NAME, optional_property_definition());
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Owner,
-            Status::Open,
-        );
+        transaction_context.add_expiring_proposal(receiving_agent_key, Role::Owner, TIMESTAMP + 1);
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
@@ -2288,276 +6139,302 @@ mod tests {
         let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
 
         assert!(transaction_handler
-            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP + 1)
             .is_ok());
 
         let proposal_list = state
             .get_proposal_list(RECORD_ID, receiving_agent_key)
             .expect("Failed to get ProposalList from state")
             .expect("ProposalList not found");
-
-        assert_eq!(proposal_list.proposals().len(), 1);
-        assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(
-                PUBLIC_KEY,
-                receiving_agent_key,
-                Role::Owner,
-                Status::Accepted
-            )
-        );
+        assert_eq!(proposal_list.proposals()[0].status(), &Status::Expired);
 
         let record = state
             .get_record(RECORD_ID)
             .expect("Failed to fetch record")
             .expect("No record found");
+        assert_eq!(record, make_record());
+    }
 
-        let old_owner = AssociatedAgentBuilder::new()
-            .with_agent_id(PUBLIC_KEY.to_string())
-            .with_timestamp(TIMESTAMP)
-            .build()
-            .expect("Failed to build AssociatedAgent");
-
-        let new_owner = AssociatedAgentBuilder::new()
-            .with_agent_id(receiving_agent_key.to_string())
-            .with_timestamp(TIMESTAMP)
+    #[test]
+    /// Test that a proposal whose range condition is not met cannot be accepted and stays open
+    fn test_answer_proposal_accept_property_range_not_met() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+        let temperature_definition = PropertyDefinitionBuilder::new()
+            .with_name("temperature".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
             .build()
-            .expect("Failed to build AssociatedAgent");
-
-        // Create record that has the receiving agent as a owner.
-        let expected_record = make_record()
-            .into_builder()
-            .with_owners(vec![old_owner, new_owner])
+            .expect("Failed to build property definition");
+        transaction_context.add_property("temperature", temperature_definition);
+        let temperature = PropertyValueBuilder::new()
+            .with_name("temperature".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_value(12)
             .build()
-            .expect("Failed to build record");
-
-        // Compare record found in state, whith what would be expected if the transaction was
-        // executed correctly
-        assert_eq!(record, expected_record);
-
-        let required_property = state
-            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
-            .expect("Failed to fetch required property")
-            .expect("Required property not found");
+            .expect("Failed to build property value");
+        transaction_context.add_property_page("temperature", temperature);
 
-        let old_reporter = ReporterBuilder::new()
-            .with_public_key(PUBLIC_KEY.to_string())
-            .with_authorized(false)
-            .with_index(0)
+        let condition = ConditionBuilder::new()
+            .with_property_range("temperature".to_string(), -20, 8)
             .build()
-            .expect("Failed to build Reporter");
+            .expect("Failed to build condition");
+        add_conditional_proposal(&mut transaction_context, receiving_agent_key, condition);
 
-        let new_reporter = ReporterBuilder::new()
-            .with_public_key(receiving_agent_key.to_string())
-            .with_authorized(true)
-            .with_index(1)
-            .build()
-            .expect("Failed to build Reporter");
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
 
-        // Create record that has the receiving agent as a reporter.
-        let expected_property =
-            make_property(REQUIRED_PROPERTY_NAME, required_property_definition())
-                .into_builder()
-                .with_reporters(vec![old_reporter, new_reporter])
-                .build()
-                .expect("Failed to build property");
+        match transaction_handler._answer_proposal(
+            &payload,
+            &mut state,
+            receiving_agent_key,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Condition is not met, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Proposal condition not met: temperature was 12 at {}, outside of -20 to 8",
+                    TIMESTAMP
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
 
-        // Compare property found in state, whith what would be expected if the transaction was
-        // executed correctly
-        assert_eq!(required_property, expected_property);
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+        assert_eq!(proposal_list.proposals()[0].status(), &Status::Open);
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction, with Reponse set to Accept and Role set to Custodian,
-    /// is valid an OK is returned and the proposal is updated to have status Accepted, the custodians
-    /// in the record have been updated.
-    fn test_answer_proposal_accept_custodian_ok() {
+    /// Test that a proposal requiring a counter-signature can be accepted once an admin of the
+    /// organization has counter-signed it
+    fn test_answer_proposal_accept_counter_signed_ok() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
+        let admin_key = "admin_key";
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_agent_with_roles(admin_key, vec!["admin".to_string()]);
         transaction_context.add_record();
-        transaction_context.add_schema();
-        transaction_context.add_property(OPTIONAL_PROPERTY_NAME, optional_property_definition());
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
 
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Custodian,
-            Status::Open,
-        );
+        let condition = ConditionBuilder::new()
+            .with_condition_type(ConditionType::CounterSignature)
+            .with_org_id("test_org".to_string())
+            .build()
+            .expect("Failed to build condition");
+        add_conditional_proposal(&mut transaction_context, receiving_agent_key, condition);
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
-
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
 
-        let payload =
-            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
+        match transaction_handler._answer_proposal(
+            &payload,
+            &mut state,
+            receiving_agent_key,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Proposal not counter-signed, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(
+                    "Proposal condition not met: no counter-signature from an admin of test_org"
+                ));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let counter_sign = CounterSignProposalActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(Role::Owner)
+            .build()
+            .expect("Failed to build CounterSignProposalAction");
+        match transaction_handler._counter_sign_proposal(
+            &counter_sign,
+            &mut state,
+            receiving_agent_key,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer is not an admin, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Agent {} is not an admin of an organization that must counter-sign",
+                    receiving_agent_key
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+        assert!(transaction_handler
+            ._counter_sign_proposal(&counter_sign, &mut state, admin_key, TIMESTAMP)
+            .is_ok());
 
         assert!(transaction_handler
-            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP)
             .is_ok());
 
         let proposal_list = state
             .get_proposal_list(RECORD_ID, receiving_agent_key)
             .expect("Failed to get ProposalList from state")
             .expect("ProposalList not found");
-
-        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(proposal_list.proposals()[0].status(), &Status::Accepted);
         assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(
-                PUBLIC_KEY,
-                receiving_agent_key,
-                Role::Custodian,
-                Status::Accepted
-            )
+            proposal_list.proposals()[0].counter_signatures()[0].agent_id(),
+            admin_key
         );
 
         let record = state
             .get_record(RECORD_ID)
             .expect("Failed to fetch record")
             .expect("No record found");
-
-        let old_custodian = AssociatedAgentBuilder::new()
-            .with_agent_id(PUBLIC_KEY.to_string())
-            .with_timestamp(TIMESTAMP)
-            .build()
-            .expect("Failed to build AssociatedAgent");
-
-        let new_custodian = AssociatedAgentBuilder::new()
-            .with_agent_id(receiving_agent_key.to_string())
-            .with_timestamp(TIMESTAMP)
-            .build()
-            .expect("Failed to build AssociatedAgent");
-
-        // Create record that has receiving agent as a custodian
-        let expected_record = make_record()
-            .into_builder()
-            .with_custodians(vec![old_custodian, new_custodian])
-            .build()
-            .expect("Failed to build record");
-
-        // Compare record found in state, whith what would be expected if the transaction was
-        // executed correctly
-        assert_eq!(record, expected_record);
+        assert_eq!(
+            record.owners().last().map(|owner| owner.agent_id()),
+            Some(receiving_agent_key)
+        );
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction, with Reponse set to Accept and Role set to Reporter,
-    /// is valid an OK is returned and the proposal is updated to have status Accepted,
-    /// and the reporters for the properties have been updated.
-    fn test_answer_proposal_accept_reporter_ok() {
+    /// Test that an accepted bulk proposal transfers the custody of every record in it
+    fn test_answer_bulk_proposal_accept_ok() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
+        let other_record_id = "other_test_record";
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_agent(receiving_agent_key);
         transaction_context.add_record();
-        transaction_context.add_schema();
-        transaction_context.add_property(OPTIONAL_PROPERTY_NAME, optional_property_definition());
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Reporter,
-            Status::Open,
-        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
+        state
+            .set_record(other_record_id, make_record_with_id(other_record_id))
+            .expect("Failed to set record");
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
-
         assert!(transaction_handler
-            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
+            ._create_bulk_proposal(
+                &create_bulk_proposal_action(vec![RECORD_ID, other_record_id], receiving_agent_key),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
             .is_ok());
 
-        let proposal_list = state
-            .get_proposal_list(RECORD_ID, receiving_agent_key)
-            .expect("Failed to get ProposalList from state")
-            .expect("ProposalList not found");
-
-        assert_eq!(proposal_list.proposals().len(), 1);
-        assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(
-                PUBLIC_KEY,
+        assert!(transaction_handler
+            ._answer_bulk_proposal(
+                &answer_bulk_proposal_action(receiving_agent_key, Response::Accept),
+                &mut state,
                 receiving_agent_key,
-                Role::Reporter,
-                Status::Accepted
+                TIMESTAMP,
             )
-        );
+            .is_ok());
 
-        let required_property = state
-            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
-            .expect("Failed to fetch required property")
-            .expect("Required property not found");
+        let proposal = state
+            .get_bulk_proposal(BULK_PROPOSAL_ID, receiving_agent_key)
+            .expect("Failed to get BulkProposal from state")
+            .expect("BulkProposal not found");
+        assert_eq!(proposal.status(), &Status::Accepted);
+
+        for record_id in &[RECORD_ID, other_record_id] {
+            let record = state
+                .get_record(record_id)
+                .expect("Failed to fetch record")
+                .expect("No record found");
+            assert_eq!(record.custodians().len(), 2);
+            assert_eq!(record.custodians()[1].agent_id(), receiving_agent_key);
+            assert_eq!(record.custodians()[1].org_id(), "test_org");
+            assert_eq!(record.custodians()[1].proposal_issuing_agent(), PUBLIC_KEY);
+            assert_eq!(record.custodians()[1].bulk_proposal_id(), BULK_PROPOSAL_ID);
+        }
+    }
 
-        let optional_property = state
-            .get_property(RECORD_ID, OPTIONAL_PROPERTY_NAME)
-            .expect("Failed to fetch optional property")
-            .expect("Optional property not found");
+    #[test]
+    /// Test that a bulk proposal is canceled without transferring any record if one of its
+    /// records can no longer be transferred when it is accepted
+    fn test_answer_bulk_proposal_accept_record_final() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        let other_record_id = "other_test_record";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
 
-        let old_reporter = ReporterBuilder::new()
-            .with_public_key(PUBLIC_KEY.to_string())
-            .with_authorized(true)
-            .with_index(0)
-            .build()
-            .expect("Failed to build Reporter");
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+        state
+            .set_record(other_record_id, make_record_with_id(other_record_id))
+            .expect("Failed to set record");
 
-        let new_reporter = ReporterBuilder::new()
-            .with_public_key(receiving_agent_key.to_string())
-            .with_authorized(true)
-            .with_index(1)
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        assert!(transaction_handler
+            ._create_bulk_proposal(
+                &create_bulk_proposal_action(vec![RECORD_ID, other_record_id], receiving_agent_key),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .is_ok());
+
+        let finalized_record = make_record_with_id(other_record_id)
+            .into_builder()
+            .with_field_final(true)
             .build()
-            .expect("Failed to build Reporter");
+            .expect("Failed to build record");
+        state
+            .set_record(other_record_id, finalized_record)
+            .expect("Failed to set record");
 
-        // Create required property that has receiving agent as an reporter
-        let expected_required_property =
-            make_property(REQUIRED_PROPERTY_NAME, required_property_definition())
-                .into_builder()
-                .with_reporters(vec![old_reporter.clone(), new_reporter.clone()])
-                .build()
-                .expect("Failed to build property");
+        assert!(transaction_handler
+            ._answer_bulk_proposal(
+                &answer_bulk_proposal_action(receiving_agent_key, Response::Accept),
+                &mut state,
+                receiving_agent_key,
+                TIMESTAMP,
+            )
+            .is_ok());
 
-        // Create optional property that has receiving agent as an reporter
-        let expected_optional_property =
-            make_property(OPTIONAL_PROPERTY_NAME, optional_property_definition())
-                .into_builder()
-                .with_reporters(vec![old_reporter.clone(), new_reporter.clone()])
-                .build()
-                .expect("Failed to build property");
+        let proposal = state
+            .get_bulk_proposal(BULK_PROPOSAL_ID, receiving_agent_key)
+            .expect("Failed to get BulkProposal from state")
+            .expect("BulkProposal not found");
+        assert_eq!(proposal.status(), &Status::Canceled);
 
-        // Compare properties found in state, whith what would be expected if the transaction was
-        // executed correctly
-        assert_eq!(required_property, expected_required_property);
-        assert_eq!(optional_property, expected_optional_property);
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(record, make_record());
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction fails if the proposal does not exists.
-    fn test_answer_proposal_does_not_exist() {
+    /// Test that the CreateBulkProposalAction fails if the signer is not the custodian of every
+    /// record in it
+    fn test_create_bulk_proposal_signer_not_custodian() {
         let mut transaction_context = MockTransactionContext::default();
         let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
-
-        match transaction_handler._answer_proposal(
-            &payload,
+        match transaction_handler._create_bulk_proposal(
+            &create_bulk_proposal_action(vec![RECORD_ID], PUBLIC_KEY),
             &mut state,
             receiving_agent_key,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Proposal does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Signer is not custodian, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "No open proposals found for record {} for {}",
-                    RECORD_ID, receiving_agent_key
+                    "Must be Custodian of record {} to propose its transfer",
+                    RECORD_ID
                 )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
@@ -2565,244 +6442,297 @@ mod tests {
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction fails if the response is set to cancel and signer
-    /// is not the proposal's issuing agent
-    fn test_answer_proposal_signer_not_issuing_agent() {
+    /// Test that when the RevokeReporterAction is valid an Ok is returned and the properties
+    /// reporters are updated.
+    fn test_revoke_reporter_valid() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
+        let reporter_key = "reporter_key";
 
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Reporter,
-            Status::Open,
+        transaction_context.add_record();
+
+        transaction_context.add_property_with_reporter(
+            REQUIRED_PROPERTY_NAME,
+            reporter_key,
+            true,
+            required_property_definition(),
         );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Cancel);
+        let payload =
+            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
 
-        match transaction_handler._answer_proposal(
-            &payload,
-            &mut state,
-            receiving_agent_key,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Signer not issuing agent, InvalidTransaction should be returned"),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Only the issuing agent can cancel a proposal"));
-            }
-            Err(err) => panic!("Should have gotten invalid error but got {}", err),
-        }
+        assert!(transaction_handler
+            ._revoke_reporter(&payload, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        let required_property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch required property")
+            .expect("Required property not found");
+
+        assert_eq!(
+            required_property,
+            make_property_with_reporter(
+                REQUIRED_PROPERTY_NAME,
+                reporter_key,
+                false,
+                required_property_definition()
+            )
+        );
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction fails if the response is set to reject and signer
-    /// is not the proposal's receiving agent
-    fn test_answer_proposal_signer_not_receiving_agent_reject() {
+    /// Test that a revoked reporter that is authorized again keeps its reporter index, and that
+    /// its reporter records both authorization intervals with their grantor and revoker.
+    fn test_revoke_reporter_reauthorize_ok() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
+        let reporter_key = "reporter_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(reporter_key);
+        transaction_context.add_record();
+        transaction_context.add_schema();
+        transaction_context.add_property(OPTIONAL_PROPERTY_NAME, optional_property_definition());
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
 
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Reporter,
-            Status::Open,
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let create_payload = create_proposal_action(Role::Reporter, reporter_key);
+        let answer_payload = answer_proposal_action(Role::Reporter, reporter_key, Response::Accept);
+        let revoke_payload = revoke_reporter_action(
+            reporter_key,
+            vec![
+                OPTIONAL_PROPERTY_NAME.to_string(),
+                REQUIRED_PROPERTY_NAME.to_string(),
+            ],
         );
 
+        transaction_handler
+            ._create_proposal(&create_payload, &mut state, PUBLIC_KEY, 2)
+            .expect("Failed to create first proposal");
+        transaction_handler
+            ._answer_proposal(&answer_payload, &mut state, reporter_key, 3)
+            .expect("Failed to accept first proposal");
+        transaction_handler
+            ._revoke_reporter(&revoke_payload, &mut state, PUBLIC_KEY, 5)
+            .expect("Failed to revoke reporter");
+        transaction_handler
+            ._create_proposal(&create_payload, &mut state, PUBLIC_KEY, 7)
+            .expect("Failed to create second proposal");
+        transaction_handler
+            ._answer_proposal(&answer_payload, &mut state, reporter_key, 8)
+            .expect("Failed to accept second proposal");
+
+        let required_property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch required property")
+            .expect("Required property not found");
+
+        assert_eq!(required_property.reporters().len(), 2);
+        let reporter = &required_property.reporters()[1];
+        assert_eq!(reporter.public_key(), reporter_key);
+        assert_eq!(*reporter.index(), 1);
+        assert!(*reporter.authorized());
+
+        let authorizations = reporter.authorizations();
+        assert_eq!(authorizations.len(), 2);
+        assert_eq!(*authorizations[0].granted_at(), 3);
+        assert_eq!(authorizations[0].granted_by(), PUBLIC_KEY);
+        assert_eq!(*authorizations[0].revoked_at(), 5);
+        assert_eq!(authorizations[0].revoked_by(), PUBLIC_KEY);
+        assert_eq!(*authorizations[1].granted_at(), 8);
+        assert_eq!(*authorizations[1].revoked_at(), 0);
+
+        assert!(reporter.authorized_at(4));
+        assert!(!reporter.authorized_at(6));
+        assert!(reporter.authorized_at(9));
+    }
+
+    #[test]
+    /// Test that when the RevokeReporterAction fails if the record does not exist
+    fn test_revoke_reporter_record_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        let reporter_key = "reporter_key";
+
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Reject);
+        let payload =
+            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
 
-        match transaction_handler._answer_proposal(&payload, &mut state, PUBLIC_KEY, TIMESTAMP) {
-            Ok(()) => panic!("Signer not issuing agent, InvalidTransaction should be returned"),
+        match transaction_handler._revoke_reporter(
+            &payload,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record does not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Only the receiving agent can reject a proposal"));
+                assert!(err.contains(&format!("Record does not exists: {}", RECORD_ID)));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction fails if the response is set to accept and signer
-    /// is not the proposal's receiving agent
-    fn test_answer_proposal_signer_not_receiving_agent_accept() {
+    /// Test that when the RevokeReporterAction fails if the signer is not the record's owner
+    fn test_revoke_reporter_signer_not_owner() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Reporter,
-            Status::Open,
-        );
+        let reporter_key = "reporter_key";
+        transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
+        let payload =
+            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
 
-        match transaction_handler._answer_proposal(&payload, &mut state, PUBLIC_KEY, TIMESTAMP) {
-            Ok(()) => panic!("Signer not issuing agent, InvalidTransaction should be returned"),
+        match transaction_handler._revoke_reporter(
+            &payload,
+            &mut state,
+            "not_owner_key",
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer not owner, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Only the receiving agent can accept a proposal"));
+                assert!(err.contains("Must be owner to revoke reporters"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction fails if the response is set to accept and the
-    /// record does not exist
-    fn test_answer_proposal_record_does_not_exist() {
+    /// Test that when the RevokeReporterAction fails if the record is final
+    fn test_revoke_reporter_record_is_final() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Reporter,
-            Status::Open,
-        );
+        let reporter_key = "reporter_key";
+        transaction_context.add_finalized_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
+        let payload =
+            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
 
-        match transaction_handler._answer_proposal(
+        match transaction_handler._revoke_reporter(
             &payload,
             &mut state,
-            receiving_agent_key,
+            PUBLIC_KEY,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Signer not issuing agent, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Record is final, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record in proposal does not exist: {}", RECORD_ID)));
+                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction is Ok if the response is set to accept and the
-    /// role is set to owner, but the proposal's issuing agent is not the owner of the record.
-    /// The status of the proposal should be set to Canceled.
-    fn test_answer_proposal_issuing_agent_not_owner() {
+    /// Test that when the RevokeReporterAction fails if one of the payload's properties do not
+    /// exist
+    fn test_revoke_reporter_property_does_not_exist() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-        let issuing_agent_key = "issuing_agent_key";
-
-        transaction_context.add_proposal(
-            issuing_agent_key,
-            receiving_agent_key,
-            Role::Owner,
-            Status::Open,
-        );
+        let reporter_key = "reporter_key";
         transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
-
-        assert!(transaction_handler
-            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
-            .is_ok());
-
-        let proposal_list = state
-            .get_proposal_list(RECORD_ID, receiving_agent_key)
-            .expect("Failed to get ProposalList from state")
-            .expect("ProposalList not found");
+        let payload =
+            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
 
-        assert_eq!(proposal_list.proposals().len(), 1);
-        assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(
-                issuing_agent_key,
-                receiving_agent_key,
-                Role::Owner,
-                Status::Canceled
-            )
-        );
+        match transaction_handler._revoke_reporter(
+            &payload,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Property does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Property does not exist: {}",
+                    REQUIRED_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction fails if the response is set to accept and the
-    /// role is set to owner, but the record's schema does not exist.
-    fn test_answer_proposal_schema_does_not_exist() {
+    /// Test that when the RevokeReporterAction fails if the reporter is already
+    /// unauthorized
+    fn test_revoke_reporter_already_unauthorized() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Owner,
-            Status::Open,
-        );
+        let reporter_key = "reporter_key";
         transaction_context.add_record();
+        transaction_context.add_property_with_reporter(
+            REQUIRED_PROPERTY_NAME,
+            reporter_key,
+            false,
+            required_property_definition(),
+        );
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
+        let payload =
+            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
 
-        match transaction_handler._answer_proposal(
+        match transaction_handler._revoke_reporter(
             &payload,
             &mut state,
-            receiving_agent_key,
+            PUBLIC_KEY,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Schema does not exist, InvalidTransaction should be returned"),
+            Ok(()) => {
+                panic!("Reporter already unauthorized, InvalidTransaction should be returned")
+            }
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Schema does not exist: {}", SCHEMA_NAME)));
+                assert!(err.contains("Reporter is already unauthorized."));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction fails if the response is set to accept and the
-    /// role is set to owner, schema's properties does not exist.
-    fn test_answer_proposal_schema_property_does_not_exist() {
+    /// Test that when the RevokeReporterAction fails if the key is not a valid reporter for the
+    /// property
+    fn test_revoke_reporter_not_valid() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Owner,
-            Status::Open,
-        );
+        let reporter_key = "reporter_key";
         transaction_context.add_record();
-        transaction_context.add_schema();
         transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-        let payload = answer_proposal_action(Role::Owner, receiving_agent_key, Response::Accept);
+        let payload =
+            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
 
-        match transaction_handler._answer_proposal(
+        match transaction_handler._revoke_reporter(
             &payload,
             &mut state,
-            receiving_agent_key,
+            PUBLIC_KEY,
             TIMESTAMP,
         ) {
-            Ok(()) => panic!("Property does not exist, InvalidTransaction should be returned"),
+            Ok(()) => {
+                panic!("Reporter already unauthorized, InvalidTransaction should be returned")
+            }
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "Property does not exist: {}",
-                    OPTIONAL_PROPERTY_NAME
+                    "{} not a reporter for property {}",
+                    reporter_key, REQUIRED_PROPERTY_NAME
                 )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
@@ -2810,332 +6740,439 @@ mod tests {
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction is Ok if the response is set to accept and the
-    /// role is set to custodian, but the proposal's issuing agent is not the custodian of the
-    /// record. The status of the proposal should be set to Canceled.
-    fn test_answer_proposal_issuing_agent_not_custodian() {
+    /// Test that splitting a record creates the children linked to the parent and that a
+    /// consumed parent is finalized
+    fn test_split_record_valid() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-        let issuing_agent_key = "issuing_agent_key";
-
-        transaction_context.add_proposal(
-            issuing_agent_key,
-            receiving_agent_key,
-            Role::Custodian,
-            Status::Open,
-        );
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_record();
-
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let action = SplitRecordActionBuilder::new()
+            .with_parent(lineage_parent(RECORD_ID, true))
+            .with_children(vec![
+                child_record_action("child_record_a"),
+                child_record_action("child_record_b"),
+            ])
+            .build()
+            .expect("Failed to build SplitRecordAction");
+
+        transaction_handler
+            ._split_record(&action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to split record");
+
+        for child_id in &["child_record_a", "child_record_b"] {
+            let child = state
+                .get_record(child_id)
+                .expect("Failed to fetch record")
+                .expect("No record found");
+            assert_eq!(child.parents(), [RECORD_ID]);
+        }
 
-        let payload =
-            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
-
-        assert!(transaction_handler
-            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
-            .is_ok());
-
-        let proposal_list = state
-            .get_proposal_list(RECORD_ID, receiving_agent_key)
-            .expect("Failed to get ProposalList from state")
-            .expect("ProposalList not found");
-
-        assert_eq!(proposal_list.proposals().len(), 1);
-        assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(
-                issuing_agent_key,
-                receiving_agent_key,
-                Role::Custodian,
-                Status::Canceled
-            )
-        );
+        let parent = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(parent.children(), ["child_record_a", "child_record_b"]);
+        assert!(*parent.field_final());
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction is Ok if the response is set to accept and the
-    /// role is set to reporter, but the proposal's issuing agent is not the owner of the record.
-    /// The status of the proposal should be set to Canceled.
-    fn test_answer_proposal_issuing_agent_not_owner_reporter() {
+    /// Test that merging records links the child to every parent and that parents which are
+    /// not consumed stay open
+    fn test_merge_records_partially_consumed() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-        let issuing_agent_key = "issuing_agent_key";
-
-        transaction_context.add_proposal(
-            issuing_agent_key,
-            receiving_agent_key,
-            Role::Reporter,
-            Status::Open,
-        );
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_record();
-
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        transaction_handler
+            ._create_record(
+                &child_record_action("other_record"),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to create record");
 
-        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
+        let action = MergeRecordsActionBuilder::new()
+            .with_parents(vec![
+                lineage_parent(RECORD_ID, false),
+                lineage_parent("other_record", true),
+            ])
+            .with_child(child_record_action("merged_record"))
+            .build()
+            .expect("Failed to build MergeRecordsAction");
 
-        assert!(transaction_handler
-            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP,)
-            .is_ok());
+        transaction_handler
+            ._merge_records(&action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to merge records");
 
-        let proposal_list = state
-            .get_proposal_list(RECORD_ID, receiving_agent_key)
-            .expect("Failed to get ProposalList from state")
-            .expect("ProposalList not found");
+        let child = state
+            .get_record("merged_record")
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(child.parents(), [RECORD_ID, "other_record"]);
 
-        assert_eq!(proposal_list.proposals().len(), 1);
-        assert_eq!(
-            proposal_list.proposals()[0],
-            make_proposal(
-                issuing_agent_key,
-                receiving_agent_key,
-                Role::Reporter,
-                Status::Canceled
-            )
-        );
+        let parent = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(parent.children(), ["merged_record"]);
+        assert!(!*parent.field_final());
+
+        let other_parent = state
+            .get_record("other_record")
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(other_parent.children(), ["merged_record"]);
+        assert!(*other_parent.field_final());
     }
 
     #[test]
-    /// Test that when the AnswerProposalAction fails if the response is set to accept and the
-    /// role is set to reporter, but one of the proposal's properties does not exist.
-    fn test_answer_proposal_property_does_not_exist() {
+    /// Test that a finalized record can not be transformed
+    fn test_transform_record_parent_final() {
         let mut transaction_context = MockTransactionContext::default();
-        let receiving_agent_key = "receiving_agent_key";
-
-        transaction_context.add_proposal(
-            PUBLIC_KEY,
-            receiving_agent_key,
-            Role::Reporter,
-            Status::Open,
-        );
-        transaction_context.add_record();
         transaction_context.add_schema();
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_finalized_record();
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let action = TransformRecordActionBuilder::new()
+            .with_parent(lineage_parent(RECORD_ID, true))
+            .with_child(child_record_action("child_record_a"))
+            .build()
+            .expect("Failed to build TransformRecordAction");
 
-        let payload = answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept);
-
-        match transaction_handler._answer_proposal(
-            &payload,
-            &mut state,
-            receiving_agent_key,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Schema does not exist, InvalidTransaction should be returned"),
+        match transaction_handler._transform_record(&action, &mut state, PUBLIC_KEY, TIMESTAMP) {
+            Ok(()) => panic!("Parent record is final, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!(
-                    "Property does not exist: {}",
-                    OPTIONAL_PROPERTY_NAME
-                )));
+                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that when the RevokeReporterAction is valid an Ok is returned and the properties
-    /// reporters are updated.
-    fn test_revoke_reporter_valid() {
+    /// Test that only the owner and custodian of the parent can split it
+    fn test_split_record_signer_not_owner() {
         let mut transaction_context = MockTransactionContext::default();
-        let reporter_key = "reporter_key";
-
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent("other_agent");
         transaction_context.add_record();
-
-        transaction_context.add_property_with_reporter(
-            REQUIRED_PROPERTY_NAME,
-            reporter_key,
-            true,
-            required_property_definition(),
-        );
-
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let action = SplitRecordActionBuilder::new()
+            .with_parent(lineage_parent(RECORD_ID, true))
+            .with_children(vec![
+                child_record_action("child_record_a"),
+                child_record_action("child_record_b"),
+            ])
+            .build()
+            .expect("Failed to build SplitRecordAction");
 
-        let payload =
-            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
-
-        assert!(transaction_handler
-            ._revoke_reporter(&payload, &mut state, PUBLIC_KEY,)
-            .is_ok());
-
-        let required_property = state
-            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
-            .expect("Failed to fetch required property")
-            .expect("Required property not found");
-
-        assert_eq!(
-            required_property,
-            make_property_with_reporter(
-                REQUIRED_PROPERTY_NAME,
-                reporter_key,
-                false,
-                required_property_definition()
-            )
-        );
+        match transaction_handler._split_record(&action, &mut state, "other_agent", TIMESTAMP) {
+            Ok(()) => panic!("Signer is not the owner, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Must be owner and custodian of parent record {}",
+                    RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+        assert!(state
+            .get_record("child_record_a")
+            .expect("Failed to fetch record")
+            .is_none());
     }
 
     #[test]
-    /// Test that when the RevokeReporterAction fails if the record does not exist
-    fn test_revoke_reporter_record_does_not_exist() {
+    /// Test that the owner of a record can recall it and that its custodian can acknowledge
+    /// the recall
+    fn test_recall_record_acknowledged() {
         let mut transaction_context = MockTransactionContext::default();
-        let reporter_key = "reporter_key";
-
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        transaction_handler
+            ._create_recall(&create_recall_action(), &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to create recall");
 
-        let payload =
-            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
-
-        match transaction_handler._revoke_reporter(&payload, &mut state, PUBLIC_KEY) {
-            Ok(()) => panic!("Record does not exist, InvalidTransaction should be returned"),
+        let acknowledge_action = AcknowledgeRecallActionBuilder::new()
+            .with_recall_id(RECALL_ID.to_string())
+            .with_record_id(RECORD_ID.to_string())
+            .build()
+            .expect("Failed to build AcknowledgeRecallAction");
+        transaction_handler
+            ._acknowledge_recall(&acknowledge_action, &mut state, PUBLIC_KEY, TIMESTAMP + 1)
+            .expect("Failed to acknowledge recall");
+
+        let recall = state
+            .get_recall(RECALL_ID)
+            .expect("Failed to fetch recall")
+            .expect("No recall found");
+        assert_eq!(recall.record_ids(), [RECORD_ID]);
+        assert_eq!(*recall.status(), RecallStatus::Open);
+        assert_eq!(recall.acknowledgements().len(), 1);
+        assert_eq!(recall.acknowledgements()[0].agent_id(), PUBLIC_KEY);
+        assert_eq!(*recall.acknowledgements()[0].timestamp(), TIMESTAMP + 1);
+
+        match transaction_handler._acknowledge_recall(
+            &acknowledge_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP + 2,
+        ) {
+            Ok(()) => panic!("Recall already acknowledged, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record does not exists: {}", RECORD_ID)));
+                assert!(err.contains(&format!(
+                    "Recall of record {} already acknowledged",
+                    RECORD_ID
+                )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that when the RevokeReporterAction fails if the signer is not the record's owner
-    fn test_revoke_reporter_signer_not_owner() {
+    /// Test that only the current custodian of a recalled record can acknowledge the recall
+    fn test_acknowledge_recall_not_custodian() {
         let mut transaction_context = MockTransactionContext::default();
-        let reporter_key = "reporter_key";
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent("other_agent");
         transaction_context.add_record();
-
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        transaction_handler
+            ._create_recall(&create_recall_action(), &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to create recall");
 
-        let payload =
-            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
-
-        match transaction_handler._revoke_reporter(&payload, &mut state, "not_owner_key") {
-            Ok(()) => panic!("Signer not owner, InvalidTransaction should be returned"),
+        let acknowledge_action = AcknowledgeRecallActionBuilder::new()
+            .with_recall_id(RECALL_ID.to_string())
+            .with_record_id(RECORD_ID.to_string())
+            .build()
+            .expect("Failed to build AcknowledgeRecallAction");
+        match transaction_handler._acknowledge_recall(
+            &acknowledge_action,
+            &mut state,
+            "other_agent",
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer is not the custodian, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Must be owner to revoke reporters"));
+                assert!(err.contains(&format!(
+                    "Must be custodian of record {} to acknowledge recall",
+                    RECORD_ID
+                )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that when the RevokeReporterAction fails if the record is final
-    fn test_revoke_reporter_record_is_final() {
+    /// Test that a record can only be recalled by its owner
+    fn test_create_recall_not_owner() {
         let mut transaction_context = MockTransactionContext::default();
-        let reporter_key = "reporter_key";
-        transaction_context.add_finalized_record();
-
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent("other_agent");
+        transaction_context.add_record();
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-
-        let payload =
-            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
-
-        match transaction_handler._revoke_reporter(&payload, &mut state, PUBLIC_KEY) {
-            Ok(()) => panic!("Record is final, InvalidTransaction should be returned"),
+        match transaction_handler._create_recall(
+            &create_recall_action(),
+            &mut state,
+            "other_agent",
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer is not the owner, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+                assert!(err.contains(&format!(
+                    "Must be owner of record {} to recall it",
+                    RECORD_ID
+                )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that when the RevokeReporterAction fails if one of the payload's properties do not
-    /// exist
-    fn test_revoke_reporter_property_does_not_exist() {
+    /// Test that owning the recalled product does not allow recalling records owned by other
+    /// agents
+    fn test_create_product_recall_not_record_owner() {
         let mut transaction_context = MockTransactionContext::default();
-        let reporter_key = "reporter_key";
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent("other_agent");
+        transaction_context.add_product("test_product");
         transaction_context.add_record();
-
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
-        let transaction_handler = TrackAndTraceTransactionHandler::new();
-
-        let payload =
-            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
+        let create_recall_action = CreateRecallActionBuilder::new()
+            .with_recall_id(RECALL_ID.to_string())
+            .with_product_id("test_product".to_string())
+            .with_record_ids(vec![RECORD_ID.to_string()])
+            .with_reason("contaminated".to_string())
+            .with_severity(Severity::High)
+            .build()
+            .expect("Failed to build CreateRecallAction");
 
-        match transaction_handler._revoke_reporter(&payload, &mut state, PUBLIC_KEY) {
-            Ok(()) => panic!("Property does not exist, InvalidTransaction should be returned"),
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        match transaction_handler._create_recall(
+            &create_recall_action,
+            &mut state,
+            "other_agent",
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer is not the owner, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "Property does not exist: {}",
-                    REQUIRED_PROPERTY_NAME
+                    "Must be owner of record {} to recall it",
+                    RECORD_ID
                 )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
+        assert!(state
+            .get_recall(RECALL_ID)
+            .expect("Failed to fetch recall")
+            .is_none());
+
+        transaction_handler
+            ._create_recall(&create_recall_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to create recall");
     }
 
     #[test]
-    /// Test that when the RevokeReporterAction fails if the reporter is already
-    /// unauthorized
-    fn test_revoke_reporter_already_unauthorized() {
+    /// Test that a recall of a product covers the records that reference the product, even
+    /// though they are not listed in the recall
+    fn test_acknowledge_product_recall_referencing_record() {
+        let product_property_definition = PropertyDefinitionBuilder::new()
+            .with_name("product".to_string())
+            .with_data_type(DataType::Reference)
+            .with_reference_kind(ReferenceKind::Product)
+            .build()
+            .expect("Failed to build property definition");
         let mut transaction_context = MockTransactionContext::default();
-        let reporter_key = "reporter_key";
+        transaction_context.add_schema_with_property(product_property_definition.clone());
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_product("test_product");
         transaction_context.add_record();
-        transaction_context.add_property_with_reporter(
-            REQUIRED_PROPERTY_NAME,
-            reporter_key,
-            false,
-            required_property_definition(),
-        );
-
+        transaction_context.add_property("product", product_property_definition);
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
-        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_recall_action = CreateRecallActionBuilder::new()
+            .with_recall_id(RECALL_ID.to_string())
+            .with_product_id("test_product".to_string())
+            .with_reason("contaminated".to_string())
+            .with_severity(Severity::High)
+            .build()
+            .expect("Failed to build CreateRecallAction");
 
-        let payload =
-            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        transaction_handler
+            ._create_recall(&create_recall_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to create recall");
 
-        match transaction_handler._revoke_reporter(&payload, &mut state, PUBLIC_KEY) {
-            Ok(()) => {
-                panic!("Reporter already unauthorized, InvalidTransaction should be returned")
-            }
+        let acknowledge_action = AcknowledgeRecallActionBuilder::new()
+            .with_recall_id(RECALL_ID.to_string())
+            .with_record_id(RECORD_ID.to_string())
+            .build()
+            .expect("Failed to build AcknowledgeRecallAction");
+        match transaction_handler._acknowledge_recall(
+            &acknowledge_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP + 1,
+        ) {
+            Ok(()) => panic!("Product not referenced, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Reporter is already unauthorized."));
+                assert!(err.contains(&format!(
+                    "Record {} is not part of recall {}",
+                    RECORD_ID, RECALL_ID
+                )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
+
+        let reference = PropertyValueBuilder::new()
+            .with_name("product".to_string())
+            .with_data_type(DataType::Reference)
+            .with_reference_value("test_product".to_string())
+            .build()
+            .expect("Failed to build property value");
+        transaction_handler
+            ._update_properties(
+                &update_property_action(vec![reference]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP + 1,
+            )
+            .expect("Failed to update properties");
+
+        transaction_handler
+            ._acknowledge_recall(&acknowledge_action, &mut state, PUBLIC_KEY, TIMESTAMP + 2)
+            .expect("Failed to acknowledge recall");
+        let recall = state
+            .get_recall(RECALL_ID)
+            .expect("Failed to fetch recall")
+            .expect("No recall found");
+        assert!(recall.record_ids().is_empty());
+        assert_eq!(recall.acknowledgements().len(), 1);
+        assert_eq!(recall.acknowledgements()[0].record_id(), RECORD_ID);
     }
 
     #[test]
-    /// Test that when the RevokeReporterAction fails if the key is not a valid reporter for the
-    /// property
-    fn test_revoke_reporter_not_valid() {
+    /// Test that a recall can only be closed by the agent that issued it
+    fn test_close_recall_not_issuing_agent() {
         let mut transaction_context = MockTransactionContext::default();
-        let reporter_key = "reporter_key";
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_record();
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
+        transaction_handler
+            ._create_recall(&create_recall_action(), &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to create recall");
 
-        let payload =
-            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
-
-        match transaction_handler._revoke_reporter(&payload, &mut state, PUBLIC_KEY) {
-            Ok(()) => {
-                panic!("Reporter already unauthorized, InvalidTransaction should be returned")
-            }
+        let close_action = CloseRecallActionBuilder::new()
+            .with_recall_id(RECALL_ID.to_string())
+            .build()
+            .expect("Failed to build CloseRecallAction");
+        match transaction_handler._close_recall(&close_action, &mut state, "other_agent") {
+            Ok(()) => panic!("Signer is not the issuer, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "{} not a reporter for property {}",
-                    reporter_key, REQUIRED_PROPERTY_NAME
+                    "Only the issuing agent can close recall {}",
+                    RECALL_ID
                 )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
+
+        transaction_handler
+            ._close_recall(&close_action, &mut state, PUBLIC_KEY)
+            .expect("Failed to close recall");
+        let recall = state
+            .get_recall(RECALL_ID)
+            .expect("Failed to fetch recall")
+            .expect("No recall found");
+        assert_eq!(*recall.status(), RecallStatus::Closed);
     }
 
     fn optional_property_value() -> PropertyValue {
@@ -3165,6 +7202,33 @@ mod tests {
             .expect("Failed to build CreateRecordAction")
     }
 
+    fn child_record_action(record_id: &str) -> CreateRecordAction {
+        CreateRecordActionBuilder::new()
+            .with_record_id(record_id.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_properties(vec![required_property_value()])
+            .build()
+            .expect("Failed to build CreateRecordAction")
+    }
+
+    fn lineage_parent(record_id: &str, consume: bool) -> LineageParent {
+        LineageParentBuilder::new()
+            .with_record_id(record_id.to_string())
+            .with_consume(consume)
+            .build()
+            .expect("Failed to build LineageParent")
+    }
+
+    fn create_recall_action() -> CreateRecallAction {
+        CreateRecallActionBuilder::new()
+            .with_recall_id(RECALL_ID.to_string())
+            .with_record_ids(vec![RECORD_ID.to_string()])
+            .with_reason("contaminated".to_string())
+            .with_severity(Severity::High)
+            .build()
+            .expect("Failed to build CreateRecallAction")
+    }
+
     fn create_finalize_record() -> FinalizeRecordAction {
         FinalizeRecordActionBuilder::new()
             .with_record_id(RECORD_ID.to_string())
@@ -3189,6 +7253,31 @@ mod tests {
             .expect("Failed to build UpdatePropertiesAction")
     }
 
+    fn ingest_readings_action(readings: Vec<(u64, i64)>) -> IngestReadingsAction {
+        let readings = readings
+            .into_iter()
+            .map(|(timestamp, number)| {
+                SensorReadingBuilder::new()
+                    .with_timestamp(timestamp)
+                    .with_value(
+                        PropertyValueBuilder::new()
+                            .with_name("temperature".to_string())
+                            .with_data_type(DataType::Number)
+                            .with_number_value(number)
+                            .build()
+                            .expect("Failed to build property value"),
+                    )
+                    .build()
+                    .expect("Failed to build SensorReading")
+            })
+            .collect();
+        IngestReadingsActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_readings(readings)
+            .build()
+            .expect("Failed to build IngestReadingsAction")
+    }
+
     fn create_proposal_action(role: Role, receiving_agent_key: &str) -> CreateProposalAction {
         CreateProposalActionBuilder::new()
             .with_record_id(RECORD_ID.to_string())
@@ -3231,6 +7320,31 @@ mod tests {
             .expect("Failed to build AnswerProposalAction")
     }
 
+    fn create_bulk_proposal_action(
+        record_ids: Vec<&str>,
+        receiving_agent_key: &str,
+    ) -> CreateBulkProposalAction {
+        CreateBulkProposalActionBuilder::new()
+            .with_proposal_id(BULK_PROPOSAL_ID.to_string())
+            .with_record_ids(record_ids.into_iter().map(String::from).collect())
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(Role::Custodian)
+            .build()
+            .expect("Failed to build CreateBulkProposalAction")
+    }
+
+    fn answer_bulk_proposal_action(
+        receiving_agent_key: &str,
+        response: Response,
+    ) -> AnswerBulkProposalAction {
+        AnswerBulkProposalActionBuilder::new()
+            .with_proposal_id(BULK_PROPOSAL_ID.to_string())
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_response(response)
+            .build()
+            .expect("Failed to build AnswerBulkProposalAction")
+    }
+
     fn revoke_reporter_action(reporter_id: &str, properties: Vec<String>) -> RevokeReporterAction {
         RevokeReporterActionBuilder::new()
             .with_record_id(RECORD_ID.to_string())
@@ -3268,6 +7382,7 @@ mod tests {
         let associated_agent = AssociatedAgentBuilder::new()
             .with_agent_id(PUBLIC_KEY.to_string())
             .with_timestamp(TIMESTAMP)
+            .with_org_id("test_org".to_string())
             .build()
             .expect("Failed to build AssociatedAgent");
 
@@ -3277,15 +7392,25 @@ mod tests {
             .with_owners(vec![associated_agent.clone()])
             .with_custodians(vec![associated_agent.clone()])
             .with_field_final(false)
+            .with_schema_version(1)
             .build()
             .expect("Failed to build new_record")
     }
 
+    fn make_record_with_id(record_id: &str) -> Record {
+        make_record()
+            .into_builder()
+            .with_record_id(record_id.to_string())
+            .build()
+            .expect("Failed to build record")
+    }
+
     fn make_property(property_name: &str, property_definition: PropertyDefinition) -> Property {
         let reporter = ReporterBuilder::new()
             .with_public_key(PUBLIC_KEY.to_string())
             .with_authorized(true)
             .with_index(0)
+            .with_authorizations(vec![make_reporter_authorization(PUBLIC_KEY)])
             .build()
             .expect("Failed to build Reporter");
 
@@ -3300,6 +7425,14 @@ mod tests {
             .expect("Failed to build property")
     }
 
+    fn make_reporter_authorization(granted_by: &str) -> ReporterAuthorization {
+        ReporterAuthorizationBuilder::new()
+            .with_granted_at(TIMESTAMP)
+            .with_granted_by(granted_by.to_string())
+            .build()
+            .expect("Failed to build ReporterAuthorization")
+    }
+
     fn make_property_with_reporter(
         property_name: &str,
         reporter_key: &str,
@@ -3340,6 +7473,26 @@ mod tests {
             .expect("Failed to build PropertyPage")
     }
 
+    fn add_conditional_proposal(
+        transaction_context: &mut MockTransactionContext,
+        receiving_agent_key: &str,
+        condition: Condition,
+    ) {
+        let proposal = make_proposal(PUBLIC_KEY, receiving_agent_key, Role::Owner, Status::Open)
+            .into_builder()
+            .with_conditions(vec![condition])
+            .build()
+            .expect("Failed to build proposal");
+        let proposal_list = ProposalListBuilder::new()
+            .with_proposals(vec![proposal])
+            .build()
+            .expect("Failed to build proposal list");
+        let mut state = TrackAndTraceState::new(transaction_context);
+        state
+            .set_proposal_list(RECORD_ID, receiving_agent_key, proposal_list)
+            .expect("Failed to set proposal list");
+    }
+
     fn make_proposal(
         issuing_agent: &str,
         receiving_agent_key: &str,
@@ -21,13 +21,35 @@ cfg_if! {
 }
 
 use dgc_config::protocol::track_and_trace::payload::{
-    Action, CreateRecordAction, TrackAndTracePayload,
+    AcknowledgeRecallAction, Action, AnswerBulkProposalAction, CreateBulkProposalAction,
+    CreateProposalAction, CreateRecallAction, CreateRecordAction, IngestReadingsAction,
+    LineageParent, MergeRecordsAction, SplitRecordAction, TrackAndTracePayload,
+    TransformRecordAction,
 };
+use dgc_config::protocol::track_and_trace::state::ConditionType;
 
 pub fn validate_payload(payload: &TrackAndTracePayload) -> Result<(), ApplyError> {
     validate_timestamp(*payload.timestamp())?;
     match payload.action() {
         Action::CreateRecord(action_payload) => validate_record_create_action(action_payload),
+        Action::CreateProposal(action_payload) => validate_create_proposal_action(action_payload),
+        Action::SplitRecord(action_payload) => validate_split_record_action(action_payload),
+        Action::MergeRecords(action_payload) => validate_merge_records_action(action_payload),
+        Action::TransformRecord(action_payload) => {
+            validate_transform_record_action(action_payload)
+        }
+        Action::CreateRecall(action_payload) => validate_create_recall_action(action_payload),
+        Action::AcknowledgeRecall(action_payload) => {
+            validate_acknowledge_recall_action(action_payload)
+        }
+        Action::CloseRecall(action_payload) => validate_recall_id(action_payload.recall_id()),
+        Action::IngestReadings(action_payload) => validate_ingest_readings_action(action_payload),
+        Action::CreateBulkProposal(action_payload) => {
+            validate_create_bulk_proposal_action(action_payload)
+        }
+        Action::AnswerBulkProposal(action_payload) => {
+            validate_answer_bulk_proposal_action(action_payload)
+        }
         _ => Ok(()),
     }
 }
@@ -49,6 +71,181 @@ fn validate_record_create_action(
     Ok(())
 }
 
+fn validate_create_proposal_action(
+    create_proposal_action: &CreateProposalAction,
+) -> Result<(), ApplyError> {
+    for condition in create_proposal_action.conditions() {
+        if condition.condition_type() == &ConditionType::PropertyRange
+            && condition.min() > condition.max()
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Condition on property {} has a min greater than its max",
+                condition.property_name()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_bulk_proposal_id(proposal_id: &str, receiving_agent: &str) -> Result<(), ApplyError> {
+    if proposal_id == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Bulk proposal id cannot be empty string",
+        )));
+    }
+    if receiving_agent == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Receiving agent cannot be empty string",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_create_bulk_proposal_action(
+    create_bulk_proposal_action: &CreateBulkProposalAction,
+) -> Result<(), ApplyError> {
+    validate_bulk_proposal_id(
+        create_bulk_proposal_action.proposal_id(),
+        create_bulk_proposal_action.receiving_agent(),
+    )?;
+    let record_ids = create_bulk_proposal_action.record_ids();
+    if record_ids.is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "A bulk proposal must transfer at least one record",
+        )));
+    }
+    for (i, record_id) in record_ids.iter().enumerate() {
+        if record_id == "" {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Record id cannot be empty string",
+            )));
+        }
+        if record_ids[..i].contains(record_id) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record {} is listed more than once",
+                record_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_answer_bulk_proposal_action(
+    answer_bulk_proposal_action: &AnswerBulkProposalAction,
+) -> Result<(), ApplyError> {
+    validate_bulk_proposal_id(
+        answer_bulk_proposal_action.proposal_id(),
+        answer_bulk_proposal_action.receiving_agent(),
+    )
+}
+
+fn validate_lineage_parent(parent: &LineageParent) -> Result<(), ApplyError> {
+    if parent.record_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Parent record id cannot be empty string",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_split_record_action(
+    split_record_action: &SplitRecordAction,
+) -> Result<(), ApplyError> {
+    validate_lineage_parent(split_record_action.parent())?;
+    if split_record_action.children().len() < 2 {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "A record must be split into at least two records",
+        )));
+    }
+    for child in split_record_action.children() {
+        validate_record_create_action(child)?;
+    }
+    Ok(())
+}
+
+fn validate_merge_records_action(
+    merge_records_action: &MergeRecordsAction,
+) -> Result<(), ApplyError> {
+    if merge_records_action.parents().len() < 2 {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "At least two records must be merged",
+        )));
+    }
+    for parent in merge_records_action.parents() {
+        validate_lineage_parent(parent)?;
+    }
+    validate_record_create_action(merge_records_action.child())
+}
+
+fn validate_transform_record_action(
+    transform_record_action: &TransformRecordAction,
+) -> Result<(), ApplyError> {
+    validate_lineage_parent(transform_record_action.parent())?;
+    validate_record_create_action(transform_record_action.child())
+}
+
+fn validate_recall_id(recall_id: &str) -> Result<(), ApplyError> {
+    if recall_id == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Recall id cannot be empty string",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_create_recall_action(
+    create_recall_action: &CreateRecallAction,
+) -> Result<(), ApplyError> {
+    validate_recall_id(create_recall_action.recall_id())?;
+    if create_recall_action.product_id() == "" && create_recall_action.record_ids().is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "A recall must name a product or at least one record",
+        )));
+    }
+    if create_recall_action.reason() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Recall reason cannot be empty string",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_acknowledge_recall_action(
+    acknowledge_recall_action: &AcknowledgeRecallAction,
+) -> Result<(), ApplyError> {
+    validate_recall_id(acknowledge_recall_action.recall_id())?;
+    if acknowledge_recall_action.record_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Record id cannot be empty string",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_ingest_readings_action(
+    ingest_readings_action: &IngestReadingsAction,
+) -> Result<(), ApplyError> {
+    if ingest_readings_action.record_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Record id cannot be empty string",
+        )));
+    }
+    if ingest_readings_action.readings().is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "At least one reading must be ingested",
+        )));
+    }
+    for reading in ingest_readings_action.readings() {
+        if *reading.timestamp() == 0 {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Reading of property {} has no timestamp",
+                reading.value().name()
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn validate_timestamp(timestamp: u64) -> Result<(), ApplyError> {
     match timestamp {
         0 => Err(ApplyError::InvalidTransaction(String::from(
@@ -63,7 +260,10 @@ mod tests {
     use super::*;
 
     use dgc_config::protos::track_and_trace_payload::{
-        CreateRecordAction as CreateRecordActionProto,
+        AnswerBulkProposalAction as AnswerBulkProposalActionProto,
+        CreateBulkProposalAction as CreateBulkProposalActionProto,
+        CreateRecordAction as CreateRecordActionProto, LineageParent as LineageParentProto,
+        SplitRecordAction as SplitRecordActionProto,
         TrackAndTracePayload as TrackAndTracePayloadProto,
         TrackAndTracePayload_Action as ActionProto,
     };
@@ -169,4 +369,89 @@ mod tests {
             "Payload should be valid"
         );
     }
+
+    #[test]
+    /// Test that an error is returned if a SplitRecordAction has fewer than two children.
+    fn test_validate_payload_split_single_child() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::SPLIT_RECORD);
+        payload_proto.set_timestamp(2);
+        let mut parent = LineageParentProto::new();
+        parent.set_record_id("my_record".to_string());
+        let mut child = CreateRecordActionProto::new();
+        child.set_record_id("my_record_a".to_string());
+        child.set_schema("my_schema".to_string());
+        let mut action = SplitRecordActionProto::new();
+        action.set_parent(parent);
+        action.mut_children().push(child);
+        payload_proto.set_split_record(action);
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Split into a single record, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("A record must be split into at least two records")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if a CreateBulkProposalAction transfers no records or
+    /// lists a record more than once.
+    fn test_validate_payload_create_bulk_proposal_records() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::CREATE_BULK_PROPOSAL);
+        payload_proto.set_timestamp(2);
+        let mut action = CreateBulkProposalActionProto::new();
+        action.set_proposal_id("my_proposal".to_string());
+        action.set_receiving_agent("receiving_agent".to_string());
+        payload_proto.set_create_bulk_proposal(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Bulk proposal without records, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("A bulk proposal must transfer at least one record")),
+        }
+
+        action.mut_record_ids().push("my_record".to_string());
+        action.mut_record_ids().push("my_record".to_string());
+        payload_proto.set_create_bulk_proposal(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Record listed twice, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("Record my_record is listed more than once")),
+        }
+
+        action.mut_record_ids().pop();
+        payload_proto.set_create_bulk_proposal(action);
+        let payload = payload_proto.into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_ok(),
+            "Payload should be valid"
+        );
+    }
+
+    #[test]
+    /// Test that an error is returned if an AnswerBulkProposalAction is missing the receiving
+    /// agent.
+    fn test_validate_payload_answer_bulk_proposal_receiving_agent_missing() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::ANSWER_BULK_PROPOSAL);
+        payload_proto.set_timestamp(2);
+        let mut action = AnswerBulkProposalActionProto::new();
+        action.set_proposal_id("my_proposal".to_string());
+        payload_proto.set_answer_bulk_proposal(action);
+        let payload = payload_proto.into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload missing receiving agent, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("Receiving agent cannot be empty string")),
+        }
+    }
 }
@@ -11,11 +11,13 @@ cfg_if! {
     }
 }
 
-use dgc_config::protocol::pike::state::{Agent, AgentList};
+use dgc_config::protocol::pike::state::{Agent, AgentList, Organization, OrganizationList};
+use dgc_config::protocol::product::state::{Product, ProductList};
 use dgc_config::protocol::schema::state::{Schema, SchemaList};
 use dgc_config::protocol::track_and_trace::state::{
-    Property, PropertyList, PropertyListBuilder, PropertyPage, PropertyPageList,
-    PropertyPageListBuilder, ProposalList, Record, RecordList, RecordListBuilder,
+    BulkProposal, BulkProposalList, BulkProposalListBuilder, Property, PropertyList,
+    PropertyListBuilder, PropertyPage, PropertyPageList, PropertyPageListBuilder, ProposalList,
+    Recall, RecallList, RecallListBuilder, Record, RecordList, RecordListBuilder,
 };
 
 use dgc_config::protos::{FromBytes, IntoBytes};
@@ -166,6 +168,61 @@ impl<'a> TrackAndTraceState<'a> {
         }
     }
 
+    /// Gets a Pike Organization. Handles retrieving the correct organization from an
+    /// OrganizationList.
+    pub fn get_organization(&self, id: &str) -> Result<Option<Organization>, ApplyError> {
+        let address = make_org_address(id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let orgs = match OrganizationList::from_bytes(packed.as_slice()) {
+                    Ok(orgs) => orgs,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize organization list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the organization with the correct id
+                for org in orgs.organizations() {
+                    if org.org_id() == id {
+                        return Ok(Some(org.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_product(&self, product_id: &str) -> Result<Option<Product>, ApplyError> {
+        let address = make_product_address(product_id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let products = match ProductList::from_bytes(packed.as_slice()) {
+                    Ok(products) => products,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize product list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the product with the correct id
+                Ok(products
+                    .products()
+                    .iter()
+                    .find(|p| p.product_id() == product_id)
+                    .cloned())
+            }
+            None => Ok(None),
+        }
+    }
+
     pub fn get_property(
         &self,
         record_id: &str,
@@ -391,6 +448,176 @@ impl<'a> TrackAndTraceState<'a> {
             .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
         Ok(())
     }
+
+    pub fn get_recall(&self, recall_id: &str) -> Result<Option<Recall>, ApplyError> {
+        let address = make_recall_address(recall_id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let recalls = match RecallList::from_bytes(packed.as_slice()) {
+                    Ok(recalls) => recalls,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize recall list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the recall with the correct id
+                Ok(recalls
+                    .recalls()
+                    .iter()
+                    .find(|recall| recall.recall_id() == recall_id)
+                    .cloned())
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_recall(&self, recall_id: &str, recall: Recall) -> Result<(), ApplyError> {
+        let address = make_recall_address(recall_id);
+        let d = self.context.get_state_entry(&address)?;
+        let mut recalls = match d {
+            Some(packed) => match RecallList::from_bytes(packed.as_slice()) {
+                Ok(recall_list) => recall_list.recalls().to_vec(),
+                Err(err) => {
+                    return Err(ApplyError::InternalError(format!(
+                        "Cannot deserialize recall list: {:?}",
+                        err
+                    )));
+                }
+            },
+            None => vec![],
+        };
+
+        let mut index = None;
+        for (i, recall) in recalls.iter().enumerate() {
+            if recall.recall_id() == recall_id {
+                index = Some(i);
+                break;
+            }
+        }
+
+        if let Some(i) = index {
+            recalls.remove(i);
+        }
+        recalls.push(recall);
+        recalls.sort_by_key(|r| r.recall_id().to_string());
+        let recall_list = RecallListBuilder::new()
+            .with_recalls(recalls)
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!("Cannot build recall list: {:?}", err))
+            })?;
+
+        let serialized = match recall_list.into_bytes() {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Cannot serialize recall list: {:?}",
+                    err
+                )));
+            }
+        };
+        self.context
+            .set_state_entry(address, serialized)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        Ok(())
+    }
+
+    pub fn get_bulk_proposal(
+        &self,
+        proposal_id: &str,
+        agent_id: &str,
+    ) -> Result<Option<BulkProposal>, ApplyError> {
+        let address = make_bulk_proposal_address(proposal_id, agent_id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let proposals = match BulkProposalList::from_bytes(packed.as_slice()) {
+                    Ok(proposals) => proposals,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize bulk proposal list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the proposal with the correct id and receiving agent
+                Ok(proposals
+                    .proposals()
+                    .iter()
+                    .find(|proposal| {
+                        proposal.proposal_id() == proposal_id
+                            && proposal.receiving_agent() == agent_id
+                    })
+                    .cloned())
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_bulk_proposal(
+        &self,
+        proposal_id: &str,
+        agent_id: &str,
+        proposal: BulkProposal,
+    ) -> Result<(), ApplyError> {
+        let address = make_bulk_proposal_address(proposal_id, agent_id);
+        let d = self.context.get_state_entry(&address)?;
+        let mut proposals = match d {
+            Some(packed) => match BulkProposalList::from_bytes(packed.as_slice()) {
+                Ok(proposal_list) => proposal_list.proposals().to_vec(),
+                Err(err) => {
+                    return Err(ApplyError::InternalError(format!(
+                        "Cannot deserialize bulk proposal list: {:?}",
+                        err
+                    )));
+                }
+            },
+            None => vec![],
+        };
+
+        proposals.retain(|p| p.proposal_id() != proposal_id || p.receiving_agent() != agent_id);
+        proposals.push(proposal);
+        proposals.sort_by_key(|p| (p.proposal_id().to_string(), p.receiving_agent().to_string()));
+        let proposal_list = BulkProposalListBuilder::new()
+            .with_proposals(proposals)
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!(
+                    "Cannot build bulk proposal list: {:?}",
+                    err
+                ))
+            })?;
+
+        let serialized = match proposal_list.into_bytes() {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Cannot serialize bulk proposal list: {:?}",
+                    err
+                )));
+            }
+        };
+        self.context
+            .set_state_entry(address, serialized)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        Ok(())
+    }
+
+    pub fn add_event(
+        &mut self,
+        event_type: &str,
+        attributes: Vec<(String, String)>,
+        data: &[u8],
+    ) -> Result<(), ApplyError> {
+        self.context
+            .add_event(event_type.to_string(), attributes, data)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))
+    }
 }
 
 #[cfg(test)]